@@ -0,0 +1,41 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use primitives::{
+	dex::{PairStats, StatsPeriodIndex},
+	CurrencyId,
+};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DEXRuntimeRPCApi {
+		/// The index of the statistics period of the current block.
+		fn current_stats_period() -> StatsPeriodIndex;
+
+		/// The kept statistics of a trading pair from `from_period` to `to_period`.
+		fn get_pair_stats(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			from_period: StatsPeriodIndex,
+			to_period: StatsPeriodIndex,
+		) -> Vec<PairStats>;
+	}
+}
@@ -0,0 +1,116 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the DEX statistics.
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use primitives::{
+	dex::{PairStats, StatsPeriodIndex},
+	CurrencyId,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use self::gen_client::Client as DEXClient;
+pub use setheum_dex_rpc_runtime_api::DEXRuntimeRPCApi;
+
+/// DEX statistics rpc interface.
+#[rpc]
+pub trait DEXApi<BlockHash> {
+	/// Get the index of the statistics period of the block.
+	#[rpc(name = "dex_currentStatsPeriod")]
+	fn current_stats_period(&self, at: Option<BlockHash>) -> Result<StatsPeriodIndex>;
+
+	/// Get the volume, fees, swap count and closing reserves of a trading
+	/// pair in each kept period from `from_period` to `to_period`.
+	#[rpc(name = "dex_getPairStats")]
+	fn get_pair_stats(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		from_period: StatsPeriodIndex,
+		to_period: StatsPeriodIndex,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PairStats>>;
+}
+
+/// A struct that implements the [`DEXApi`].
+pub struct DEX<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> DEX<C, B> {
+	/// Create new `DEX` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		DEX {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block> DEXApi<<Block as BlockT>::Hash> for DEX<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DEXRuntimeRPCApi<Block>,
+{
+	fn current_stats_period(&self, at: Option<<Block as BlockT>::Hash>) -> Result<StatsPeriodIndex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.current_stats_period(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get current stats period.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pair_stats(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		from_period: StatsPeriodIndex,
+		to_period: StatsPeriodIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PairStats>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_pair_stats(&at, currency_id_a, currency_id_b, from_period, to_period)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pair stats.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
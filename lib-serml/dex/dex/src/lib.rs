@@ -30,17 +30,26 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::collapsible_if)]
 
-use frame_support::{log, pallet_prelude::*, traits::MaxEncodedLen, transactional, PalletId};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	log,
+	pallet_prelude::*,
+	traits::MaxEncodedLen,
+	transactional, PalletId,
+};
 use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
-use primitives::{Balance, CurrencyId, TradingPair};
+use primitives::{
+	dex::{PairStats, StatsPeriodIndex},
+	Balance, CurrencyId, TradingPair,
+};
 use sp_core::{H160, U256};
 use sp_runtime::{
-	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
+	traits::{AccountIdConversion, CheckedDiv, One, UniqueSaturatedInto, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{convert::TryInto, prelude::*, vec};
-use support::{CurrencyIdMapping, DEXIncentives, DEXManager, Price, Ratio};
+use support::{CurrencyIdMapping, DEXIncentives, DEXManager, Price, Ratio, SerpTreasury};
 
 mod mock;
 mod tests;
@@ -116,6 +125,42 @@ pub mod module {
 
 		/// The origin which may list, enable or disable trading pairs.
 		type ListingOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The extended provisioning blocks since the `not_before` of
+		/// provisioning, after which a Provisioning trading pair that has not
+		/// reached its target is aborted.
+		#[pallet::constant]
+		type ExtendedProvisioningBlocks: Get<Self::BlockNumber>;
+
+		/// The number of blocks in a period of the swap and liquidity
+		/// statistics.
+		#[pallet::constant]
+		type StatsPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of periods of statistics kept for each trading
+		/// pair, the oldest period is overwritten once it is reached.
+		#[pallet::constant]
+		type MaxStatsPeriods: Get<u32>;
+
+		/// SERP Treasury to receive the accrued protocol fees as serplus.
+		type SerpTreasury: SerpTreasury<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The number of blocks between forwarding the accrued protocol fees
+		/// to the SERP Treasury.
+		#[pallet::constant]
+		type ProtocolFeeForwardPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of trading pairs whose protocol fees are
+		/// forwarded in a block, the rest are forwarded in the following
+		/// blocks.
+		#[pallet::constant]
+		type MaxProtocolFeeForwardsPerBlock: Get<u32>;
+
+		/// The callback call which is dispatched while the borrowed
+		/// currency of a flash swap is lent out.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
 	}
 
 	#[pallet::error]
@@ -154,6 +199,19 @@ pub mod module {
 		UnacceptableShareIncrement,
 		/// The liquidity withdrawn is unacceptable
 		UnacceptableLiquidityWithdrawn,
+		/// The provisioning of this trading pair can not be aborted yet
+		NotAllowedAbort,
+		/// Trading pair must not be in Provisioning status
+		StillProvisioning,
+		/// There is no provision to refund
+		NotAllowedRefund,
+		/// The protocol fee rate must not be greater than one
+		InvalidProtocolFeeRate,
+		/// The liquidity pool is locked by an ongoing flash swap
+		FlashSwapLocked,
+		/// The repayment of flash swap breaks the constant product of the
+		/// liquidity pool
+		InsufficientRepayment,
 	}
 
 	#[pallet::event]
@@ -181,6 +239,25 @@ pub mod module {
 		/// Provisioning trading pair convert to Enabled. \[trading_pair,
 		/// pool_0_amount, pool_1_amount, total_share_amount\]
 		ProvisioningToEnabled(TradingPair, Balance, Balance, Balance),
+		/// Provisioning trading pair is aborted and its provisions become
+		/// refundable. \[trading_pair, accumulated_provision_0,
+		/// accumulated_provision_1\]
+		ProvisioningAborted(TradingPair, Balance, Balance),
+		/// Refund provision success. \[who, currency_id_0, contribution_0,
+		/// currency_id_1, contribution_1\]
+		RefundProvision(T::AccountId, CurrencyId, Balance, CurrencyId, Balance),
+		/// The protocol fee rate of trading pair is updated, `None` means the
+		/// protocol fee is turned off. \[trading_pair, protocol_fee_rate\]
+		ProtocolFeeRateUpdated(TradingPair, Option<Ratio>),
+		/// Protocol fee accrued as lp shares. \[trading_pair, share_amount\]
+		ProtocolFeeAccrued(TradingPair, Balance),
+		/// Accrued protocol fees are forwarded to SERP Treasury as serplus.
+		/// \[trading_pair, currency_id_0, amount_0, currency_id_1, amount_1\]
+		ProtocolFeeForwarded(TradingPair, CurrencyId, Balance, CurrencyId, Balance),
+		/// Flash swap success, it's a flash loan if the borrowed currency is
+		/// repaid. \[who, borrow_currency_id, borrow_amount,
+		/// repay_currency_id, repay_amount\]
+		FlashSwap(T::AccountId, CurrencyId, Balance, CurrencyId, Balance),
 	}
 
 	/// Liquidity pool for TradingPair.
@@ -198,6 +275,14 @@ pub mod module {
 	pub type TradingPairStatuses<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, TradingPairStatus<Balance, T::BlockNumber>, ValueQuery>;
 
+	/// The Provisioning trading pairs to check for abort at a block, which is
+	/// right after their provisioning deadline.
+	///
+	/// ProvisioningDeadlines: double_map BlockNumber, TradingPair => ()
+	#[pallet::storage]
+	pub type ProvisioningDeadlines<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, TradingPair, (), ValueQuery>;
+
 	/// Provision of TradingPair by AccountId.
 	///
 	/// ProvisioningPool: double_map TradingPair, AccountId => (Balance,
@@ -216,6 +301,51 @@ pub mod module {
 	#[pallet::getter(fn get_exchange_fee)]
 	pub type GetExchangeFee<T: Config> = StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
 
+	/// The fraction of exchange fees of TradingPair that is accrued to the
+	/// protocol, the protocol fee is off if it's not set.
+	///
+	/// ProtocolFeeRates: map TradingPair => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_rates)]
+	pub type ProtocolFeeRates<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Ratio, OptionQuery>;
+
+	/// The trading pairs whose protocol fees are waiting to be forwarded in
+	/// the current forward period.
+	///
+	/// ProtocolFeeForwardQueue: Vec<TradingPair>
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_forward_queue)]
+	pub type ProtocolFeeForwardQueue<T: Config> = StorageValue<_, Vec<TradingPair>, ValueQuery>;
+
+	/// The product of the liquidity pool of TradingPair as of the latest
+	/// liquidity change, used to calculate the accrued protocol fee.
+	///
+	/// KLast: map TradingPair => U256
+	#[pallet::storage]
+	#[pallet::getter(fn k_last)]
+	pub type KLast<T: Config> = StorageMap<_, Twox64Concat, TradingPair, U256, ValueQuery>;
+
+	/// The liquidity pools which are lent out by an ongoing flash swap, swaps
+	/// and liquidity changes of them are rejected until it's repaid.
+	///
+	/// FlashSwapLocks: map TradingPair => bool
+	#[pallet::storage]
+	#[pallet::getter(fn flash_swap_locks)]
+	pub type FlashSwapLocks<T: Config> = StorageMap<_, Twox64Concat, TradingPair, bool, ValueQuery>;
+
+	/// Rolling swap and liquidity statistics of TradingPair, stored in
+	/// `MaxStatsPeriods` slots which are reused by later periods.
+	///
+	/// Only read through the runtime api for now: the `prices` pallet does
+	/// not build against the `SetheumDEXManager` trait of `support`, so its
+	/// market prices can't use them until it is ported.
+	///
+	/// PairStatistics: double_map TradingPair, slot => Option<PairStats>
+	#[pallet::storage]
+	#[pallet::getter(fn pair_statistics)]
+	pub type PairStatistics<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, u32, PairStats, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub initial_listing_trading_pairs: Vec<(TradingPair, (Balance, Balance), (Balance, Balance), T::BlockNumber)>,
@@ -252,6 +382,7 @@ pub mod module {
 							not_before: *not_before,
 						}),
 					);
+					<Pallet<T>>::schedule_provisioning_deadline(*trading_pair, *not_before);
 				},
 			);
 
@@ -297,7 +428,63 @@ pub mod module {
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Abort the Provisioning trading pairs which have passed their
+		/// provisioning deadline without reaching the target provision, and
+		/// forward the protocol fees of at most
+		/// `MaxProtocolFeeForwardsPerBlock` trading pairs.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut reads: Weight = 0;
+			let mut writes: Weight = 0;
+
+			// only the trading pairs whose provisioning deadline has just passed are checked
+			let mut abort_weight: Weight = 0;
+			for (trading_pair, _) in ProvisioningDeadlines::<T>::drain_prefix(now) {
+				if let TradingPairStatus::<_, _>::Provisioning(provision_parameters) =
+					Self::trading_pair_statuses(trading_pair)
+				{
+					if Self::is_provisioning_expired(&provision_parameters, now) {
+						Self::do_abort_provisioning(trading_pair, provision_parameters);
+					}
+				}
+				abort_weight = abort_weight.saturating_add(<T as Config>::WeightInfo::abort_provisioning());
+			}
+
+			// queue the trading pairs with protocol fee at the start of a forward period,
+			// the queue is consumed over the following blocks
+			let mut forward_queue = Self::protocol_fee_forward_queue();
+			reads += 1;
+			let forward_period = T::ProtocolFeeForwardPeriod::get();
+			if forward_queue.is_empty() && !forward_period.is_zero() && (now % forward_period).is_zero() {
+				forward_queue = ProtocolFeeRates::<T>::iter()
+					.map(|(trading_pair, _)| trading_pair)
+					.collect();
+				reads += forward_queue.len() as Weight;
+			}
+
+			let mut forward_weight: Weight = 0;
+			if !forward_queue.is_empty() {
+				let count = forward_queue
+					.len()
+					.min(T::MaxProtocolFeeForwardsPerBlock::get() as usize);
+				for trading_pair in forward_queue.drain(..count) {
+					Self::forward_protocol_fee(trading_pair);
+					forward_weight = forward_weight.saturating_add(<T as Config>::WeightInfo::forward_protocol_fee());
+				}
+				if forward_queue.is_empty() {
+					ProtocolFeeForwardQueue::<T>::kill();
+				} else {
+					ProtocolFeeForwardQueue::<T>::put(forward_queue);
+				}
+				writes += 1;
+			}
+
+			T::DbWeight::get()
+				.reads_writes(reads, writes)
+				.saturating_add(abort_weight)
+				.saturating_add(forward_weight)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -460,6 +647,10 @@ pub mod module {
 				T::Currency::total_issuance(dex_share_currency_id).is_zero(),
 				Error::<T>::NotAllowedList
 			);
+			ensure!(
+				ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none(),
+				Error::<T>::NotAllowedList
+			);
 
 			if let CurrencyId::Erc20(address) = currency_id_a {
 				T::CurrencyIdMapping::set_erc20_mapping(address)?;
@@ -489,6 +680,7 @@ pub mod module {
 					not_before,
 				}),
 			);
+			Self::schedule_provisioning_deadline(trading_pair, not_before);
 			Self::deposit_event(Event::ListTradingPair(trading_pair));
 			Ok(().into())
 		}
@@ -559,6 +751,132 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Abort a Provisioning trading pair, the provisions of contributors
+		/// become refundable through `refund_provision`.
+		///
+		/// Before the provisioning deadline, only a Provisioning trading pair
+		/// that has not reached its target provision can be aborted.
+		#[pallet::weight((<T as Config>::WeightInfo::abort_provisioning(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn abort_provisioning(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			match Self::trading_pair_statuses(trading_pair) {
+				TradingPairStatus::<_, _>::Provisioning(provision_parameters) => {
+					ensure!(
+						!Self::is_provision_target_reached(&provision_parameters),
+						Error::<T>::NotAllowedAbort
+					);
+					Self::do_abort_provisioning(trading_pair, provision_parameters);
+				}
+				_ => return Err(Error::<T>::MustBeProvisioning.into()),
+			};
+			Ok(().into())
+		}
+
+		/// Refund the provision of `owner` to an aborted Provisioning trading
+		/// pair, and release the reference held for the provision.
+		///
+		/// - `owner`: the contributor whose provision is refunded.
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[pallet::weight(<T as Config>::WeightInfo::refund_provision())]
+		#[transactional]
+		pub fn refund_provision(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+			Self::do_refund_provision(&owner, currency_id_a, currency_id_b)?;
+			Ok(().into())
+		}
+
+		/// Set the fraction of exchange fees of a trading pair that is
+		/// accrued to the protocol and forwarded to SERP Treasury.
+		///
+		/// - `protocol_fee_rate`: the fraction of exchange fees, `None` to turn
+		///   the protocol fee off.
+		#[pallet::weight((<T as Config>::WeightInfo::set_protocol_fee_rate(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_protocol_fee_rate(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			protocol_fee_rate: Option<Ratio>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			// settle the protocol fee accrued with the previous rate
+			let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+			Self::mint_protocol_fee(trading_pair, pool_0, pool_1);
+
+			match protocol_fee_rate {
+				Some(rate) => {
+					ensure!(rate <= Ratio::one(), Error::<T>::InvalidProtocolFeeRate);
+					ProtocolFeeRates::<T>::insert(trading_pair, rate);
+					KLast::<T>::insert(trading_pair, U256::from(pool_0).saturating_mul(U256::from(pool_1)));
+				}
+				None => {
+					ProtocolFeeRates::<T>::remove(trading_pair);
+					KLast::<T>::remove(trading_pair);
+				}
+			}
+
+			Self::deposit_event(Event::ProtocolFeeRateUpdated(trading_pair, protocol_fee_rate));
+			Ok(().into())
+		}
+
+		/// Borrow currency from the liquidity pool of a trading pair,
+		/// dispatch `call` as the caller, and then repay with the exchange fee
+		/// from the caller. The repayment must keep the constant product of
+		/// the liquidity pool, otherwise the whole operation is reverted.
+		/// Repaying in the borrowed currency makes it a flash loan.
+		///
+		/// - `borrow_currency_id`: the currency id to borrow.
+		/// - `paired_currency_id`: the other currency id of the trading pair.
+		/// - `borrow_amount`: the amount to borrow.
+		/// - `repay_currency_id`: the currency id to repay, must be one of the trading pair.
+		/// - `max_repay_amount`: acceptable maximum repay amount.
+		/// - `call`: the call dispatched after the borrowed currency is transferred to caller.
+		#[pallet::weight(<T as Config>::WeightInfo::flash_swap().saturating_add(call.get_dispatch_info().weight))]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			borrow_currency_id: CurrencyId,
+			paired_currency_id: CurrencyId,
+			#[pallet::compact] borrow_amount: Balance,
+			repay_currency_id: CurrencyId,
+			#[pallet::compact] max_repay_amount: Balance,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_flash_swap(
+				&who,
+				borrow_currency_id,
+				paired_currency_id,
+				borrow_amount,
+				repay_currency_id,
+				max_repay_amount,
+				|| {
+					(*call)
+						.dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+						.map(|_| ())
+						.map_err(|e| e.error)
+				},
+			)?;
+			Ok(().into())
+		}
+
 		#[pallet::weight(<T as Config>::WeightInfo::update_exchange_fee(numerator.len() as u32, denominator.len() as u32))]
 		#[transactional]
 		pub fn update_exchange_fee(
@@ -580,6 +898,292 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account()
 	}
 
+	/// The account which holds the lp shares accrued as protocol fee.
+	pub fn protocol_fee_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account(b"protocol-fee")
+	}
+
+	/// Mint the protocol fee accrued since `KLast` as lp shares to the
+	/// protocol fee account, in the way of Uniswap V2. Return whether the
+	/// protocol fee is on for this trading pair.
+	fn mint_protocol_fee(trading_pair: TradingPair, pool_0: Balance, pool_1: Balance) -> bool {
+		let protocol_fee_rate = match Self::protocol_fee_rates(trading_pair) {
+			Some(rate) => rate,
+			None => return false,
+		};
+		let k_last = Self::k_last(trading_pair);
+		if k_last.is_zero() || protocol_fee_rate.is_zero() {
+			return true;
+		}
+
+		let root_k: Balance = U256::from(pool_0)
+			.saturating_mul(U256::from(pool_1))
+			.integer_sqrt()
+			.saturated_into();
+		let root_k_last: Balance = k_last.integer_sqrt().saturated_into();
+		if root_k <= root_k_last {
+			return true;
+		}
+
+		let lp_share_currency_id = match trading_pair.get_dex_share_currency_id() {
+			Some(currency_id) => currency_id,
+			None => return true,
+		};
+		let total_shares = T::Currency::total_issuance(lp_share_currency_id);
+
+		// share = total_shares * rate * (root_k - root_k_last) / ((1 - rate) * root_k + rate * root_k_last)
+		let denominator = Ratio::one()
+			.saturating_sub(protocol_fee_rate)
+			.saturating_mul_int(root_k)
+			.saturating_add(protocol_fee_rate.saturating_mul_int(root_k_last));
+		let share_amount: Balance = U256::from(total_shares)
+			.saturating_mul(U256::from(root_k.saturating_sub(root_k_last)))
+			.checked_div(U256::from(denominator))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.map(|n| protocol_fee_rate.saturating_mul_int(n))
+			.unwrap_or_else(Zero::zero);
+
+		if !share_amount.is_zero() {
+			match T::Currency::deposit(lp_share_currency_id, &Self::protocol_fee_account_id(), share_amount) {
+				Ok(_) => Self::deposit_event(Event::ProtocolFeeAccrued(trading_pair, share_amount)),
+				Err(e) => {
+					log::warn!(
+						target: "dex",
+						"mint_protocol_fee: failed to deposit {:?} {:?} to protocol fee account: {:?}. \
+						This is unexpected but should be safe",
+						share_amount, lp_share_currency_id, e
+					);
+				}
+			}
+		}
+		true
+	}
+
+	/// Withdraw the lp shares accrued as protocol fee of a trading pair, and
+	/// deposit the withdrawn currencies to SERP Treasury as serplus.
+	fn forward_protocol_fee(trading_pair: TradingPair) {
+		let lp_share_currency_id = match trading_pair.get_dex_share_currency_id() {
+			Some(currency_id) => currency_id,
+			None => return,
+		};
+		let protocol_fee_account_id = Self::protocol_fee_account_id();
+
+		// mint the pending protocol fee before withdrawing
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		if Self::mint_protocol_fee(trading_pair, pool_0, pool_1) {
+			KLast::<T>::insert(trading_pair, U256::from(pool_0).saturating_mul(U256::from(pool_1)));
+		}
+
+		let share_amount = T::Currency::free_balance(lp_share_currency_id, &protocol_fee_account_id);
+		if share_amount.is_zero() {
+			return;
+		}
+
+		match Self::do_forward_protocol_fee(trading_pair, share_amount) {
+			Ok((amount_0, amount_1)) => Self::deposit_event(Event::ProtocolFeeForwarded(
+				trading_pair,
+				trading_pair.0,
+				amount_0,
+				trading_pair.1,
+				amount_1,
+			)),
+			Err(e) => {
+				log::warn!(
+					target: "dex",
+					"forward_protocol_fee: failed to forward protocol fee of {:?}: {:?}. \
+					This is unexpected but should be safe",
+					trading_pair, e
+				);
+			}
+		}
+	}
+
+	/// Remove the liquidity of `share_amount` lp shares held by the protocol
+	/// fee account and deposit the withdrawn currencies to SERP Treasury.
+	#[transactional]
+	fn do_forward_protocol_fee(
+		trading_pair: TradingPair,
+		share_amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let protocol_fee_account_id = Self::protocol_fee_account_id();
+		let balance_0 = T::Currency::free_balance(trading_pair.0, &protocol_fee_account_id);
+		let balance_1 = T::Currency::free_balance(trading_pair.1, &protocol_fee_account_id);
+
+		Self::do_remove_liquidity(
+			&protocol_fee_account_id,
+			trading_pair.0,
+			trading_pair.1,
+			share_amount,
+			Zero::zero(),
+			Zero::zero(),
+			false,
+		)?;
+
+		let amount_0 = T::Currency::free_balance(trading_pair.0, &protocol_fee_account_id).saturating_sub(balance_0);
+		let amount_1 = T::Currency::free_balance(trading_pair.1, &protocol_fee_account_id).saturating_sub(balance_1);
+		T::SerpTreasury::deposit_serplus(trading_pair.0, &protocol_fee_account_id, amount_0)?;
+		T::SerpTreasury::deposit_serplus(trading_pair.1, &protocol_fee_account_id, amount_1)?;
+		Ok((amount_0, amount_1))
+	}
+
+	/// The index of the statistics period of the current block.
+	pub fn current_stats_period() -> StatsPeriodIndex {
+		let now = frame_system::Pallet::<T>::block_number();
+		now.checked_div(&T::StatsPeriod::get())
+			.unwrap_or_else(Zero::zero)
+			.unique_saturated_into()
+	}
+
+	/// Get the statistics of a trading pair from `from_period` to
+	/// `to_period`, periods without any statistics or which have been
+	/// overwritten are skipped.
+	pub fn get_pair_stats(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		from_period: StatsPeriodIndex,
+		to_period: StatsPeriodIndex,
+	) -> Vec<PairStats> {
+		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+		let mut stats: Vec<PairStats> = PairStatistics::<T>::iter_prefix_values(trading_pair)
+			.filter(|stats| stats.period >= from_period && stats.period <= to_period)
+			.collect();
+		stats.sort_by_key(|stats| stats.period);
+		stats
+	}
+
+	/// Update the statistics of the current period of a trading pair. The
+	/// slot of the period is reset first if it holds an earlier period.
+	fn update_pair_stats(trading_pair: TradingPair, f: impl FnOnce(&mut PairStats)) {
+		let max_periods = T::MaxStatsPeriods::get();
+		if max_periods.is_zero() {
+			return;
+		}
+		let period = Self::current_stats_period();
+		PairStatistics::<T>::mutate(trading_pair, period % max_periods, |maybe_stats| {
+			let mut stats = match maybe_stats.take() {
+				Some(stats) if stats.period == period => stats,
+				_ => PairStats {
+					period,
+					..Default::default()
+				},
+			};
+			f(&mut stats);
+			stats.closing_reserves = Self::liquidity_pool(trading_pair);
+			*maybe_stats = Some(stats);
+		});
+	}
+
+	/// Record a swap through a trading pair in the statistics.
+	fn record_swap_stats(
+		trading_pair: TradingPair,
+		supply_currency_id: CurrencyId,
+		supply_amount: Balance,
+		target_amount: Balance,
+	) {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+		let fee = Ratio::checked_from_rational(fee_numerator, fee_denominator)
+			.unwrap_or_default()
+			.saturating_mul_int(supply_amount);
+
+		Self::update_pair_stats(trading_pair, |stats| {
+			if supply_currency_id == trading_pair.0 {
+				stats.volume.0 = stats.volume.0.saturating_add(supply_amount);
+				stats.volume.1 = stats.volume.1.saturating_add(target_amount);
+				stats.fees.0 = stats.fees.0.saturating_add(fee);
+			} else {
+				stats.volume.0 = stats.volume.0.saturating_add(target_amount);
+				stats.volume.1 = stats.volume.1.saturating_add(supply_amount);
+				stats.fees.1 = stats.fees.1.saturating_add(fee);
+			}
+			stats.swap_count = stats.swap_count.saturating_add(1);
+		});
+	}
+
+	/// Check if the accumulated provision of a Provisioning trading pair
+	/// has reached its target.
+	fn is_provision_target_reached(
+		provision_parameters: &TradingPairProvisionParameters<Balance, T::BlockNumber>,
+	) -> bool {
+		!provision_parameters.accumulated_provision.0.is_zero()
+			&& !provision_parameters.accumulated_provision.1.is_zero()
+			&& (provision_parameters.accumulated_provision.0 >= provision_parameters.target_provision.0
+				|| provision_parameters.accumulated_provision.1 >= provision_parameters.target_provision.1)
+	}
+
+	/// Check if a Provisioning trading pair has passed its deadline without
+	/// reaching the target provision.
+	fn is_provisioning_expired(
+		provision_parameters: &TradingPairProvisionParameters<Balance, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> bool {
+		now > provision_parameters
+			.not_before
+			.saturating_add(T::ExtendedProvisioningBlocks::get())
+			&& !Self::is_provision_target_reached(provision_parameters)
+	}
+
+	/// Check a Provisioning trading pair for abort at the block after its
+	/// provisioning deadline, or at the next block if the deadline has
+	/// already passed. A stale check of a re-listed trading pair is harmless,
+	/// its own deadline is checked again.
+	fn schedule_provisioning_deadline(trading_pair: TradingPair, not_before: T::BlockNumber) {
+		let check_at = not_before
+			.saturating_add(T::ExtendedProvisioningBlocks::get())
+			.saturating_add(One::one())
+			.max(<frame_system::Pallet<T>>::block_number().saturating_add(One::one()));
+		ProvisioningDeadlines::<T>::insert(check_at, trading_pair, ());
+	}
+
+	/// Abort a Provisioning trading pair. The contributions are kept in
+	/// `ProvisioningPool` until they are refunded by `refund_provision`.
+	fn do_abort_provisioning(
+		trading_pair: TradingPair,
+		provision_parameters: TradingPairProvisionParameters<Balance, T::BlockNumber>,
+	) {
+		TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::<_, _>::NotEnabled);
+		Self::deposit_event(Event::ProvisioningAborted(
+			trading_pair,
+			provision_parameters.accumulated_provision.0,
+			provision_parameters.accumulated_provision.1,
+		));
+	}
+
+	/// Refund the provision of `who` to a trading pair which is not
+	/// Provisioning any more.
+	fn do_refund_provision(who: &T::AccountId, currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_token_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			!matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Provisioning(_)
+			),
+			Error::<T>::StillProvisioning
+		);
+
+		ensure!(
+			ProvisioningPool::<T>::contains_key(trading_pair, who),
+			Error::<T>::NotAllowedRefund
+		);
+
+		let (contribution_0, contribution_1) = ProvisioningPool::<T>::take(trading_pair, who);
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.0, &module_account_id, who, contribution_0)?;
+		T::Currency::transfer(trading_pair.1, &module_account_id, who, contribution_1)?;
+
+		// decrease ref count
+		frame_system::Pallet::<T>::dec_consumers(who);
+
+		Self::deposit_event(Event::RefundProvision(
+			who.clone(),
+			trading_pair.0,
+			contribution_0,
+			trading_pair.1,
+			contribution_1,
+		));
+		Ok(())
+	}
+
 	/// Access status of specific trading_pair,
 	/// if status is Provisioning and able to be `Enabled`, update it and
 	/// return `Enabled`
@@ -588,10 +1192,7 @@ impl<T: Config> Pallet<T> {
 		{
 			// check if able to be converted to Enable status
 			if frame_system::Pallet::<T>::block_number() >= provision_parameters.not_before
-				&& !provision_parameters.accumulated_provision.0.is_zero()
-				&& !provision_parameters.accumulated_provision.1.is_zero()
-				&& (provision_parameters.accumulated_provision.0 >= provision_parameters.target_provision.0
-					|| provision_parameters.accumulated_provision.1 >= provision_parameters.target_provision.1)
+				&& Self::is_provision_target_reached(&provision_parameters)
 			{
 				let lp_share_currency_id = trading_pair.get_dex_share_currency_id().expect("shouldn't be invalid!");
 				let mut total_shares_issued: Balance = Default::default();
@@ -646,6 +1247,7 @@ impl<T: Config> Pallet<T> {
 
 				// update trading_pair to Enabled status
 				TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::<_, _>::Enabled);
+				Self::update_pair_stats(trading_pair, |_| {});
 
 				Self::deposit_event(Event::ProvisioningToEnabled(
 					trading_pair,
@@ -751,8 +1353,10 @@ impl<T: Config> Pallet<T> {
 			),
 			Error::<T>::MustBeEnabled,
 		);
+		ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
 
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+			let protocol_fee_on = Self::mint_protocol_fee(trading_pair, *pool_0, *pool_1);
 			let total_shares = T::Currency::total_issuance(lp_share_currency_id);
 			let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.0 {
 				(max_amount_a, max_amount_b)
@@ -814,6 +1418,9 @@ impl<T: Config> Pallet<T> {
 
 			*pool_0 = pool_0.saturating_add(pool_0_increment);
 			*pool_1 = pool_1.saturating_add(pool_1_increment);
+			if protocol_fee_on {
+				KLast::<T>::insert(trading_pair, U256::from(*pool_0).saturating_mul(U256::from(*pool_1)));
+			}
 
 			if deposit_increment_share {
 				T::DEXIncentives::do_deposit_dex_share(who, lp_share_currency_id, share_increment)?;
@@ -828,7 +1435,10 @@ impl<T: Config> Pallet<T> {
 				share_increment,
 			));
 			Ok(())
-		})
+		})?;
+
+		Self::update_pair_stats(trading_pair, |_| {});
+		Ok(())
 	}
 
 	#[transactional]
@@ -849,6 +1459,7 @@ impl<T: Config> Pallet<T> {
 		let lp_share_currency_id = trading_pair
 			.get_dex_share_currency_id()
 			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
 
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
 			let (min_withdrawn_0, min_withdrawn_1) = if currency_id_a == trading_pair.0 {
@@ -856,6 +1467,7 @@ impl<T: Config> Pallet<T> {
 			} else {
 				(min_withdrawn_b, min_withdrawn_a)
 			};
+			let protocol_fee_on = Self::mint_protocol_fee(trading_pair, *pool_0, *pool_1);
 			let total_shares = T::Currency::total_issuance(lp_share_currency_id);
 			let proportion = Ratio::checked_from_rational(remove_share, total_shares).unwrap_or_default();
 			let pool_0_decrement = proportion.saturating_mul_int(*pool_0);
@@ -876,6 +1488,9 @@ impl<T: Config> Pallet<T> {
 
 			*pool_0 = pool_0.saturating_sub(pool_0_decrement);
 			*pool_1 = pool_1.saturating_sub(pool_1_decrement);
+			if protocol_fee_on {
+				KLast::<T>::insert(trading_pair, U256::from(*pool_0).saturating_mul(U256::from(*pool_1)));
+			}
 
 			Self::deposit_event(Event::RemoveLiquidity(
 				who.clone(),
@@ -886,7 +1501,10 @@ impl<T: Config> Pallet<T> {
 				remove_share,
 			));
 			Ok(())
-		})
+		})?;
+
+		Self::update_pair_stats(trading_pair, |_| {});
+		Ok(())
 	}
 
 	fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
@@ -941,6 +1559,48 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Get how much of the borrowed currency will be repaid for a flash loan
+	/// of `borrow_amount`, so that the repayment after fee covers the loan.
+	fn get_flash_loan_repay_amount(borrow_amount: Balance) -> Balance {
+		if borrow_amount.is_zero() {
+			Zero::zero()
+		} else {
+			let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+			let numerator: U256 = U256::from(borrow_amount).saturating_mul(U256::from(fee_denominator));
+			let denominator: U256 = U256::from(fee_denominator.saturating_sub(fee_numerator));
+
+			numerator
+				.checked_div(denominator)
+				.and_then(|r| r.checked_add(U256::one())) // add 1 to result so that correct the possible losses caused by remainder discarding in
+				.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+				.unwrap_or_else(Zero::zero)
+		}
+	}
+
+	/// Ensure the product of the liquidity pool after a flash swap, with the
+	/// exchange fee of the repaid amounts deducted, is not less than before.
+	fn ensure_constant_product(
+		(pool_0, pool_1): (Balance, Balance),
+		(new_pool_0, new_pool_1): (Balance, Balance),
+		(amount_in_0, amount_in_1): (Balance, Balance),
+	) -> DispatchResult {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+		let adjusted_balance = |balance: Balance, amount_in: Balance| -> U256 {
+			U256::from(balance)
+				.saturating_mul(U256::from(fee_denominator))
+				.saturating_sub(U256::from(amount_in).saturating_mul(U256::from(fee_numerator)))
+		};
+		let k_before: U256 = U256::from(pool_0)
+			.saturating_mul(U256::from(pool_1))
+			.saturating_mul(U256::from(fee_denominator))
+			.saturating_mul(U256::from(fee_denominator));
+		let k_after: U256 =
+			adjusted_balance(new_pool_0, amount_in_0).saturating_mul(adjusted_balance(new_pool_1, amount_in_1));
+
+		ensure!(k_after >= k_before, Error::<T>::InsufficientRepayment);
+		Ok(())
+	}
+
 	// TODO: Set General Governed FlexibleTradingFee that is updatable
 	fn get_target_amounts(
 		path: &[CurrencyId],
@@ -964,6 +1624,10 @@ impl<T: Config> Pallet<T> {
 				),
 				Error::<T>::MustBeEnabled
 			);
+			ensure!(
+				!Self::flash_swap_locks(TradingPair::new(path[i], path[i + 1])),
+				Error::<T>::FlashSwapLocked
+			);
 			let (supply_pool, target_pool) = Self::get_liquidity(path[i], path[i + 1]);
 			ensure!(
 				!supply_pool.is_zero() && !target_pool.is_zero(),
@@ -1007,6 +1671,10 @@ impl<T: Config> Pallet<T> {
 				),
 				Error::<T>::MustBeEnabled
 			);
+			ensure!(
+				!Self::flash_swap_locks(TradingPair::new(path[i - 1], path[i])),
+				Error::<T>::FlashSwapLocked
+			);
 			let (supply_pool, target_pool) = Self::get_liquidity(path[i - 1], path[i]);
 			ensure!(
 				!supply_pool.is_zero() && !target_pool.is_zero(),
@@ -1045,6 +1713,7 @@ impl<T: Config> Pallet<T> {
 					*pool_1 = pool_1.saturating_add(supply_increment);
 				}
 			});
+			Self::record_swap_stats(trading_pair, supply_currency_id, supply_increment, target_decrement);
 		}
 	}
 
@@ -1119,6 +1788,101 @@ impl<T: Config> Pallet<T> {
 		));
 		Ok(actual_supply_amount)
 	}
+
+	/// Lend `borrow_amount` of `borrow_currency_id` from the liquidity pool
+	/// to `who`, run `callback`, and then charge the repayment in
+	/// `repay_currency_id` from `who`. The liquidity pool is locked while the
+	/// callback runs. Return the actual repay amount.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn do_flash_swap<F: FnOnce() -> DispatchResult>(
+		who: &T::AccountId,
+		borrow_currency_id: CurrencyId,
+		paired_currency_id: CurrencyId,
+		borrow_amount: Balance,
+		repay_currency_id: CurrencyId,
+		max_repay_amount: Balance,
+		callback: F,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let trading_pair = TradingPair::from_token_currency_ids(borrow_currency_id, paired_currency_id)
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			repay_currency_id == borrow_currency_id || repay_currency_id == paired_currency_id,
+			Error::<T>::InvalidCurrencyId
+		);
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Enabled
+			),
+			Error::<T>::MustBeEnabled
+		);
+		ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
+		ensure!(!borrow_amount.is_zero(), Error::<T>::ZeroTargetAmount);
+
+		let (borrow_pool, paired_pool) = Self::get_liquidity(borrow_currency_id, paired_currency_id);
+		ensure!(
+			borrow_amount < borrow_pool && !paired_pool.is_zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+		let repay_amount = if repay_currency_id == borrow_currency_id {
+			Self::get_flash_loan_repay_amount(borrow_amount)
+		} else {
+			Self::get_supply_amount(paired_pool, borrow_pool, borrow_amount)
+		};
+		ensure!(!repay_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
+		ensure!(repay_amount <= max_repay_amount, Error::<T>::ExcessiveSupplyAmount);
+
+		let module_account_id = Self::account_id();
+		FlashSwapLocks::<T>::insert(trading_pair, true);
+		T::Currency::transfer(borrow_currency_id, &module_account_id, who, borrow_amount)?;
+		callback()?;
+		T::Currency::transfer(repay_currency_id, who, &module_account_id, repay_amount)?;
+		FlashSwapLocks::<T>::remove(trading_pair);
+
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		let (amount_out_0, amount_out_1) = if borrow_currency_id == trading_pair.0 {
+			(borrow_amount, Zero::zero())
+		} else {
+			(Zero::zero(), borrow_amount)
+		};
+		let (amount_in_0, amount_in_1) = if repay_currency_id == trading_pair.0 {
+			(repay_amount, Zero::zero())
+		} else {
+			(Zero::zero(), repay_amount)
+		};
+		let new_pool_0 = pool_0.saturating_sub(amount_out_0).saturating_add(amount_in_0);
+		let new_pool_1 = pool_1.saturating_sub(amount_out_1).saturating_add(amount_in_1);
+		Self::ensure_constant_product(
+			(pool_0, pool_1),
+			(new_pool_0, new_pool_1),
+			(amount_in_0, amount_in_1),
+		)?;
+		LiquidityPool::<T>::insert(trading_pair, (new_pool_0, new_pool_1));
+
+		if repay_currency_id == borrow_currency_id {
+			let fee = repay_amount.saturating_sub(borrow_amount);
+			Self::update_pair_stats(trading_pair, |stats| {
+				if repay_currency_id == trading_pair.0 {
+					stats.fees.0 = stats.fees.0.saturating_add(fee);
+				} else {
+					stats.fees.1 = stats.fees.1.saturating_add(fee);
+				}
+			});
+		} else {
+			Self::record_swap_stats(trading_pair, repay_currency_id, repay_amount, borrow_amount);
+		}
+
+		Self::deposit_event(Event::FlashSwap(
+			who.clone(),
+			borrow_currency_id,
+			borrow_amount,
+			repay_currency_id,
+			repay_amount,
+		));
+		Ok(repay_amount)
+	}
 }
 
 impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
@@ -1216,4 +1980,24 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 			by_withdraw,
 		)
 	}
+
+	fn flash_swap<F: FnOnce() -> DispatchResult>(
+		who: &T::AccountId,
+		borrow_currency_id: CurrencyId,
+		paired_currency_id: CurrencyId,
+		borrow_amount: Balance,
+		repay_currency_id: CurrencyId,
+		max_repay_amount: Balance,
+		callback: F,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Self::do_flash_swap(
+			who,
+			borrow_currency_id,
+			paired_currency_id,
+			borrow_amount,
+			repay_currency_id,
+			max_repay_amount,
+			callback,
+		)
+	}
 }
@@ -23,8 +23,9 @@
 use super::*;
 use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
-use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
+use orml_traits::{parameter_type_with_key, MultiCurrency, MultiReservableCurrency};
 use primitives::{Amount, TokenSymbol};
+use support::SerpTreasury;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 
@@ -33,6 +34,7 @@ pub type AccountId = u128;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const SERP_TREASURY: AccountId = 10;
 pub const USDJ: CurrencyId = CurrencyId::Token(TokenSymbol::USDJ);
 pub const EURJ: CurrencyId = CurrencyId::Token(TokenSymbol::EURJ);
 pub const CHFJ: CurrencyId = CurrencyId::Token(TokenSymbol::CHFJ);
@@ -104,6 +106,98 @@ impl DEXIncentives<AccountId, CurrencyId, Balance> for MockDEXIncentives {
 	}
 }
 
+pub struct MockSerpTreasury;
+impl SerpTreasury<AccountId> for MockSerpTreasury {
+	type Amount = Amount;
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+	type BlockNumber = BlockNumber;
+
+	fn get_adjustment_frequency() -> BlockNumber {
+		Default::default()
+	}
+
+	fn get_total_setter() -> Balance {
+		Default::default()
+	}
+
+	fn get_standard_proportion(_: Balance) -> Ratio {
+		Default::default()
+	}
+
+	fn get_serplus_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_settpay_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_treasury_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_charity_fund_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_surpup(_: CurrencyId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_surpdown(_: CurrencyId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_serp_tes() -> DispatchResult {
+		Ok(())
+	}
+
+	fn serp_tes(_: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_standard(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_standard(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_propper(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_propper(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_setter(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_setter(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_dexer(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_dexer(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn deposit_serplus(currency_id: CurrencyId, from: &AccountId, serplus: Balance) -> DispatchResult {
+		Tokens::transfer(currency_id, from, &SERP_TREASURY, serplus)
+	}
+
+	fn deposit_setter(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
 ord_parameter_types! {
 	pub const ListingOrigin: AccountId = 3;
 	pub const UpdateOrigin: AccountId = 3;
@@ -112,6 +206,11 @@ ord_parameter_types! {
 parameter_types! {
 	pub const TradingPathLimit: u32 = 3;
 	pub const DexPalletId: PalletId = PalletId(*b"set/sdex");
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2000;
+	pub const StatsPeriod: BlockNumber = 10;
+	pub const MaxStatsPeriods: u32 = 3;
+	pub const ProtocolFeeForwardPeriod: BlockNumber = 10;
+	pub const MaxProtocolFeeForwardsPerBlock: u32 = 1;
 }
 
 impl Config for Runtime {
@@ -124,6 +223,13 @@ impl Config for Runtime {
 	type DEXIncentives = MockDEXIncentives;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type StatsPeriod = StatsPeriod;
+	type MaxStatsPeriods = MaxStatsPeriods;
+	type SerpTreasury = MockSerpTreasury;
+	type ProtocolFeeForwardPeriod = ProtocolFeeForwardPeriod;
+	type MaxProtocolFeeForwardsPerBlock = MaxProtocolFeeForwardsPerBlock;
+	type Call = Call;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -23,10 +23,11 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	SetheumDEX, Event, ExtBuilder, ListingOrigin, UpdateOrigin Origin, Runtime, System, Tokens, 
-	ALICE, BOB, DNAR, USDJ, EURJ, CHFJ, USDJ_DNAR_PAIR, USDJ_CHFJ_PAIR,
+	Call, SetheumDEX, Event, ExtBuilder, ListingOrigin, UpdateOrigin, Origin, Runtime, System, Tokens,
+	ALICE, BOB, DNAR, USDJ, EURJ, CHFJ, USDJ_DNAR_PAIR, USDJ_CHFJ_PAIR, DNAR_CHFJ_PAIR, SERP_TREASURY,
 };
 use orml_traits::MultiReservableCurrency;
+use primitives::dex::PairStats;
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -104,6 +105,7 @@ fn list_new_trading_pair_work() {
 			})
 		);
 		System::assert_last_event(Event::dex(crate::Event::ListTradingPair(USDJ_DNAR_PAIR)));
+		assert!(ProvisioningDeadlines::<Runtime>::contains_key(2011, USDJ_DNAR_PAIR));
 
 		assert_noop!(
 			SetheumDEX::list_trading_pair(
@@ -254,6 +256,227 @@ fn disable_provisioning_trading_pair_work() {
 		});
 }
 
+#[test]
+fn abort_provisioning_work() {
+	ExtBuilder::default()
+		.initialize_listing_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				SetheumDEX::abort_provisioning(Origin::signed(ALICE), USDJ, DNAR),
+				BadOrigin
+			);
+			assert_noop!(
+				SetheumDEX::abort_provisioning(Origin::signed(ListingOrigin::get()), USDJ, EURJ),
+				Error::<Runtime>::MustBeProvisioning
+			);
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				5_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+				false
+			));
+			assert_ok!(SetheumDEX::abort_provisioning(
+				Origin::signed(ListingOrigin::get()),
+				USDJ,
+				DNAR
+			));
+			assert_eq!(
+				SetheumDEX::trading_pair_statuses(USDJ_DNAR_PAIR),
+				TradingPairStatus::<_, _>::NotEnabled
+			);
+			System::assert_last_event(Event::dex(crate::Event::ProvisioningAborted(
+				USDJ_DNAR_PAIR,
+				5_000_000_000_000u128,
+				1_000_000_000_000u128,
+			)));
+
+			// provision is kept until it is refunded
+			assert_eq!(
+				SetheumDEX::provisioning_pool(USDJ_DNAR_PAIR, ALICE),
+				(5_000_000_000_000u128, 1_000_000_000_000u128)
+			);
+
+			// trading pair with pending refunds can not be listed again
+			assert_noop!(
+				SetheumDEX::list_trading_pair(
+					Origin::signed(ListingOrigin::get()),
+					USDJ,
+					DNAR,
+					1_000_000_000_000u128,
+					1_000_000_000_000u128,
+					5_000_000_000_000u128,
+					2_000_000_000_000u128,
+					10,
+				),
+				Error::<Runtime>::NotAllowedList
+			);
+		});
+}
+
+#[test]
+fn abort_provisioning_reached_target_not_allowed() {
+	ExtBuilder::default()
+		.initialize_listing_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				5_000_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+				false
+			));
+			assert_noop!(
+				SetheumDEX::abort_provisioning(Origin::signed(ListingOrigin::get()), USDJ, DNAR),
+				Error::<Runtime>::NotAllowedAbort
+			);
+		});
+}
+
+#[test]
+fn expired_provisioning_aborted_on_initialize() {
+	ExtBuilder::default()
+		.initialize_listing_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				5_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+				false
+			));
+
+			// not_before of USDJ_DNAR_PAIR is 10, and ExtendedProvisioningBlocks is 2000
+			assert!(ProvisioningDeadlines::<Runtime>::contains_key(2011, USDJ_DNAR_PAIR));
+			SetheumDEX::on_initialize(2010);
+			assert!(matches!(
+				SetheumDEX::trading_pair_statuses(USDJ_DNAR_PAIR),
+				TradingPairStatus::<_, _>::Provisioning(_)
+			));
+
+			SetheumDEX::on_initialize(2011);
+			assert_eq!(
+				SetheumDEX::trading_pair_statuses(USDJ_DNAR_PAIR),
+				TradingPairStatus::<_, _>::NotEnabled
+			);
+			assert!(!ProvisioningDeadlines::<Runtime>::contains_key(2011, USDJ_DNAR_PAIR));
+			System::assert_has_event(Event::dex(crate::Event::ProvisioningAborted(
+				USDJ_DNAR_PAIR,
+				5_000_000_000_000u128,
+				1_000_000_000_000u128,
+			)));
+
+			// not_before of DNAR_CHFJ_PAIR is 20, it is still Provisioning
+			assert!(matches!(
+				SetheumDEX::trading_pair_statuses(DNAR_CHFJ_PAIR),
+				TradingPairStatus::<_, _>::Provisioning(_)
+			));
+		});
+}
+
+#[test]
+fn provisioning_listed_after_its_deadline_is_aborted_on_initialize() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(3000);
+
+		assert_ok!(SetheumDEX::list_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			USDJ,
+			DNAR,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000u128,
+			2_000_000_000_000u128,
+			10,
+		));
+
+		// the deadline of not_before 10 has passed, the trading pair is checked at the next block
+		assert!(!ProvisioningDeadlines::<Runtime>::contains_key(2011, USDJ_DNAR_PAIR));
+		assert!(ProvisioningDeadlines::<Runtime>::contains_key(3001, USDJ_DNAR_PAIR));
+
+		SetheumDEX::on_initialize(3001);
+		assert_eq!(
+			SetheumDEX::trading_pair_statuses(USDJ_DNAR_PAIR),
+			TradingPairStatus::<_, _>::NotEnabled
+		);
+		assert!(!ProvisioningDeadlines::<Runtime>::contains_key(3001, USDJ_DNAR_PAIR));
+	});
+}
+
+#[test]
+fn refund_provision_work() {
+	ExtBuilder::default()
+		.initialize_listing_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				5_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+				false
+			));
+			let alice_ref_count_0 = System::consumers(&ALICE);
+
+			assert_noop!(
+				SetheumDEX::refund_provision(Origin::signed(BOB), ALICE, USDJ, DNAR),
+				Error::<Runtime>::StillProvisioning
+			);
+
+			assert_ok!(SetheumDEX::abort_provisioning(
+				Origin::signed(ListingOrigin::get()),
+				USDJ,
+				DNAR
+			));
+
+			assert_noop!(
+				SetheumDEX::refund_provision(Origin::signed(ALICE), BOB, USDJ, DNAR),
+				Error::<Runtime>::NotAllowedRefund
+			);
+
+			// anyone can refund the provision to its owner
+			assert_ok!(SetheumDEX::refund_provision(Origin::signed(BOB), ALICE, DNAR, USDJ));
+			System::assert_last_event(Event::dex(crate::Event::RefundProvision(
+				ALICE,
+				USDJ,
+				5_000_000_000_000u128,
+				DNAR,
+				1_000_000_000_000u128,
+			)));
+			assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1_000_000_000_000_000_000u128);
+			assert_eq!(Tokens::free_balance(DNAR, &ALICE), 1_000_000_000_000_000_000u128);
+			assert_eq!(Tokens::free_balance(USDJ, &SetheumDEX::account_id()), 0);
+			assert_eq!(Tokens::free_balance(DNAR, &SetheumDEX::account_id()), 0);
+			assert_eq!(SetheumDEX::provisioning_pool(USDJ_DNAR_PAIR, ALICE), (0, 0));
+			assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+
+			assert_noop!(
+				SetheumDEX::refund_provision(Origin::signed(ALICE), ALICE, USDJ, DNAR),
+				Error::<Runtime>::NotAllowedRefund
+			);
+		});
+}
+
 #[test]
 fn add_provision_work() {
 	ExtBuilder::default()
@@ -1198,3 +1421,395 @@ fn initialize_added_liquidity_pools_genesis_work() {
 			);
 		});
 }
+
+#[test]
+fn pair_stats_recorded_in_rolling_periods() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(SetheumDEX::current_stats_period(), 0);
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+			assert_eq!(
+				SetheumDEX::pair_statistics(USDJ_DNAR_PAIR, 0),
+				Some(PairStats {
+					period: 0,
+					volume: (0, 0),
+					fees: (0, 0),
+					swap_count: 0,
+					closing_reserves: (500_000_000_000_000, 100_000_000_000_000),
+				})
+			);
+
+			let target_amount =
+				SetheumDEX::do_swap_with_exact_supply(&BOB, &[DNAR, USDJ], 100_000_000_000_000, 0, None).unwrap();
+			let (fee_numerator, fee_denominator) = SetheumDEX::get_exchange_fee();
+			let fee = Ratio::checked_from_rational(fee_numerator, fee_denominator)
+				.unwrap_or_default()
+				.saturating_mul_int(100_000_000_000_000u128);
+			assert_eq!(
+				SetheumDEX::pair_statistics(USDJ_DNAR_PAIR, 0),
+				Some(PairStats {
+					period: 0,
+					volume: (target_amount, 100_000_000_000_000),
+					fees: (0, fee),
+					swap_count: 1,
+					closing_reserves: SetheumDEX::liquidity_pool(USDJ_DNAR_PAIR),
+				})
+			);
+
+			System::set_block_number(25);
+			assert_eq!(SetheumDEX::current_stats_period(), 2);
+			assert_ok!(SetheumDEX::do_swap_with_exact_supply(
+				&BOB,
+				&[USDJ, DNAR],
+				1_000_000_000_000,
+				0,
+				None
+			));
+			assert_eq!(SetheumDEX::pair_statistics(USDJ_DNAR_PAIR, 2).unwrap().swap_count, 1);
+			assert_eq!(
+				SetheumDEX::get_pair_stats(DNAR, USDJ, 0, 2)
+					.iter()
+					.map(|stats| stats.period)
+					.collect::<Vec<_>>(),
+				vec![0, 2]
+			);
+
+			// period 3 reuses the slot of period 0
+			System::set_block_number(35);
+			assert_ok!(SetheumDEX::do_swap_with_exact_supply(
+				&BOB,
+				&[USDJ, DNAR],
+				1_000_000_000_000,
+				0,
+				None
+			));
+			assert_eq!(SetheumDEX::pair_statistics(USDJ_DNAR_PAIR, 0).unwrap().period, 3);
+			assert_eq!(SetheumDEX::pair_statistics(USDJ_DNAR_PAIR, 0).unwrap().swap_count, 1);
+			assert_eq!(
+				SetheumDEX::get_pair_stats(USDJ, DNAR, 0, 3)
+					.iter()
+					.map(|stats| stats.period)
+					.collect::<Vec<_>>(),
+				vec![2, 3]
+			);
+			assert_eq!(SetheumDEX::get_pair_stats(USDJ, DNAR, 0, 1), vec![]);
+		});
+}
+
+#[test]
+fn set_protocol_fee_rate_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				SetheumDEX::set_protocol_fee_rate(Origin::signed(ALICE), USDJ, DNAR, Some(Ratio::one())),
+				BadOrigin
+			);
+			assert_noop!(
+				SetheumDEX::set_protocol_fee_rate(
+					Origin::signed(UpdateOrigin::get()),
+					USDJ,
+					DNAR,
+					Some(Ratio::saturating_from_integer(2))
+				),
+				Error::<Runtime>::InvalidProtocolFeeRate
+			);
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				1_000_000,
+				1_000_000,
+				0,
+				false,
+			));
+			assert_ok!(SetheumDEX::set_protocol_fee_rate(
+				Origin::signed(UpdateOrigin::get()),
+				USDJ,
+				DNAR,
+				Some(Ratio::saturating_from_rational(1, 2))
+			));
+			System::assert_last_event(Event::dex(crate::Event::ProtocolFeeRateUpdated(
+				USDJ_DNAR_PAIR,
+				Some(Ratio::saturating_from_rational(1, 2)),
+			)));
+			assert_eq!(
+				SetheumDEX::protocol_fee_rates(USDJ_DNAR_PAIR),
+				Some(Ratio::saturating_from_rational(1, 2))
+			);
+			assert_eq!(SetheumDEX::k_last(USDJ_DNAR_PAIR), U256::from(1_000_000_000_000u128));
+
+			assert_ok!(SetheumDEX::set_protocol_fee_rate(
+				Origin::signed(UpdateOrigin::get()),
+				USDJ,
+				DNAR,
+				None
+			));
+			assert_eq!(SetheumDEX::protocol_fee_rates(USDJ_DNAR_PAIR), None);
+			assert_eq!(SetheumDEX::k_last(USDJ_DNAR_PAIR), U256::zero());
+		});
+}
+
+#[test]
+fn protocol_fee_accrued_and_forwarded_to_serp_treasury() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let lp_currency_id = USDJ_DNAR_PAIR.get_dex_share_currency_id().unwrap();
+			let protocol_fee_account_id = SetheumDEX::protocol_fee_account_id();
+
+			assert_ok!(SetheumDEX::add_liquidity(
+				Origin::signed(ALICE),
+				USDJ,
+				DNAR,
+				1_000_000,
+				1_000_000,
+				0,
+				false,
+			));
+			assert_eq!(Tokens::total_issuance(lp_currency_id), 2_000_000);
+			assert_ok!(SetheumDEX::set_protocol_fee_rate(
+				Origin::signed(UpdateOrigin::get()),
+				USDJ,
+				DNAR,
+				Some(Ratio::saturating_from_rational(1, 2))
+			));
+
+			// simulate the growth of liquidity pool by exchange fees,
+			// root_k grows from 1_000_000 to 2_000_000
+			LiquidityPool::<Runtime>::insert(USDJ_DNAR_PAIR, (4_000_000, 1_000_000));
+
+			// share = 2_000_000 * 1/2 * (2_000_000 - 1_000_000) / (1/2 * 2_000_000 + 1/2 * 1_000_000)
+			SetheumDEX::on_initialize(10);
+			System::assert_has_event(Event::dex(crate::Event::ProtocolFeeAccrued(USDJ_DNAR_PAIR, 666_666)));
+			assert_eq!(Tokens::free_balance(lp_currency_id, &protocol_fee_account_id), 0);
+			assert_eq!(Tokens::total_issuance(lp_currency_id), 2_000_000);
+
+			let serplus_0 = Tokens::free_balance(USDJ, &SERP_TREASURY);
+			let serplus_1 = Tokens::free_balance(DNAR, &SERP_TREASURY);
+			assert!(!serplus_0.is_zero() && !serplus_1.is_zero());
+			System::assert_last_event(Event::dex(crate::Event::ProtocolFeeForwarded(
+				USDJ_DNAR_PAIR,
+				USDJ,
+				serplus_0,
+				DNAR,
+				serplus_1,
+			)));
+
+			let (pool_0, pool_1) = SetheumDEX::liquidity_pool(USDJ_DNAR_PAIR);
+			assert_eq!((pool_0 + serplus_0, pool_1 + serplus_1), (4_000_000, 1_000_000));
+			assert_eq!(
+				SetheumDEX::k_last(USDJ_DNAR_PAIR),
+				U256::from(pool_0).saturating_mul(U256::from(pool_1))
+			);
+		});
+}
+
+#[test]
+fn protocol_fee_forwarding_spread_across_blocks() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			for (currency_id_a, currency_id_b) in vec![(USDJ, DNAR), (USDJ, CHFJ)] {
+				assert_ok!(SetheumDEX::add_liquidity(
+					Origin::signed(ALICE),
+					currency_id_a,
+					currency_id_b,
+					1_000_000,
+					1_000_000,
+					0,
+					false,
+				));
+				assert_ok!(SetheumDEX::set_protocol_fee_rate(
+					Origin::signed(UpdateOrigin::get()),
+					currency_id_a,
+					currency_id_b,
+					Some(Ratio::saturating_from_rational(1, 2))
+				));
+			}
+			LiquidityPool::<Runtime>::insert(USDJ_DNAR_PAIR, (4_000_000, 1_000_000));
+			LiquidityPool::<Runtime>::insert(USDJ_CHFJ_PAIR, (4_000_000, 1_000_000));
+
+			// nothing is queued before the forward period
+			SetheumDEX::on_initialize(9);
+			assert!(SetheumDEX::protocol_fee_forward_queue().is_empty());
+
+			// MaxProtocolFeeForwardsPerBlock is 1
+			SetheumDEX::on_initialize(10);
+			assert_eq!(SetheumDEX::protocol_fee_forward_queue().len(), 1);
+			let queued_pair = SetheumDEX::protocol_fee_forward_queue()[0];
+			assert_eq!(SetheumDEX::liquidity_pool(queued_pair), (4_000_000, 1_000_000));
+
+			SetheumDEX::on_initialize(11);
+			assert!(SetheumDEX::protocol_fee_forward_queue().is_empty());
+			assert_ne!(SetheumDEX::liquidity_pool(USDJ_DNAR_PAIR), (4_000_000, 1_000_000));
+			assert_ne!(SetheumDEX::liquidity_pool(USDJ_CHFJ_PAIR), (4_000_000, 1_000_000));
+			assert!(!Tokens::free_balance(DNAR, &SERP_TREASURY).is_zero());
+			assert!(!Tokens::free_balance(CHFJ, &SERP_TREASURY).is_zero());
+		});
+}
+
+#[test]
+fn flash_loan_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let module_account_id = SetheumDEX::account_id();
+			let remark = Box::new(Call::System(frame_system::Call::remark(vec![])));
+
+			assert_noop!(
+				SetheumDEX::flash_swap(Origin::signed(BOB), USDJ, DNAR, 100_000, EURJ, 200_000, remark.clone()),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+			assert_noop!(
+				SetheumDEX::flash_swap(Origin::signed(BOB), USDJ, DNAR, 1_000_000, USDJ, 2_000_000, remark.clone()),
+				Error::<Runtime>::InsufficientLiquidity
+			);
+			assert_noop!(
+				SetheumDEX::flash_swap(Origin::signed(BOB), USDJ, DNAR, 100_000, USDJ, 101_010, remark.clone()),
+				Error::<Runtime>::ExcessiveSupplyAmount
+			);
+
+			let bob_usdj = Tokens::free_balance(USDJ, &BOB);
+			let module_usdj = Tokens::free_balance(USDJ, &module_account_id);
+
+			// repay = 100_000 / (1 - 1%) + 1
+			assert_ok!(SetheumDEX::flash_swap(
+				Origin::signed(BOB),
+				USDJ,
+				DNAR,
+				100_000,
+				USDJ,
+				101_011,
+				remark
+			));
+			System::assert_last_event(Event::dex(crate::Event::FlashSwap(BOB, USDJ, 100_000, USDJ, 101_011)));
+			assert_eq!(SetheumDEX::liquidity_pool(USDJ_DNAR_PAIR), (1_001_011, 2_000_000));
+			assert_eq!(Tokens::free_balance(USDJ, &BOB), bob_usdj - 1_011);
+			assert_eq!(Tokens::free_balance(USDJ, &module_account_id), module_usdj + 1_011);
+			assert!(!SetheumDEX::flash_swap_locks(USDJ_DNAR_PAIR));
+		});
+}
+
+#[test]
+fn flash_swap_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let bob_usdj = Tokens::free_balance(USDJ, &BOB);
+			let bob_dnar = Tokens::free_balance(DNAR, &BOB);
+
+			// borrow DNAR and repay with USDJ, which equals to swap with exact target
+			assert_eq!(SetheumDEX::get_supply_amount(1_000_000, 2_000_000, 100_000), 53_164);
+			assert_ok!(SetheumDEX::flash_swap(
+				Origin::signed(BOB),
+				DNAR,
+				USDJ,
+				100_000,
+				USDJ,
+				53_164,
+				Box::new(Call::System(frame_system::Call::remark(vec![])))
+			));
+			System::assert_last_event(Event::dex(crate::Event::FlashSwap(BOB, DNAR, 100_000, USDJ, 53_164)));
+			assert_eq!(SetheumDEX::liquidity_pool(USDJ_DNAR_PAIR), (1_053_164, 1_900_000));
+			assert_eq!(Tokens::free_balance(USDJ, &BOB), bob_usdj - 53_164);
+			assert_eq!(Tokens::free_balance(DNAR, &BOB), bob_dnar + 100_000);
+		});
+}
+
+#[test]
+fn flash_swap_locks_liquidity_pool_during_callback() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			// swap through the lent liquidity pool in the callback is rejected,
+			// and the whole flash swap is reverted
+			assert_noop!(
+				SetheumDEX::flash_swap(
+					Origin::signed(BOB),
+					USDJ,
+					DNAR,
+					100_000,
+					USDJ,
+					101_011,
+					Box::new(Call::SetheumDEX(crate::Call::swap_with_exact_supply(
+						vec![USDJ, DNAR],
+						100_000,
+						0
+					)))
+				),
+				Error::<Runtime>::FlashSwapLocked
+			);
+			assert!(!SetheumDEX::flash_swap_locks(USDJ_DNAR_PAIR));
+
+			// other liquidity pools are still available in the callback
+			assert_ok!(SetheumDEX::flash_swap(
+				Origin::signed(BOB),
+				USDJ,
+				DNAR,
+				100_000,
+				USDJ,
+				101_011,
+				Box::new(Call::SetheumDEX(crate::Call::swap_with_exact_supply(
+					vec![USDJ, CHFJ],
+					100_000,
+					0
+				)))
+			));
+			assert_eq!(SetheumDEX::liquidity_pool(USDJ_DNAR_PAIR), (1_001_011, 2_000_000));
+		});
+}
+
+#[test]
+fn ensure_constant_product_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumDEX::ensure_constant_product(
+			(1_000_000, 2_000_000),
+			(1_001_011, 2_000_000),
+			(101_011, 0)
+		));
+		assert_noop!(
+			SetheumDEX::ensure_constant_product((1_000_000, 2_000_000), (1_001_000, 2_000_000), (101_000, 0)),
+			Error::<Runtime>::InsufficientRepayment
+		);
+		assert_ok!(SetheumDEX::ensure_constant_product(
+			(1_000_000, 2_000_000),
+			(1_053_164, 1_900_000),
+			(53_164, 0)
+		));
+		assert_noop!(
+			SetheumDEX::ensure_constant_product((1_000_000, 2_000_000), (1_053_163, 1_900_000), (53_163, 0)),
+			Error::<Runtime>::InsufficientRepayment
+		);
+	});
+}
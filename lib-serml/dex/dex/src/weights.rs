@@ -55,6 +55,11 @@ pub trait WeightInfo {
 	fn remove_liquidity_by_withdraw() -> Weight;
 	fn swap_with_exact_supply(u: u32, ) -> Weight;
 	fn swap_with_exact_target(u: u32, ) -> Weight;
+	fn abort_provisioning() -> Weight;
+	fn refund_provision() -> Weight;
+	fn set_protocol_fee_rate() -> Weight;
+	fn flash_swap() -> Weight;
+	fn forward_protocol_fee() -> Weight;
 }
 
 /// Weights for setheum_dex using the Setheum node and recommended hardware.
@@ -77,37 +82,66 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 	}
 	fn add_liquidity() -> Weight {
 		(197_944_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(8 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	fn add_liquidity_and_deposit() -> Weight {
 		(296_383_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(13 as Weight))
-			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
 		(205_562_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	fn remove_liquidity_by_withdraw() -> Weight {
 		(339_614_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(12 as Weight))
-			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
 	}
 	fn swap_with_exact_supply(u: u32, ) -> Weight {
 		(156_409_000 as Weight)
 			// Standard Error: 185_000
 			.saturating_add((488_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
 	fn swap_with_exact_target(u: u32, ) -> Weight {
 		(155_993_000 as Weight)
 			// Standard Error: 138_000
 			.saturating_add((654_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn abort_provisioning() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn refund_provision() -> Weight {
+		(79_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn set_protocol_fee_rate() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(92_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn forward_protocol_fee() -> Weight {
+		(121_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
 	}
 }
 
@@ -130,36 +164,65 @@ impl WeightInfo for () {
 	}
 	fn add_liquidity() -> Weight {
 		(197_944_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	fn add_liquidity_and_deposit() -> Weight {
 		(296_383_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
 		(205_562_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	fn remove_liquidity_by_withdraw() -> Weight {
 		(339_614_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
 	}
 	fn swap_with_exact_supply(u: u32, ) -> Weight {
 		(156_409_000 as Weight)
 			// Standard Error: 185_000
 			.saturating_add((488_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
 	fn swap_with_exact_target(u: u32, ) -> Weight {
 		(155_993_000 as Weight)
 			// Standard Error: 138_000
 			.saturating_add((654_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn abort_provisioning() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn refund_provision() -> Weight {
+		(79_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn set_protocol_fee_rate() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(92_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn forward_protocol_fee() -> Weight {
+		(121_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
 	}
 }
@@ -22,11 +22,11 @@
 
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
-use orml_traits::{Happened, MultiCurrency, RewardHandler};
+use orml_traits::{Happened, MultiCurrency, MultiReservableCurrency, RewardHandler};
 use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, MaybeDisplay, UniqueSaturatedInto, Zero},
-	DispatchResult, FixedPointNumber, RuntimeDebug,
+	traits::{AccountIdConversion, MaybeDisplay, One, Saturating, UniqueSaturatedInto, Zero},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, vec::Vec};
 use support::{SerpTreasury, DEXIncentives, DEXManager, Rate};
@@ -49,6 +49,53 @@ pub enum PoolId<AccountId> {
 	/// Rewards pool(SetterCurrencyId) (SETT) for market makers who provide Dex liquidity
 	/// for SettCurrency (System Stablecoins) pools only.
 	DexPremium(CurrencyId),
+
+	/// Rewards pool(IncentiveCurrencyId) (DRAM or MENA) for minters of
+	/// SettCurrency(CurrencyId) through Settmint positions, share is the
+	/// standard of position.
+	SettmintManager(CurrencyId),
+}
+
+/// The lp shares locked by a staker for a lock period, in return for a
+/// boosted share of the rewards pools.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct DexShareLock<BlockNumber> {
+	/// The amount of lp shares reserved for this lock.
+	pub amount: Balance,
+	/// The share added to rewards pools for this lock.
+	pub boosted_share: Balance,
+	/// The block number after which the lp shares can be unlocked without
+	/// penalty.
+	pub unlock_at: BlockNumber,
+}
+
+/// The id of a reward stream.
+pub type RewardStreamId = u32;
+
+/// A reward stream of a sponsor, which emits the reward currency to the
+/// stakers of a kind of lp share in proportion to their share.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RewardStream<AccountId, BlockNumber> {
+	/// The id of the reward stream, the reward debts of stakers are kept
+	/// under it.
+	pub id: RewardStreamId,
+	/// The sponsor who funded the reward stream.
+	pub sponsor: AccountId,
+	/// The native currency reserved from sponsor while the reward stream
+	/// exists.
+	pub deposit: Balance,
+	/// The amount of reward currency emitted per block.
+	pub reward_per_block: Balance,
+	/// The block number after which the reward stream stops emission.
+	pub end_block: BlockNumber,
+	/// The block number until which the rewards have been accumulated.
+	pub last_update_block: BlockNumber,
+	/// The funded reward which has not been emitted yet.
+	pub remaining_reward: Balance,
+	/// The emitted reward which has not been claimed by stakers yet.
+	pub unclaimed_reward: Balance,
+	/// The accumulated reward per share since the stream started.
+	pub accumulated_reward_per_share: FixedU128,
 }
 
 #[frame_support::pallet]
@@ -73,6 +120,11 @@ pub mod module {
 		#[pallet::constant]
 		type DexPremiumPool: Get<Self::AccountId>;
 
+		/// The vault account to keep rewards for type SettmintManager PoolId
+		/// Receives Setheum Dirham - DRAM/MENA
+		#[pallet::constant]
+		type SettMintIncentivePool: Get<Self::AccountId>;
+
 		/// The stable currency ids (SettCurrencies)
 		type StableCurrencyIds: Get<Vec<CurrencyId>>;
 
@@ -106,8 +158,8 @@ pub mod module {
 		/// SERP treasury to issue rewards in stablecoin (Setter (SETT)).
 		type SerpTreasury: SerpTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
-		/// Currency to transfer/issue assets
-		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+		/// Currency to transfer/issue/reserve assets
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
 		/// Dex to supply liquidity info
 		type Dex: DEXManager<Self::AccountId, CurrencyId, Balance>;
@@ -116,6 +168,27 @@ pub mod module {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The maximum number of locks a staker can hold for a kind of lp
+		/// share.
+		#[pallet::constant]
+		type MaxDexShareLocks: Get<u32>;
+
+		/// The maximum number of reward streams of a kind of lp share.
+		#[pallet::constant]
+		type MaxRewardStreams: Get<u32>;
+
+		/// The deposit of native currency reserved from the sponsor of a
+		/// reward stream, unreserved when the reward stream is removed.
+		#[pallet::constant]
+		type RewardStreamDeposit: Get<Balance>;
+
+		/// The period after the end of a reward stream during which stakers
+		/// can still claim its emitted reward. The reward stream is removed
+		/// once its emitted reward is all claimed or this period has passed,
+		/// and the rest is refunded to sponsor.
+		#[pallet::constant]
+		type RewardStreamClaimPeriod: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -126,6 +199,28 @@ pub mod module {
 		NotEnough,
 		/// Invalid currency id
 		InvalidCurrencyId,
+		/// The lock period is not supported
+		InvalidLockPeriod,
+		/// The multiplier of lock period must not be less than one
+		InvalidMultiplier,
+		/// The early unlock penalty rate must not be greater than one
+		InvalidPenaltyRate,
+		/// The staker has too many locks for this lp share
+		TooManyLocks,
+		/// The lock does not exist
+		LockNotFound,
+		/// The lock is not expired and early unlock is not allowed
+		StillLocked,
+		/// The reward stream already exists
+		RewardStreamExists,
+		/// The reward stream does not exist
+		RewardStreamNotFound,
+		/// The lp share has too many reward streams
+		TooManyRewardStreams,
+		/// The amount, emission rate or end block of reward stream is invalid
+		InvalidRewardStream,
+		/// No id is available for a new reward stream
+		NoAvailableRewardStreamId,
 	}
 
 	#[pallet::event]
@@ -137,6 +232,31 @@ pub mod module {
 		WithdrawDexShare(T::AccountId, CurrencyId, Balance),
 		/// Claim rewards. \[who, pool_id\]
 		ClaimRewards(T::AccountId, T::PoolId),
+		/// Lock Dex share. \[who, dex_share_type, lock_amount, boosted_share,
+		/// unlock_at\]
+		LockDexShare(T::AccountId, CurrencyId, Balance, Balance, T::BlockNumber),
+		/// Unlock Dex share, the penalty is charged for early unlock. \[who,
+		/// dex_share_type, unlock_amount, penalty_amount\]
+		UnlockDexShare(T::AccountId, CurrencyId, Balance, Balance),
+		/// The multiplier of lock period is updated. \[lock_period,
+		/// multiplier\]
+		LockPeriodMultiplierUpdated(T::BlockNumber, Option<Rate>),
+		/// The early unlock penalty is updated, `None` means early unlock is
+		/// disallowed. \[penalty_rate\]
+		EarlyUnlockPenaltyUpdated(Option<Rate>),
+		/// Reward stream is created. \[lp_currency_id, reward_currency_id,
+		/// sponsor, amount, reward_per_block, end_block\]
+		RewardStreamCreated(CurrencyId, CurrencyId, T::AccountId, Balance, Balance, T::BlockNumber),
+		/// Reward stream is cancelled and the remaining reward is refunded to
+		/// sponsor. \[lp_currency_id, reward_currency_id, refund_amount\]
+		RewardStreamCancelled(CurrencyId, CurrencyId, Balance),
+		/// Reward stream is ended and its emitted reward is all claimed or
+		/// the claim period has passed, the rest is refunded to sponsor.
+		/// \[lp_currency_id, reward_currency_id, refund_amount\]
+		RewardStreamRemoved(CurrencyId, CurrencyId, Balance),
+		/// Claim reward of reward stream. \[who, lp_currency_id,
+		/// reward_currency_id, reward_amount\]
+		ClaimStreamReward(T::AccountId, CurrencyId, CurrencyId, Balance),
 	}
 
 	/// Mapping from dex liquidity currency type to its Incentive rewards
@@ -152,6 +272,12 @@ pub mod module {
 	#[pallet::getter(fn dex_premium_rewards)]
 	pub type DexPremiumRewards<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
 
+	/// Mapping from SettCurrency type to its Incentive rewards amount per
+	/// period for Settmint minters.
+	#[pallet::storage]
+	#[pallet::getter(fn settmint_incentive_rewards)]
+	pub type SettMintIncentiveRewards<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	/// Mapping to AccumulatePeriod for reward accumulation
 	/// in blocknumber to accumulate rewards periodically.
 	/// The period to accumulate rewards.
@@ -159,6 +285,70 @@ pub mod module {
 	#[pallet::getter(fn accumulate_period)]
 	pub type AccumulatePeriod<T: Config> = StorageMap<_, Twox64Concat, BlockNumber, ValueQuery>;
 
+	/// Mapping from supported lock period to the multiplier applied on the
+	/// share of the locked lp shares.
+	///
+	/// LockPeriodMultipliers: map BlockNumber => Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn lock_period_multipliers)]
+	pub type LockPeriodMultipliers<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Rate, OptionQuery>;
+
+	/// The fraction of locked lp shares charged for unlocking before expiry,
+	/// early unlock is disallowed if it's not set.
+	///
+	/// EarlyUnlockPenalty: Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn early_unlock_penalty)]
+	pub type EarlyUnlockPenalty<T: Config> = StorageValue<_, Rate, OptionQuery>;
+
+	/// The locks of lp shares of stakers.
+	///
+	/// DexShareLocks: double_map AccountId, CurrencyId =>
+	/// Vec<DexShareLock>
+	#[pallet::storage]
+	#[pallet::getter(fn dex_share_locks)]
+	pub type DexShareLocks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyId,
+		Vec<DexShareLock<T::BlockNumber>>,
+		ValueQuery,
+	>;
+
+	/// The reward streams of lp shares.
+	///
+	/// RewardStreams: double_map CurrencyId, CurrencyId =>
+	/// Option<RewardStream>
+	#[pallet::storage]
+	#[pallet::getter(fn reward_streams)]
+	pub type RewardStreams<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyId,
+		Twox64Concat,
+		CurrencyId,
+		RewardStream<T::AccountId, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The id of the next reward stream.
+	///
+	/// NextRewardStreamId: RewardStreamId
+	#[pallet::storage]
+	#[pallet::getter(fn next_reward_stream_id)]
+	pub type NextRewardStreamId<T: Config> = StorageValue<_, RewardStreamId, ValueQuery>;
+
+	/// The reward of reward stream which has been settled for the current
+	/// share of staker.
+	///
+	/// StreamRewardDebts: double_map RewardStreamId, AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn stream_reward_debts)]
+	pub type StreamRewardDebts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, RewardStreamId, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -199,6 +389,13 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			<orml_rewards::Pallet<T>>::claim_rewards(&who, &pool_id);
+			match pool_id {
+				PoolId::DexIncentive(lp_currency_id) | PoolId::DexPremium(lp_currency_id) => {
+					Self::claim_stream_rewards(&who, lp_currency_id);
+					Self::reset_stream_reward_debts(&who, lp_currency_id);
+				}
+				PoolId::SettmintManager(_) => {}
+			}
 			Self::deposit_event(Event::ClaimRewards(who, pool_id));
 			Ok(().into())
 		}
@@ -238,6 +435,24 @@ pub mod module {
 			Ok(().into())
 		}
 
+		#[pallet::weight(<T as Config>::WeightInfo::update_settmint_incentive_rewards(updates.len() as u32))]
+		#[transactional]
+		pub fn update_settmint_incentive_rewards(
+			origin: OriginFor<T>,
+			updates: Vec<(CurrencyId, Balance)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (currency_id, amount) in updates {
+				// only SettCurrencies (system stablecoins) can be minted through Settmint
+				ensure!(
+					T::StableCurrencyIds::get().contains(&currency_id),
+					Error::<T>::InvalidCurrencyId,
+				);
+				SettMintIncentiveRewards::<T>::insert(currency_id, amount);
+			}
+			Ok(().into())
+		}
+
 		#[pallet::weight(<T as Config>::WeightInfo::update_accumulate_period(updates.len() as u32))]
 		#[transactional]
 		pub fn update_accumulate_period(
@@ -250,6 +465,237 @@ pub mod module {
 			}
 			Ok(().into())
 		}
+
+		/// Lock lp shares for a supported lock period. The lp shares are
+		/// reserved until the lock expires, and earn rewards with the share
+		/// boosted by the multiplier of the lock period.
+		///
+		/// - `lp_currency_id`: the lp share currency id.
+		/// - `amount`: the amount of lp shares to lock.
+		/// - `lock_period`: the lock period in blocks.
+		#[pallet::weight(<T as Config>::WeightInfo::lock_dex_share())]
+		#[transactional]
+		pub fn lock_dex_share(
+			origin: OriginFor<T>,
+			lp_currency_id: CurrencyId,
+			amount: Balance,
+			lock_period: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_lock_dex_share(&who, lp_currency_id, amount, lock_period)?;
+			Ok(().into())
+		}
+
+		/// Unlock the lp shares of a lock. Unlocking before expiry is charged
+		/// with the early unlock penalty, or disallowed if it's not set.
+		///
+		/// - `lp_currency_id`: the lp share currency id.
+		/// - `lock_index`: the index of the lock in the locks of caller.
+		#[pallet::weight(<T as Config>::WeightInfo::unlock_dex_share())]
+		#[transactional]
+		pub fn unlock_dex_share(
+			origin: OriginFor<T>,
+			lp_currency_id: CurrencyId,
+			lock_index: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_unlock_dex_share(&who, lp_currency_id, lock_index)?;
+			Ok(().into())
+		}
+
+		/// Update the multipliers of lock periods, `None` removes the lock
+		/// period. Existing locks are not affected.
+		#[pallet::weight(<T as Config>::WeightInfo::update_lock_period_multipliers(updates.len() as u32))]
+		#[transactional]
+		pub fn update_lock_period_multipliers(
+			origin: OriginFor<T>,
+			updates: Vec<(T::BlockNumber, Option<Rate>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (lock_period, multiplier) in updates {
+				ensure!(!lock_period.is_zero(), Error::<T>::InvalidLockPeriod);
+				match multiplier {
+					Some(rate) => {
+						ensure!(rate >= Rate::one(), Error::<T>::InvalidMultiplier);
+						LockPeriodMultipliers::<T>::insert(lock_period, rate);
+					}
+					None => LockPeriodMultipliers::<T>::remove(lock_period),
+				}
+				Self::deposit_event(Event::LockPeriodMultiplierUpdated(lock_period, multiplier));
+			}
+			Ok(().into())
+		}
+
+		/// Update the early unlock penalty rate, `None` disallows early
+		/// unlock.
+		#[pallet::weight(<T as Config>::WeightInfo::update_early_unlock_penalty())]
+		#[transactional]
+		pub fn update_early_unlock_penalty(
+			origin: OriginFor<T>,
+			penalty_rate: Option<Rate>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			match penalty_rate {
+				Some(rate) => {
+					ensure!(rate <= Rate::one(), Error::<T>::InvalidPenaltyRate);
+					EarlyUnlockPenalty::<T>::put(rate);
+				}
+				None => EarlyUnlockPenalty::<T>::kill(),
+			}
+			Self::deposit_event(Event::EarlyUnlockPenaltyUpdated(penalty_rate));
+			Ok(().into())
+		}
+
+		/// Create a reward stream for the stakers of a kind of lp share,
+		/// funded by the deposit of caller. `RewardStreamDeposit` of native
+		/// currency is reserved from caller until the reward stream is
+		/// removed. A reward stream which has ended but is still in its claim
+		/// period is taken over by the new one, its unclaimed reward stays
+		/// claimable by stakers.
+		///
+		/// - `lp_currency_id`: the lp share currency id.
+		/// - `reward_currency_id`: the currency id to reward.
+		/// - `amount`: the amount of reward currency to deposit.
+		/// - `reward_per_block`: the amount of reward currency emitted per block.
+		/// - `end_block`: the block number after which emission stops.
+		#[pallet::weight(<T as Config>::WeightInfo::create_reward_stream())]
+		#[transactional]
+		pub fn create_reward_stream(
+			origin: OriginFor<T>,
+			lp_currency_id: CurrencyId,
+			reward_currency_id: CurrencyId,
+			amount: Balance,
+			reward_per_block: Balance,
+			end_block: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				!amount.is_zero() && !reward_per_block.is_zero() && end_block > now,
+				Error::<T>::InvalidRewardStream
+			);
+
+			// remove the finished reward streams before counting them
+			let reward_currency_ids: Vec<CurrencyId> = RewardStreams::<T>::iter_prefix(lp_currency_id)
+				.map(|(reward_currency_id, _)| reward_currency_id)
+				.collect();
+			for id in reward_currency_ids {
+				Self::update_reward_stream(lp_currency_id, id);
+			}
+
+			let deposit = T::RewardStreamDeposit::get();
+			let stream = match Self::reward_streams(lp_currency_id, reward_currency_id) {
+				Some(ended) => {
+					ensure!(ended.last_update_block >= ended.end_block, Error::<T>::RewardStreamExists);
+					T::Currency::transfer(
+						reward_currency_id,
+						&Self::reward_stream_account_id(),
+						&ended.sponsor,
+						ended.remaining_reward,
+					)?;
+					T::Currency::unreserve(T::NativeCurrencyId::get(), &ended.sponsor, ended.deposit);
+					RewardStream {
+						sponsor: sponsor.clone(),
+						deposit,
+						reward_per_block,
+						end_block,
+						last_update_block: now,
+						remaining_reward: amount,
+						..ended
+					}
+				}
+				None => {
+					ensure!(
+						(RewardStreams::<T>::iter_prefix(lp_currency_id).count() as u32) < T::MaxRewardStreams::get(),
+						Error::<T>::TooManyRewardStreams
+					);
+					let id = NextRewardStreamId::<T>::try_mutate(
+						|next_id| -> sp_std::result::Result<RewardStreamId, DispatchError> {
+							let current_id = *next_id;
+							*next_id = next_id
+								.checked_add(1)
+								.ok_or(Error::<T>::NoAvailableRewardStreamId)?;
+							Ok(current_id)
+						},
+					)?;
+					RewardStream {
+						id,
+						sponsor: sponsor.clone(),
+						deposit,
+						reward_per_block,
+						end_block,
+						last_update_block: now,
+						remaining_reward: amount,
+						unclaimed_reward: Zero::zero(),
+						accumulated_reward_per_share: FixedU128::zero(),
+					}
+				}
+			};
+			T::Currency::reserve(T::NativeCurrencyId::get(), &sponsor, deposit)?;
+			T::Currency::transfer(reward_currency_id, &sponsor, &Self::reward_stream_account_id(), amount)?;
+			RewardStreams::<T>::insert(lp_currency_id, reward_currency_id, stream);
+			Self::deposit_event(Event::RewardStreamCreated(
+				lp_currency_id,
+				reward_currency_id,
+				sponsor,
+				amount,
+				reward_per_block,
+				end_block,
+			));
+			Ok(().into())
+		}
+
+		/// Cancel a reward stream and refund the reward which has not been
+		/// emitted to sponsor. The emitted reward can still be claimed by
+		/// stakers during the claim period, the reward stream is removed
+		/// at once if there is none. Only the sponsor or `UpdateOrigin` can
+		/// cancel.
+		///
+		/// - `lp_currency_id`: the lp share currency id.
+		/// - `reward_currency_id`: the currency id of reward stream.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_reward_stream())]
+		#[transactional]
+		pub fn cancel_reward_stream(
+			origin: OriginFor<T>,
+			lp_currency_id: CurrencyId,
+			reward_currency_id: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			let maybe_sponsor = match T::UpdateOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			Self::update_reward_stream(lp_currency_id, reward_currency_id);
+
+			let refund_amount = RewardStreams::<T>::try_mutate(
+				lp_currency_id,
+				reward_currency_id,
+				|maybe_stream| -> sp_std::result::Result<Balance, DispatchError> {
+					let stream = maybe_stream.as_mut().ok_or(Error::<T>::RewardStreamNotFound)?;
+					if let Some(who) = maybe_sponsor {
+						ensure!(who == stream.sponsor, DispatchError::BadOrigin);
+					}
+
+					let refund_amount = stream.remaining_reward;
+					T::Currency::transfer(
+						reward_currency_id,
+						&Self::reward_stream_account_id(),
+						&stream.sponsor,
+						refund_amount,
+					)?;
+					stream.remaining_reward = Zero::zero();
+					stream.end_block = stream.last_update_block;
+					Ok(refund_amount)
+				},
+			)?;
+			Self::deposit_event(Event::RewardStreamCancelled(
+				lp_currency_id,
+				reward_currency_id,
+				refund_amount,
+			));
+			Self::update_reward_stream(lp_currency_id, reward_currency_id);
+			Ok(().into())
+		}
 	}
 }
 
@@ -257,6 +703,249 @@ impl<T: Config> Pallet<T> {
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account()
 	}
+
+	/// The account which keeps the deposits of reward streams.
+	pub fn reward_stream_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account(b"streams")
+	}
+
+	/// Accumulate the reward emitted by a reward stream since its last
+	/// update. Nothing is emitted while there is no staker. The reward
+	/// stream is removed if it has finished.
+	fn update_reward_stream(lp_currency_id: CurrencyId, reward_currency_id: CurrencyId) {
+		let now = frame_system::Pallet::<T>::block_number();
+		RewardStreams::<T>::mutate_exists(lp_currency_id, reward_currency_id, |maybe_stream| {
+			if let Some(stream) = maybe_stream {
+				let until = now.min(stream.end_block);
+				if until > stream.last_update_block {
+					let total_shares =
+						orml_rewards::Pools::<T>::get(PoolId::DexIncentive(lp_currency_id)).total_shares;
+					if !total_shares.is_zero() {
+						let blocks: Balance = until.saturating_sub(stream.last_update_block).unique_saturated_into();
+						let reward = stream
+							.reward_per_block
+							.saturating_mul(blocks)
+							.min(stream.remaining_reward);
+						stream.accumulated_reward_per_share = stream
+							.accumulated_reward_per_share
+							.saturating_add(FixedU128::checked_from_rational(reward, total_shares).unwrap_or_default());
+						stream.remaining_reward = stream.remaining_reward.saturating_sub(reward);
+						stream.unclaimed_reward = stream.unclaimed_reward.saturating_add(reward);
+						// the reward stream ends once all the funded reward is emitted
+						if stream.remaining_reward.is_zero() {
+							stream.end_block = until;
+						}
+					}
+					stream.last_update_block = until;
+				}
+
+				if Self::is_reward_stream_finished(stream, now) {
+					Self::remove_reward_stream(lp_currency_id, reward_currency_id, stream);
+					*maybe_stream = None;
+				}
+			}
+		});
+	}
+
+	/// Whether a reward stream has ended, and its emitted reward is all
+	/// claimed or the claim period has passed.
+	fn is_reward_stream_finished(stream: &RewardStream<T::AccountId, T::BlockNumber>, now: T::BlockNumber) -> bool {
+		stream.last_update_block >= stream.end_block
+			&& (stream.unclaimed_reward.is_zero()
+				|| now >= stream.end_block.saturating_add(T::RewardStreamClaimPeriod::get()))
+	}
+
+	/// Refund the rest of a finished reward stream and the deposit to
+	/// sponsor, and clear the reward debts of its stakers. The caller removes
+	/// the reward stream from storage.
+	fn remove_reward_stream(
+		lp_currency_id: CurrencyId,
+		reward_currency_id: CurrencyId,
+		stream: &RewardStream<T::AccountId, T::BlockNumber>,
+	) {
+		let refund_amount = stream.remaining_reward.saturating_add(stream.unclaimed_reward);
+		// it should not affect the process, ignore the result to continue. if it
+		// fails, the rest will be kept by the reward stream account.
+		if T::Currency::transfer(
+			reward_currency_id,
+			&Self::reward_stream_account_id(),
+			&stream.sponsor,
+			refund_amount,
+		)
+		.is_err()
+		{
+			log::warn!(
+				target: "incentives",
+				"refund reward stream: failed to transfer {:?} {:?} to {:?}. \
+				This is unexpected but should be safe",
+				refund_amount, reward_currency_id, stream.sponsor
+			);
+		}
+		T::Currency::unreserve(T::NativeCurrencyId::get(), &stream.sponsor, stream.deposit);
+		StreamRewardDebts::<T>::remove_prefix(stream.id);
+		Self::deposit_event(Event::RewardStreamRemoved(
+			lp_currency_id,
+			reward_currency_id,
+			refund_amount,
+		));
+	}
+
+	/// Pay the pending rewards of all reward streams of a kind of lp share to
+	/// `who`.
+	fn claim_stream_rewards(who: &T::AccountId, lp_currency_id: CurrencyId) {
+		let share = <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(PoolId::DexIncentive(lp_currency_id), who).0;
+		let reward_currency_ids: Vec<CurrencyId> = RewardStreams::<T>::iter_prefix(lp_currency_id)
+			.map(|(reward_currency_id, _)| reward_currency_id)
+			.collect();
+
+		for reward_currency_id in reward_currency_ids {
+			Self::update_reward_stream(lp_currency_id, reward_currency_id);
+			if let Some(stream) = Self::reward_streams(lp_currency_id, reward_currency_id) {
+				let reward = stream
+					.accumulated_reward_per_share
+					.saturating_mul_int(share)
+					.saturating_sub(Self::stream_reward_debts(stream.id, who))
+					.min(stream.unclaimed_reward);
+				if reward.is_zero() {
+					continue;
+				}
+
+				// it should not affect the process, ignore the result to continue. if it
+				// fails, just the user will not be rewarded.
+				if T::Currency::transfer(reward_currency_id, &Self::reward_stream_account_id(), who, reward).is_ok() {
+					Self::deposit_event(Event::ClaimStreamReward(
+						who.clone(),
+						lp_currency_id,
+						reward_currency_id,
+						reward,
+					));
+					let now = frame_system::Pallet::<T>::block_number();
+					RewardStreams::<T>::mutate_exists(lp_currency_id, reward_currency_id, |maybe_stream| {
+						if let Some(stream) = maybe_stream {
+							stream.unclaimed_reward = stream.unclaimed_reward.saturating_sub(reward);
+							if Self::is_reward_stream_finished(stream, now) {
+								Self::remove_reward_stream(lp_currency_id, reward_currency_id, stream);
+								*maybe_stream = None;
+							}
+						}
+					});
+				}
+			}
+		}
+	}
+
+	/// Mark the rewards of all reward streams of a kind of lp share for the
+	/// current share of `who` as settled.
+	fn reset_stream_reward_debts(who: &T::AccountId, lp_currency_id: CurrencyId) {
+		let share = <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(PoolId::DexIncentive(lp_currency_id), who).0;
+		for (_, stream) in RewardStreams::<T>::iter_prefix(lp_currency_id) {
+			let debt = stream.accumulated_reward_per_share.saturating_mul_int(share);
+			if debt.is_zero() {
+				StreamRewardDebts::<T>::remove(stream.id, who);
+			} else {
+				StreamRewardDebts::<T>::insert(stream.id, who, debt);
+			}
+		}
+	}
+
+	/// Add share of lp shares to the rewards pools, the rewards of reward
+	/// streams are settled before the share changes.
+	fn add_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, share: Balance) {
+		Self::claim_stream_rewards(who, lp_currency_id);
+		<orml_rewards::Pallet<T>>::add_share(who, PoolId::DexIncentive(lp_currency_id), share);
+		<orml_rewards::Pallet<T>>::add_share(who, PoolId::DexPremium(lp_currency_id), share);
+		Self::reset_stream_reward_debts(who, lp_currency_id);
+	}
+
+	/// Remove share of lp shares from the rewards pools, the rewards of
+	/// reward streams are settled before the share changes.
+	fn remove_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, share: Balance) {
+		Self::claim_stream_rewards(who, lp_currency_id);
+		<orml_rewards::Pallet<T>>::remove_share(who, PoolId::DexIncentive(lp_currency_id), share);
+		<orml_rewards::Pallet<T>>::remove_share(who, PoolId::DexPremium(lp_currency_id), share);
+		Self::reset_stream_reward_debts(who, lp_currency_id);
+	}
+
+	/// The share in rewards pools of the locked lp shares of `who`.
+	pub fn locked_share(who: &T::AccountId, lp_currency_id: CurrencyId) -> Balance {
+		Self::dex_share_locks(who, lp_currency_id)
+			.iter()
+			.fold(Zero::zero(), |total: Balance, lock| total.saturating_add(lock.boosted_share))
+	}
+
+	fn do_lock_dex_share(
+		who: &T::AccountId,
+		lp_currency_id: CurrencyId,
+		amount: Balance,
+		lock_period: T::BlockNumber,
+	) -> DispatchResult {
+		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+		let multiplier = Self::lock_period_multipliers(lock_period).ok_or(Error::<T>::InvalidLockPeriod)?;
+		let boosted_share = multiplier.saturating_mul_int(amount);
+		let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(lock_period);
+
+		DexShareLocks::<T>::try_mutate(who, lp_currency_id, |locks| -> DispatchResult {
+			ensure!(
+				locks.len() < T::MaxDexShareLocks::get() as usize,
+				Error::<T>::TooManyLocks
+			);
+			T::Currency::reserve(lp_currency_id, who, amount)?;
+			locks.push(DexShareLock {
+				amount,
+				boosted_share,
+				unlock_at,
+			});
+			Ok(())
+		})?;
+
+		Self::add_dex_share(who, lp_currency_id, boosted_share);
+		Self::deposit_event(Event::LockDexShare(
+			who.clone(),
+			lp_currency_id,
+			amount,
+			boosted_share,
+			unlock_at,
+		));
+		Ok(())
+	}
+
+	fn do_unlock_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, lock_index: u32) -> DispatchResult {
+		let lock = DexShareLocks::<T>::try_mutate_exists(
+			who,
+			lp_currency_id,
+			|maybe_locks| -> sp_std::result::Result<DexShareLock<T::BlockNumber>, DispatchError> {
+				let locks = maybe_locks.as_mut().ok_or(Error::<T>::LockNotFound)?;
+				ensure!((lock_index as usize) < locks.len(), Error::<T>::LockNotFound);
+				let lock = locks.remove(lock_index as usize);
+				if locks.is_empty() {
+					*maybe_locks = None;
+				}
+				Ok(lock)
+			},
+		)?;
+
+		let penalty = if frame_system::Pallet::<T>::block_number() < lock.unlock_at {
+			Self::early_unlock_penalty()
+				.ok_or(Error::<T>::StillLocked)?
+				.saturating_mul_int(lock.amount)
+		} else {
+			Zero::zero()
+		};
+
+		T::Currency::unreserve(lp_currency_id, who, lock.amount);
+		if !penalty.is_zero() {
+			T::SerpTreasury::deposit_serplus(lp_currency_id, who, penalty)?;
+		}
+
+		Self::remove_dex_share(who, lp_currency_id, lock.boosted_share);
+		Self::deposit_event(Event::UnlockDexShare(
+			who.clone(),
+			lp_currency_id,
+			lock.amount,
+			penalty,
+		));
+		Ok(())
+	}
 }
 
 impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
@@ -264,47 +953,50 @@ impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
 
 		T::Currency::transfer(lp_currency_id, who, &Self::account_id(), amount)?;
-		<orml_rewards::Pallet<T>>::add_share(
-			who,
-			PoolId::DexIncentive(lp_currency_id),
-			amount.unique_saturated_into(),
-		);
-		<orml_rewards::Pallet<T>>::add_share(
-			who,
-			PoolId::DexPremium(lp_currency_id),
-			amount.unique_saturated_into(),
-		);
+		Self::add_dex_share(who, lp_currency_id, amount.unique_saturated_into());
 		Self::deposit_event(Event::DepositDexShare(who.clone(), lp_currency_id, amount));
 		Ok(())
 	}
 
 	fn do_withdraw_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
 		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+		// the share of locked lp shares can only be removed by unlocking
+		let required_share = amount.saturating_add(Self::locked_share(who, lp_currency_id));
 		ensure!(
 			<orml_rewards::Pallet<T>>::share_and_withdrawn_reward(
 				PoolId::DexIncentive(lp_currency_id), &who
-			).0 >= amount && <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(
+			).0 >= required_share && <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(
 				PoolId::DexPremium(lp_currency_id), &who
-			).0 >= amount,
+			).0 >= required_share,
 			Error::<T>::NotEnough,
 		);
 
 		T::Currency::transfer(lp_currency_id, &Self::account_id(), &who, amount)?;
-		<orml_rewards::Pallet<T>>::remove_share(
-			who,
-			PoolId::DexIncentive(lp_currency_id),
-			amount.unique_saturated_into(),
-		);
-		<orml_rewards::Pallet<T>>::remove_share(
-			who,
-			PoolId::DexPremium(lp_currency_id),
-			amount.unique_saturated_into(),
-		);
+		Self::remove_dex_share(who, lp_currency_id, amount.unique_saturated_into());
 		Self::deposit_event(Event::WithdrawDexShare(who.clone(), lp_currency_id, amount));
 		Ok(())
 	}
 }
 
+/// Update the share of Settmint minter in the rewards pool of SettCurrency
+/// when the standard of Settmint position changes.
+pub struct OnUpdateSettMint<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpdateSettMint<T> {
+	fn happened(info: &(T::AccountId, CurrencyId, Amount, Balance)) {
+		let (who, currency_id, adjustment, _previous_amount) = info;
+		let adjustment_abs =
+			sp_std::convert::TryInto::<Balance>::try_into(adjustment.saturating_abs()).unwrap_or_default();
+
+		if !adjustment_abs.is_zero() {
+			if adjustment.is_positive() {
+				<orml_rewards::Pallet<T>>::add_share(who, PoolId::SettmintManager(*currency_id), adjustment_abs);
+			} else {
+				<orml_rewards::Pallet<T>>::remove_share(who, PoolId::SettmintManager(*currency_id), adjustment_abs);
+			}
+		}
+	}
+}
+
 impl<T: Config> RewardHandler<T::AccountId> for Pallet<T> {
 	type Balance = Balance;
 	type CurrencyId = CurrencyId;
@@ -358,6 +1050,23 @@ impl<T: Config> RewardHandler<T::AccountId> for Pallet<T> {
 								accumulated_premium = accumulated_premium.saturating_add(premium_reward);
 							}
 						}
+
+						PoolId::SettmintManager(currency_id) => {
+							let incentive_reward = Self::settmint_incentive_rewards(currency_id);
+
+							/// issue Dex Incentive Currency for Settmint minters
+							if !incentive_reward.is_zero()
+								&& T::Currency::deposit(
+									incentive_currency_id,
+									&T::SettMintIncentivePool::get(),
+									incentive_reward,
+								)
+								.is_ok()
+							{
+								callback(pool_id, incentive_reward);
+								accumulated_incentive = accumulated_incentive.saturating_add(incentive_reward);
+							}
+						}
 					}
 				}
 			}
@@ -375,6 +1084,7 @@ impl<T: Config> RewardHandler<T::AccountId> for Pallet<T> {
 		let (pool_account, currency_id) = match pool_id {
 			PoolId::DexIncentive(_) => (T::DexIncentivePool::get(), T::IncentiveCurrencyId::get()),
 			PoolId::DexPremium(_) => (T::DexPremiumPool::get(), T::PremiumCurrencyId::get()),
+			PoolId::SettmintManager(_) => (T::SettMintIncentivePool::get(), T::IncentiveCurrencyId::get()),
 		};
 
 		// payout the reward to user from the pool. it should not affect the
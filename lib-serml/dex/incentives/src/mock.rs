@@ -39,6 +39,8 @@ pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CAROL: AccountId = 3;
+pub const SERP_TREASURY: AccountId = 12;
 
 // Currencies constants - CurrencyId/TokenSymbol
 pub const DNAR: CurrencyId = CurrencyId::Token(TokenSymbol::DNAR);
@@ -198,8 +200,8 @@ impl SerpTreasury<AccountId> for MockSerpTreasury {
 		unimplemented!()
 	}
 
-	fn deposit_serplus(_: CurrencyId, _: AccountId, _: Balance) -> DispatchResult {
-		unimplemented!()
+	fn deposit_serplus(currency_id: CurrencyId, from: &AccountId, serplus: Balance) -> DispatchResult {
+		TokensModule::transfer(currency_id, from, &SERP_TREASURY, serplus)
 	}
 
 	fn deposit_setter(_: AccountId, _:Balance) -> DispatchResult {
@@ -279,6 +281,7 @@ parameter_types! {
 	// TODO - implemented from `setheum_dex` module.
 	pub const DexIncentivePool: AccountId = 10;
 	pub const DexPremiumPool: AccountId = 11;
+	pub const SettMintIncentivePool: AccountId = 13;
 	pub const IncentiveCurrencyId: CurrencyId = DRAM;
 	pub const PremiumCurrencyId: CurrencyId = SETT;
 	pub const NativeCurrencyId: CurrencyId = DNAR;
@@ -296,6 +299,10 @@ parameter_types! {
 		GIPJ, // Setheum GIP (Gibraltar Pound stablecoin)
 	];
 	pub const IncentivesPalletId: PalletId = PalletId(*b"set/inct");
+	pub const MaxDexShareLocks: u32 = 2;
+	pub const MaxRewardStreams: u32 = 2;
+	pub const RewardStreamDeposit: Balance = 100;
+	pub const RewardStreamClaimPeriod: BlockNumber = 20;
 }
 
 ord_parameter_types! {
@@ -306,6 +313,7 @@ impl Config for Runtime {
 	type Event = Event;
 	type DexIncentivePool = DexIncentivePool;
 	type DexPremiumPool = DexPremiumPool;
+	type SettMintIncentivePool = SettMintIncentivePool;
 	type IncentiveCurrencyId = IncentiveCurrencyId;
 	type PremiumCurrencyId = PremiumCurrencyId;
 	type ExtraCurrencyId = ExtraCurrencyId
@@ -317,6 +325,10 @@ impl Config for Runtime {
 	type Currency = TokensModule;
 	type Dex = MockDex;
 	type PalletId = IncentivesPalletId;
+	type MaxDexShareLocks = MaxDexShareLocks;
+	type MaxRewardStreams = MaxRewardStreams;
+	type RewardStreamDeposit = RewardStreamDeposit;
+	type RewardStreamClaimPeriod = RewardStreamClaimPeriod;
 	type WeightInfo = ();
 }
 
@@ -24,7 +24,7 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
 use orml_rewards::PoolInfo;
-use orml_traits::MultiCurrency;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
 #[test]
@@ -218,6 +218,83 @@ fn update_dex_incentive_rewards_works() {
 	});
 }
 
+#[test]
+fn update_settmint_incentive_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_settmint_incentive_rewards(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_settmint_incentive_rewards(Origin::signed(4), vec![(DNAR, 200)]),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_eq!(IncentivesModule::settmint_incentive_rewards(USDJ), 0);
+
+		assert_ok!(IncentivesModule::update_settmint_incentive_rewards(
+			Origin::signed(4),
+			vec![(USDJ, 200), (SETT, 1000)],
+		));
+		assert_eq!(IncentivesModule::settmint_incentive_rewards(USDJ), 200);
+		assert_eq!(IncentivesModule::settmint_incentive_rewards(SETT), 1000);
+
+		assert_ok!(IncentivesModule::update_settmint_incentive_rewards(
+			Origin::signed(4),
+			vec![(SETT, 100), (SETT, 300), (SETT, 500)],
+		));
+		assert_eq!(IncentivesModule::settmint_incentive_rewards(SETT), 500);
+	});
+}
+
+#[test]
+fn on_update_settmint_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			RewardsModule::pools(PoolId::SettmintManager(USDJ)),
+			PoolInfo::default()
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::SettmintManager(USDJ), ALICE),
+			(0, 0)
+		);
+
+		OnUpdateSettMint::<Runtime>::happened(&(ALICE, USDJ, 100, 0));
+		assert_eq!(
+			RewardsModule::pools(PoolId::SettmintManager(USDJ)),
+			PoolInfo {
+				total_shares: 100,
+				total_rewards: 0,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::SettmintManager(USDJ), ALICE),
+			(100, 0)
+		);
+
+		OnUpdateSettMint::<Runtime>::happened(&(BOB, USDJ, 100, 500));
+		OnUpdateSettMint::<Runtime>::happened(&(ALICE, USDJ, -40, 100));
+		assert_eq!(
+			RewardsModule::pools(PoolId::SettmintManager(USDJ)),
+			PoolInfo {
+				total_shares: 160,
+				total_rewards: 0,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::SettmintManager(USDJ), ALICE),
+			(60, 0)
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::SettmintManager(USDJ), BOB),
+			(100, 0)
+		);
+	});
+}
+
 #[test]
 fn pay_out_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -235,6 +312,11 @@ fn pay_out_works() {
 		IncentivesModule::payout(&ALICE, PoolId::DexPremium(DNAR), 1000);
 		assert_eq!(TokensModule::free_balance(SETT, &DexPremiumPool::get()), 9000);
 		assert_eq!(TokensModule::free_balance(SETT, &ALICE), 1000);
+
+		assert_ok!(TokensModule::deposit(DRAM, &SettMintIncentivePool::get(), 10000));
+		IncentivesModule::payout(&BOB, PoolId::SettmintManager(USDJ), 1000);
+		assert_eq!(TokensModule::free_balance(DRAM, &SettMintIncentivePool::get()), 9000);
+		assert_eq!(TokensModule::free_balance(DRAM, &BOB), 1000);
 	});
 }
 
@@ -267,5 +349,611 @@ fn accumulate_reward_works() {
 		);
 
 		assert_eq!(IncentivesModule::accumulate_reward(59, |_, _| {}), vec![]);
+
+		assert_ok!(IncentivesModule::update_settmint_incentive_rewards(
+			Origin::signed(4),
+			vec![(USDJ, 500)],
+		));
+		RewardsModule::add_share(&BOB, PoolId::SettmintManager(USDJ), 1);
+		assert_eq!(
+			IncentivesModule::accumulate_reward(80, |_, _| {}),
+			vec![(DRAM, 3800), (SETT, 3300)]
+		);
+	});
+}
+
+#[test]
+fn update_lock_period_multipliers_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_lock_period_multipliers(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_lock_period_multipliers(
+				Origin::signed(4),
+				vec![(100, Some(Rate::saturating_from_rational(1, 2)))]
+			),
+			Error::<Runtime>::InvalidMultiplier
+		);
+		assert_noop!(
+			IncentivesModule::update_lock_period_multipliers(Origin::signed(4), vec![(0, Some(Rate::one()))]),
+			Error::<Runtime>::InvalidLockPeriod
+		);
+
+		assert_ok!(IncentivesModule::update_lock_period_multipliers(
+			Origin::signed(4),
+			vec![(100, Some(Rate::saturating_from_rational(3, 2))), (200, Some(Rate::saturating_from_integer(2)))]
+		));
+		System::assert_last_event(Event::incentives(crate::Event::LockPeriodMultiplierUpdated(
+			200,
+			Some(Rate::saturating_from_integer(2)),
+		)));
+		assert_eq!(
+			IncentivesModule::lock_period_multipliers(100),
+			Some(Rate::saturating_from_rational(3, 2))
+		);
+
+		assert_ok!(IncentivesModule::update_lock_period_multipliers(
+			Origin::signed(4),
+			vec![(200, None)]
+		));
+		assert_eq!(IncentivesModule::lock_period_multipliers(200), None);
+	});
+}
+
+#[test]
+fn update_early_unlock_penalty_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_early_unlock_penalty(Origin::signed(ALICE), None),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_early_unlock_penalty(Origin::signed(4), Some(Rate::saturating_from_integer(2))),
+			Error::<Runtime>::InvalidPenaltyRate
+		);
+
+		assert_ok!(IncentivesModule::update_early_unlock_penalty(
+			Origin::signed(4),
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		System::assert_last_event(Event::incentives(crate::Event::EarlyUnlockPenaltyUpdated(Some(
+			Rate::saturating_from_rational(1, 10)
+		))));
+		assert_eq!(
+			IncentivesModule::early_unlock_penalty(),
+			Some(Rate::saturating_from_rational(1, 10))
+		);
+
+		assert_ok!(IncentivesModule::update_early_unlock_penalty(Origin::signed(4), None));
+		assert_eq!(IncentivesModule::early_unlock_penalty(), None);
+	});
+}
+
+#[test]
+fn lock_dex_share_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &ALICE, 10000));
+		assert_ok!(IncentivesModule::update_lock_period_multipliers(
+			Origin::signed(4),
+			vec![(100, Some(Rate::saturating_from_rational(3, 2)))]
+		));
+
+		assert_noop!(
+			IncentivesModule::lock_dex_share(Origin::signed(ALICE), CHFJ_SETT_LP, 1000, 50),
+			Error::<Runtime>::InvalidLockPeriod
+		);
+		assert_noop!(
+			IncentivesModule::lock_dex_share(Origin::signed(ALICE), DNAR, 1000, 100),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_ok!(IncentivesModule::lock_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000,
+			100
+		));
+		System::assert_last_event(Event::incentives(crate::Event::LockDexShare(
+			ALICE,
+			CHFJ_SETT_LP,
+			1000,
+			1500,
+			101,
+		)));
+		assert_eq!(TokensModule::free_balance(CHFJ_SETT_LP, &ALICE), 9000);
+		assert_eq!(TokensModule::reserved_balance(CHFJ_SETT_LP, &ALICE), 1000);
+		assert_eq!(
+			IncentivesModule::dex_share_locks(ALICE, CHFJ_SETT_LP),
+			vec![DexShareLock {
+				amount: 1000,
+				boosted_share: 1500,
+				unlock_at: 101,
+			}]
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexIncentive(CHFJ_SETT_LP), ALICE),
+			(1500, 0)
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexPremium(CHFJ_SETT_LP), ALICE),
+			(1500, 0)
+		);
+
+		// the share of locked lp shares can not be withdrawn
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000
+		));
+		assert_noop!(
+			IncentivesModule::withdraw_dex_share(Origin::signed(ALICE), CHFJ_SETT_LP, 1001),
+			Error::<Runtime>::NotEnough,
+		);
+		assert_ok!(IncentivesModule::withdraw_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000
+		));
+
+		assert_ok!(IncentivesModule::lock_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000,
+			100
+		));
+		assert_noop!(
+			IncentivesModule::lock_dex_share(Origin::signed(ALICE), CHFJ_SETT_LP, 1000, 100),
+			Error::<Runtime>::TooManyLocks
+		);
+	});
+}
+
+#[test]
+fn unlock_dex_share_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &ALICE, 10000));
+		assert_ok!(IncentivesModule::update_lock_period_multipliers(
+			Origin::signed(4),
+			vec![(100, Some(Rate::saturating_from_rational(3, 2)))]
+		));
+		assert_ok!(IncentivesModule::lock_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000,
+			100
+		));
+		assert_ok!(IncentivesModule::lock_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			2000,
+			100
+		));
+
+		assert_noop!(
+			IncentivesModule::unlock_dex_share(Origin::signed(ALICE), CHFJ_SETT_LP, 2),
+			Error::<Runtime>::LockNotFound
+		);
+		// early unlock is disallowed without penalty
+		assert_noop!(
+			IncentivesModule::unlock_dex_share(Origin::signed(ALICE), CHFJ_SETT_LP, 0),
+			Error::<Runtime>::StillLocked
+		);
+
+		// early unlock is charged with penalty
+		assert_ok!(IncentivesModule::update_early_unlock_penalty(
+			Origin::signed(4),
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		assert_ok!(IncentivesModule::unlock_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1
+		));
+		System::assert_last_event(Event::incentives(crate::Event::UnlockDexShare(
+			ALICE,
+			CHFJ_SETT_LP,
+			2000,
+			200,
+		)));
+		assert_eq!(TokensModule::free_balance(CHFJ_SETT_LP, &ALICE), 8800);
+		assert_eq!(TokensModule::reserved_balance(CHFJ_SETT_LP, &ALICE), 1000);
+		assert_eq!(TokensModule::free_balance(CHFJ_SETT_LP, &SERP_TREASURY), 200);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexIncentive(CHFJ_SETT_LP), ALICE),
+			(1500, 0)
+		);
+
+		// unlock after expiry is free
+		System::set_block_number(101);
+		assert_ok!(IncentivesModule::unlock_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			0
+		));
+		System::assert_last_event(Event::incentives(crate::Event::UnlockDexShare(
+			ALICE,
+			CHFJ_SETT_LP,
+			1000,
+			0,
+		)));
+		assert_eq!(TokensModule::free_balance(CHFJ_SETT_LP, &ALICE), 9800);
+		assert_eq!(TokensModule::reserved_balance(CHFJ_SETT_LP, &ALICE), 0);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexIncentive(CHFJ_SETT_LP), ALICE),
+			(0, 0)
+		);
+		assert!(!DexShareLocks::<Runtime>::contains_key(ALICE, CHFJ_SETT_LP));
+	});
+}
+
+#[test]
+fn create_reward_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(DNAR, &CAROL, 10000));
+
+		assert_noop!(
+			IncentivesModule::create_reward_stream(Origin::signed(CAROL), DNAR, DNAR, 10000, 100, 101),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::create_reward_stream(Origin::signed(CAROL), CHFJ_SETT_LP, DNAR, 0, 100, 101),
+			Error::<Runtime>::InvalidRewardStream
+		);
+		assert_noop!(
+			IncentivesModule::create_reward_stream(Origin::signed(CAROL), CHFJ_SETT_LP, DNAR, 10000, 0, 101),
+			Error::<Runtime>::InvalidRewardStream
+		);
+		assert_noop!(
+			IncentivesModule::create_reward_stream(Origin::signed(CAROL), CHFJ_SETT_LP, DNAR, 10000, 100, 1),
+			Error::<Runtime>::InvalidRewardStream
+		);
+
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR,
+			5000,
+			100,
+			101
+		));
+		System::assert_last_event(Event::incentives(crate::Event::RewardStreamCreated(
+			CHFJ_SETT_LP,
+			DNAR,
+			CAROL,
+			5000,
+			100,
+			101,
+		)));
+		assert_eq!(TokensModule::free_balance(DNAR, &CAROL), 4900);
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 100);
+		assert_eq!(
+			TokensModule::free_balance(DNAR, &IncentivesModule::reward_stream_account_id()),
+			5000
+		);
+		assert_eq!(
+			IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR),
+			Some(RewardStream {
+				id: 0,
+				sponsor: CAROL,
+				deposit: 100,
+				reward_per_block: 100,
+				end_block: 101,
+				last_update_block: 1,
+				remaining_reward: 5000,
+				unclaimed_reward: 0,
+				accumulated_reward_per_share: FixedU128::zero(),
+			})
+		);
+		assert_eq!(IncentivesModule::next_reward_stream_id(), 1);
+
+		assert_noop!(
+			IncentivesModule::create_reward_stream(Origin::signed(CAROL), CHFJ_SETT_LP, DNAR, 5000, 100, 101),
+			Error::<Runtime>::RewardStreamExists
+		);
+		assert_ok!(TokensModule::deposit(SETT, &CAROL, 10000));
+		assert_ok!(TokensModule::deposit(USDJ, &CAROL, 10000));
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			SETT,
+			5000,
+			100,
+			101
+		));
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 200);
+		assert_noop!(
+			IncentivesModule::create_reward_stream(Origin::signed(CAROL), CHFJ_SETT_LP, USDJ, 5000, 100, 101),
+			Error::<Runtime>::TooManyRewardStreams
+		);
+	});
+}
+
+#[test]
+fn reward_streams_emit_to_stakers_by_share() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &ALICE, 1000));
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &BOB, 1000));
+		assert_ok!(TokensModule::deposit(DNAR, &CAROL, 10200));
+		assert_ok!(TokensModule::deposit(SETT, &CAROL, 10000));
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR,
+			10000,
+			100,
+			51
+		));
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			SETT,
+			10000,
+			10,
+			51
+		));
+
+		// nothing is emitted before anyone stakes
+		System::set_block_number(5);
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000
+		));
+		assert_eq!(
+			IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR).unwrap().remaining_reward,
+			10000
+		);
+
+		// claim pays out every reward stream at once
+		System::set_block_number(15);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_eq!(TokensModule::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(TokensModule::free_balance(SETT, &ALICE), 100);
+
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			CHFJ_SETT_LP,
+			1000
+		));
+		assert_eq!(TokensModule::free_balance(DNAR, &BOB), 0);
+
+		System::set_block_number(25);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(BOB),
+			PoolId::DexPremium(CHFJ_SETT_LP)
+		));
+		assert_eq!(TokensModule::free_balance(DNAR, &ALICE), 1500);
+		assert_eq!(TokensModule::free_balance(DNAR, &BOB), 500);
+		assert_eq!(TokensModule::free_balance(SETT, &ALICE), 150);
+		assert_eq!(TokensModule::free_balance(SETT, &BOB), 50);
+
+		// emission stops at the end block
+		System::set_block_number(60);
+		assert_ok!(IncentivesModule::withdraw_dex_share(
+			Origin::signed(BOB),
+			CHFJ_SETT_LP,
+			1000
+		));
+		System::assert_has_event(Event::incentives(crate::Event::ClaimStreamReward(
+			BOB,
+			CHFJ_SETT_LP,
+			DNAR,
+			1300,
+		)));
+		assert_eq!(TokensModule::free_balance(DNAR, &BOB), 1800);
+		assert_eq!(
+			IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR).unwrap().remaining_reward,
+			5400
+		);
+	});
+}
+
+#[test]
+fn cancel_reward_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &ALICE, 1000));
+		assert_ok!(TokensModule::deposit(DNAR, &CAROL, 10100));
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR,
+			10000,
+			100,
+			101
+		));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000
+		));
+
+		System::set_block_number(11);
+		assert_noop!(
+			IncentivesModule::cancel_reward_stream(Origin::signed(BOB), CHFJ_SETT_LP, DNAR),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::cancel_reward_stream(Origin::signed(CAROL), CHFJ_SETT_LP, SETT),
+			Error::<Runtime>::RewardStreamNotFound
+		);
+		assert_ok!(IncentivesModule::cancel_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR
+		));
+		System::assert_last_event(Event::incentives(crate::Event::RewardStreamCancelled(
+			CHFJ_SETT_LP,
+			DNAR,
+			9000,
+		)));
+		assert_eq!(TokensModule::free_balance(DNAR, &CAROL), 9000);
+
+		// the emitted reward can still be claimed, then the reward stream is removed
+		System::set_block_number(21);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_eq!(TokensModule::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(
+			TokensModule::free_balance(DNAR, &IncentivesModule::reward_stream_account_id()),
+			0
+		);
+		System::assert_has_event(Event::incentives(crate::Event::RewardStreamRemoved(
+			CHFJ_SETT_LP,
+			DNAR,
+			0,
+		)));
+		assert_eq!(IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR), None);
+		assert_eq!(TokensModule::free_balance(DNAR, &CAROL), 9100);
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 0);
+
+		// UpdateOrigin can cancel the reward stream of any sponsor, it is removed at
+		// once if there is no unclaimed reward
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR,
+			1000,
+			100,
+			101
+		));
+		assert_ok!(IncentivesModule::cancel_reward_stream(
+			Origin::signed(4),
+			CHFJ_SETT_LP,
+			DNAR
+		));
+		System::assert_last_event(Event::incentives(crate::Event::RewardStreamRemoved(
+			CHFJ_SETT_LP,
+			DNAR,
+			0,
+		)));
+		assert_eq!(IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR), None);
+		assert_eq!(TokensModule::free_balance(DNAR, &CAROL), 9100);
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 0);
+	});
+}
+
+#[test]
+fn finished_reward_stream_is_removed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &ALICE, 1000));
+		assert_ok!(TokensModule::deposit(CHFJ_SETT_LP, &BOB, 1000));
+		assert_ok!(TokensModule::deposit(DNAR, &CAROL, 10000));
+		assert_ok!(TokensModule::deposit(SETT, &CAROL, 10000));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			CHFJ_SETT_LP,
+			1000
+		));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			CHFJ_SETT_LP,
+			1000
+		));
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR,
+			1000,
+			100,
+			11
+		));
+
+		// the ended reward stream does not block re-creation, its unclaimed reward
+		// stays claimable
+		System::set_block_number(11);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_eq!(TokensModule::free_balance(DNAR, &ALICE), 500);
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			DNAR,
+			1000,
+			100,
+			21
+		));
+		let stream = IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR).unwrap();
+		assert_eq!(stream.id, 0);
+		assert_eq!(stream.remaining_reward, 1000);
+		assert_eq!(stream.unclaimed_reward, 500);
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 100);
+
+		// the reward stream is removed once its emitted reward is all claimed
+		System::set_block_number(21);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(BOB),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_eq!(TokensModule::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(TokensModule::free_balance(DNAR, &BOB), 1000);
+		System::assert_has_event(Event::incentives(crate::Event::RewardStreamRemoved(
+			CHFJ_SETT_LP,
+			DNAR,
+			0,
+		)));
+		assert_eq!(IncentivesModule::reward_streams(CHFJ_SETT_LP, DNAR), None);
+		assert_eq!(StreamRewardDebts::<Runtime>::iter_prefix(0).count(), 0);
+		assert_eq!(TokensModule::free_balance(DNAR, &CAROL), 8000);
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 0);
+
+		// the unclaimed reward is refunded to sponsor after the claim period
+		assert_ok!(IncentivesModule::create_reward_stream(
+			Origin::signed(CAROL),
+			CHFJ_SETT_LP,
+			SETT,
+			1000,
+			100,
+			31
+		));
+		assert_eq!(IncentivesModule::reward_streams(CHFJ_SETT_LP, SETT).unwrap().id, 1);
+		System::set_block_number(31);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		assert_eq!(TokensModule::free_balance(SETT, &ALICE), 500);
+		assert!(StreamRewardDebts::<Runtime>::contains_key(1, ALICE));
+
+		System::set_block_number(51);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(BOB),
+			PoolId::DexIncentive(CHFJ_SETT_LP)
+		));
+		System::assert_has_event(Event::incentives(crate::Event::RewardStreamRemoved(
+			CHFJ_SETT_LP,
+			SETT,
+			500,
+		)));
+		assert_eq!(IncentivesModule::reward_streams(CHFJ_SETT_LP, SETT), None);
+		// the reward debts of the stakers are removed with the reward stream
+		assert!(!StreamRewardDebts::<Runtime>::contains_key(1, ALICE));
+		assert_eq!(TokensModule::free_balance(SETT, &BOB), 0);
+		assert_eq!(TokensModule::free_balance(SETT, &CAROL), 9500);
+		assert_eq!(TokensModule::reserved_balance(DNAR, &CAROL), 0);
 	});
 }
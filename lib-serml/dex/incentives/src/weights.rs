@@ -54,6 +54,13 @@ pub trait WeightInfo {
 	fn update_dex_incentive_rewards(c: u32, ) -> Weight;
 	fn update_dex_saving_rewards(c: u32, ) -> Weight;
 	fn add_allowance() -> Weight;
+	fn lock_dex_share() -> Weight;
+	fn unlock_dex_share() -> Weight;
+	fn update_lock_period_multipliers(c: u32, ) -> Weight;
+	fn update_early_unlock_penalty() -> Weight;
+	fn create_reward_stream() -> Weight;
+	fn cancel_reward_stream() -> Weight;
+	fn update_settmint_incentive_rewards(c: u32, ) -> Weight;
 }
 
 /// Weights for setheum_incentives using the Setheum node and recommended hardware.
@@ -96,6 +103,40 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 		(2_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn lock_dex_share() -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn unlock_dex_share() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn update_lock_period_multipliers(c: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_early_unlock_penalty() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn create_reward_stream() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_reward_stream() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn update_settmint_incentive_rewards(c: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -137,4 +178,38 @@ impl WeightInfo for () {
 		(2_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn lock_dex_share() -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn unlock_dex_share() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn update_lock_period_multipliers(c: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_early_unlock_penalty() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn create_reward_stream() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_reward_stream() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn update_settmint_incentive_rewards(c: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 }
@@ -213,10 +213,7 @@ impl<T: Config> Pallet<T> {
 	// using the Ethereum RPC's `personal_sign` and `eth_sign`.
 	pub fn eth_recover(s: &EcdsaSignature, what: &[u8], extra: &[u8]) -> Option<EvmAddress> {
 		let msg = keccak_256(&Self::ethereum_signable_message(what, extra));
-		let mut res = EvmAddress::default();
-		res.0
-			.copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, &msg).ok()?[..])[12..]);
-		Some(res)
+		eth_recover_hash(s, &msg)
 	}
 
 	// Returns an Etherum public key derived from an Ethereum secret key.
@@ -340,6 +337,15 @@ impl<T: Config> StaticLookup for Pallet<T> {
 	}
 }
 
+/// Recovers the Ethereum address that signed the given 32 byte hash, such as
+/// the hash of EIP-712 typed data.
+pub fn eth_recover_hash(s: &EcdsaSignature, msg: &[u8; 32]) -> Option<EvmAddress> {
+	let mut res = EvmAddress::default();
+	res.0
+		.copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, msg).ok()?[..])[12..]);
+	Some(res)
+}
+
 /// Converts the given binary data into ASCII-encoded hex. It will be twice
 /// the length.
 pub fn to_ascii_hex(data: &[u8]) -> Vec<u8> {
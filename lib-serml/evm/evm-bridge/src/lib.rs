@@ -29,7 +29,7 @@ use primitive_types::H256;
 use sp_core::{H160, U256};
 use sp_runtime::SaturatedConversion;
 use sp_std::vec::Vec;
-use support::{EVMBridge as EVMBridgeTrait, ExecutionMode, InvokeContext, EVM};
+use support::{EVMBridge as EVMBridgeTrait, ExecutionMode, InvokeContext, ERC20_TRANSFER_GAS_LIMIT, EVM};
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::EVM as EVM<AccountIdOf<T>>>::Balance;
@@ -168,7 +168,7 @@ impl<T: Config> EVMBridgeTrait<AccountIdOf<T>, BalanceOf<T>> for Pallet<T> {
 			context,
 			input,
 			Default::default(),
-			2_100_000,
+			ERC20_TRANSFER_GAS_LIMIT,
 			storage_limit,
 			ExecutionMode::Execute,
 		)?;
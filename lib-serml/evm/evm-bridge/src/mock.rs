@@ -120,7 +120,10 @@ impl setheum_evm::Config for Runtime {
 	type Precompiles = ();
 	type ChainId = ();
 	type GasToWeight = ();
+	type MaxGasPerExtrinsic = ();
 	type ChargeTransactionPayment = ();
+	type EthTransactionStorageLimit = ();
+	type HardFork = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
 
@@ -128,6 +131,9 @@ impl setheum_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CodeUpgradeDelay = ();
+	type MaxCodeVersions = ();
+	type CodeUpgradeCancelOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type WeightInfo = ();
 }
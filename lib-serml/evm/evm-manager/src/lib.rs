@@ -133,12 +133,14 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_0))
 						.filter(|v| v.address == address)
 						.map(|v| v.name),
+					DexShare::Fraction(_) => None,
 				}?;
 				let name_1 = match symbol_1 {
 					DexShare::Token(symbol) => CurrencyId::Token(symbol).name().map(|v| v.as_bytes().to_vec()),
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_1))
 						.filter(|v| v.address == address)
 						.map(|v| v.name),
+					DexShare::Fraction(_) => None,
 				}?;
 
 				let mut vec = Vec::new();
@@ -151,7 +153,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(address)))
 				.filter(|v| v.address == address)
 				.map(|v| v.name),
-			CurrencyId::ChainBridge(_) => None,
+			CurrencyId::ChainBridge(_) | CurrencyId::Fraction(_) => None,
 		}?;
 
 		// More than 32 bytes will be truncated.
@@ -174,12 +176,14 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_0))
 						.filter(|v| v.address == address)
 						.map(|v| v.symbol),
+					DexShare::Fraction(_) => None,
 				}?;
 				let token_symbol_1 = match symbol_1 {
 					DexShare::Token(symbol) => CurrencyId::Token(symbol).symbol().map(|v| v.as_bytes().to_vec()),
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_1))
 						.filter(|v| v.address == address)
 						.map(|v| v.symbol),
+					DexShare::Fraction(_) => None,
 				}?;
 
 				let mut vec = Vec::new();
@@ -192,7 +196,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(address)))
 				.filter(|v| v.address == address)
 				.map(|v| v.symbol),
-			CurrencyId::ChainBridge(_) => None,
+			CurrencyId::ChainBridge(_) | CurrencyId::Fraction(_) => None,
 		}?;
 
 		// More than 32 bytes will be truncated.
@@ -215,12 +219,14 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_0))
 						.filter(|v| v.address == address)
 						.map(|v| v.decimals),
+					DexShare::Fraction(_) => None,
 				}?;
 				let decimals_1 = match symbol_1 {
 					DexShare::Token(symbol) => CurrencyId::Token(symbol).decimals(),
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_1))
 						.filter(|v| v.address == address)
 						.map(|v| v.decimals),
+					DexShare::Fraction(_) => None,
 				}?;
 
 				Some(sp_std::cmp::max(decimals_0, decimals_1))
@@ -228,7 +234,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(address)))
 				.filter(|v| v.address == address)
 				.map(|v| v.decimals),
-			CurrencyId::ChainBridge(_) => None,
+			CurrencyId::ChainBridge(_) | CurrencyId::Fraction(_) => None,
 		}
 	}
 
@@ -244,6 +250,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 						let id: u32 = left.into();
 						CurrencyIdMap::<T>::get(id).filter(|v| v.address == address).map(|_| id)
 					}
+					DexShare::Fraction(_) => None,
 				}?;
 				let symbol_1 = match right {
 					DexShare::Token(_) => Some(right.into()),
@@ -251,6 +258,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 						let id: u32 = right.into();
 						CurrencyIdMap::<T>::get(id).filter(|v| v.address == address).map(|_| id)
 					}
+					DexShare::Fraction(_) => None,
 				}?;
 
 				let mut prefix = EvmAddress::default();
@@ -258,7 +266,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 				Some(prefix | EvmAddress::from_low_u64_be(u64::from(symbol_0) << 32 | u64::from(symbol_1)))
 			}
 
-			// Token or Erc20 or ChainBridge
+			// Token or Erc20 or ChainBridge or Fraction
 			_ => EvmAddress::try_from(v).ok(),
 		}
 	}
@@ -19,11 +19,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use ethereum_types::H160;
-use primitives::evm::{CallInfo, CreateInfo, EstimateResourcesRequest};
+use ethereum_types::{H160, H256};
+use primitives::evm::{
+	AccessListItem, Account, BlockLog, CallInfo, CreateInfo, EstimateResourcesRequest, TraceOutcome, TracerConfig,
+};
 use sp_runtime::{
 	codec::Codec,
-	traits::{MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
 };
 use sp_std::vec::Vec;
 
@@ -38,6 +40,7 @@ sp_api::decl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError>;
 
@@ -47,9 +50,61 @@ sp_api::decl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
+
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		fn trace_create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		/// Re-execute `extrinsics` of the block with `header` up to `index` and
+		/// trace the evm call, create or raw Ethereum transaction at `index`,
+		/// after applying its signed extensions. Must be called at the parent
+		/// block.
+		fn trace_extrinsic(
+			header: <Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		/// The chain id raw Ethereum transactions must be signed for.
+		fn chain_id() -> u64;
+
+		/// The most gas an extrinsic may use.
+		fn max_gas_per_extrinsic() -> u64;
+
+		fn account_basic(address: H160) -> Account;
+
+		fn code_at(address: H160) -> Vec<u8>;
+
+		fn storage_at(address: H160, index: H256) -> H256;
+
+		/// The logs emitted by `extrinsics`, the extrinsics of the block called
+		/// at.
+		fn block_logs(extrinsics: Vec<<Block as BlockT>::Extrinsic>) -> Vec<BlockLog>;
+
+		/// Wrap a raw Ethereum transaction into an unsigned extrinsic.
+		fn eth_transaction(raw: Vec<u8>) -> <Block as BlockT>::Extrinsic;
 	}
 }
@@ -18,6 +18,7 @@
 
 use ethereum_types::{H160, U256};
 use serde::{Deserialize, Serialize};
+use setheum_evm::AccessListItem;
 use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
 
@@ -38,6 +39,8 @@ pub struct CallRequest {
 	pub value: Option<NumberOrHex>,
 	/// Data
 	pub data: Option<Bytes>,
+	/// Access List
+	pub access_list: Option<Vec<AccessListItem>>,
 }
 
 /// EstimateResources response
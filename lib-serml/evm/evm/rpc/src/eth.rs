@@ -0,0 +1,329 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum compatible rpc implementation.
+
+use ethereum_types::{H160, H256, U256, U64};
+use jsonrpc_core::{Error, ErrorCode, Result};
+use sc_client_api::BlockBackend;
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+	codec::Codec,
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, MaybeDisplay, MaybeFromStr, UniqueSaturatedInto},
+	transaction_validity::TransactionSource,
+};
+use sp_transaction_pool::TransactionPool;
+use std::convert::{TryFrom, TryInto};
+use std::{marker::PhantomData, sync::Arc};
+
+use crate::{
+	error_on_execution_failure,
+	eth_api::EthApi as EthApiT,
+	eth_types::{BlockNumber, EthCallRequest, EthLog, Filter},
+	internal_err, limits_at, EVMRuntimeRPCApi, RpcLimits,
+};
+
+/// The most blocks a single `eth_getLogs` request can scan.
+const MAX_LOGS_BLOCK_RANGE: u64 = 1024;
+
+fn invalid_params<T: ToString>(message: T) -> Error {
+	Error {
+		code: ErrorCode::InvalidParams,
+		message: message.to_string(),
+		data: None,
+	}
+}
+
+pub struct EthApi<B, C, P, Balance> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	deny_unsafe: DenyUnsafe,
+	limits: RpcLimits,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<B, C, P, Balance> EthApi<B, C, P, Balance> {
+	pub fn new(client: Arc<C>, pool: Arc<P>, deny_unsafe: DenyUnsafe, limits: RpcLimits) -> Self {
+		Self {
+			client,
+			pool,
+			deny_unsafe,
+			limits,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C, P, Balance> EthApi<B, C, P, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn block_id(&self, number: Option<BlockNumber>) -> BlockId<B> {
+		match number.unwrap_or_default() {
+			BlockNumber::Latest | BlockNumber::Pending => BlockId::Hash(self.client.info().best_hash),
+			BlockNumber::Earliest => BlockId::Number(0u32.into()),
+			BlockNumber::Num(n) => BlockId::Number(n.unique_saturated_into()),
+		}
+	}
+
+	/// Run `request` without committing it, returning the output and the
+	/// used gas.
+	fn execute(&self, request: EthCallRequest, number: Option<BlockNumber>, estimate: bool) -> Result<(Vec<u8>, U256)> {
+		let EthCallRequest {
+			from,
+			to,
+			gas,
+			value,
+			data,
+			input,
+			access_list,
+			..
+		} = request;
+
+		let gas_limit = gas
+			.map(|gas| {
+				gas.try_into()
+					.map_err(|_| invalid_params(format!("Invalid parameter gas: {:?}", gas)))
+			})
+			.transpose()?;
+		let id = self.block_id(number);
+		let limits = limits_at(&*self.client, self.limits, &id)?;
+		let gas_limit = limits.gas_limit(gas_limit)?;
+		let storage_limit = limits.storage_limit(None)?;
+		let data = input.or(data).map(|d| d.0).unwrap_or_default();
+
+		let balance_value = match value {
+			Some(value) => u128::try_from(value)
+				.ok()
+				.and_then(|v| Balance::try_from(v).ok())
+				.ok_or_else(|| invalid_params(format!("Invalid parameter value: {:?}", value)))?,
+			None => Default::default(),
+		};
+
+		let api = self.client.runtime_api();
+
+		let (exit_reason, output, used_gas) = match to {
+			Some(to) => {
+				let info = api
+					.call(
+						&id,
+						from.unwrap_or_default(),
+						to,
+						data,
+						balance_value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						estimate,
+					)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+				(info.exit_reason, info.output, info.used_gas)
+			}
+			None => {
+				let info = api
+					.create(
+						&id,
+						from.unwrap_or_default(),
+						data,
+						balance_value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						estimate,
+					)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+				(info.exit_reason, info.output, info.used_gas)
+			}
+		};
+
+		error_on_execution_failure(&exit_reason, &output)?;
+
+		Ok((output, used_gas))
+	}
+}
+
+impl<B, C, P, Balance> EthApiT for EthApi<B, C, P, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	P: TransactionPool<Block = B> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn chain_id(&self) -> Result<U64> {
+		let hash = self.client.info().best_hash;
+		self.client
+			.runtime_api()
+			.chain_id(&BlockId::Hash(hash))
+			.map(Into::into)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn block_number(&self) -> Result<U256> {
+		let number: u64 = self.client.info().best_number.unique_saturated_into();
+		Ok(number.into())
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		self.client
+			.runtime_api()
+			.account_basic(&self.block_id(number), address)
+			.map(|account| account.balance)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes> {
+		self.client
+			.runtime_api()
+			.code_at(&self.block_id(number), address)
+			.map(Bytes)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256> {
+		let mut key = [0u8; 32];
+		index.to_big_endian(&mut key);
+
+		self.client
+			.runtime_api()
+			.storage_at(&self.block_id(number), address, H256(key))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		self.client
+			.runtime_api()
+			.account_basic(&self.block_id(number), address)
+			.map(|account| account.nonce)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let (output, _) = self.execute(request, number, false)?;
+		Ok(Bytes(output))
+	}
+
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<U256> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let (_, used_gas) = self.execute(request, number, true)?;
+		Ok(used_gas)
+	}
+
+	fn logs(&self, filter: Filter) -> Result<Vec<EthLog>> {
+		let hashes = match filter.block_hash {
+			Some(hash) => vec![hash],
+			None => {
+				let best: u64 = self.client.info().best_number.unique_saturated_into();
+				let number = |number: Option<BlockNumber>| match number.unwrap_or_default() {
+					BlockNumber::Latest | BlockNumber::Pending => best,
+					BlockNumber::Earliest => 0,
+					BlockNumber::Num(n) => n.min(best),
+				};
+				let from = number(filter.from_block);
+				let to = number(filter.to_block);
+				if to.saturating_sub(from) >= MAX_LOGS_BLOCK_RANGE {
+					return Err(invalid_params(format!(
+						"block range is too wide, at most {} blocks",
+						MAX_LOGS_BLOCK_RANGE
+					)));
+				}
+
+				let mut hashes = Vec::new();
+				for n in from..=to {
+					if let Some(hash) = self
+						.client
+						.hash(n.unique_saturated_into())
+						.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+					{
+						hashes.push(hash);
+					}
+				}
+				hashes
+			}
+		};
+
+		let mut logs = Vec::new();
+		for hash in hashes {
+			let id = BlockId::Hash(hash);
+			let header = self
+				.client
+				.header(id)
+				.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("block not found: {:?}", hash)))?;
+			let extrinsics = self
+				.client
+				.block_body(&id)
+				.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("block body not found: {:?}", hash)))?;
+
+			let block_number: u64 = (*header.number()).unique_saturated_into();
+			let block_logs = self
+				.client
+				.runtime_api()
+				.block_logs(&id, extrinsics)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+			for (log_index, log) in block_logs.into_iter().enumerate() {
+				if !filter.matches(&log) {
+					continue;
+				}
+				logs.push(EthLog {
+					address: log.log.address,
+					topics: log.log.topics,
+					data: Bytes(log.log.data),
+					block_hash: hash,
+					block_number: block_number.into(),
+					transaction_hash: log.transaction_hash,
+					transaction_index: log.transaction_index.into(),
+					log_index: log_index.into(),
+					removed: false,
+				});
+			}
+		}
+
+		Ok(logs)
+	}
+
+	fn send_raw_transaction(&self, raw: Bytes) -> Result<H256> {
+		let hash = setheum_evm::transaction::transaction_hash(&raw);
+		let best = BlockId::Hash(self.client.info().best_hash);
+
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.eth_transaction(&best, raw.0)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		futures::executor::block_on(self.pool.submit_one(&best, TransactionSource::External, extrinsic))
+			.map_err(|err| internal_err(format!("transaction pool error: {:?}", err)))?;
+
+		Ok(hash)
+	}
+}
@@ -0,0 +1,74 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum compatible rpc interface.
+
+use ethereum_types::{H160, H256, U256, U64};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sp_core::Bytes;
+
+pub use rpc_impl_EthApi::gen_server::EthApi as EthApiServer;
+
+use crate::eth_types::{BlockNumber, EthCallRequest, EthLog, Filter};
+
+/// The subset of the Ethereum JSON-RPC `eth_` namespace needed by wallets
+/// and development tools.
+#[rpc(server)]
+pub trait EthApi {
+	/// Returns the chain id transactions are signed for.
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> Result<U64>;
+
+	/// Returns the number of the best block.
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> Result<U256>;
+
+	/// Returns the free native balance of the account mapped to `address`.
+	#[rpc(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the code deployed at `address`.
+	#[rpc(name = "eth_getCode")]
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Returns the storage slot `index` of the contract at `address`.
+	#[rpc(name = "eth_getStorageAt")]
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256>;
+
+	/// Returns the nonce of `address`, the nonce its next transaction must
+	/// have.
+	#[rpc(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Call contract, returning the output data.
+	#[rpc(name = "eth_call")]
+	fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Estimate the gas used by a call or contract creation.
+	#[rpc(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the logs matching `filter`.
+	#[rpc(name = "eth_getLogs")]
+	fn logs(&self, filter: Filter) -> Result<Vec<EthLog>>;
+
+	/// Submit a signed, RLP encoded transaction, returning its hash.
+	#[rpc(name = "eth_sendRawTransaction")]
+	fn send_raw_transaction(&self, raw: Bytes) -> Result<H256>;
+}
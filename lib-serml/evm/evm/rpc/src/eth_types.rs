@@ -0,0 +1,192 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum JSON-RPC types.
+
+use ethereum_types::{H160, H256, U256};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use setheum_evm::{AccessListItem, BlockLog};
+use sp_core::Bytes;
+
+/// A block number or tag.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockNumber {
+	Latest,
+	Earliest,
+	Pending,
+	Num(u64),
+}
+
+impl Default for BlockNumber {
+	fn default() -> Self {
+		BlockNumber::Latest
+	}
+}
+
+impl<'de> Deserialize<'de> for BlockNumber {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Repr {
+			Num(u64),
+			Str(String),
+		}
+
+		match Repr::deserialize(deserializer)? {
+			Repr::Num(n) => Ok(BlockNumber::Num(n)),
+			Repr::Str(s) => match s.as_str() {
+				"latest" => Ok(BlockNumber::Latest),
+				"earliest" => Ok(BlockNumber::Earliest),
+				"pending" => Ok(BlockNumber::Pending),
+				s if s.starts_with("0x") => u64::from_str_radix(&s[2..], 16)
+					.map(BlockNumber::Num)
+					.map_err(D::Error::custom),
+				s => Err(D::Error::custom(format!("invalid block number: {}", s))),
+			},
+		}
+	}
+}
+
+/// Call request of `eth_call` and `eth_estimateGas`.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallRequest {
+	/// From
+	pub from: Option<H160>,
+	/// To
+	pub to: Option<H160>,
+	/// Gas Limit
+	pub gas: Option<U256>,
+	/// Gas Price, ignored
+	pub gas_price: Option<U256>,
+	/// Value
+	pub value: Option<U256>,
+	/// Data
+	pub data: Option<Bytes>,
+	/// Data, as named by newer clients
+	pub input: Option<Bytes>,
+	/// Access list of an EIP-2930 call
+	pub access_list: Option<Vec<AccessListItem>>,
+}
+
+/// One value or a set of values to match.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum VariadicValue<T> {
+	Single(T),
+	Multiple(Vec<T>),
+}
+
+impl<T: PartialEq> VariadicValue<T> {
+	pub fn matches(&self, value: &T) -> bool {
+		match self {
+			VariadicValue::Single(v) => v == value,
+			VariadicValue::Multiple(values) => values.is_empty() || values.contains(value),
+		}
+	}
+}
+
+/// Log filter of `eth_getLogs`.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+	pub from_block: Option<BlockNumber>,
+	pub to_block: Option<BlockNumber>,
+	/// Only the given block, instead of `from_block` and `to_block`.
+	pub block_hash: Option<H256>,
+	pub address: Option<VariadicValue<H160>>,
+	/// The topics to match by position, `null` matching any topic.
+	pub topics: Option<Vec<Option<VariadicValue<H256>>>>,
+}
+
+impl Filter {
+	pub fn matches(&self, log: &BlockLog) -> bool {
+		if let Some(ref address) = self.address {
+			if !address.matches(&log.log.address) {
+				return false;
+			}
+		}
+
+		self.topics.iter().flatten().enumerate().all(|(i, topic)| match topic {
+			Some(topic) => log.log.topics.get(i).map_or(false, |t| topic.matches(t)),
+			None => true,
+		})
+	}
+}
+
+/// A log returned by `eth_getLogs`.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLog {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: Bytes,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub transaction_hash: H256,
+	pub transaction_index: U256,
+	pub log_index: U256,
+	pub removed: bool,
+}
+
+#[test]
+fn block_number_should_deserialize() {
+	let numbers: Vec<BlockNumber> = serde_json::from_str(r#"["latest", "earliest", "pending", "0x1f", 12]"#).unwrap();
+	assert_eq!(
+		numbers,
+		vec![
+			BlockNumber::Latest,
+			BlockNumber::Earliest,
+			BlockNumber::Pending,
+			BlockNumber::Num(31),
+			BlockNumber::Num(12),
+		]
+	);
+	assert!(serde_json::from_str::<BlockNumber>(r#""safe""#).is_err());
+}
+
+#[test]
+fn filter_should_match_logs() {
+	let log = BlockLog {
+		transaction_index: 0,
+		transaction_hash: H256::zero(),
+		log: setheum_evm::Log {
+			address: H160::repeat_byte(1),
+			topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+			data: vec![],
+		},
+	};
+
+	let filter: Filter = serde_json::from_str(r#"{"address":"0x0101010101010101010101010101010101010101"}"#).unwrap();
+	assert!(filter.matches(&log));
+
+	let filter: Filter = serde_json::from_str(r#"{"address":["0x0202020202020202020202020202020202020202"]}"#).unwrap();
+	assert!(!filter.matches(&log));
+
+	let filter: Filter = serde_json::from_str(
+		r#"{"topics":[null,["0x0303030303030303030303030303030303030303030303030303030303030303"]]}"#,
+	)
+	.unwrap();
+	assert!(filter.matches(&log));
+
+	let filter: Filter = serde_json::from_str(
+		r#"{"topics":[null,null,"0x0303030303030303030303030303030303030303030303030303030303030303"]}"#,
+	)
+	.unwrap();
+	assert!(!filter.matches(&log));
+}
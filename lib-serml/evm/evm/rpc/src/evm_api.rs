@@ -25,7 +25,10 @@ use sp_core::Bytes;
 
 pub use rpc_impl_EVMApi::gen_server::EVMApi as EVMApiServer;
 
-use crate::call_request::{CallRequest, EstimateResourcesResponse};
+use crate::{
+	call_request::{CallRequest, EstimateResourcesResponse},
+	trace::{TraceOptions, TraceResponse},
+};
 
 /// EVM rpc interface.
 #[rpc(server)]
@@ -42,4 +45,15 @@ pub trait EVMApi<BlockHash> {
 		unsigned_extrinsic: Bytes,
 		at: Option<BlockHash>,
 	) -> Result<EstimateResourcesResponse>;
+
+	/// Trace a call or contract creation at the given block, with geth's
+	/// struct logger or `callTracer`.
+	#[rpc(name = "evm_traceCall")]
+	fn trace_call(&self, _: CallRequest, options: Option<TraceOptions>, at: Option<BlockHash>)
+		-> Result<TraceResponse>;
+
+	/// Re-execute the block `block` up to the evm extrinsic at `index` and
+	/// trace it, with geth's struct logger or `callTracer`.
+	#[rpc(name = "evm_traceExtrinsic")]
+	fn trace_extrinsic(&self, block: BlockHash, index: u32, options: Option<TraceOptions>) -> Result<TraceResponse>;
 }
@@ -23,6 +23,7 @@ use frame_support::log;
 use jsonrpc_core::{Error, ErrorCode, Result, Value};
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
 use rustc_hex::ToHex;
+use sc_client_api::BlockBackend;
 use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -31,20 +32,30 @@ use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
 	generic::BlockId,
-	traits::{self, Block as BlockT, MaybeDisplay, MaybeFromStr},
+	traits::{self, Block as BlockT, Header as HeaderT, MaybeDisplay, MaybeFromStr},
 	SaturatedConversion,
 };
 use std::convert::{TryFrom, TryInto};
 use std::{marker::PhantomData, sync::Arc};
 
 use call_request::{CallRequest, EstimateResourcesResponse};
-pub use setheum_evm::{ExitError, ExitReason};
+pub use setheum_evm::{ExitError, ExitReason, TracerConfig};
 pub use setheum_evm_rpc_runtime_api::EVMRuntimeRPCApi;
 
+pub use crate::eth::EthApi;
+pub use crate::eth_api::{EthApi as EthApiT, EthApiServer};
+pub use crate::eth_types::{BlockNumber, EthCallRequest, EthLog, Filter, VariadicValue};
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
+pub use crate::limits::RpcLimits;
+pub use crate::trace::{CallFrame, StructLogEntry, StructLoggerResult, TraceOptions, TraceResponse};
 
 mod call_request;
+mod eth;
+mod eth_api;
+mod eth_types;
 mod evm_api;
+mod limits;
+mod trace;
 
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
@@ -106,30 +117,124 @@ fn decode_revert_message(data: &[u8]) -> Option<String> {
 	None
 }
 
+/// Binary search the gas limit a call needs, up to `cap`, returning the gas
+/// and storage used. `calculate_gas_used` runs the call with a gas limit.
+fn binary_search_estimate<F>(cap: u64, calculate_gas_used: F) -> Result<(U256, i32)>
+where
+	F: Fn(u64) -> Result<(U256, i32)>,
+{
+	let mut lower = U256::from(21_000).min(U256::from(cap));
+	let mut upper = U256::from(cap);
+	let mut mid = upper;
+	let mut best: Option<(U256, i32)> = None;
+
+	// if the gas estimation depends on the gas limit, then we want to binary
+	// search until the change is under some threshold. but if not dependent,
+	// we want to stop immediately.
+	let mut change_pct = U256::from(100);
+	let threshold_pct = U256::from(10);
+
+	// invariant: lower <= mid <= upper
+	while change_pct > threshold_pct {
+		match calculate_gas_used(mid.as_u64()) {
+			// if Ok -- try to reduce the gas used
+			Ok((used_gas, used_storage)) => {
+				log::debug!(
+					target: "evm",
+					"calculate_gas_used ok, used_gas: {:?}, used_storage: {:?}",
+					used_gas, used_storage,
+				);
+
+				if let Some((old_best, _)) = best {
+					change_pct = (U256::from(100) * old_best.saturating_sub(used_gas))
+						.checked_div(old_best)
+						.unwrap_or_default();
+				}
+				best = Some((used_gas, used_storage));
+				upper = mid;
+				mid = (lower + upper + 1) / 2;
+			}
+
+			Err(err) => {
+				log::debug!(
+					target: "evm",
+					"calculate_gas_used err, lower: {:?}, upper: {:?}, mid: {:?}",
+					lower, upper, mid
+				);
+
+				// if Err == OutofGas, we need more gas
+				if err.code == ErrorCode::ServerError(0) {
+					lower = mid;
+					mid = (lower + upper + 1) / 2;
+					if mid == lower {
+						break;
+					}
+				} else {
+					// Other errors, return directly
+					return Err(err);
+				}
+			}
+		}
+	}
+
+	// out of gas even at the cap
+	best.ok_or_else(|| Error {
+		code: ErrorCode::ServerError(0),
+		message: format!("out of gas, the gas required exceeds the rpc cap of {}", cap),
+		data: None,
+	})
+}
+
 pub struct EVMApi<B, C, Balance> {
 	client: Arc<C>,
 	deny_unsafe: DenyUnsafe,
+	limits: RpcLimits,
 	_marker: PhantomData<(B, Balance)>,
 }
 
 impl<B, C, Balance> EVMApi<B, C, Balance> {
-	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe, limits: RpcLimits) -> Self {
 		Self {
 			client,
 			deny_unsafe,
+			limits,
 			_marker: Default::default(),
 		}
 	}
 }
 
+/// The limits of the rpc at `at`, with the gas cap lowered to the most gas
+/// an extrinsic may use there.
+fn limits_at<B, C, Balance>(client: &C, limits: RpcLimits, at: &BlockId<B>) -> Result<RpcLimits>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	let max_gas = client
+		.runtime_api()
+		.max_gas_per_extrinsic(at)
+		.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+	Ok(limits.clamp_gas_limit(max_gas))
+}
+
 fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 	val.into_u256().try_into().map_err(|_| ())
 }
 
+fn tracer_config(options: Option<TraceOptions>) -> Result<TracerConfig> {
+	options.unwrap_or_default().tracer_config().map_err(|message| Error {
+		code: ErrorCode::InvalidParams,
+		message,
+		data: None,
+	})
+}
+
 impl<B, C, Balance> EVMApiT<<B as BlockT>::Hash> for EVMApi<B, C, Balance>
 where
 	B: BlockT,
-	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
 	C::Api: TransactionPaymentApi<B, Balance>,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
@@ -146,10 +251,12 @@ where
 			storage_limit,
 			value,
 			data,
+			access_list,
 		} = request;
 
-		let gas_limit = gas_limit.unwrap_or_else(u64::max_value); // TODO: set a limit
-		let storage_limit = storage_limit.unwrap_or_else(u32::max_value); // TODO: set a limit
+		let limits = limits_at(&*self.client, self.limits, &BlockId::Hash(hash))?;
+		let gas_limit = limits.gas_limit(gas_limit)?;
+		let storage_limit = limits.storage_limit(storage_limit)?;
 		let data = data.map(|d| d.0).unwrap_or_default();
 
 		let api = self.client.runtime_api();
@@ -177,6 +284,7 @@ where
 						balance_value,
 						gas_limit,
 						storage_limit,
+						access_list,
 						false,
 					)
 					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
@@ -195,6 +303,7 @@ where
 						balance_value,
 						gas_limit,
 						storage_limit,
+						access_list,
 						false,
 					)
 					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
@@ -230,10 +339,14 @@ where
 			storage_limit: request.storage_limit,
 			value: request.value.map(|v| NumberOrHex::Hex(U256::from(v))),
 			data: request.data.map(Bytes),
+			access_list: request.access_list,
 		};
 
+		let best_hash = self.client.info().best_hash;
+		let limits = limits_at(&*self.client, self.limits, &BlockId::Hash(best_hash))?;
+
 		let calculate_gas_used = |request| -> Result<(U256, i32)> {
-			let hash = self.client.info().best_hash;
+			let hash = best_hash;
 
 			let CallRequest {
 				from,
@@ -242,10 +355,11 @@ where
 				storage_limit,
 				value,
 				data,
+				access_list,
 			} = request;
 
-			let gas_limit = gas_limit.unwrap_or_else(u64::max_value); // TODO: set a limit
-			let storage_limit = storage_limit.unwrap_or_else(u32::max_value); // TODO: set a limit
+			let gas_limit = limits.gas_limit(gas_limit)?;
+			let storage_limit = limits.storage_limit(storage_limit)?;
 			let data = data.map(|d| d.0).unwrap_or_default();
 
 			let balance_value = if let Some(value) = value {
@@ -273,6 +387,7 @@ where
 							balance_value,
 							gas_limit,
 							storage_limit,
+							access_list,
 							true,
 						)
 						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
@@ -293,6 +408,7 @@ where
 							balance_value,
 							gas_limit,
 							storage_limit,
+							access_list,
 							true,
 						)
 						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
@@ -308,64 +424,11 @@ where
 		};
 
 		if cfg!(feature = "rpc_binary_search_estimate") {
-			let mut lower = U256::from(21_000);
-			// TODO: get a good upper limit, but below U64::max to operation overflow
-			let mut upper = U256::from(1_000_000_000);
-			let mut mid = upper;
-			let mut best = mid;
-			let mut old_best: U256;
-			let mut storage: i32 = Default::default();
-
-			// if the gas estimation depends on the gas limit, then we want to binary
-			// search until the change is under some threshold. but if not dependent,
-			// we want to stop immediately.
-			let mut change_pct = U256::from(100);
-			let threshold_pct = U256::from(10);
-
-			// invariant: lower <= mid <= upper
-			while change_pct > threshold_pct {
+			let (best, storage) = binary_search_estimate(limits.gas_limit, |gas_limit| {
 				let mut test_request = request.clone();
-				test_request.gas_limit = Some(mid.as_u64());
-				match calculate_gas_used(test_request) {
-					// if Ok -- try to reduce the gas used
-					Ok((used_gas, used_storage)) => {
-						log::debug!(
-							target: "evm",
-							"calculate_gas_used ok, used_gas: {:?}, used_storage: {:?}",
-							used_gas, used_storage,
-						);
-
-						old_best = best;
-						best = used_gas;
-						change_pct = (U256::from(100) * (old_best - best))
-							.checked_div(old_best)
-							.unwrap_or_default();
-						upper = mid;
-						mid = (lower + upper + 1) / 2;
-						storage = used_storage;
-					}
-
-					Err(err) => {
-						log::debug!(
-							target: "evm",
-							"calculate_gas_used err, lower: {:?}, upper: {:?}, mid: {:?}",
-							lower, upper, mid
-						);
-
-						// if Err == OutofGas, we need more gas
-						if err.code == ErrorCode::ServerError(0) {
-							lower = mid;
-							mid = (lower + upper + 1) / 2;
-							if mid == lower {
-								break;
-							}
-						} else {
-							// Other errors, return directly
-							return Err(err);
-						}
-					}
-				}
-			}
+				test_request.gas_limit = Some(gas_limit);
+				calculate_gas_used(test_request)
+			})?;
 
 			let uxt: <B as traits::Block>::Extrinsic =
 				Decode::decode(&mut &*unsigned_extrinsic).map_err(|e| Error {
@@ -424,6 +487,108 @@ where
 			})
 		}
 	}
+
+	fn trace_call(
+		&self,
+		request: CallRequest,
+		options: Option<TraceOptions>,
+		at: Option<<B as BlockT>::Hash>,
+	) -> Result<TraceResponse> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let tracer = tracer_config(options)?;
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+			access_list,
+		} = request;
+
+		let limits = limits_at(&*self.client, self.limits, &BlockId::Hash(hash))?;
+		let gas_limit = limits.gas_limit(gas_limit)?;
+		let storage_limit = limits.storage_limit(storage_limit)?;
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value = balance_value.map_err(|_| Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid parameter value: {:?}", value),
+			data: None,
+		})?;
+
+		let api = self.client.runtime_api();
+		let outcome = match to {
+			Some(to) => api.trace_call(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				tracer,
+			),
+			None => api.trace_create(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				tracer,
+			),
+		}
+		.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+		.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		Ok(outcome.into())
+	}
+
+	fn trace_extrinsic(
+		&self,
+		block: <B as BlockT>::Hash,
+		index: u32,
+		options: Option<TraceOptions>,
+	) -> Result<TraceResponse> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let tracer = tracer_config(options)?;
+		let id = BlockId::Hash(block);
+
+		let header = self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("block not found: {:?}", block)))?;
+		let extrinsics = self
+			.client
+			.block_body(&id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("block body not found: {:?}", block)))?;
+
+		// re-execute the block on top of its parent state
+		let parent = BlockId::Hash(*header.parent_hash());
+		let outcome = self
+			.client
+			.runtime_api()
+			.trace_extrinsic(&parent, header, extrinsics, index, tracer)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		Ok(outcome.into())
+	}
 }
 
 #[test]
@@ -450,3 +615,34 @@ fn decode_revert_message_should_work() {
 	let data = from_hex("0x98640818c3187918a0000000000000000000000000000000000000000000000000000000000000001820000000000000000000000000000000000000000000000000000000000000000d186518721872186f18721820186d18651873187318611867186500000000000000000000000000000000000000").unwrap();
 	assert_eq!(decode_revert_message(&data), None);
 }
+
+#[test]
+fn binary_search_estimate_should_work() {
+	let out_of_gas = || Error {
+		code: ErrorCode::ServerError(0),
+		message: "out of gas".into(),
+		data: None,
+	};
+
+	// the call needs a gas limit of 60_000 and uses 50_000
+	let estimate = binary_search_estimate(1_000_000, |gas_limit| {
+		assert!(gas_limit <= 1_000_000);
+		if gas_limit >= 60_000 {
+			Ok((50_000.into(), 10))
+		} else {
+			Err(out_of_gas())
+		}
+	});
+	assert_eq!(estimate, Ok((50_000.into(), 10)));
+
+	// out of gas at the cap
+	let estimate = binary_search_estimate(40_000, |gas_limit| {
+		assert!(gas_limit <= 40_000);
+		Err(out_of_gas())
+	});
+	assert_eq!(estimate.map_err(|e| e.code), Err(ErrorCode::ServerError(0)));
+
+	// other errors are returned directly
+	let estimate = binary_search_estimate(1_000_000, |_| Err(internal_err("execution revert")));
+	assert_eq!(estimate, Err(internal_err("execution revert")));
+}
@@ -0,0 +1,141 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Caps on the resources of the calls dry run by the rpc.
+
+use jsonrpc_core::{Error, ErrorCode, Result};
+
+/// The caps on the gas and storage of the calls dry run by `evm_call`,
+/// `evm_estimateResources`, `evm_traceCall`, `eth_call` and
+/// `eth_estimateGas`, set by the node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RpcLimits {
+	/// The most gas a call may use, and the gas limit of calls that do not
+	/// set one.
+	pub gas_limit: u64,
+	/// The most storage in bytes a call may use, and the storage limit of
+	/// calls that do not set one.
+	pub storage_limit: u32,
+}
+
+impl Default for RpcLimits {
+	fn default() -> Self {
+		Self {
+			// the `eth_call` gas cap of geth
+			gas_limit: 50_000_000,
+			storage_limit: 4 * 1024 * 1024,
+		}
+	}
+}
+
+impl RpcLimits {
+	/// The gas limit of a call requesting `gas_limit`, which must not
+	/// exceed the cap.
+	pub fn gas_limit(&self, gas_limit: Option<u64>) -> Result<u64> {
+		match gas_limit {
+			Some(gas_limit) if gas_limit > self.gas_limit => Err(exceeds_cap("gas limit", gas_limit, self.gas_limit)),
+			Some(gas_limit) => Ok(gas_limit),
+			None => Ok(self.gas_limit),
+		}
+	}
+
+	/// The storage limit of a call requesting `storage_limit`, which must
+	/// not exceed the cap.
+	pub fn storage_limit(&self, storage_limit: Option<u32>) -> Result<u32> {
+		match storage_limit {
+			Some(storage_limit) if storage_limit > self.storage_limit => Err(exceeds_cap(
+				"storage limit",
+				storage_limit.into(),
+				self.storage_limit.into(),
+			)),
+			Some(storage_limit) => Ok(storage_limit),
+			None => Ok(self.storage_limit),
+		}
+	}
+
+	/// The limits with the gas cap lowered to `max_gas`, the most gas an
+	/// extrinsic may use, so dry runs don't allow more than can be submitted.
+	pub fn clamp_gas_limit(self, max_gas: u64) -> Self {
+		Self {
+			gas_limit: self.gas_limit.min(max_gas),
+			..self
+		}
+	}
+}
+
+fn exceeds_cap(name: &str, requested: u64, cap: u64) -> Error {
+	Error {
+		code: ErrorCode::InvalidParams,
+		message: format!("Invalid parameter {}: {} exceeds the rpc cap of {}", name, requested, cap),
+		data: None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn limits_default_to_the_caps() {
+		let limits = RpcLimits {
+			gas_limit: 1_000,
+			storage_limit: 100,
+		};
+		assert_eq!(limits.gas_limit(None), Ok(1_000));
+		assert_eq!(limits.storage_limit(None), Ok(100));
+		assert_eq!(limits.gas_limit(Some(500)), Ok(500));
+		assert_eq!(limits.storage_limit(Some(100)), Ok(100));
+	}
+
+	#[test]
+	fn limits_above_the_caps_are_rejected() {
+		let limits = RpcLimits {
+			gas_limit: 1_000,
+			storage_limit: 100,
+		};
+		assert_eq!(
+			limits.gas_limit(Some(1_001)),
+			Err(Error {
+				code: ErrorCode::InvalidParams,
+				message: "Invalid parameter gas limit: 1001 exceeds the rpc cap of 1000".into(),
+				data: None,
+			})
+		);
+		assert_eq!(
+			limits.storage_limit(Some(u32::max_value())).map_err(|e| e.code),
+			Err(ErrorCode::InvalidParams)
+		);
+	}
+
+	#[test]
+	fn gas_cap_is_clamped_to_the_max_gas_per_extrinsic() {
+		let limits = RpcLimits {
+			gas_limit: 1_000,
+			storage_limit: 100,
+		};
+		assert_eq!(limits.clamp_gas_limit(2_000), limits);
+		assert_eq!(
+			limits.clamp_gas_limit(500),
+			RpcLimits {
+				gas_limit: 500,
+				storage_limit: 100,
+			}
+		);
+		assert_eq!(limits.clamp_gas_limit(500).gas_limit(None), Ok(500));
+	}
+}
@@ -0,0 +1,421 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Geth compatible trace formats.
+
+use ethereum_types::{H160, H256, U256};
+use rustc_hex::ToHex;
+use serde::{Deserialize, Serialize};
+use setheum_evm::{CallTrace, CallType, ExitError, ExitReason, StructLog, TraceOutcome, TracerConfig};
+use sp_core::Bytes;
+use std::collections::BTreeMap;
+
+use crate::decode_revert_message;
+
+/// Tracer options, following geth's `debug_trace*` options.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+	/// `callTracer` for the call tracer, the struct logger otherwise.
+	pub tracer: Option<String>,
+	#[serde(default)]
+	pub disable_stack: bool,
+	#[serde(default)]
+	pub disable_storage: bool,
+	#[serde(default)]
+	pub enable_memory: bool,
+}
+
+impl TraceOptions {
+	pub fn tracer_config(&self) -> Result<TracerConfig, String> {
+		match self.tracer.as_deref() {
+			None => Ok(TracerConfig::StructLogger {
+				disable_stack: self.disable_stack,
+				disable_storage: self.disable_storage,
+				enable_memory: self.enable_memory,
+			}),
+			Some("callTracer") => Ok(TracerConfig::CallTracer),
+			Some(tracer) => Err(format!("unsupported tracer: {}", tracer)),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TraceResponse {
+	CallTracer(CallFrame),
+	StructLogger(StructLoggerResult),
+}
+
+impl From<TraceOutcome> for TraceResponse {
+	fn from(outcome: TraceOutcome) -> Self {
+		match outcome {
+			TraceOutcome::CallTracer(trace) => TraceResponse::CallTracer(trace.into()),
+			TraceOutcome::StructLogger {
+				exit_reason,
+				used_gas,
+				return_value,
+				struct_logs,
+			} => TraceResponse::StructLogger(StructLoggerResult {
+				gas: used_gas,
+				failed: !exit_reason.is_succeed(),
+				return_value: return_value.to_hex(),
+				struct_logs: struct_log_entries(struct_logs),
+			}),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	#[serde(rename = "type")]
+	pub call_type: String,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Bytes,
+	pub output: Bytes,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl From<CallTrace> for CallFrame {
+	fn from(trace: CallTrace) -> Self {
+		let call_type = match trace.call_type {
+			CallType::Call => "CALL",
+			CallType::CallCode => "CALLCODE",
+			CallType::DelegateCall => "DELEGATECALL",
+			CallType::StaticCall => "STATICCALL",
+			CallType::Create => "CREATE",
+			CallType::Create2 => "CREATE2",
+		};
+		let revert_reason = match trace.exit_reason {
+			ExitReason::Revert(_) => decode_revert_message(&trace.output),
+			_ => None,
+		};
+
+		CallFrame {
+			call_type: call_type.to_string(),
+			from: trace.from,
+			to: trace.to,
+			value: trace.value,
+			gas: trace.gas.into(),
+			gas_used: trace.gas_used.into(),
+			input: Bytes(trace.input),
+			output: Bytes(trace.output),
+			error: exit_error(&trace.exit_reason),
+			revert_reason,
+			calls: trace.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerResult {
+	pub gas: u64,
+	pub failed: bool,
+	/// Hex encoded, without `0x` prefix.
+	pub return_value: String,
+	pub struct_logs: Vec<StructLogEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogEntry {
+	pub pc: u64,
+	pub op: String,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub stack: Vec<H256>,
+	/// 32 bytes words, hex encoded without `0x` prefix.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub memory: Vec<String>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<String, String>,
+}
+
+impl From<StructLog> for StructLogEntry {
+	fn from(log: StructLog) -> Self {
+		StructLogEntry {
+			pc: log.pc,
+			op: opcode_name(log.op),
+			gas: log.gas,
+			gas_cost: log.gas_cost,
+			depth: log.depth,
+			stack: log.stack,
+			memory: log.memory.chunks(32).map(|word| word.to_hex()).collect(),
+			storage: log
+				.storage
+				.into_iter()
+				.map(|(key, value)| (key.as_bytes().to_hex(), value.as_bytes().to_hex()))
+				.collect(),
+		}
+	}
+}
+
+/// Convert the struct logs, replacing the slot touched by `SLOAD` and
+/// `SSTORE` with all the slots touched so far by the call frame, as geth does.
+fn struct_log_entries(struct_logs: Vec<StructLog>) -> Vec<StructLogEntry> {
+	// storage touched by each call frame entered but not exited yet
+	let mut frames: Vec<BTreeMap<String, String>> = Vec::new();
+
+	struct_logs
+		.into_iter()
+		.map(|log| {
+			let depth = log.depth as usize;
+			frames.truncate(depth);
+			frames.resize_with(depth, Default::default);
+
+			let mut entry = StructLogEntry::from(log);
+			if !entry.storage.is_empty() {
+				if let Some(storage) = frames.last_mut() {
+					storage.append(&mut entry.storage);
+					entry.storage = storage.clone();
+				}
+			}
+			entry
+		})
+		.collect()
+}
+
+/// The geth error message of a failed call.
+fn exit_error(reason: &ExitReason) -> Option<String> {
+	match reason {
+		ExitReason::Succeed(_) => None,
+		ExitReason::Revert(_) => Some("execution reverted".into()),
+		ExitReason::Error(ExitError::OutOfGas) => Some("out of gas".into()),
+		ExitReason::Error(ExitError::StackUnderflow) => Some("stack underflow".into()),
+		ExitReason::Error(ExitError::StackOverflow) => Some("stack limit reached".into()),
+		ExitReason::Error(ExitError::InvalidJump) => Some("invalid jump destination".into()),
+		ExitReason::Error(ExitError::DesignatedInvalid) => Some("invalid opcode".into()),
+		ExitReason::Error(ExitError::CallTooDeep) => Some("max call depth exceeded".into()),
+		ExitReason::Error(ExitError::CreateCollision) => Some("contract address collision".into()),
+		ExitReason::Error(ExitError::Other(message)) => Some(message.to_string()),
+		ExitReason::Error(e) => Some(format!("{:?}", e)),
+		ExitReason::Fatal(e) => Some(format!("fatal: {:?}", e)),
+	}
+}
+
+fn opcode_name(op: u8) -> String {
+	let name = match op {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "SHA3",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x60..=0x7f => return format!("PUSH{}", op - 0x5f),
+		0x80..=0x8f => return format!("DUP{}", op - 0x7f),
+		0x90..=0x9f => return format!("SWAP{}", op - 0x8f),
+		0xa0..=0xa4 => return format!("LOG{}", op - 0xa0),
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return format!("opcode 0x{:x} not defined", op),
+	};
+	name.to_string()
+}
+
+#[test]
+fn opcode_name_works() {
+	assert_eq!(opcode_name(0x00), "STOP");
+	assert_eq!(opcode_name(0x60), "PUSH1");
+	assert_eq!(opcode_name(0x7f), "PUSH32");
+	assert_eq!(opcode_name(0x80), "DUP1");
+	assert_eq!(opcode_name(0x9f), "SWAP16");
+	assert_eq!(opcode_name(0xa4), "LOG4");
+	assert_eq!(opcode_name(0xfd), "REVERT");
+	assert_eq!(opcode_name(0x0c), "opcode 0xc not defined");
+}
+
+#[test]
+fn trace_options_should_work() {
+	let options: TraceOptions = serde_json::from_str(r#"{"tracer":"callTracer"}"#).unwrap();
+	assert_eq!(options.tracer_config(), Ok(TracerConfig::CallTracer));
+
+	let options: TraceOptions = serde_json::from_str(r#"{"enableMemory":true}"#).unwrap();
+	assert_eq!(
+		options.tracer_config(),
+		Ok(TracerConfig::StructLogger {
+			disable_stack: false,
+			disable_storage: false,
+			enable_memory: true,
+		})
+	);
+
+	let options: TraceOptions = serde_json::from_str(r#"{"tracer":"prestateTracer"}"#).unwrap();
+	assert!(options.tracer_config().is_err());
+}
+
+#[test]
+fn struct_log_entries_should_accumulate_storage() {
+	let log = |depth: u32, storage: Vec<(H256, H256)>| StructLog {
+		pc: 0,
+		op: 0x54,
+		gas: 0,
+		gas_cost: 0,
+		depth,
+		stack: vec![],
+		memory: vec![],
+		storage,
+	};
+	let slot = |key: u8, value: u8| (H256::repeat_byte(key), H256::repeat_byte(value));
+
+	let entries = struct_log_entries(vec![
+		log(1, vec![slot(1, 1)]),
+		log(2, vec![slot(2, 2)]),
+		log(1, vec![]),
+		log(1, vec![slot(1, 3)]),
+		log(2, vec![slot(3, 3)]),
+		log(1, vec![slot(4, 4)]),
+	]);
+	let keys = |entry: &StructLogEntry| {
+		entry
+			.storage
+			.iter()
+			.map(|(k, v)| (k[..2].to_string(), v[..2].to_string()))
+			.collect::<Vec<(String, String)>>()
+	};
+
+	assert_eq!(keys(&entries[0]), vec![("01".into(), "01".into())]);
+	// a nested frame starts with no storage
+	assert_eq!(keys(&entries[1]), vec![("02".into(), "02".into())]);
+	assert!(entries[2].storage.is_empty());
+	assert_eq!(keys(&entries[3]), vec![("01".into(), "03".into())]);
+	// a new nested frame does not see the storage of the previous one
+	assert_eq!(keys(&entries[4]), vec![("03".into(), "03".into())]);
+	assert_eq!(keys(&entries[5]), vec![("01".into(), "03".into()), ("04".into(), "04".into())]);
+}
+
+#[test]
+fn call_frame_should_work() {
+	use sp_core::bytes::from_hex;
+
+	let revert = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000d6572726f72206d65737361676500000000000000000000000000000000000000").unwrap();
+	let trace = CallTrace {
+		call_type: CallType::Call,
+		from: H160::repeat_byte(1),
+		to: H160::repeat_byte(2),
+		value: 0.into(),
+		gas: 100,
+		gas_used: 50,
+		input: vec![0xc2, 0x98, 0x55, 0x78],
+		output: vec![],
+		exit_reason: ExitReason::Succeed(setheum_evm::ExitSucceed::Stopped),
+		calls: vec![CallTrace {
+			call_type: CallType::DelegateCall,
+			from: H160::repeat_byte(2),
+			to: H160::repeat_byte(3),
+			value: 0.into(),
+			gas: 40,
+			gas_used: 40,
+			input: vec![],
+			output: revert,
+			exit_reason: ExitReason::Revert(setheum_evm::ExitRevert::Reverted),
+			calls: vec![],
+		}],
+	};
+
+	let frame: CallFrame = trace.into();
+	assert_eq!(frame.call_type, "CALL");
+	assert_eq!(frame.error, None);
+	assert_eq!(frame.calls[0].call_type, "DELEGATECALL");
+	assert_eq!(frame.calls[0].error, Some("execution reverted".into()));
+	assert_eq!(frame.calls[0].revert_reason, Some("error message".into()));
+
+	let json = serde_json::to_value(&frame).unwrap();
+	assert_eq!(json["type"], "CALL");
+	assert_eq!(json["gasUsed"], "0x32");
+	assert_eq!(json["input"], "0xc2985578");
+	assert_eq!(json.get("revertReason"), None);
+	assert_eq!(json["calls"][0]["revertReason"], "error message");
+}
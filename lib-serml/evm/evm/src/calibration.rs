@@ -0,0 +1,94 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The opcode mixes the weight of gas is benchmarked with.
+//!
+//! Each mix is the runtime code of a contract looping over its opcodes until
+//! less than `30_000` gas is left, so a call burns its gas limit whatever the
+//! hard fork prices the opcodes at. A benchmark of `n` steps calls the mix with
+//! `n * GAS_PER_STEP` gas, and the slope of its weight is the weight of
+//! `GAS_PER_STEP` gas.
+
+use crate::{Config, WeightInfo};
+use frame_support::{
+	traits::Get,
+	weights::{DispatchClass, Weight},
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The gas of a step of the opcode mix benchmarks.
+pub const GAS_PER_STEP: u64 = 100_000;
+
+/// The most steps of the opcode mix benchmarks.
+pub const MAX_STEPS: u32 = 100;
+
+/// PUSH1, MUL, ADD, DIV, MOD and POP.
+pub const ARITHMETIC: [u8; 30] = [
+	0x5b, 0x61, 0x75, 0x30, 0x5a, 0x11, 0x15, 0x60, 0x1c, 0x57, 0x60, 0x07, 0x60, 0x03, 0x02, 0x60,
+	0x05, 0x01, 0x60, 0x02, 0x04, 0x60, 0x03, 0x06, 0x50, 0x60, 0x00, 0x56, 0x5b, 0x00,
+];
+
+/// SLOAD and SSTORE of new slots.
+pub const STORAGE: [u8; 21] = [
+	0x5b, 0x61, 0x75, 0x30, 0x5a, 0x11, 0x15, 0x60, 0x13, 0x57, 0x5a, 0x54, 0x50, 0x5a, 0x5a, 0x55,
+	0x60, 0x00, 0x56, 0x5b, 0x00,
+];
+
+/// SHA3 of 64 bytes of memory.
+pub const HASHING: [u8; 21] = [
+	0x5b, 0x61, 0x75, 0x30, 0x5a, 0x11, 0x15, 0x60, 0x13, 0x57, 0x60, 0x40, 0x60, 0x00, 0x20, 0x50,
+	0x60, 0x00, 0x56, 0x5b, 0x00,
+];
+
+/// The code deploying the runtime code `runtime` of a mix.
+pub fn init_code(runtime: &[u8]) -> Vec<u8> {
+	// PUSH1 len DUP1 PUSH1 11 PUSH1 0 CODECOPY PUSH1 0 RETURN
+	let mut init = vec![0x60, runtime.len() as u8, 0x80, 0x60, 0x0b, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3];
+	init.extend_from_slice(runtime);
+	init
+}
+
+/// The weight of a unit of gas: the highest of the benchmarked opcode mixes,
+/// so no mix is undercharged.
+pub struct WeightPerGas<T>(PhantomData<T>);
+impl<T: Config> Get<Weight> for WeightPerGas<T> {
+	fn get() -> Weight {
+		let per_gas = |weight: fn(u32) -> Weight| {
+			weight(MAX_STEPS).saturating_sub(weight(1)) / GAS_PER_STEP.saturating_mul((MAX_STEPS - 1).into())
+		};
+
+		per_gas(T::WeightInfo::evm_arithmetic)
+			.max(per_gas(T::WeightInfo::evm_storage))
+			.max(per_gas(T::WeightInfo::evm_hashing))
+			.max(1)
+	}
+}
+
+/// The most gas an extrinsic may use: the most weight of a normal extrinsic
+/// at `WeightPerGas` weight per unit of gas.
+pub struct MaxGasPerExtrinsic<T>(PhantomData<T>);
+impl<T: Config> Get<u64> for MaxGasPerExtrinsic<T> {
+	fn get() -> u64 {
+		let block_weights = T::BlockWeights::get();
+		let max_weight = block_weights
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(block_weights.max_block);
+		max_weight / WeightPerGas::<T>::get()
+	}
+}
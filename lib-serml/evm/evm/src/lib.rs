@@ -38,22 +38,31 @@ use frame_support::{
 		ReservableCurrency,
 	},
 	transactional,
-	weights::{Pays, PostDispatchInfo, Weight},
+	weights::{DispatchClass, Pays, PostDispatchInfo, Weight},
 	BoundedVec, RuntimeDebug,
 };
-use frame_system::{ensure_root, ensure_signed, pallet_prelude::*, EnsureOneOf, EnsureRoot, EnsureSigned};
+use frame_system::{ensure_none, ensure_root, ensure_signed, pallet_prelude::*, EnsureOneOf, EnsureRoot, EnsureSigned};
 use primitive_types::{H256, U256};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{
 	traits::{
-		Convert, DispatchInfoOf, One, PostDispatchInfoOf, Saturating, SignedExtension, UniqueSaturatedInto, Zero,
+		Convert, DispatchInfoOf, One, PostDispatchInfoOf, SaturatedConversion, Saturating, SignedExtension,
+		UniqueSaturatedInto, Zero,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
 	},
-	transaction_validity::TransactionValidityError,
 	Either, TransactionOutcome,
 };
-use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+use sp_std::{
+	convert::{TryFrom, TryInto},
+	marker::PhantomData,
+	prelude::*,
+};
+use transaction::EthereumTransaction;
 
 pub use support::{
 	AddressMapping, EVMStateRentTrait, ExecutionMode, InvokeContext, TransactionPayment, EVM as EVMTrait,
@@ -64,12 +73,18 @@ pub use crate::runner::Runner;
 pub use evm::{Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed};
 pub use orml_traits::currency::TransferAll;
 pub use primitives::{
-	evm::{Account, CallInfo, CreateInfo, EvmAddress, Log, Vicinity},
+	evm::{
+		AccessListItem, Account, BlockLog, CallInfo, CallTrace, CallType, CreateInfo, EvmAddress, Log, StructLog,
+		TraceOutcome, TracerConfig, Vicinity,
+	},
 	MIRRORED_NFT_ADDRESS_START,
 };
 
+pub mod calibration;
+pub mod mirrored_nft;
 pub mod precompiles;
 pub mod runner;
+pub mod transaction;
 
 mod mock;
 mod tests;
@@ -122,6 +137,87 @@ static SETHEUM_CONFIG: EvmConfig = EvmConfig {
 	estimate: false,
 };
 
+// Berlin hard fork configuration. Accounts and storage slots cost the warm
+// access, the runner charges the first, cold, access on top (EIP-2929).
+static SETHEUM_BERLIN_CONFIG: EvmConfig = EvmConfig {
+	gas_ext_code: 100,
+	gas_ext_code_hash: 100,
+	gas_balance: 100,
+	gas_sload: 100,
+	gas_sstore_set: 20000,
+	gas_sstore_reset: 2900,
+	refund_sstore_clears: 0, // no gas refund
+	gas_suicide: 5000,
+	gas_suicide_new_account: 25000,
+	gas_call: 100,
+	gas_expbyte: 50,
+	gas_transaction_create: 53000,
+	gas_transaction_call: 21000,
+	gas_transaction_zero_data: 4,
+	gas_transaction_non_zero_data: 16,
+	sstore_gas_metering: false,         // no gas refund
+	sstore_revert_under_stipend: false, // ignored
+	err_on_call_with_more_gas: false,
+	empty_considered_exists: false,
+	create_increase_nonce: true,
+	call_l64_after_gas: true,
+	stack_limit: 1024,
+	memory_limit: usize::max_value(),
+	call_stack_limit: 1024,
+	create_contract_limit: None, // ignored
+	call_stipend: 2300,
+	has_delegate_call: true,
+	has_create2: true,
+	has_revert: true,
+	has_return_data: true,
+	has_bitwise_shifting: true,
+	has_chain_id: true,
+	has_self_balance: true,
+	has_ext_code_hash: true,
+	estimate: false,
+};
+
+/// The Ethereum hard fork whose gas rules the runner follows.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum HardFork {
+	/// Every access to an account or a storage slot costs the same.
+	Istanbul,
+	/// The first access to an account or a storage slot in a transaction is
+	/// cold and costs more (EIP-2929). Transactions may declare access lists
+	/// to warm them up front (EIP-2930).
+	Berlin,
+	/// Berlin, and new contract code starting with `0xEF` is rejected
+	/// (EIP-3541). Gas refunds are disabled regardless of the hard fork.
+	London,
+}
+
+impl Default for HardFork {
+	fn default() -> Self {
+		HardFork::Istanbul
+	}
+}
+
+impl HardFork {
+	/// The EVM config of the hard fork.
+	pub fn config(&self) -> &'static EvmConfig {
+		match self {
+			HardFork::Istanbul => &SETHEUM_CONFIG,
+			HardFork::Berlin | HardFork::London => &SETHEUM_BERLIN_CONFIG,
+		}
+	}
+
+	/// Whether accounts and storage slots are charged by warmth, and access
+	/// lists are honoured.
+	pub fn has_access_lists(&self) -> bool {
+		!matches!(self, HardFork::Istanbul)
+	}
+
+	/// Whether new contract code starting with `0xEF` is rejected.
+	pub fn rejects_ef_code(&self) -> bool {
+		matches!(self, HardFork::London)
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use crate::runner::handler;
@@ -166,12 +262,24 @@ pub mod module {
 		/// Convert gas to weight.
 		type GasToWeight: Convert<u64, Weight>;
 
+		/// The most gas an extrinsic may use.
+		#[pallet::constant]
+		type MaxGasPerExtrinsic: Get<u64>;
+
 		/// ChargeTransactionPayment convert weight to fee.
 		type ChargeTransactionPayment: TransactionPayment<Self::AccountId, BalanceOf<Self>, NegativeImbalanceOf<Self>>;
 
+		/// The total bytes the contract's storage can increase by in raw
+		/// Ethereum transactions, which carry no storage limit.
+		#[pallet::constant]
+		type EthTransactionStorageLimit: Get<u32>;
+
+		/// The hard fork whose gas rules the runner follows.
+		type HardFork: Get<HardFork>;
+
 		/// EVM config used in the module.
 		fn config() -> &'static EvmConfig {
-			&SETHEUM_CONFIG
+			Self::HardFork::get().config()
 		}
 
 		/// Required origin for creating system contract.
@@ -194,6 +302,19 @@ pub mod module {
 
 		type FreeDeploymentOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The blocks between a contract code upgrade being proposed and it
+		/// being activated.
+		#[pallet::constant]
+		type CodeUpgradeDelay: Get<Self::BlockNumber>;
+
+		/// The most prior code versions kept for a contract to roll back to.
+		#[pallet::constant]
+		type MaxCodeVersions: Get<u32>;
+
+		/// Required origin for canceling the proposed code upgrade of any
+		/// contract.
+		type CodeUpgradeCancelOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -228,6 +349,14 @@ pub mod module {
 		pub ref_count: u32,
 	}
 
+	/// A code upgrade of a contract, which can be activated from block
+	/// `activate_at`.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode)]
+	pub struct CodeUpgrade<BlockNumber> {
+		pub code_hash: H256,
+		pub activate_at: BlockNumber,
+	}
+
 	#[cfg(feature = "std")]
 	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Serialize, Deserialize)]
 	/// Account definition used for genesis block construction.
@@ -281,6 +410,25 @@ pub mod module {
 	#[pallet::getter(fn code_infos)]
 	pub type CodeInfos<T: Config> = StorageMap<_, Identity, H256, CodeInfo, OptionQuery>;
 
+	/// The prior code hashes of contracts, oldest first, which they can roll
+	/// back to. Each holds a reference in `CodeInfos`.
+	///
+	/// CodeVersions: map EvmAddress => BoundedVec<H256, MaxCodeVersions>
+	#[pallet::storage]
+	#[pallet::getter(fn code_versions)]
+	pub type CodeVersions<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, BoundedVec<H256, T::MaxCodeVersions>, ValueQuery>;
+
+	/// The proposed code upgrades of contracts, awaiting activation. Unless
+	/// it rolls back to a prior code version, the code holds a reference in
+	/// `CodeInfos`.
+	///
+	/// CodeUpgrades: map EvmAddress => Option<CodeUpgrade<BlockNumber>>
+	#[pallet::storage]
+	#[pallet::getter(fn code_upgrades)]
+	pub type CodeUpgrades<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, CodeUpgrade<T::BlockNumber>, OptionQuery>;
+
 	/// Next available system contract address.
 	///
 	/// NetworkContractIndex: u64
@@ -372,6 +520,7 @@ pub mod module {
 				}
 			});
 			NetworkContractIndex::<T>::put(MIRRORED_NFT_ADDRESS_START);
+			<Pallet<T>>::deploy_mirrored_nft();
 		}
 	}
 
@@ -419,6 +568,13 @@ pub mod module {
 		ContractSetCode(EvmAddress),
 		/// Selfdestructed contract code. \[contract\]
 		ContractSelfdestructed(EvmAddress),
+		/// Proposed a contract code upgrade. \[contract, code_hash,
+		/// activate_at\]
+		CodeUpgradeProposed(EvmAddress, H256, T::BlockNumber),
+		/// Canceled a proposed contract code upgrade. \[contract, code_hash\]
+		CodeUpgradeCanceled(EvmAddress, H256),
+		/// Activated a contract code upgrade. \[contract, code_hash\]
+		CodeUpgradeActivated(EvmAddress, H256),
 	}
 
 	#[pallet::error]
@@ -449,13 +605,33 @@ pub mod module {
 		CannotKillContract,
 		/// Contract address conflicts with the system contract
 		ConflictContractAddress,
+		/// Raw Ethereum transaction is malformed or wrongly signed
+		InvalidEthTransaction,
+		/// Contract code starts with the reserved `0xEF` byte
+		InvalidCode,
+		/// Contract code is the current code of the contract
+		CodeUnchanged,
+		/// Contract code version not found
+		CodeVersionNotFound,
+		/// Contract already has a proposed code upgrade
+		CodeUpgradeAlreadyProposed,
+		/// Contract code upgrade not found
+		CodeUpgradeNotFound,
+		/// Contract code upgrade cannot be activated before its delay passes
+		CodeUpgradeNotDue,
+		/// Gas limit exceeds the most gas an extrinsic may use
+		GasLimitTooHigh,
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::deploy_mirrored_nft()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -467,6 +643,7 @@ pub mod module {
 		/// - `value`: the amount sent for payable calls
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		/// - `access_list`: the accounts and storage slots to warm up front
 		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
 		pub fn call(
 			origin: OriginFor<T>,
@@ -475,8 +652,10 @@ pub mod module {
 			value: BalanceOf<T>,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Vec<AccessListItem>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(gas_limit <= T::MaxGasPerExtrinsic::get(), Error::<T>::GasLimitTooHigh);
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
 			let info = Runner::<T>::call(
@@ -487,6 +666,7 @@ pub mod module {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list,
 				T::config(),
 			)?;
 
@@ -537,7 +717,17 @@ pub mod module {
 				_payed = imbalance;
 			}
 
-			let info = Runner::<T>::call(from, from, target, input, value, gas_limit, storage_limit, T::config())?;
+			let info = Runner::<T>::call(
+				from,
+				from,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				vec![],
+				T::config(),
+			)?;
 
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Executed(target));
@@ -576,6 +766,7 @@ pub mod module {
 		/// - `value`: the amount sent to the contract upon creation
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		/// - `access_list`: the accounts and storage slots to warm up front
 		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
 		pub fn create(
 			origin: OriginFor<T>,
@@ -583,11 +774,13 @@ pub mod module {
 			value: BalanceOf<T>,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Vec<AccessListItem>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(gas_limit <= T::MaxGasPerExtrinsic::get(), Error::<T>::GasLimitTooHigh);
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
-			let info = Runner::<T>::create(source, init, value, gas_limit, storage_limit, T::config())?;
+			let info = Runner::<T>::create(source, init, value, gas_limit, storage_limit, access_list, T::config())?;
 
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
@@ -611,6 +804,7 @@ pub mod module {
 		/// - `value`: the amount sent for payable calls
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		/// - `access_list`: the accounts and storage slots to warm up front
 		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
 		pub fn create2(
 			origin: OriginFor<T>,
@@ -619,11 +813,22 @@ pub mod module {
 			value: BalanceOf<T>,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Vec<AccessListItem>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(gas_limit <= T::MaxGasPerExtrinsic::get(), Error::<T>::GasLimitTooHigh);
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
-			let info = Runner::<T>::create2(source, init, salt, value, gas_limit, storage_limit, T::config())?;
+			let info = Runner::<T>::create2(
+				source,
+				init,
+				salt,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				T::config(),
+			)?;
 
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
@@ -777,7 +982,8 @@ pub mod module {
 			Ok(().into())
 		}
 
-		/// Set the code of a contract at a given address.
+		/// Set the code of a contract at a given address. Deployed contracts
+		/// are upgraded with `propose_code_upgrade` instead.
 		///
 		/// - `contract`: The contract whose code is being set, must not be marked as deployed
 		/// - `code`: The new ABI bundle for the contract
@@ -806,6 +1012,146 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Propose new code for a contract, which the maintainer can activate
+		/// once `CodeUpgradeDelay` blocks have passed. The maintainer pays
+		/// the storage deposit of the code until it is dropped from the
+		/// contract's code versions.
+		///
+		/// - `contract`: The contract to upgrade, the caller must be the contract's maintainer
+		/// - `code`: The new runtime code of the contract
+		#[pallet::weight(<T as Config>::WeightInfo::propose_code_upgrade())]
+		#[transactional]
+		pub fn propose_code_upgrade(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			code: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let upgrade = Self::do_propose_code_upgrade(maintainer, contract, code)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeProposed(
+				contract,
+				upgrade.code_hash,
+				upgrade.activate_at,
+			));
+
+			Ok(().into())
+		}
+
+		/// Propose to roll a contract back to a prior code version, which the
+		/// maintainer can activate once `CodeUpgradeDelay` blocks have passed.
+		///
+		/// - `contract`: The contract to roll back, the caller must be the contract's maintainer
+		/// - `code_hash`: The hash of one of the contract's code versions
+		#[pallet::weight(<T as Config>::WeightInfo::propose_code_rollback())]
+		#[transactional]
+		pub fn propose_code_rollback(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			code_hash: H256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let contract_info = Self::maintained_contract_info(&contract, Some(maintainer))?;
+			ensure!(
+				Self::code_versions(contract).contains(&code_hash),
+				Error::<T>::CodeVersionNotFound
+			);
+			let upgrade = Self::schedule_code_upgrade(&contract_info, contract, code_hash)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeProposed(
+				contract,
+				upgrade.code_hash,
+				upgrade.activate_at,
+			));
+
+			Ok(().into())
+		}
+
+		/// Cancel the proposed code upgrade of a contract.
+		///
+		/// - `contract`: The contract whose code upgrade is canceled, the caller must be the
+		///   contract's maintainer or `CodeUpgradeCancelOrigin`
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_code_upgrade())]
+		#[transactional]
+		pub fn cancel_code_upgrade(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let maintainer = match T::CodeUpgradeCancelOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => {
+					let who = ensure_signed(origin)?;
+					Some(T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?)
+				}
+			};
+			let code_hash = Self::do_cancel_code_upgrade(maintainer, contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeCanceled(contract, code_hash));
+
+			Ok(().into())
+		}
+
+		/// Activate the proposed code upgrade of a contract. The replaced
+		/// code is kept as a code version to roll back to.
+		///
+		/// - `contract`: The contract to upgrade, the caller must be the contract's maintainer
+		#[pallet::weight(<T as Config>::WeightInfo::activate_code_upgrade())]
+		#[transactional]
+		pub fn activate_code_upgrade(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let code_hash = Self::do_activate_code_upgrade(maintainer, contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeActivated(contract, code_hash));
+
+			Ok(().into())
+		}
+
+		/// Execute a raw Ethereum transaction, as sent to
+		/// `eth_sendRawTransaction`. The sender recovered from the signature
+		/// pays the base and length fee of `raw` and the fee for `gas_limit`
+		/// from its mapped account, and gets refunded for the unused gas.
+		///
+		/// - `raw`: the RLP encoded, EIP-155 signed transaction
+		#[pallet::weight(T::GasToWeight::convert(EthereumTransaction::decode(raw).map_or(0, |tx| tx.gas_limit)))]
+		pub fn eth_transact(origin: OriginFor<T>, raw: Vec<u8>) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let (tx, source, value) =
+				Self::check_eth_transaction(&raw).map_err(|_| Error::<T>::InvalidEthTransaction)?;
+
+			let who = T::AddressMapping::get_account_id(&source);
+			// unsigned extrinsics are not charged by `ChargeTransactionPayment`,
+			// so take the transaction fee here
+			let (_, payed) =
+				Self::charge_eth_transaction_fee(&who, &tx, raw.len()).map_err(|_| Error::<T>::ChargeFeeFailed)?;
+
+			ExtrinsicOrigin::<T>::set(Some(who.clone()));
+			let result = Self::do_eth_transact(source, &tx, value);
+			ExtrinsicOrigin::<T>::kill();
+
+			// the nonce protects against replay, so it must be used up even if the
+			// transaction was rejected before execution
+			if Self::account_basic(&source).nonce == tx.nonce {
+				Handler::<T>::inc_nonce(source);
+			}
+
+			let used_gas = *result.as_ref().unwrap_or(&tx.gas_limit);
+
+			let refund_gas = tx.gas_limit.saturating_sub(used_gas);
+			if !refund_gas.is_zero() {
+				// ignore the result to continue. if it fails, just the user will not
+				// be refunded, there will not increase user balance.
+				let res = T::ChargeTransactionPayment::refund_fee(&who, T::GasToWeight::convert(refund_gas), payed);
+				debug_assert!(res.is_ok());
+			}
+
+			result?;
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				pays_fee: Pays::No,
+			})
+		}
 	}
 }
 
@@ -821,18 +1167,16 @@ impl<T: Config> Pallet<T> {
 
 			T::TransferAll::transfer_all(&address_account, &dest_account)?;
 
-			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| {
-				if let Some(code_info) = maybe_code_info.as_mut() {
-					code_info.ref_count = code_info.ref_count.saturating_sub(1);
-					if code_info.ref_count == 0 {
-						Codes::<T>::remove(&contract_info.code_hash);
-						*maybe_code_info = None;
-					}
-				} else {
-					// code info removed while still having reference to it?
-					debug_assert!(false);
+			Self::dec_code_ref(&contract_info.code_hash);
+
+			// the proposed and prior code versions are referenced by the contract too
+			let code_versions = CodeVersions::<T>::take(address);
+			if let Some(upgrade) = CodeUpgrades::<T>::take(address) {
+				if !code_versions.contains(&upgrade.code_hash) {
+					Self::dec_code_ref(&upgrade.code_hash);
 				}
-			});
+			}
+			code_versions.iter().for_each(Self::dec_code_ref);
 
 			AccountStorages::<T>::remove_prefix(address);
 
@@ -936,6 +1280,10 @@ impl<T: Config> Pallet<T> {
 		maintainer: &EvmAddress,
 		code: Vec<u8>,
 	) -> Result<(), ExitError> {
+		if T::HardFork::get().rejects_ef_code() && code.first() == Some(&0xEF) {
+			return Err(ExitError::Other(Into::<&str>::into(Error::<T>::InvalidCode).into()));
+		}
+
 		let bounded_code: BoundedVec<u8, T::MaxCodeSize> = code.try_into().map_err(|_| ExitError::OutOfGas)?;
 		let code_hash = code_hash(&bounded_code.as_slice());
 		let code_size = bounded_code.len() as u32;
@@ -1075,6 +1423,176 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// The contract info of a contract.
+	///
+	/// If maintainer is provided then it will check maintainer
+	fn maintained_contract_info(
+		contract: &EvmAddress,
+		maintainer: Option<EvmAddress>,
+	) -> Result<ContractInfo, DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+		if let Some(maintainer) = maintainer {
+			ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+		}
+		Ok(contract_info)
+	}
+
+	/// Drop a reference to the code of `code_hash`, removing the code once
+	/// it is no longer referenced.
+	fn dec_code_ref(code_hash: &H256) {
+		CodeInfos::<T>::mutate_exists(code_hash, |maybe_code_info| {
+			if let Some(code_info) = maybe_code_info.as_mut() {
+				code_info.ref_count = code_info.ref_count.saturating_sub(1);
+				if code_info.ref_count == 0 {
+					Codes::<T>::remove(code_hash);
+					*maybe_code_info = None;
+				}
+			} else {
+				// code info removed while still having reference to it?
+				debug_assert!(false);
+			}
+		});
+	}
+
+	/// Drop a code version of a contract, refunding its storage deposit to
+	/// the maintainer.
+	fn release_code_version(contract: &EvmAddress, code_hash: &H256, maintainer: EvmAddress) -> DispatchResult {
+		let code_size = Self::code_infos(code_hash).map_or(0, |code_info| code_info.code_size);
+		Self::dec_code_ref(code_hash);
+		Self::update_contract_storage_size(contract, -(code_size as i32));
+
+		let mut handler = StorageMeterHandlerImpl::<T>::new(maintainer);
+		handler.charge_storage(contract, 0, code_size)?;
+		handler.unreserve_storage(0, 0, code_size)
+	}
+
+	/// Propose the code upgrade of a contract to `code_hash`.
+	fn schedule_code_upgrade(
+		contract_info: &ContractInfo,
+		contract: EvmAddress,
+		code_hash: H256,
+	) -> Result<CodeUpgrade<T::BlockNumber>, DispatchError> {
+		ensure!(code_hash != contract_info.code_hash, Error::<T>::CodeUnchanged);
+		ensure!(
+			!CodeUpgrades::<T>::contains_key(contract),
+			Error::<T>::CodeUpgradeAlreadyProposed
+		);
+
+		let upgrade = CodeUpgrade {
+			code_hash,
+			activate_at: frame_system::Pallet::<T>::block_number().saturating_add(T::CodeUpgradeDelay::get()),
+		};
+		CodeUpgrades::<T>::insert(contract, upgrade.clone());
+		Ok(upgrade)
+	}
+
+	/// Propose new code for a contract.
+	///
+	/// - Ensures signer is maintainer.
+	/// - Update codes info.
+	/// - Save `code` if not saved yet, and charge its storage to the maintainer.
+	fn do_propose_code_upgrade(
+		maintainer: EvmAddress,
+		contract: EvmAddress,
+		code: Vec<u8>,
+	) -> Result<CodeUpgrade<T::BlockNumber>, DispatchError> {
+		let contract_info = Self::maintained_contract_info(&contract, Some(maintainer))?;
+
+		if T::HardFork::get().rejects_ef_code() {
+			ensure!(code.first() != Some(&0xEF), Error::<T>::InvalidCode);
+		}
+		let bounded_code: BoundedVec<u8, T::MaxCodeSize> =
+			code.try_into().map_err(|_| Error::<T>::ContractExceedsMaxCodeSize)?;
+		let code_hash = code_hash(&bounded_code.as_slice());
+		let code_size = bounded_code.len() as u32;
+
+		let upgrade = Self::schedule_code_upgrade(&contract_info, contract, code_hash)?;
+
+		// a prior code version is already referenced and paid for
+		if Self::code_versions(contract).contains(&code_hash) {
+			return Ok(upgrade);
+		}
+
+		CodeInfos::<T>::mutate_exists(&code_hash, |maybe_code_info| {
+			if let Some(code_info) = maybe_code_info.as_mut() {
+				code_info.ref_count = code_info.ref_count.saturating_add(1);
+			} else {
+				let new = CodeInfo {
+					code_size,
+					ref_count: 1,
+				};
+				*maybe_code_info = Some(new);
+
+				Codes::<T>::insert(&code_hash, bounded_code);
+			}
+		});
+
+		Self::update_contract_storage_size(&contract, code_size as i32);
+
+		let mut handler = StorageMeterHandlerImpl::<T>::new(maintainer);
+		handler.reserve_storage(code_size)?;
+		handler.charge_storage(&contract, code_size, 0)?;
+
+		Ok(upgrade)
+	}
+
+	/// Cancel the proposed code upgrade of a contract.
+	///
+	/// If maintainer is provided then it will check maintainer
+	fn do_cancel_code_upgrade(maintainer: Option<EvmAddress>, contract: EvmAddress) -> Result<H256, DispatchError> {
+		let contract_info = Self::maintained_contract_info(&contract, maintainer)?;
+		let upgrade = CodeUpgrades::<T>::take(contract).ok_or(Error::<T>::CodeUpgradeNotFound)?;
+
+		if !Self::code_versions(contract).contains(&upgrade.code_hash) {
+			Self::release_code_version(&contract, &upgrade.code_hash, contract_info.maintainer)?;
+		}
+
+		Ok(upgrade.code_hash)
+	}
+
+	/// Activate the proposed code upgrade of a contract.
+	///
+	/// - Ensures signer is maintainer, and the delay has passed.
+	/// - Keep the replaced code as a code version, dropping the oldest ones
+	///   beyond `MaxCodeVersions`.
+	fn do_activate_code_upgrade(maintainer: EvmAddress, contract: EvmAddress) -> Result<H256, DispatchError> {
+		let contract_info = Self::maintained_contract_info(&contract, Some(maintainer))?;
+		let upgrade = CodeUpgrades::<T>::take(contract).ok_or(Error::<T>::CodeUpgradeNotFound)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= upgrade.activate_at,
+			Error::<T>::CodeUpgradeNotDue
+		);
+
+		let mut code_versions = Self::code_versions(contract).into_inner();
+		// a rollback moves its reference from the code versions to the contract
+		if let Some(index) = code_versions.iter().position(|code_hash| *code_hash == upgrade.code_hash) {
+			code_versions.remove(index);
+		}
+		code_versions.push(contract_info.code_hash);
+
+		let dropped = code_versions.len().saturating_sub(T::MaxCodeVersions::get() as usize);
+		for code_hash in code_versions.drain(..dropped) {
+			Self::release_code_version(&contract, &code_hash, maintainer)?;
+		}
+		let code_versions: BoundedVec<H256, T::MaxCodeVersions> =
+			code_versions.try_into().map_err(|_| Error::<T>::NumOutOfBound)?;
+		CodeVersions::<T>::insert(contract, code_versions);
+
+		Accounts::<T>::mutate(contract, |maybe_account_info| {
+			if let Some(AccountInfo {
+				contract_info: Some(contract_info),
+				..
+			}) = maybe_account_info.as_mut()
+			{
+				contract_info.code_hash = upgrade.code_hash;
+			}
+		});
+
+		Ok(upgrade.code_hash)
+	}
+
 	/// Selfdestruct a contract at a given address.
 	fn do_selfdestruct(who: T::AccountId, maintainer: &EvmAddress, contract: EvmAddress) -> DispatchResult {
 		let account_info = Self::accounts(contract).ok_or(Error::<T>::ContractNotFound)?;
@@ -1097,6 +1615,155 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Decode a raw Ethereum transaction for this chain, returning it with
+	/// its sender and value.
+	pub fn check_eth_transaction(
+		raw: &[u8],
+	) -> Result<(EthereumTransaction, EvmAddress, BalanceOf<T>), InvalidTransaction> {
+		let tx = EthereumTransaction::decode(raw).map_err(|_| InvalidTransaction::Call)?;
+		// only accept EIP-155 signatures, which cannot be replayed on other chains
+		if tx.chain_id != Some(T::ChainId::get()) {
+			return Err(InvalidTransaction::BadProof);
+		}
+		if tx.gas_limit > T::MaxGasPerExtrinsic::get() {
+			return Err(InvalidTransaction::ExhaustsResources);
+		}
+		let source = tx.sender().ok_or(InvalidTransaction::BadProof)?;
+		let value = u128::try_from(tx.value)
+			.ok()
+			.and_then(|value| value.try_into().ok())
+			.ok_or(InvalidTransaction::Call)?;
+		Ok((tx, source, value))
+	}
+
+	/// Take the fee of a raw Ethereum transaction from `who`: the base and
+	/// length fee of `len` bytes, and the fee for `gas_limit`. Returns the
+	/// total fee, and the imbalance of the fee for `gas_limit` to refund the
+	/// unused gas from.
+	fn charge_eth_transaction_fee(
+		who: &T::AccountId,
+		tx: &EthereumTransaction,
+		len: usize,
+	) -> Result<(BalanceOf<T>, NegativeImbalanceOf<T>), TransactionValidityError> {
+		let inclusion_fee = T::ChargeTransactionPayment::charge_fee(
+			who,
+			len.saturated_into(),
+			0,
+			Zero::zero(),
+			Pays::Yes,
+			DispatchClass::Normal,
+		)?;
+		let weight = T::GasToWeight::convert(tx.gas_limit);
+		T::ChargeTransactionPayment::reserve_fee(who, weight).map_err(|_| InvalidTransaction::Payment)?;
+		let (gas_fee, payed) = T::ChargeTransactionPayment::unreserve_and_charge_fee(who, weight)?;
+		Ok((inclusion_fee.saturating_add(gas_fee), payed))
+	}
+
+	/// The priority of a raw Ethereum transaction by the fee it pays,
+	/// computed like the priority of signed extrinsics, so the sender can't
+	/// raise it by `gas_price` alone.
+	fn eth_transaction_priority(fee: BalanceOf<T>, gas_limit: u64, len: usize) -> TransactionPriority {
+		let weight_saturation = T::BlockWeights::get().max_block / T::GasToWeight::convert(gas_limit).max(1);
+		let max_block_length = *T::BlockLength::get().max.get(DispatchClass::Normal);
+		let len_saturation = max_block_length as u64 / (len as u64).max(1);
+		let coefficient: BalanceOf<T> = weight_saturation.min(len_saturation).saturated_into();
+		fee.saturating_mul(coefficient).saturated_into()
+	}
+
+	/// Run a raw Ethereum transaction, returning the gas used.
+	fn do_eth_transact(
+		source: EvmAddress,
+		tx: &EthereumTransaction,
+		value: BalanceOf<T>,
+	) -> Result<u64, DispatchError> {
+		let storage_limit = T::EthTransactionStorageLimit::get();
+
+		let used_gas = match tx.action {
+			Some(target) => {
+				let info = Runner::<T>::call(
+					source,
+					source,
+					target,
+					tx.input.clone(),
+					value,
+					tx.gas_limit,
+					storage_limit,
+					vec![],
+					T::config(),
+				)?;
+
+				if info.exit_reason.is_succeed() {
+					Pallet::<T>::deposit_event(Event::<T>::Executed(target));
+				} else {
+					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed(target, info.exit_reason, info.output));
+				}
+				info.used_gas
+			}
+			None => {
+				let info = Runner::<T>::create(
+					source,
+					tx.input.clone(),
+					value,
+					tx.gas_limit,
+					storage_limit,
+					vec![],
+					T::config(),
+				)?;
+
+				if info.exit_reason.is_succeed() {
+					Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
+				} else {
+					Pallet::<T>::deposit_event(Event::<T>::CreatedFailed(info.address, info.exit_reason, info.output));
+				}
+				info.used_gas
+			}
+		};
+
+		Ok(used_gas.unique_saturated_into())
+	}
+
+	/// Trace a raw Ethereum transaction as `eth_transact` runs it, after
+	/// taking its fee, without committing the execution.
+	pub fn trace_eth_transact(raw: &[u8], tracer: TracerConfig) -> Result<TraceOutcome, DispatchError> {
+		let (tx, source, value) = Self::check_eth_transaction(raw).map_err(|_| Error::<T>::InvalidEthTransaction)?;
+		// `pre_dispatch` rejects the transaction otherwise
+		ensure!(tx.nonce == Self::account_basic(&source).nonce, Error::<T>::InvalidEthTransaction);
+
+		let who = T::AddressMapping::get_account_id(&source);
+		Self::charge_eth_transaction_fee(&who, &tx, raw.len()).map_err(|_| Error::<T>::ChargeFeeFailed)?;
+
+		let storage_limit = T::EthTransactionStorageLimit::get();
+		ExtrinsicOrigin::<T>::set(Some(who));
+		let outcome = match tx.action {
+			Some(target) => Runner::<T>::trace_call(
+				source,
+				source,
+				target,
+				tx.input,
+				value,
+				tx.gas_limit,
+				storage_limit,
+				vec![],
+				T::config(),
+				tracer,
+			),
+			None => Runner::<T>::trace_create(
+				source,
+				tx.input,
+				None,
+				value,
+				tx.gas_limit,
+				storage_limit,
+				vec![],
+				T::config(),
+				tracer,
+			),
+		};
+		ExtrinsicOrigin::<T>::kill();
+
+		outcome
+	}
+
 	fn ensure_root_or_signed(o: T::Origin) -> Result<Either<(), T::AccountId>, BadOrigin> {
 		EnsureOneOf::<T::AccountId, EnsureRoot<T::AccountId>, EnsureSigned<T::AccountId>>::try_origin(o)
 			.map_or(Err(BadOrigin), Ok)
@@ -1127,6 +1794,7 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 				value,
 				gas_limit,
 				storage_limit,
+				vec![],
 				&config,
 			);
 
@@ -1193,6 +1861,61 @@ impl<T: Config> EVMStateRentTrait<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	}
 }
 
+#[allow(deprecated)]
+impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		match call {
+			Call::eth_transact(raw) => {
+				let (tx, source, _) = Self::check_eth_transaction(raw)?;
+
+				let nonce = Self::account_basic(&source).nonce;
+				if tx.nonce < nonce {
+					return InvalidTransaction::Stale.into();
+				}
+
+				// the fee is taken on dispatch, make sure it can be paid
+				let who = T::AddressMapping::get_account_id(&source);
+				let fee = frame_support::storage::with_transaction(|| {
+					TransactionOutcome::Rollback(
+						Self::charge_eth_transaction_fee(&who, &tx, raw.len()).map(|(fee, _)| fee),
+					)
+				})?;
+
+				let mut builder = ValidTransaction::with_tag_prefix("setheum-evm")
+					.priority(Self::eth_transaction_priority(fee, tx.gas_limit, raw.len()))
+					.and_provides((source, tx.nonce))
+					.longevity(64_u64)
+					.propagate(true);
+				if tx.nonce > nonce {
+					builder = builder.and_requires((source, tx.nonce - 1));
+				}
+				builder.build()
+			}
+			_ => InvalidTransaction::Call.into(),
+		}
+	}
+
+	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+		match call {
+			Call::eth_transact(raw) => {
+				let (tx, source, _) = Self::check_eth_transaction(raw)?;
+
+				let nonce = Self::account_basic(&source).nonce;
+				if tx.nonce < nonce {
+					Err(InvalidTransaction::Stale.into())
+				} else if tx.nonce > nonce {
+					Err(InvalidTransaction::Future.into())
+				} else {
+					Ok(())
+				}
+			}
+			_ => Err(InvalidTransaction::Call.into()),
+		}
+	}
+}
+
 pub struct CallKillAccount<T>(PhantomData<T>);
 impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 	fn on_killed_account(who: &T::AccountId) {
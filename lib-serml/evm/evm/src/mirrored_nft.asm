@@ -0,0 +1,236 @@
+; This file is part of Setheum.
+
+; Copyright (C) 2019-2021 Setheum Labs.
+; SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+; The source of `mirrored_nft::CODE`, the ERC-721 contract of the mirrored NFT
+; classes. `mirrored_nft_code_is_assembled_from_source` checks that `CODE` is
+; this source assembled.
+;
+; Syntax: one or more instructions per line, `; ` starts a comment. `name:`
+; is a `JUMPDEST` labelled `name`, and `PUSH2 @name` pushes its offset.
+
+	; Reject value, calls outside of the mirrored NFT class addresses and calls without a selector.
+	; CALLVALUE == 0
+	CALLVALUE PUSH2 @revert JUMPI
+	; ADDRESS >> 32 == 0x0200000000
+	ADDRESS PUSH1 0x20 SHR PUSH5 0x0200000000 EQ ISZERO PUSH2 @revert JUMPI
+	; CALLDATASIZE < 4
+	PUSH1 0x04 CALLDATASIZE LT PUSH2 @revert JUMPI
+	; selector
+	PUSH1 0x00 CALLDATALOAD PUSH1 0xe0 SHR
+	; balanceOf(address)
+	DUP1 PUSH4 0x70a08231 EQ PUSH2 @balance_of JUMPI
+	; ownerOf(uint256)
+	DUP1 PUSH4 0x6352211e EQ PUSH2 @owner_of JUMPI
+	; transferFrom(address,address,uint256)
+	DUP1 PUSH4 0x23b872dd EQ PUSH2 @transfer_from JUMPI
+	; safeTransferFrom(address,address,uint256)
+	DUP1 PUSH4 0x42842e0e EQ PUSH2 @safe_transfer_from JUMPI
+	; safeTransferFrom(address,address,uint256,bytes)
+	DUP1 PUSH4 0xb88d4fde EQ PUSH2 @safe_transfer_from_data JUMPI
+	; approve(address,uint256)
+	DUP1 PUSH4 0x095ea7b3 EQ PUSH2 @approve JUMPI
+	; getApproved(uint256)
+	DUP1 PUSH4 0x081812fc EQ PUSH2 @get_approved JUMPI
+	; setApprovalForAll(address,bool)
+	DUP1 PUSH4 0xa22cb465 EQ PUSH2 @set_approval_for_all JUMPI
+	; isApprovedForAll(address,address)
+	DUP1 PUSH4 0xe985e9c5 EQ PUSH2 @is_approved_for_all JUMPI
+	; tokenURI(uint256)
+	DUP1 PUSH4 0xc87b56dd EQ PUSH2 @token_uri JUMPI
+	; supportsInterface(bytes4)
+	DUP1 PUSH4 0x01ffc9a7 EQ PUSH2 @supports_interface JUMPI
+revert:
+	; REVERT(0, 0)
+	PUSH1 0x00 DUP1 REVERT
+
+	; Call the NFT precompile, copy its output to memory 0 and jump back, or revert with it.
+precompile:
+	; CALL(GAS, 0x401, 0, 0, len, 0, 0)
+	PUSH1 0x00 DUP1 PUSH2 0x0401 GAS CALL
+	; RETURNDATACOPY(0, 0, RETURNDATASIZE)
+	RETURNDATASIZE PUSH1 0x00 DUP1 RETURNDATACOPY
+	; jump back on success
+	ISZERO PUSH2 @bubble JUMPI JUMP
+bubble:
+	; REVERT(0, RETURNDATASIZE)
+	RETURNDATASIZE PUSH1 0x00 REVERT
+return_output:
+	; RETURN(0, RETURNDATASIZE)
+	RETURNDATASIZE PUSH1 0x00 RETURN
+stop:
+	; STOP
+	STOP
+
+	; balanceOf(owner): class balance of owner, which must not be zero.
+balance_of:
+	; owner != 0
+	PUSH1 0x04 CALLDATALOAD PUSH1 0x60 SHL ISZERO PUSH2 @revert JUMPI
+	; action
+	PUSH4 0x00fdd58e PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; owner, class_id
+	PUSH1 0x04 CALLDATALOAD PUSH1 0x24 MSTORE ADDRESS PUSH4 0xffffffff AND PUSH1 0x44 MSTORE
+	; query
+	PUSH2 @return_output PUSH1 0x00 PUSH1 0x00 PUSH1 0x64 PUSH2 @precompile JUMP
+
+	; ownerOf(tokenId): owner of the token, which must exist.
+owner_of:
+	; action
+	PUSH4 0xd9dad80d PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD DUP1 PUSH1 0x40 SHR PUSH2 @revert JUMPI PUSH1 0x44 MSTORE
+	; query
+	PUSH2 @owner_exists PUSH1 0x00 PUSH1 0x00 PUSH1 0x64 PUSH2 @precompile JUMP
+owner_exists:
+	; owner != 0
+	PUSH1 0x00 MLOAD ISZERO PUSH2 @revert JUMPI
+	; RETURN(0, 32)
+	PUSH1 0x20 PUSH1 0x00 RETURN
+
+	; transferFrom(from, to, tokenId): transfer by the caller, emitting Transfer.
+transfer_from:
+	; transfer then stop
+	PUSH2 @stop PUSH2 @transfer JUMP
+transfer:
+	; action
+	PUSH4 0x10f02113 PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; caller, from, to
+	CALLER PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD PUSH1 0x44 MSTORE PUSH1 0x24 CALLDATALOAD PUSH1 0x64 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x84 MSTORE PUSH1 0x44 CALLDATALOAD DUP1 PUSH1 0x40 SHR PUSH2 @revert JUMPI PUSH1 0xa4 MSTORE
+	; transfer
+	PUSH2 @transferred PUSH1 0x00 PUSH1 0x00 PUSH1 0xc4 PUSH2 @precompile JUMP
+transferred:
+	; tokenId, to, from
+	PUSH1 0x44 CALLDATALOAD PUSH1 0x24 CALLDATALOAD PUSH1 0x60 SHL PUSH1 0x60 SHR PUSH1 0x04 CALLDATALOAD PUSH1 0x60 SHL PUSH1 0x60 SHR
+	; LOG4(0, 0, Transfer, from, to, tokenId) and jump back
+	PUSH32 0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef PUSH1 0x00 DUP1 LOG4 JUMP
+
+	; safeTransferFrom(from, to, tokenId[, data]): transferFrom, then onERC721Received of a contract recipient.
+safe_transfer_from:
+	; transfer then check the recipient
+	PUSH2 @received_no_data PUSH2 @transfer JUMP
+safe_transfer_from_data:
+	; transfer then check the recipient
+	PUSH2 @received_data PUSH2 @transfer JUMP
+received_no_data:
+	; data length 0
+	PUSH1 0x00 PUSH2 0x0184 MSTORE
+	; call data length
+	PUSH1 0xa4 PUSH2 @received JUMP
+received_data:
+	; data offset
+	PUSH1 0x64 CALLDATALOAD PUSH1 0x04 ADD
+	; offset <= CALLDATASIZE
+	CALLDATASIZE DUP2 GT PUSH2 @revert JUMPI
+	; CALLDATACOPY(0x184, offset, CALLDATASIZE - offset)
+	DUP1 CALLDATASIZE SUB DUP1 SWAP2 PUSH2 0x0184 CALLDATACOPY
+	; call data length
+	PUSH1 0x84 ADD
+received:
+	; recipient without code
+	PUSH1 0x24 CALLDATALOAD PUSH1 0x60 SHL PUSH1 0x60 SHR EXTCODESIZE ISZERO PUSH2 @stop JUMPI
+	; onERC721Received selector
+	PUSH4 0x150b7a02 PUSH1 0xe0 SHL PUSH2 0x0100 MSTORE
+	; caller, from, tokenId, data offset
+	CALLER PUSH2 0x0104 MSTORE PUSH1 0x04 CALLDATALOAD PUSH2 0x0124 MSTORE PUSH1 0x44 CALLDATALOAD PUSH2 0x0144 MSTORE PUSH1 0x80 PUSH2 0x0164 MSTORE
+	; CALL(GAS, to, 0, 0x100, len, 0, 32)
+	PUSH1 0x20 PUSH1 0x00 DUP3 PUSH2 0x0100 PUSH1 0x00 PUSH1 0x24 CALLDATALOAD PUSH1 0x60 SHL PUSH1 0x60 SHR GAS CALL
+	; revert with the output of a failed call
+	ISZERO PUSH2 @bubble_call JUMPI
+	; RETURNDATASIZE >= 32
+	PUSH1 0x20 RETURNDATASIZE LT PUSH2 @revert JUMPI
+	; output == onERC721Received selector
+	PUSH1 0x00 MLOAD PUSH1 0xe0 SHR PUSH4 0x150b7a02 EQ ISZERO PUSH2 @revert JUMPI STOP
+bubble_call:
+	; RETURNDATACOPY(0, 0, RETURNDATASIZE)
+	RETURNDATASIZE PUSH1 0x00 DUP1 RETURNDATACOPY
+	; REVERT(0, RETURNDATASIZE)
+	RETURNDATASIZE PUSH1 0x00 REVERT
+
+	; approve(to, tokenId): approval by the caller, emitting Approval with the owner.
+approve:
+	; action
+	PUSH4 0x4dc5ecb3 PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; caller, to
+	CALLER PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD PUSH1 0x44 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x64 MSTORE PUSH1 0x24 CALLDATALOAD DUP1 PUSH1 0x40 SHR PUSH2 @revert JUMPI PUSH1 0x84 MSTORE
+	; approve
+	PUSH2 @approved PUSH1 0x00 PUSH1 0x00 PUSH1 0xa4 PUSH2 @precompile JUMP
+approved:
+	; action
+	PUSH4 0xd9dad80d PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x24 MSTORE PUSH1 0x24 CALLDATALOAD PUSH1 0x44 MSTORE
+	; query owner
+	PUSH2 @approved_owner PUSH1 0x00 PUSH1 0x00 PUSH1 0x64 PUSH2 @precompile JUMP
+approved_owner:
+	; tokenId, to, owner
+	PUSH1 0x24 CALLDATALOAD PUSH1 0x04 CALLDATALOAD PUSH1 0x60 SHL PUSH1 0x60 SHR PUSH1 0x00 MLOAD
+	; LOG4(0, 0, Approval, owner, to, tokenId)
+	PUSH32 0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925 PUSH1 0x00 DUP1 LOG4 STOP
+
+	; getApproved(tokenId): approved account of the token, which must exist.
+get_approved:
+	; action
+	PUSH4 0xd9dad80d PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD DUP1 PUSH1 0x40 SHR PUSH2 @revert JUMPI PUSH1 0x44 MSTORE
+	; query owner
+	PUSH2 @get_approved_exists PUSH1 0x00 PUSH1 0x00 PUSH1 0x64 PUSH2 @precompile JUMP
+get_approved_exists:
+	; owner != 0
+	PUSH1 0x00 MLOAD ISZERO PUSH2 @revert JUMPI
+	; action
+	PUSH4 0x5b8ef74e PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD PUSH1 0x44 MSTORE
+	; query
+	PUSH2 @return_output PUSH1 0x00 PUSH1 0x00 PUSH1 0x64 PUSH2 @precompile JUMP
+
+	; setApprovalForAll(operator, approved): approval by the caller, emitting ApprovalForAll.
+set_approval_for_all:
+	; action
+	PUSH4 0x96215862 PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; caller, operator, class_id
+	CALLER PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD PUSH1 0x44 MSTORE ADDRESS PUSH4 0xffffffff AND PUSH1 0x64 MSTORE
+	; approved
+	PUSH1 0x24 CALLDATALOAD ISZERO ISZERO PUSH1 0x84 MSTORE
+	; set
+	PUSH2 @set_approval PUSH1 0x00 PUSH1 0x00 PUSH1 0xa4 PUSH2 @precompile JUMP
+set_approval:
+	; MSTORE(0, approved)
+	PUSH1 0x24 CALLDATALOAD ISZERO ISZERO PUSH1 0x00 MSTORE
+	; operator, caller
+	PUSH1 0x04 CALLDATALOAD PUSH1 0x60 SHL PUSH1 0x60 SHR CALLER
+	; LOG3(0, 32, ApprovalForAll, caller, operator)
+	PUSH32 0x17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31 PUSH1 0x20 PUSH1 0x00 LOG3 STOP
+
+	; isApprovedForAll(owner, operator).
+is_approved_for_all:
+	; action
+	PUSH4 0xba24bb37 PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; owner, operator, class_id
+	PUSH1 0x04 CALLDATALOAD PUSH1 0x24 MSTORE PUSH1 0x24 CALLDATALOAD PUSH1 0x44 MSTORE ADDRESS PUSH4 0xffffffff AND PUSH1 0x64 MSTORE
+	; query
+	PUSH2 @return_output PUSH1 0x00 PUSH1 0x00 PUSH1 0x84 PUSH2 @precompile JUMP
+
+	; tokenURI(tokenId).
+token_uri:
+	; action
+	PUSH4 0x92cb829d PUSH1 0xe0 SHL PUSH1 0x20 MSTORE
+	; class_id, tokenId
+	ADDRESS PUSH4 0xffffffff AND PUSH1 0x24 MSTORE PUSH1 0x04 CALLDATALOAD DUP1 PUSH1 0x40 SHR PUSH2 @revert JUMPI PUSH1 0x44 MSTORE
+	; query
+	PUSH2 @return_output PUSH1 0x00 PUSH1 0x00 PUSH1 0x64 PUSH2 @precompile JUMP
+
+	; supportsInterface(interfaceId): ERC-165 and ERC-721.
+supports_interface:
+	; interfaceId
+	PUSH1 0x04 CALLDATALOAD PUSH1 0xe0 SHR
+	; ERC-165 or ERC-721
+	DUP1 PUSH4 0x01ffc9a7 EQ SWAP1 PUSH4 0x80ac58cd EQ OR
+	; RETURN(0, 32)
+	PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
@@ -0,0 +1,301 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The ERC-721 contract of the mirrored NFT classes.
+//!
+//! The code is deployed at `MIRRORED_NFT_PREDEPLOY_ADDRESS` and runs at the
+//! mirrored address of each NFT class, whose last 4 bytes are the class id.
+//! It forwards the ERC-721 calls to the NFT precompile with the caller as the
+//! account acting on the tokens, and emits the `Transfer`, `Approval` and
+//! `ApprovalForAll` logs. `safeTransferFrom` checks `onERC721Received` of
+//! recipients with code. Token ids above `u64::MAX` are rejected.
+//!
+//! Precompile input is built at memory 0: a 32 bytes word, the 4 bytes
+//! action and its 32 bytes parameters.
+
+use crate::{Config, Pallet};
+use frame_support::{log, traits::Get, weights::Weight};
+use primitives::{evm::EvmAddress, MIRRORED_NFT_PREDEPLOY_ADDRESS};
+use sp_std::prelude::*;
+
+/// The runtime code, assembled from `mirrored_nft.asm`. Change the source
+/// and update the code with its assembly, checked by the tests.
+pub const CODE: [u8; 1100] = [
+	// Reject value, calls outside of the mirrored NFT class addresses and calls without a selector.
+	// CALLVALUE == 0
+	0x34, 0x61, 0x00, 0x9c, 0x57,
+	// ADDRESS >> 32 == 0x0200000000
+	0x30, 0x60, 0x20, 0x1c, 0x64, 0x02, 0x00, 0x00, 0x00, 0x00, 0x14, 0x15, 0x61, 0x00, 0x9c, 0x57,
+	// CALLDATASIZE < 4
+	0x60, 0x04, 0x36, 0x10, 0x61, 0x00, 0x9c, 0x57,
+	// selector
+	0x60, 0x00, 0x35, 0x60, 0xe0, 0x1c,
+	// balanceOf(address)
+	0x80, 0x63, 0x70, 0xa0, 0x82, 0x31, 0x14, 0x61, 0x00, 0xc1, 0x57,
+	// ownerOf(uint256)
+	0x80, 0x63, 0x63, 0x52, 0x21, 0x1e, 0x14, 0x61, 0x00, 0xf5, 0x57,
+	// transferFrom(address,address,uint256)
+	0x80, 0x63, 0x23, 0xb8, 0x72, 0xdd, 0x14, 0x61, 0x01, 0x34, 0x57,
+	// safeTransferFrom(address,address,uint256)
+	0x80, 0x63, 0x42, 0x84, 0x2e, 0x0e, 0x14, 0x61, 0x01, 0xb9, 0x57,
+	// safeTransferFrom(address,address,uint256,bytes)
+	0x80, 0x63, 0xb8, 0x8d, 0x4f, 0xde, 0x14, 0x61, 0x01, 0xc1, 0x57,
+	// approve(address,uint256)
+	0x80, 0x63, 0x09, 0x5e, 0xa7, 0xb3, 0x14, 0x61, 0x02, 0x63, 0x57,
+	// getApproved(uint256)
+	0x80, 0x63, 0x08, 0x18, 0x12, 0xfc, 0x14, 0x61, 0x02, 0xfd, 0x57,
+	// setApprovalForAll(address,bool)
+	0x80, 0x63, 0xa2, 0x2c, 0xb4, 0x65, 0x14, 0x61, 0x03, 0x5f, 0x57,
+	// isApprovedForAll(address,address)
+	0x80, 0x63, 0xe9, 0x85, 0xe9, 0xc5, 0x14, 0x61, 0x03, 0xce, 0x57,
+	// tokenURI(uint256)
+	0x80, 0x63, 0xc8, 0x7b, 0x56, 0xdd, 0x14, 0x61, 0x03, 0xfd, 0x57,
+	// supportsInterface(bytes4)
+	0x80, 0x63, 0x01, 0xff, 0xc9, 0xa7, 0x14, 0x61, 0x04, 0x2e, 0x57,
+	// 0x009c revert:
+	// REVERT(0, 0)
+	0x5b, 0x60, 0x00, 0x80, 0xfd,
+
+	// Call the NFT precompile, copy its output to memory 0 and jump back, or revert with it.
+	// 0x00a1 precompile:
+	// CALL(GAS, 0x401, 0, 0, len, 0, 0)
+	0x5b, 0x60, 0x00, 0x80, 0x61, 0x04, 0x01, 0x5a, 0xf1,
+	// RETURNDATACOPY(0, 0, RETURNDATASIZE)
+	0x3d, 0x60, 0x00, 0x80, 0x3e,
+	// jump back on success
+	0x15, 0x61, 0x00, 0xb5, 0x57, 0x56,
+	// 0x00b5 bubble:
+	// REVERT(0, RETURNDATASIZE)
+	0x5b, 0x3d, 0x60, 0x00, 0xfd,
+	// 0x00ba return_output:
+	// RETURN(0, RETURNDATASIZE)
+	0x5b, 0x3d, 0x60, 0x00, 0xf3,
+	// 0x00bf stop:
+	// STOP
+	0x5b, 0x00,
+
+	// balanceOf(owner): class balance of owner, which must not be zero.
+	// 0x00c1 balance_of:
+	// owner != 0
+	0x5b, 0x60, 0x04, 0x35, 0x60, 0x60, 0x1b, 0x15, 0x61, 0x00, 0x9c, 0x57,
+	// action
+	0x63, 0x00, 0xfd, 0xd5, 0x8e, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// owner, class_id
+	0x60, 0x04, 0x35, 0x60, 0x24, 0x52, 0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x44, 0x52,
+	// query
+	0x61, 0x00, 0xba, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x61, 0x00, 0xa1, 0x56,
+
+	// ownerOf(tokenId): owner of the token, which must exist.
+	// 0x00f5 owner_of:
+	// action
+	0x5b, 0x63, 0xd9, 0xda, 0xd8, 0x0d, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x80, 0x60, 0x40,
+	0x1c, 0x61, 0x00, 0x9c, 0x57, 0x60, 0x44, 0x52,
+	// query
+	0x61, 0x01, 0x26, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x61, 0x00, 0xa1, 0x56,
+	// 0x0126 owner_exists:
+	// owner != 0
+	0x5b, 0x60, 0x00, 0x51, 0x15, 0x61, 0x00, 0x9c, 0x57,
+	// RETURN(0, 32)
+	0x60, 0x20, 0x60, 0x00, 0xf3,
+
+	// transferFrom(from, to, tokenId): transfer by the caller, emitting Transfer.
+	// 0x0134 transfer_from:
+	// transfer then stop
+	0x5b, 0x61, 0x00, 0xbf, 0x61, 0x01, 0x3c, 0x56,
+	// 0x013c transfer:
+	// action
+	0x5b, 0x63, 0x10, 0xf0, 0x21, 0x13, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// caller, from, to
+	0x33, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x60, 0x44, 0x52, 0x60, 0x24, 0x35, 0x60, 0x64, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x84, 0x52, 0x60, 0x44, 0x35, 0x80, 0x60, 0x40,
+	0x1c, 0x61, 0x00, 0x9c, 0x57, 0x60, 0xa4, 0x52,
+	// transfer
+	0x61, 0x01, 0x7d, 0x60, 0x00, 0x60, 0x00, 0x60, 0xc4, 0x61, 0x00, 0xa1, 0x56,
+	// 0x017d transferred:
+	// tokenId, to, from
+	0x5b, 0x60, 0x44, 0x35, 0x60, 0x24, 0x35, 0x60, 0x60, 0x1b, 0x60, 0x60, 0x1c, 0x60, 0x04, 0x35,
+	0x60, 0x60, 0x1b, 0x60, 0x60, 0x1c,
+	// LOG4(0, 0, Transfer, from, to, tokenId) and jump back
+	0x7f, 0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d,
+	0xaa, 0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3,
+	0xef, 0x60, 0x00, 0x80, 0xa4, 0x56,
+
+	// safeTransferFrom(from, to, tokenId[, data]): transferFrom, then onERC721Received of a contract recipient.
+	// 0x01b9 safe_transfer_from:
+	// transfer then check the recipient
+	0x5b, 0x61, 0x01, 0xc9, 0x61, 0x01, 0x3c, 0x56,
+	// 0x01c1 safe_transfer_from_data:
+	// transfer then check the recipient
+	0x5b, 0x61, 0x01, 0xd6, 0x61, 0x01, 0x3c, 0x56,
+	// 0x01c9 received_no_data:
+	// data length 0
+	0x5b, 0x60, 0x00, 0x61, 0x01, 0x84, 0x52,
+	// call data length
+	0x60, 0xa4, 0x61, 0x01, 0xf0, 0x56,
+	// 0x01d6 received_data:
+	// data offset
+	0x5b, 0x60, 0x64, 0x35, 0x60, 0x04, 0x01,
+	// offset <= CALLDATASIZE
+	0x36, 0x81, 0x11, 0x61, 0x00, 0x9c, 0x57,
+	// CALLDATACOPY(0x184, offset, CALLDATASIZE - offset)
+	0x80, 0x36, 0x03, 0x80, 0x91, 0x61, 0x01, 0x84, 0x37,
+	// call data length
+	0x60, 0x84, 0x01,
+	// 0x01f0 received:
+	// recipient without code
+	0x5b, 0x60, 0x24, 0x35, 0x60, 0x60, 0x1b, 0x60, 0x60, 0x1c, 0x3b, 0x15, 0x61, 0x00, 0xbf, 0x57,
+	// onERC721Received selector
+	0x63, 0x15, 0x0b, 0x7a, 0x02, 0x60, 0xe0, 0x1b, 0x61, 0x01, 0x00, 0x52,
+	// caller, from, tokenId, data offset
+	0x33, 0x61, 0x01, 0x04, 0x52, 0x60, 0x04, 0x35, 0x61, 0x01, 0x24, 0x52, 0x60, 0x44, 0x35, 0x61,
+	0x01, 0x44, 0x52, 0x60, 0x80, 0x61, 0x01, 0x64, 0x52,
+	// CALL(GAS, to, 0, 0x100, len, 0, 32)
+	0x60, 0x20, 0x60, 0x00, 0x82, 0x61, 0x01, 0x00, 0x60, 0x00, 0x60, 0x24, 0x35, 0x60, 0x60, 0x1b,
+	0x60, 0x60, 0x1c, 0x5a, 0xf1,
+	// revert with the output of a failed call
+	0x15, 0x61, 0x02, 0x59, 0x57,
+	// RETURNDATASIZE >= 32
+	0x60, 0x20, 0x3d, 0x10, 0x61, 0x00, 0x9c, 0x57,
+	// output == onERC721Received selector
+	0x60, 0x00, 0x51, 0x60, 0xe0, 0x1c, 0x63, 0x15, 0x0b, 0x7a, 0x02, 0x14, 0x15, 0x61, 0x00, 0x9c,
+	0x57, 0x00,
+	// 0x0259 bubble_call:
+	// RETURNDATACOPY(0, 0, RETURNDATASIZE)
+	0x5b, 0x3d, 0x60, 0x00, 0x80, 0x3e,
+	// REVERT(0, RETURNDATASIZE)
+	0x3d, 0x60, 0x00, 0xfd,
+
+	// approve(to, tokenId): approval by the caller, emitting Approval with the owner.
+	// 0x0263 approve:
+	// action
+	0x5b, 0x63, 0x4d, 0xc5, 0xec, 0xb3, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// caller, to
+	0x33, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x60, 0x44, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x64, 0x52, 0x60, 0x24, 0x35, 0x80, 0x60, 0x40,
+	0x1c, 0x61, 0x00, 0x9c, 0x57, 0x60, 0x84, 0x52,
+	// approve
+	0x61, 0x02, 0x9e, 0x60, 0x00, 0x60, 0x00, 0x60, 0xa4, 0x61, 0x00, 0xa1, 0x56,
+	// 0x029e approved:
+	// action
+	0x5b, 0x63, 0xd9, 0xda, 0xd8, 0x0d, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x24, 0x52, 0x60, 0x24, 0x35, 0x60, 0x44, 0x52,
+	// query owner
+	0x61, 0x02, 0xc7, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x61, 0x00, 0xa1, 0x56,
+	// 0x02c7 approved_owner:
+	// tokenId, to, owner
+	0x5b, 0x60, 0x24, 0x35, 0x60, 0x04, 0x35, 0x60, 0x60, 0x1b, 0x60, 0x60, 0x1c, 0x60, 0x00, 0x51,
+	// LOG4(0, 0, Approval, owner, to, tokenId)
+	0x7f, 0x8c, 0x5b, 0xe1, 0xe5, 0xeb, 0xec, 0x7d, 0x5b, 0xd1, 0x4f, 0x71, 0x42, 0x7d, 0x1e, 0x84,
+	0xf3, 0xdd, 0x03, 0x14, 0xc0, 0xf7, 0xb2, 0x29, 0x1e, 0x5b, 0x20, 0x0a, 0xc8, 0xc7, 0xc3, 0xb9,
+	0x25, 0x60, 0x00, 0x80, 0xa4, 0x00,
+
+	// getApproved(tokenId): approved account of the token, which must exist.
+	// 0x02fd get_approved:
+	// action
+	0x5b, 0x63, 0xd9, 0xda, 0xd8, 0x0d, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x80, 0x60, 0x40,
+	0x1c, 0x61, 0x00, 0x9c, 0x57, 0x60, 0x44, 0x52,
+	// query owner
+	0x61, 0x03, 0x2e, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x61, 0x00, 0xa1, 0x56,
+	// 0x032e get_approved_exists:
+	// owner != 0
+	0x5b, 0x60, 0x00, 0x51, 0x15, 0x61, 0x00, 0x9c, 0x57,
+	// action
+	0x63, 0x5b, 0x8e, 0xf7, 0x4e, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x60, 0x44, 0x52,
+	// query
+	0x61, 0x00, 0xba, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x61, 0x00, 0xa1, 0x56,
+
+	// setApprovalForAll(operator, approved): approval by the caller, emitting ApprovalForAll.
+	// 0x035f set_approval_for_all:
+	// action
+	0x5b, 0x63, 0x96, 0x21, 0x58, 0x62, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// caller, operator, class_id
+	0x33, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x60, 0x44, 0x52, 0x30, 0x63, 0xff, 0xff, 0xff, 0xff,
+	0x16, 0x60, 0x64, 0x52,
+	// approved
+	0x60, 0x24, 0x35, 0x15, 0x15, 0x60, 0x84, 0x52,
+	// set
+	0x61, 0x03, 0x94, 0x60, 0x00, 0x60, 0x00, 0x60, 0xa4, 0x61, 0x00, 0xa1, 0x56,
+	// 0x0394 set_approval:
+	// MSTORE(0, approved)
+	0x5b, 0x60, 0x24, 0x35, 0x15, 0x15, 0x60, 0x00, 0x52,
+	// operator, caller
+	0x60, 0x04, 0x35, 0x60, 0x60, 0x1b, 0x60, 0x60, 0x1c, 0x33,
+	// LOG3(0, 32, ApprovalForAll, caller, operator)
+	0x7f, 0x17, 0x30, 0x7e, 0xab, 0x39, 0xab, 0x61, 0x07, 0xe8, 0x89, 0x98, 0x45, 0xad, 0x3d, 0x59,
+	0xbd, 0x96, 0x53, 0xf2, 0x00, 0xf2, 0x20, 0x92, 0x04, 0x89, 0xca, 0x2b, 0x59, 0x37, 0x69, 0x6c,
+	0x31, 0x60, 0x20, 0x60, 0x00, 0xa3, 0x00,
+
+	// isApprovedForAll(owner, operator).
+	// 0x03ce is_approved_for_all:
+	// action
+	0x5b, 0x63, 0xba, 0x24, 0xbb, 0x37, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// owner, operator, class_id
+	0x60, 0x04, 0x35, 0x60, 0x24, 0x52, 0x60, 0x24, 0x35, 0x60, 0x44, 0x52, 0x30, 0x63, 0xff, 0xff,
+	0xff, 0xff, 0x16, 0x60, 0x64, 0x52,
+	// query
+	0x61, 0x00, 0xba, 0x60, 0x00, 0x60, 0x00, 0x60, 0x84, 0x61, 0x00, 0xa1, 0x56,
+
+	// tokenURI(tokenId).
+	// 0x03fd token_uri:
+	// action
+	0x5b, 0x63, 0x92, 0xcb, 0x82, 0x9d, 0x60, 0xe0, 0x1b, 0x60, 0x20, 0x52,
+	// class_id, tokenId
+	0x30, 0x63, 0xff, 0xff, 0xff, 0xff, 0x16, 0x60, 0x24, 0x52, 0x60, 0x04, 0x35, 0x80, 0x60, 0x40,
+	0x1c, 0x61, 0x00, 0x9c, 0x57, 0x60, 0x44, 0x52,
+	// query
+	0x61, 0x00, 0xba, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x61, 0x00, 0xa1, 0x56,
+
+	// supportsInterface(interfaceId): ERC-165 and ERC-721.
+	// 0x042e supports_interface:
+	// interfaceId
+	0x5b, 0x60, 0x04, 0x35, 0x60, 0xe0, 0x1c,
+	// ERC-165 or ERC-721
+	0x80, 0x63, 0x01, 0xff, 0xc9, 0xa7, 0x14, 0x90, 0x63, 0x80, 0xac, 0x58, 0xcd, 0x14, 0x17,
+	// RETURN(0, 32)
+	0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+
+impl<T: Config> Pallet<T> {
+	/// Deploy `CODE` at `MIRRORED_NFT_PREDEPLOY_ADDRESS`, maintained by
+	/// `NetworkContractSource`, unless a contract is already there.
+	pub fn deploy_mirrored_nft() -> Weight {
+		let address = EvmAddress::from_low_u64_be(MIRRORED_NFT_PREDEPLOY_ADDRESS);
+		if Self::accounts(address).and_then(|account| account.contract_info).is_some() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		if let Err(e) = Self::on_contract_initialization(&address, &T::NetworkContractSource::get(), CODE.to_vec()) {
+			log::error!(target: "evm", "deploy_mirrored_nft: failed to deploy the code: {:?}", e);
+			return T::DbWeight::get().reads(3);
+		}
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		Self::mark_deployed(address, None).expect("the contract has just been initialized; qed");
+
+		T::DbWeight::get().reads_writes(4, 6)
+	}
+}
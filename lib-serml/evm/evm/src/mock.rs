@@ -130,6 +130,7 @@ impl Convert<u64, u64> for GasToWeight {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice();
+	pub static EvmHardFork: HardFork = HardFork::Istanbul;
 }
 
 ord_parameter_types! {
@@ -142,6 +143,10 @@ ord_parameter_types! {
 	pub const DeploymentFee: u64 = 200;
 	pub const MaxCodeSize: u32 = 1000;
 	pub const ChainId: u64 = 1;
+	pub const MaxGasPerExtrinsic: u64 = 10_000_000;
+	pub const EthTransactionStorageLimit: u32 = 1000;
+	pub const CodeUpgradeDelay: u64 = 10;
+	pub const MaxCodeVersions: u32 = 2;
 }
 
 impl Config for Test {
@@ -156,7 +161,10 @@ impl Config for Test {
 	type Precompiles = ();
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type MaxGasPerExtrinsic = MaxGasPerExtrinsic;
 	type ChargeTransactionPayment = ();
+	type EthTransactionStorageLimit = EthTransactionStorageLimit;
+	type HardFork = EvmHardFork;
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
@@ -164,6 +172,9 @@ impl Config for Test {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CodeUpgradeDelay = CodeUpgradeDelay;
+	type MaxCodeVersions = MaxCodeVersions;
+	type CodeUpgradeCancelOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type WeightInfo = ();
 }
@@ -178,7 +189,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
-		EVM: setheum_evm::{Pallet, Config<T>, Call, Storage, Event<T>},
+		EVM: setheum_evm::{Pallet, Config<T>, Call, Storage, Event<T>, ValidateUnsigned},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
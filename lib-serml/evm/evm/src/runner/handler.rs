@@ -20,9 +20,12 @@
 
 use crate::{
 	precompiles::Precompiles,
-	runner::storage_meter::{StorageMeter, StorageMeterHandler},
-	AccountInfo, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo, Error, Event, Log, Pallet,
-	Vicinity,
+	runner::{
+		storage_meter::{StorageMeter, StorageMeterHandler},
+		tracing::Tracer,
+	},
+	AccessListItem, AccountInfo, AccountStorages, Accounts, AddressMapping, CallType, Codes, Config, ContractInfo,
+	Error, Event, Log, Pallet, Vicinity,
 };
 use evm::{Capture, Context, CreateScheme, ExitError, ExitReason, Opcode, Runtime, Stack, Transfer};
 use evm_gasometer::{self as gasometer, Gasometer};
@@ -32,23 +35,94 @@ use frame_support::{
 	traits::{BalanceStatus, Currency, ExistenceRequirement, Get, ReservableCurrency},
 };
 use primitive_types::{H160, H256, U256};
-use primitives::{H160_PREFIX_DEXSHARE, H160_PREFIX_TOKEN, PREDEPLOY_ADDRESS_START, SYSTEM_CONTRACT_ADDRESS_PREFIX};
+use primitives::{
+	H160_PREFIX_DEXSHARE, H160_PREFIX_NFT, H160_PREFIX_TOKEN, MIRRORED_NFT_PREDEPLOY_ADDRESS, PREDEPLOY_ADDRESS_START,
+	SYSTEM_CONTRACT_ADDRESS_PREFIX,
+};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{
 	traits::{One, Saturating, UniqueSaturatedInto, Zero},
 	DispatchError, DispatchResult, SaturatedConversion, TransactionOutcome,
 };
-use sp_std::{cmp::min, convert::Infallible, marker::PhantomData, prelude::*, rc::Rc};
+use sp_std::{
+	cell::RefCell, cmp::min, collections::btree_set::BTreeSet, convert::Infallible, marker::PhantomData, prelude::*,
+	rc::Rc,
+};
 
 /// Storage key size and storage value size.
 pub const STORAGE_SIZE: u32 = 64;
 
+/// The cost of the first access to an account in a transaction (EIP-2929).
+pub const GAS_ACCOUNT_ACCESS_COLD: u64 = 2600;
+/// The cost of the first access to a storage slot in a transaction
+/// (EIP-2929).
+pub const GAS_SLOAD_COLD: u64 = 2100;
+/// The cost of any further access to an account or a storage slot
+/// (EIP-2929).
+pub const GAS_STORAGE_READ_WARM: u64 = 100;
+/// The cost of each account of an access list (EIP-2930).
+pub const GAS_ACCESS_LIST_ADDRESS: u64 = 2400;
+/// The cost of each storage slot of an access list (EIP-2930).
+pub const GAS_ACCESS_LIST_STORAGE_KEY: u64 = 1900;
+
+/// The accounts and storage slots accessed so far in a transaction, which
+/// further accesses find warm (EIP-2929).
+#[derive(Clone, Default)]
+pub struct AccessedSet {
+	addresses: BTreeSet<H160>,
+	storage_keys: BTreeSet<(H160, H256)>,
+}
+
+impl AccessedSet {
+	/// The set of a transaction, starting warm with `addresses` and
+	/// `access_list`.
+	pub fn new(addresses: &[H160], access_list: &[AccessListItem]) -> Self {
+		let mut accessed = Self::default();
+		for address in addresses {
+			accessed.access_address(*address);
+		}
+		for item in access_list {
+			accessed.access_address(item.address);
+			for index in &item.storage_keys {
+				accessed.access_storage(item.address, *index);
+			}
+		}
+		accessed
+	}
+
+	/// Mark `address` accessed, returning whether it was cold.
+	pub fn access_address(&mut self, address: H160) -> bool {
+		// precompiles and the other system contracts are always warm
+		!address.as_bytes().starts_with(&SYSTEM_CONTRACT_ADDRESS_PREFIX) && self.addresses.insert(address)
+	}
+
+	/// Mark the storage slot `index` of `address` accessed, returning
+	/// whether it was cold.
+	pub fn access_storage(&mut self, address: H160, index: H256) -> bool {
+		self.storage_keys.insert((address, index))
+	}
+}
+
+/// The intrinsic cost of `access_list` (EIP-2930).
+pub fn access_list_cost(access_list: &[AccessListItem]) -> u64 {
+	access_list.iter().fold(0u64, |cost, item| {
+		cost.saturating_add(GAS_ACCESS_LIST_ADDRESS).saturating_add(
+			GAS_ACCESS_LIST_STORAGE_KEY.saturating_mul(item.storage_keys.len() as u64),
+		)
+	})
+}
+
 pub struct Handler<'vicinity, 'config, 'meter, T: Config> {
 	pub vicinity: &'vicinity Vicinity,
 	pub config: &'config EvmRuntimeConfig,
 	pub gasometer: Gasometer<'config>,
 	pub storage_meter: StorageMeter<'meter>,
 	pub is_static: bool,
+	/// Set when the execution is traced, shared with all sub calls.
+	pub tracer: Option<Rc<RefCell<Tracer>>>,
+	/// Set when the hard fork charges accesses by warmth. Sub calls start
+	/// from a copy, kept only if they succeed.
+	pub accessed: Option<AccessedSet>,
 	_marker: PhantomData<T>,
 }
 
@@ -63,6 +137,8 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		storage_meter: StorageMeter<'meter>,
 		is_static: bool,
 		config: &'config EvmRuntimeConfig,
+		tracer: Option<Rc<RefCell<Tracer>>>,
+		accessed: Option<AccessedSet>,
 	) -> Self {
 		Handler::<'vicinity, 'config, '_, T> {
 			vicinity,
@@ -70,6 +146,8 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 			is_static,
 			gasometer: Gasometer::new(gas_limit, config),
 			storage_meter,
+			tracer,
+			accessed,
 			_marker: PhantomData,
 		}
 	}
@@ -81,6 +159,8 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		contract: H160,
 		is_static: bool,
 		config: &'config EvmRuntimeConfig,
+		tracer: Option<Rc<RefCell<Tracer>>>,
+		accessed: Option<AccessedSet>,
 		f: F,
 	) -> Result<R, DispatchError> {
 		frame_support::storage::with_transaction(|| {
@@ -90,7 +170,7 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 				Err(e) => return TransactionOutcome::Rollback(Err(e)),
 			};
 
-			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config);
+			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config, tracer, accessed);
 
 			match f(&mut substate) {
 				TransactionOutcome::Commit(r) => match substate.storage_meter.finish() {
@@ -115,17 +195,22 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		config: &'config EvmRuntimeConfig,
 		f: F,
 	) -> Result<R, DispatchError> {
+		let tracer = self.tracer.clone();
+		let accessed = self.accessed.clone();
 		frame_support::storage::with_transaction(|| {
 			let storage_meter = match self.storage_meter.child_meter(contract) {
 				Ok(x) => x,
 				Err(e) => return TransactionOutcome::Rollback(Err(e)),
 			};
 
-			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config);
+			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config, tracer, accessed);
 
 			match f(&mut substate, &mut self.gasometer) {
 				TransactionOutcome::Commit(r) => match substate.storage_meter.finish() {
-					Ok(_) => TransactionOutcome::Commit(Ok(r)),
+					Ok(_) => {
+						self.accessed = substate.accessed;
+						TransactionOutcome::Commit(Ok(r))
+					}
 					Err(e) => TransactionOutcome::Rollback(Err(e)),
 				},
 				TransactionOutcome::Rollback(e) => TransactionOutcome::Rollback(Ok(e)),
@@ -162,9 +247,28 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 
 		let mut runtime = Runtime::new(Rc::new(code), Rc::new(input), context, self.config);
 
-		let reason = match runtime.run(self) {
-			Capture::Exit(s) => s,
-			Capture::Trap(_) => unreachable!("Trap is Infallible"),
+		let reason = if self.is_struct_logging() {
+			// step through the opcodes one by one to record them
+			loop {
+				let step = self.trace_step(&runtime, address);
+				let gas_before = self.gasometer.gas();
+				let result = runtime.step(self);
+				if let (Some(tracer), Some(index)) = (self.tracer.as_ref(), step) {
+					tracer
+						.borrow_mut()
+						.step_cost(index, gas_before.saturating_sub(self.gasometer.gas()));
+				}
+				match result {
+					Ok(()) => (),
+					Err(Capture::Exit(s)) => break s,
+					Err(Capture::Trap(_)) => unreachable!("Trap is Infallible"),
+				}
+			}
+		} else {
+			match runtime.run(self) {
+				Capture::Exit(s) => s,
+				Capture::Trap(_) => unreachable!("Trap is Infallible"),
+			}
 		};
 
 		match reason {
@@ -178,6 +282,90 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		}
 	}
 
+	/// The cost of the cold accesses of `opcode` run by `address`, on top of
+	/// the warm ones charged by the gasometer. The accessed accounts and
+	/// storage slots become warm.
+	fn cold_access_cost(&mut self, address: H160, opcode: Opcode, stack: &Stack) -> u64 {
+		let accessed = match self.accessed.as_mut() {
+			Some(accessed) => accessed,
+			None => return 0,
+		};
+		// a stack underflow is left to the gasometer
+		let touched_address = |n| stack.peek(n).ok().map(H160::from);
+
+		let (is_cold, cost) = match opcode {
+			Opcode::SLOAD => (
+				stack.peek(0).map_or(false, |index| accessed.access_storage(address, index)),
+				GAS_SLOAD_COLD - GAS_STORAGE_READ_WARM,
+			),
+			Opcode::SSTORE => (
+				stack.peek(0).map_or(false, |index| accessed.access_storage(address, index)),
+				GAS_SLOAD_COLD,
+			),
+			Opcode::BALANCE | Opcode::EXTCODESIZE | Opcode::EXTCODECOPY | Opcode::EXTCODEHASH => (
+				touched_address(0).map_or(false, |address| accessed.access_address(address)),
+				GAS_ACCOUNT_ACCESS_COLD - GAS_STORAGE_READ_WARM,
+			),
+			Opcode::CALL | Opcode::CALLCODE | Opcode::DELEGATECALL | Opcode::STATICCALL => (
+				touched_address(1).map_or(false, |address| accessed.access_address(address)),
+				GAS_ACCOUNT_ACCESS_COLD - GAS_STORAGE_READ_WARM,
+			),
+			Opcode::SUICIDE => (
+				touched_address(0).map_or(false, |address| accessed.access_address(address)),
+				GAS_ACCOUNT_ACCESS_COLD,
+			),
+			_ => (false, 0),
+		};
+
+		if is_cold {
+			cost
+		} else {
+			0
+		}
+	}
+
+	fn is_struct_logging(&self) -> bool {
+		self.tracer
+			.as_ref()
+			.map_or(false, |tracer| tracer.borrow().is_struct_logger())
+	}
+
+	fn trace_step(&self, runtime: &Runtime, address: H160) -> Option<usize> {
+		let tracer = self.tracer.as_ref()?;
+		let machine = runtime.machine();
+		let pc = *machine.position().as_ref().ok()?;
+		let (opcode, stack) = machine.inspect()?;
+		let touched = match opcode {
+			Opcode::SLOAD => stack.peek(0).ok().map(|index| (index, self.storage(address, index))),
+			Opcode::SSTORE => match (stack.peek(0), stack.peek(1)) {
+				(Ok(index), Ok(value)) => Some((index, value)),
+				_ => None,
+			},
+			_ => None,
+		};
+
+		Some(tracer.borrow_mut().step(
+			pc,
+			opcode.as_u8(),
+			self.gasometer.gas(),
+			stack.data(),
+			machine.memory().data(),
+			touched,
+		))
+	}
+
+	fn trace_enter(&self, call_type: CallType, from: H160, to: H160, value: U256, gas: u64, input: &[u8]) {
+		if let Some(tracer) = self.tracer.as_ref() {
+			tracer.borrow_mut().enter(call_type, from, to, value, gas, input);
+		}
+	}
+
+	fn trace_exit(&self, reason: &ExitReason, output: &[u8], gas_used: u64) {
+		if let Some(tracer) = self.tracer.as_ref() {
+			tracer.borrow_mut().exit(reason, output, gas_used);
+		}
+	}
+
 	fn transfer(transfer: Transfer) -> Result<(), ExitError> {
 		let source = T::AddressMapping::get_account_id(&transfer.source);
 		let target = T::AddressMapping::get_account_id(&transfer.target);
@@ -291,6 +479,16 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 				token_address
 			);
 			token_address
+		} else if addr.starts_with(&H160_PREFIX_NFT) {
+			// ERC-721 contracts of NFT classes.
+			let nft_address = H160::from_low_u64_be(MIRRORED_NFT_PREDEPLOY_ADDRESS);
+			log::debug!(
+				target: "evm",
+				"handle_mirrored_token: origin address: {:?}, nft address: {:?}",
+				address,
+				nft_address
+			);
+			nft_address
 		} else {
 			address
 		}
@@ -500,7 +698,8 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			return Capture::Exit((ExitError::OutOfGas.into(), None, Vec::new()));
 		}
 
-		let mut after_gas = self.gasometer.gas();
+		let gas_before = self.gasometer.gas();
+		let mut after_gas = gas_before;
 		if self.config.call_l64_after_gas {
 			after_gas = l64(after_gas);
 		}
@@ -508,6 +707,10 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 		target_gas = min(target_gas, after_gas);
 		try_or_fail!(self.gasometer.record_cost(target_gas));
 
+		let call_type = match scheme {
+			CreateScheme::Create2 { .. } => CallType::Create2,
+			_ => CallType::Create,
+		};
 		let maybe_address = Self::create_address(scheme);
 		let address = if let Err(e) = maybe_address {
 			return Capture::Exit((ExitReason::Error(e), None, Vec::new()));
@@ -515,60 +718,79 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			maybe_address.unwrap()
 		};
 		Self::inc_nonce(caller);
+		if let Some(accessed) = self.accessed.as_mut() {
+			accessed.access_address(address);
+		}
 
-		let origin = &self.vicinity.origin;
+		self.trace_enter(call_type, caller, address, value, target_gas, &init_code);
 
-		self.run_sub_transaction(
-			self.vicinity,
-			target_gas,
-			address,
-			self.is_static,
-			self.config,
-			|substate, gasometer| {
-				try_or_rollback!(Self::transfer(Transfer {
-					source: caller,
-					target: address,
-					value,
-				}));
-
-				let (reason, out) = substate.execute(caller, address, value, init_code, Vec::new());
-
-				match reason {
-					ExitReason::Succeed(s) => match substate.gasometer.record_deposit(out.len()) {
-						Ok(()) => {
-							try_or_rollback!(gasometer.record_stipend(substate.gasometer.gas()));
-							try_or_rollback!(gasometer.record_refund(substate.gasometer.refunded_gas()));
+		let origin = &self.vicinity.origin;
 
-							Handler::<T>::inc_nonce(address);
-							try_or_rollback!(substate
-								.storage_meter
-								.charge((out.len() as u32).saturating_add(T::NewContractExtraBytes::get()))
-								.map_err(|_| ExitError::OutOfGas));
-							match <Pallet<T>>::on_contract_initialization(&address, origin, out) {
-								Ok(()) => {
-									TransactionOutcome::Commit(Capture::Exit((s.into(), Some(address), Vec::new())))
+		let result = self
+			.run_sub_transaction(
+				self.vicinity,
+				target_gas,
+				address,
+				self.is_static,
+				self.config,
+				|substate, gasometer| {
+					try_or_rollback!(Self::transfer(Transfer {
+						source: caller,
+						target: address,
+						value,
+					}));
+
+					let (reason, out) = substate.execute(caller, address, value, init_code, Vec::new());
+
+					match reason {
+						ExitReason::Succeed(s) => match substate.gasometer.record_deposit(out.len()) {
+							Ok(()) => {
+								try_or_rollback!(gasometer.record_stipend(substate.gasometer.gas()));
+								try_or_rollback!(gasometer.record_refund(substate.gasometer.refunded_gas()));
+
+								Handler::<T>::inc_nonce(address);
+								try_or_rollback!(substate
+									.storage_meter
+									.charge((out.len() as u32).saturating_add(T::NewContractExtraBytes::get()))
+									.map_err(|_| ExitError::OutOfGas));
+								match <Pallet<T>>::on_contract_initialization(&address, origin, out) {
+									Ok(()) => {
+										TransactionOutcome::Commit(Capture::Exit((s.into(), Some(address), Vec::new())))
+									}
+									Err(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new()))),
 								}
-								Err(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new()))),
 							}
+							Err(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new()))),
+						},
+						ExitReason::Revert(r) => TransactionOutcome::Rollback(Capture::Exit((r.into(), None, out))),
+						ExitReason::Error(e) => {
+							TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new())))
+						}
+						ExitReason::Fatal(e) => {
+							gasometer.fail();
+							TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new())))
 						}
-						Err(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new()))),
-					},
-					ExitReason::Revert(r) => TransactionOutcome::Rollback(Capture::Exit((r.into(), None, out))),
-					ExitReason::Error(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new()))),
-					ExitReason::Fatal(e) => {
-						gasometer.fail();
-						TransactionOutcome::Rollback(Capture::Exit((e.into(), None, Vec::new())))
 					}
-				}
-			},
-		)
-		.unwrap_or_else(|x| {
-			Capture::Exit((
-				ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
-				None,
-				Vec::new(),
-			))
-		})
+				},
+			)
+			.unwrap_or_else(|x| {
+				Capture::Exit((
+					ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
+					None,
+					Vec::new(),
+				))
+			});
+
+		if let Capture::Exit((reason, _, out)) = &result {
+			let gas_used = gas_before.saturating_sub(self.gasometer.gas());
+			if reason.is_succeed() {
+				self.trace_exit(reason, &self.code(address), gas_used);
+			} else {
+				self.trace_exit(reason, out, gas_used);
+			}
+		}
+
+		result
 	}
 
 	fn call(
@@ -596,7 +818,8 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			return Capture::Exit((ExitError::OutOfGas.into(), Vec::new()));
 		}
 
-		let mut after_gas = self.gasometer.gas();
+		let gas_before = self.gasometer.gas();
+		let mut after_gas = gas_before;
 		if self.config.call_l64_after_gas {
 			after_gas = l64(after_gas);
 		}
@@ -611,78 +834,105 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 
 		let code = self.code(code_address);
 
-		self.run_sub_transaction(
-			self.vicinity,
-			target_gas,
-			context.address,
-			self.is_static || is_static,
-			self.config,
-			|substate, gasometer| {
-				if let Some(transfer) = transfer {
-					try_or_rollback!(Self::transfer(transfer));
-				}
+		if self.tracer.is_some() {
+			// delegatecall and callcode run `code_address` in the context of the caller
+			let (call_type, from) = if is_static {
+				(CallType::StaticCall, context.caller)
+			} else if code_address == context.address {
+				(CallType::Call, context.caller)
+			} else if transfer.is_some() {
+				(CallType::CallCode, context.address)
+			} else {
+				(CallType::DelegateCall, context.address)
+			};
+			let value = transfer.as_ref().map_or_else(U256::zero, |transfer| transfer.value);
+			self.trace_enter(call_type, from, code_address, value, target_gas, &input);
+		}
+
+		let result = self
+			.run_sub_transaction(
+				self.vicinity,
+				target_gas,
+				context.address,
+				self.is_static || is_static,
+				self.config,
+				|substate, gasometer| {
+					if let Some(transfer) = transfer {
+						try_or_rollback!(Self::transfer(transfer));
+					}
+
+					try_or_rollback!(gasometer.record_cost(target_gas));
+
+					if let Some(ret) = T::Precompiles::execute(code_address, &input, Some(target_gas), &context) {
+						log::debug!(
+							target: "evm",
+							"handler: call-result: precompile result {:?}",
+							ret
+						);
+
+						return match ret {
+							Ok((s, out, cost)) => {
+								// TODO: write some test to make sure following 3 lines is correct
+								try_or_rollback!(substate.gasometer.record_cost(cost));
+								try_or_rollback!(gasometer.record_stipend(substate.gasometer.gas()));
+								try_or_rollback!(gasometer.record_refund(substate.gasometer.refunded_gas()));
+								// precompile contract cost 0
+								// try_or_rollback!(self.storage_meter.record_cost(0));
+								TransactionOutcome::Commit(Capture::Exit((s.into(), out)))
+							}
+							Err(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), Vec::new()))),
+						};
+					}
 
-				try_or_rollback!(gasometer.record_cost(target_gas));
+					let (reason, out) = substate.execute(
+						context.caller,
+						context.address,
+						context.apparent_value,
+						code.clone(),
+						input,
+					);
 
-				if let Some(ret) = T::Precompiles::execute(code_address, &input, Some(target_gas), &context) {
 					log::debug!(
 						target: "evm",
-						"handler: call-result: precompile result {:?}",
-						ret
+						"handler: call-result: reason {:?} out {:?} gas_left {:?}",
+						reason, out, substate.gas_left()
 					);
 
-					return match ret {
-						Ok((s, out, cost)) => {
-							// TODO: write some test to make sure following 3 lines is correct
-							try_or_rollback!(substate.gasometer.record_cost(cost));
+					match reason {
+						ExitReason::Succeed(s) => {
 							try_or_rollback!(gasometer.record_stipend(substate.gasometer.gas()));
 							try_or_rollback!(gasometer.record_refund(substate.gasometer.refunded_gas()));
-							// precompile contract cost 0
-							// try_or_rollback!(self.storage_meter.record_cost(0));
 							TransactionOutcome::Commit(Capture::Exit((s.into(), out)))
 						}
-						Err(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), Vec::new()))),
-					};
-				}
-
-				let (reason, out) = substate.execute(
-					context.caller,
-					context.address,
-					context.apparent_value,
-					code.clone(),
-					input,
-				);
-
-				log::debug!(
-					target: "evm",
-					"handler: call-result: reason {:?} out {:?} gas_left {:?}",
-					reason, out, substate.gas_left()
-				);
-
-				match reason {
-					ExitReason::Succeed(s) => {
-						try_or_rollback!(gasometer.record_stipend(substate.gasometer.gas()));
-						try_or_rollback!(gasometer.record_refund(substate.gasometer.refunded_gas()));
-						TransactionOutcome::Commit(Capture::Exit((s.into(), out)))
-					}
-					ExitReason::Revert(r) => TransactionOutcome::Rollback(Capture::Exit((r.into(), out))),
-					ExitReason::Error(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), Vec::new()))),
-					ExitReason::Fatal(e) => {
-						gasometer.fail();
-						TransactionOutcome::Rollback(Capture::Exit((e.into(), Vec::new())))
+						ExitReason::Revert(r) => TransactionOutcome::Rollback(Capture::Exit((r.into(), out))),
+						ExitReason::Error(e) => TransactionOutcome::Rollback(Capture::Exit((e.into(), Vec::new()))),
+						ExitReason::Fatal(e) => {
+							gasometer.fail();
+							TransactionOutcome::Rollback(Capture::Exit((e.into(), Vec::new())))
+						}
 					}
-				}
-			},
-		)
-		.unwrap_or_else(|x| {
-			Capture::Exit((
-				ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
-				Vec::new(),
-			))
-		})
+				},
+			)
+			.unwrap_or_else(|x| {
+				Capture::Exit((
+					ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
+					Vec::new(),
+				))
+			});
+
+		if let Capture::Exit((reason, out)) = &result {
+			self.trace_exit(reason, out, gas_before.saturating_sub(self.gasometer.gas()));
+		}
+
+		result
 	}
 
 	fn pre_validate(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
+		let cold_access_cost = self.cold_access_cost(context.address, opcode, stack);
+		if cold_access_cost > 0 {
+			self.gasometer.record_cost(cold_access_cost)?;
+		}
+
 		if let Some(cost) = gasometer::static_opcode_cost(opcode) {
 			self.gasometer.record_cost(cost)?;
 		} else {
@@ -18,8 +18,12 @@
 
 pub mod handler;
 pub mod storage_meter;
+pub mod tracing;
 
-use crate::{AddressMapping, BalanceOf, CallInfo, Config, CreateInfo, Error, Pallet, Vicinity};
+use crate::{
+	AccessListItem, AddressMapping, BalanceOf, CallInfo, CallType, Config, CreateInfo, Error, Pallet, TraceOutcome,
+	TracerConfig, Vicinity,
+};
 use evm::{CreateScheme, ExitError, ExitReason};
 use evm_gasometer::{self as gasometer};
 use evm_runtime::Handler as HandlerT;
@@ -27,11 +31,12 @@ use frame_support::{
 	log,
 	traits::{Currency, ExistenceRequirement, Get},
 };
-use handler::Handler;
+use handler::{access_list_cost, AccessedSet, Handler};
 use primitive_types::{H160, H256, U256};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{traits::Zero, DispatchError, DispatchResult, SaturatedConversion, TransactionOutcome};
-use sp_std::{marker::PhantomData, vec::Vec};
+use sp_std::{cell::RefCell, marker::PhantomData, rc::Rc, vec::Vec};
+use tracing::Tracer;
 
 #[derive(Default)]
 pub struct Runner<T: Config> {
@@ -47,8 +52,10 @@ impl<T: Config> Runner<T> {
 		storage_limit: u32,
 		assigned_address: Option<H160>,
 		salt: Option<H256>,
+		access_list: Vec<AccessListItem>,
 		tag: &'static str,
 		config: &evm::Config,
+		tracer: Option<Rc<RefCell<Tracer>>>,
 	) -> Result<CreateInfo, DispatchError> {
 		log::debug!(
 			target: "evm",
@@ -64,6 +71,11 @@ impl<T: Config> Runner<T> {
 			origin: source,
 		};
 
+		let call_type = if salt.is_some() {
+			CallType::Create2
+		} else {
+			CallType::Create
+		};
 		let address = if let Some(addr) = assigned_address {
 			Ok(addr)
 		} else {
@@ -82,6 +94,19 @@ impl<T: Config> Runner<T> {
 
 		Handler::<T>::inc_nonce(source);
 
+		let accessed = Self::accessed_set(&[source, address], &access_list);
+
+		if let Some(tracer) = tracer.as_ref() {
+			tracer.borrow_mut().enter(
+				call_type,
+				source,
+				address,
+				U256::from(value.saturated_into::<u128>()),
+				gas_limit,
+				&init,
+			);
+		}
+
 		Handler::<T>::run_transaction(
 			&vicinity,
 			gas_limit,
@@ -89,6 +114,8 @@ impl<T: Config> Runner<T> {
 			address,
 			false,
 			config,
+			tracer,
+			accessed,
 			|substate| {
 				if let Err(e) = Self::transfer(source, address, value) {
 					return TransactionOutcome::Rollback(Err(e));
@@ -98,6 +125,11 @@ impl<T: Config> Runner<T> {
 				if substate.gasometer.record_transaction(transaction_cost).is_err() {
 					return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
 				}
+				if substate.accessed.is_some()
+					&& substate.gasometer.record_cost(access_list_cost(&access_list)).is_err()
+				{
+					return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
+				}
 
 				let (reason, out) = substate.execute(
 					source,
@@ -157,19 +189,7 @@ impl<T: Config> Runner<T> {
 		)?
 	}
 
-	fn transfer(source: H160, target: H160, value: BalanceOf<T>) -> DispatchResult {
-		if value.is_zero() {
-			return Ok(());
-		}
-
-		let from = T::AddressMapping::get_account_id(&source);
-		let to = T::AddressMapping::get_account_id(&target);
-		T::Currency::transfer(&from, &to, value, ExistenceRequirement::AllowDeath)
-	}
-}
-
-impl<T: Config> Runner<T> {
-	pub fn call(
+	fn inner_call(
 		sender: H160,
 		origin: H160,
 		target: H160,
@@ -177,7 +197,9 @@ impl<T: Config> Runner<T> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
+		tracer: Option<Rc<RefCell<Tracer>>>,
 	) -> Result<CallInfo, DispatchError> {
 		log::debug!(
 			target: "evm",
@@ -203,39 +225,114 @@ impl<T: Config> Runner<T> {
 
 		Handler::<T>::inc_nonce(sender);
 
-		Handler::<T>::run_transaction(&vicinity, gas_limit, storage_limit, target, false, config, |substate| {
-			if let Err(e) = Self::transfer(sender, target, value) {
-				return TransactionOutcome::Rollback(Err(e));
-			}
-
-			let code = substate.code(target);
-			let transaction_cost = gasometer::call_transaction_cost(&code);
-			if substate.gasometer.record_transaction(transaction_cost).is_err() {
-				return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
-			}
-
-			let (reason, out) =
-				substate.execute(sender, target, U256::from(value.saturated_into::<u128>()), code, input);
-
-			let call_info = CallInfo {
-				exit_reason: reason.clone(),
-				output: out,
-				used_gas: U256::from(substate.used_gas()),
-				used_storage: substate.used_storage(),
-			};
+		let accessed = Self::accessed_set(&[sender, origin, target], &access_list);
 
-			log::debug!(
-				target: "evm",
-				"call-result: call_info {:?}",
-				call_info
+		if let Some(tracer) = tracer.as_ref() {
+			tracer.borrow_mut().enter(
+				CallType::Call,
+				sender,
+				target,
+				U256::from(value.saturated_into::<u128>()),
+				gas_limit,
+				&input,
 			);
+		}
+
+		Handler::<T>::run_transaction(
+			&vicinity,
+			gas_limit,
+			storage_limit,
+			target,
+			false,
+			config,
+			tracer,
+			accessed,
+			|substate| {
+				if let Err(e) = Self::transfer(sender, target, value) {
+					return TransactionOutcome::Rollback(Err(e));
+				}
+
+				let code = substate.code(target);
+				let transaction_cost = gasometer::call_transaction_cost(&code);
+				if substate.gasometer.record_transaction(transaction_cost).is_err() {
+					return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
+				}
+				if substate.accessed.is_some()
+					&& substate.gasometer.record_cost(access_list_cost(&access_list)).is_err()
+				{
+					return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
+				}
+
+				let (reason, out) =
+					substate.execute(sender, target, U256::from(value.saturated_into::<u128>()), code, input);
+
+				let call_info = CallInfo {
+					exit_reason: reason.clone(),
+					output: out,
+					used_gas: U256::from(substate.used_gas()),
+					used_storage: substate.used_storage(),
+				};
+
+				log::debug!(
+					target: "evm",
+					"call-result: call_info {:?}",
+					call_info
+				);
+
+				if !reason.is_succeed() {
+					return TransactionOutcome::Rollback(Ok(call_info));
+				}
+
+				TransactionOutcome::Commit(Ok(call_info))
+			},
+		)?
+	}
 
-			if !reason.is_succeed() {
-				return TransactionOutcome::Rollback(Ok(call_info));
-			}
+	/// The accessed set a transaction starts with, if the hard fork charges
+	/// accesses by warmth. Access lists are ignored otherwise.
+	fn accessed_set(addresses: &[H160], access_list: &[AccessListItem]) -> Option<AccessedSet> {
+		if T::HardFork::get().has_access_lists() {
+			Some(AccessedSet::new(addresses, access_list))
+		} else {
+			None
+		}
+	}
 
-			TransactionOutcome::Commit(Ok(call_info))
-		})?
+	fn transfer(source: H160, target: H160, value: BalanceOf<T>) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
+		}
+
+		let from = T::AddressMapping::get_account_id(&source);
+		let to = T::AddressMapping::get_account_id(&target);
+		T::Currency::transfer(&from, &to, value, ExistenceRequirement::AllowDeath)
+	}
+}
+
+impl<T: Config> Runner<T> {
+	pub fn call(
+		sender: H160,
+		origin: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+		config: &evm::Config,
+	) -> Result<CallInfo, DispatchError> {
+		Self::inner_call(
+			sender,
+			origin,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list,
+			config,
+			None,
+		)
 	}
 
 	pub fn create(
@@ -244,6 +341,7 @@ impl<T: Config> Runner<T> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError> {
 		Self::inner_create(
@@ -254,8 +352,10 @@ impl<T: Config> Runner<T> {
 			storage_limit,
 			None,
 			None,
+			access_list,
 			"create",
 			config,
+			None,
 		)
 	}
 
@@ -266,6 +366,7 @@ impl<T: Config> Runner<T> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError> {
 		Self::inner_create(
@@ -276,8 +377,10 @@ impl<T: Config> Runner<T> {
 			storage_limit,
 			None,
 			Some(salt),
+			access_list,
 			"create2",
 			config,
+			None,
 		)
 	}
 
@@ -298,8 +401,91 @@ impl<T: Config> Runner<T> {
 			storage_limit,
 			Some(assigned_address),
 			None,
+			Vec::new(),
 			"create-system-contract",
 			config,
+			None,
 		)
 	}
+
+	/// Run `call` with `tracer`, without committing any state.
+	pub fn trace_call(
+		sender: H160,
+		origin: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+		config: &evm::Config,
+		tracer: TracerConfig,
+	) -> Result<TraceOutcome, DispatchError> {
+		let tracer = Rc::new(RefCell::new(Tracer::new(tracer)));
+
+		frame_support::storage::with_transaction(|| {
+			let outcome = Self::inner_call(
+				sender,
+				origin,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				config,
+				Some(tracer.clone()),
+			)
+			.and_then(|info| {
+				tracer
+					.borrow_mut()
+					.finish(info.exit_reason, info.output, info.used_gas.saturated_into())
+			});
+			TransactionOutcome::Rollback(outcome)
+		})
+	}
+
+	/// Run `create`, or `create2` if `salt` is given, with `tracer`, without
+	/// committing any state.
+	pub fn trace_create(
+		source: H160,
+		init: Vec<u8>,
+		salt: Option<H256>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+		config: &evm::Config,
+		tracer: TracerConfig,
+	) -> Result<TraceOutcome, DispatchError> {
+		let tracer = Rc::new(RefCell::new(Tracer::new(tracer)));
+
+		frame_support::storage::with_transaction(|| {
+			let outcome = Self::inner_create(
+				source,
+				init,
+				value,
+				gas_limit,
+				storage_limit,
+				None,
+				salt,
+				access_list,
+				"trace-create",
+				config,
+				Some(tracer.clone()),
+			)
+			.and_then(|info| {
+				// the deployed code is the output of a successful creation
+				let output = if info.exit_reason.is_succeed() {
+					Pallet::<T>::code_at_address(&info.address).into_inner()
+				} else {
+					info.output
+				};
+				tracer
+					.borrow_mut()
+					.finish(info.exit_reason, output, info.used_gas.saturated_into())
+			});
+			TransactionOutcome::Rollback(outcome)
+		})
+	}
 }
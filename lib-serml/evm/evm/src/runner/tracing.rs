@@ -0,0 +1,187 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Opt-in tracing of EVM executions, used by the tracing runtime api.
+
+use crate::{CallTrace, CallType, StructLog, TraceOutcome, TracerConfig};
+use evm::{ExitReason, ExitSucceed};
+use primitive_types::{H160, H256, U256};
+use sp_runtime::DispatchError;
+use sp_std::{mem, prelude::*};
+
+/// The maximum number of opcode steps recorded by the struct logger.
+pub const MAX_STRUCT_LOGS: usize = 100_000;
+/// The maximum size in bytes of the steps recorded by the struct logger.
+pub const MAX_STRUCT_LOGS_SIZE: usize = 32 * 1024 * 1024;
+
+pub struct Tracer {
+	config: TracerConfig,
+	/// Call frames entered but not exited yet, outermost first.
+	call_stack: Vec<CallTrace>,
+	struct_logs: Vec<StructLog>,
+	/// Size in bytes of `struct_logs`.
+	struct_logs_size: usize,
+	/// Set once `struct_logs` would exceed a limit, after which no step is
+	/// recorded anymore.
+	exceeded: bool,
+}
+
+impl Tracer {
+	pub fn new(config: TracerConfig) -> Self {
+		Self {
+			config,
+			call_stack: Vec::new(),
+			struct_logs: Vec::new(),
+			struct_logs_size: 0,
+			exceeded: false,
+		}
+	}
+
+	pub fn is_struct_logger(&self) -> bool {
+		matches!(self.config, TracerConfig::StructLogger { .. })
+	}
+
+	/// Open a call frame, nested in the current one.
+	pub fn enter(&mut self, call_type: CallType, from: H160, to: H160, value: U256, gas: u64, input: &[u8]) {
+		self.call_stack.push(CallTrace {
+			call_type,
+			from,
+			to,
+			value,
+			gas,
+			gas_used: 0,
+			input: input.to_vec(),
+			output: Vec::new(),
+			exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+			calls: Vec::new(),
+		});
+	}
+
+	/// Close the current call frame. The outermost frame is closed by
+	/// `finish`.
+	pub fn exit(&mut self, exit_reason: &ExitReason, output: &[u8], gas_used: u64) {
+		if self.call_stack.len() < 2 {
+			return;
+		}
+
+		if let Some(mut frame) = self.call_stack.pop() {
+			frame.exit_reason = exit_reason.clone();
+			frame.output = output.to_vec();
+			frame.gas_used = gas_used;
+			if let Some(parent) = self.call_stack.last_mut() {
+				parent.calls.push(frame);
+			}
+		}
+	}
+
+	/// Record the opcode about to be executed, returning its index for
+	/// `step_cost`. `touched` is the storage slot read or written by `SLOAD`
+	/// and `SSTORE`, and is the only slot recorded by the step.
+	pub fn step(
+		&mut self,
+		pc: usize,
+		op: u8,
+		gas: u64,
+		stack: &[H256],
+		memory: &[u8],
+		touched: Option<(H256, H256)>,
+	) -> usize {
+		let (disable_stack, disable_storage, enable_memory) = match self.config {
+			TracerConfig::StructLogger {
+				disable_stack,
+				disable_storage,
+				enable_memory,
+			} => (disable_stack, disable_storage, enable_memory),
+			TracerConfig::CallTracer => (true, true, false),
+		};
+
+		if self.exceeded {
+			return usize::MAX;
+		}
+
+		let stack = if disable_stack { &[][..] } else { stack };
+		let memory = if enable_memory { memory } else { &[][..] };
+		let storage = match touched {
+			Some(slot) if !disable_storage => vec![slot],
+			_ => Vec::new(),
+		};
+
+		let size = mem::size_of::<StructLog>()
+			.saturating_add(stack.len().saturating_mul(mem::size_of::<H256>()))
+			.saturating_add(memory.len())
+			.saturating_add(storage.len().saturating_mul(mem::size_of::<(H256, H256)>()));
+		self.struct_logs_size = self.struct_logs_size.saturating_add(size);
+		if self.struct_logs.len() >= MAX_STRUCT_LOGS || self.struct_logs_size > MAX_STRUCT_LOGS_SIZE {
+			// the trace is discarded by `finish`, free it right away
+			self.exceeded = true;
+			self.struct_logs = Vec::new();
+			return usize::MAX;
+		}
+
+		self.struct_logs.push(StructLog {
+			pc: pc as u64,
+			op,
+			gas,
+			gas_cost: 0,
+			depth: self.call_stack.len() as u32,
+			stack: stack.to_vec(),
+			memory: memory.to_vec(),
+			storage,
+		});
+		self.struct_logs.len() - 1
+	}
+
+	pub fn step_cost(&mut self, index: usize, gas_cost: u64) {
+		if let Some(log) = self.struct_logs.get_mut(index) {
+			log.gas_cost = gas_cost;
+		}
+	}
+
+	/// Close the outermost call frame with the result of the whole execution.
+	/// Fails if the struct logger exceeded `MAX_STRUCT_LOGS` or
+	/// `MAX_STRUCT_LOGS_SIZE`.
+	pub fn finish(
+		&mut self,
+		exit_reason: ExitReason,
+		output: Vec<u8>,
+		used_gas: u64,
+	) -> Result<TraceOutcome, DispatchError> {
+		if self.exceeded {
+			return Err(DispatchError::Other("TraceTooLarge"));
+		}
+
+		let mut root = self
+			.call_stack
+			.drain(..)
+			.next()
+			.ok_or(DispatchError::Other("NoTrace"))?;
+		root.exit_reason = exit_reason;
+		root.output = output;
+		root.gas_used = used_gas;
+
+		Ok(match self.config {
+			TracerConfig::CallTracer => TraceOutcome::CallTracer(root),
+			TracerConfig::StructLogger { .. } => TraceOutcome::StructLogger {
+				exit_reason: root.exit_reason,
+				used_gas,
+				return_value: root.output,
+				struct_logs: mem::take(&mut self.struct_logs),
+			},
+		})
+	}
+}
@@ -22,7 +22,7 @@ use super::*;
 use mock::{Event, *};
 
 use crate::runner::handler::{Handler, STORAGE_SIZE};
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok, weights::GetDispatchInfo};
 use sp_core::{
 	bytes::{from_hex, to_hex},
 	H160,
@@ -38,8 +38,8 @@ fn fail_call_return_ok() {
 		let signer: AccountId32 = AccountId32::from(data).into();
 
 		let origin = Origin::signed(signer);
-		assert_ok!(EVM::call(origin.clone(), contract_a(), Vec::new(), 0, 1000000, 0));
-		assert_ok!(EVM::call(origin, contract_b(), Vec::new(), 0, 1000000, 0));
+		assert_ok!(EVM::call(origin.clone(), contract_a(), Vec::new(), 0, 1000000, 0, vec![]));
+		assert_ok!(EVM::call(origin, contract_b(), Vec::new(), 0, 1000000, 0, vec![]));
 	});
 }
 
@@ -89,6 +89,7 @@ fn should_create_and_call_contract() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		).unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
@@ -116,6 +117,7 @@ fn should_create_and_call_contract() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		).unwrap();
 		assert_eq!(
@@ -160,8 +162,16 @@ fn create_reverts_with_message() {
 		"0x6080604052348015600f57600080fd5b5060006083576040517f08c379a000000000000000000000000000000000000000000000000000000000815260040180806020018281038252600d8152602001807f6572726f72206d6573736167650000000000000000000000000000000000000081525060200191505060405180910390fd5b603e8060906000396000f3fe6080604052600080fdfea265627a7a723158204741083d83bf4e3ee8099dd0b3471c81061237c2e8eccfcb513dfa4c04634b5b64736f6c63430005110032"
 	).unwrap();
 	new_test_ext().execute_with(|| {
-		let result =
-			Runner::<Test>::create(alice(), contract, 0, 12_000_000, 12_000_000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			12_000_000,
+			12_000_000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
 		assert!(String::from_utf8_lossy(&result.output).contains("error message"));
 	});
@@ -189,6 +199,7 @@ fn call_reverts_with_message() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		).unwrap();
 
@@ -213,6 +224,7 @@ fn call_reverts_with_message() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		).unwrap();
 
@@ -255,8 +267,16 @@ fn should_deploy_payable_contract() {
 			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
 		contract.append(&mut stored_value.clone());
 
-		let result =
-			Runner::<Test>::create(alice(), contract, amount, 100000, 100000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			amount,
+			100000,
+			100000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		let contract_address = result.address;
 
 		#[cfg(not(feature = "with-ethereum-compatibility"))]
@@ -278,6 +298,7 @@ fn should_deploy_payable_contract() {
 			amount,
 			100000,
 			100000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -329,8 +350,16 @@ fn should_transfer_from_contract() {
 	new_test_ext().execute_with(|| {
 		let amount = 1000u64;
 
-		let result = Runner::<Test>::create(alice(), contract, 0, 10000000, 10000000, <Test as Config>::config())
-			.expect("create shouldn't fail");
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			10000000,
+			10000000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.expect("create shouldn't fail");
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
 		assert_eq!(result.used_storage, 892);
 
@@ -354,6 +383,7 @@ fn should_transfer_from_contract() {
 			amount,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -374,6 +404,7 @@ fn should_transfer_from_contract() {
 			amount,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -394,6 +425,7 @@ fn should_transfer_from_contract() {
 			amount,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -428,6 +460,7 @@ fn contract_should_deploy_contracts() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -459,6 +492,7 @@ fn contract_should_deploy_contracts() {
 			amount,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -499,6 +533,7 @@ fn contract_should_deploy_contracts_without_payable() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -522,6 +557,7 @@ fn contract_should_deploy_contracts_without_payable() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -556,7 +592,16 @@ fn deploy_factory() {
 		"0x608060405234801561001057600080fd5b5060405161001d90610121565b604051809103906000f080158015610039573d6000803e3d6000fd5b506000806101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055506000809054906101000a900473ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff1663c29855786040518163ffffffff1660e01b815260040160206040518083038186803b1580156100e057600080fd5b505afa1580156100f4573d6000803e3d6000fd5b505050506040513d602081101561010a57600080fd5b81019080805190602001909291905050505061012d565b60a58061017983390190565b603e8061013b6000396000f3fe6080604052600080fdfea265627a7a7231582064177030ee644a03aaf8d65027df9e0331c8bc4b161de25bfb8aa3142848e0f864736f6c634300051100326080604052348015600f57600080fd5b5060878061001e6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063c298557814602d575b600080fd5b60336049565b6040518082815260200191505060405180910390f35b6000607b90509056fea265627a7a7231582031e5a4abae00962cfe9875df1b5b0d3ce6624e220cb8c714a948794fcddb6b4f64736f6c63430005110032"
 	).unwrap();
 	new_test_ext().execute_with(|| {
-		let result = Runner::<Test>::create(alice(), contract, 0, 2_000_000, 5000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			2_000_000,
+			5000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
 		assert_eq!(result.used_gas.as_u64(), 124_479u64);
 		assert_eq!(result.used_storage, 461);
@@ -649,8 +694,16 @@ fn should_transfer_maintainer() {
 		"0x608060405234801561001057600080fd5b5060405161001d90610121565b604051809103906000f080158015610039573d6000803e3d6000fd5b506000806101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055506000809054906101000a900473ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff1663c29855786040518163ffffffff1660e01b815260040160206040518083038186803b1580156100e057600080fd5b505afa1580156100f4573d6000803e3d6000fd5b505050506040513d602081101561010a57600080fd5b81019080805190602001909291905050505061012d565b60a58061017983390190565b603e8061013b6000396000f3fe6080604052600080fdfea265627a7a7231582064177030ee644a03aaf8d65027df9e0331c8bc4b161de25bfb8aa3142848e0f864736f6c634300051100326080604052348015600f57600080fd5b5060878061001e6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063c298557814602d575b600080fd5b60336049565b6040518082815260200191505060405180910390f35b6000607b90509056fea265627a7a7231582031e5a4abae00962cfe9875df1b5b0d3ce6624e220cb8c714a948794fcddb6b4f64736f6c63430005110032"
 	).unwrap();
 	new_test_ext().execute_with(|| {
-		let result =
-			Runner::<Test>::create(alice(), contract, 0, 12_000_000, 12_000_000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			12_000_000,
+			12_000_000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
 		assert_eq!(result.used_storage, 461);
 		let alice_balance = INITIAL_BALANCE - 461 * <Test as Config>::StorageDepositPerByte::get();
@@ -714,13 +767,23 @@ fn should_deploy() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		).unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
 		assert_eq!(result.used_storage, 0);
 
 		// create contract
-		let result = Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			21_000_000,
+			21_000_000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		let contract_address = result.address;
 
 		assert_eq!(result.used_storage, 284);
@@ -742,6 +805,7 @@ fn should_deploy() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		));
 
@@ -754,6 +818,7 @@ fn should_deploy() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		), Error::<Test>::NoPermission);
 
@@ -767,6 +832,7 @@ fn should_deploy() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		));
 
@@ -789,6 +855,7 @@ fn should_deploy() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		));
 
@@ -815,7 +882,16 @@ fn should_deploy_free() {
 		assert_noop!(EVM::deploy_free(Origin::signed(CouncilAccount::get()), H160::default()), Error::<Test>::ContractNotFound);
 
 		// create contract
-		let result = Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			21_000_000,
+			21_000_000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		let contract_address = result.address;
 
 		// multiply(2, 3)
@@ -832,6 +908,7 @@ fn should_deploy_free() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		), Error::<Test>::NoPermission);
 
@@ -846,6 +923,7 @@ fn should_deploy_free() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Test as Config>::config(),
 		));
 
@@ -934,6 +1012,7 @@ fn should_set_code() {
 			0,
 			21_000_000,
 			21_000_000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1004,8 +1083,16 @@ fn should_selfdestruct() {
 		contract.append(&mut stored_value.clone());
 
 		// create contract
-		let result =
-			Runner::<Test>::create(alice(), contract, amount, 100000, 100000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			amount,
+			100000,
+			100000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 
 		let contract_address = result.address;
 		assert_eq!(result.used_storage, 287);
@@ -1067,8 +1154,16 @@ fn storage_limit_should_work() {
 	).unwrap();
 
 	new_test_ext().execute_with(|| {
-		let result =
-			Runner::<Test>::create(alice(), contract.clone(), 0, 200_000, 1000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract.clone(),
+			0,
+			200_000,
+			1000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
 		assert_eq!(result.used_storage, 516);
 		let alice_balance = INITIAL_BALANCE - 516 * <Test as Config>::StorageDepositPerByte::get();
@@ -1097,6 +1192,7 @@ fn storage_limit_should_work() {
 			amount,
 			1000000000,
 			0,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1115,6 +1211,7 @@ fn storage_limit_should_work() {
 			amount,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1133,6 +1230,7 @@ fn storage_limit_should_work() {
 			amount,
 			1000000000,
 			127,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1151,6 +1249,7 @@ fn storage_limit_should_work() {
 			amount,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1188,6 +1287,7 @@ fn evm_execute_mode_should_work() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1349,7 +1449,16 @@ fn should_update_storage() {
 
 	new_test_ext().execute_with(|| {
 		// create contract
-		let result = Runner::<Test>::create(alice(), contract, 0, 500000, 100000, <Test as Config>::config()).unwrap();
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			500000,
+			100000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
 
 		let contract_address = result.address;
 
@@ -1374,6 +1483,7 @@ fn should_update_storage() {
 				0,
 				1000000,
 				0,
+				vec![],
 				<Test as Config>::config(),
 			),
 			DispatchError::Other("OutOfStorage")
@@ -1388,6 +1498,7 @@ fn should_update_storage() {
 			0,
 			1000000,
 			STORAGE_SIZE,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1406,6 +1517,7 @@ fn should_update_storage() {
 			0,
 			1000000,
 			STORAGE_SIZE,
+			vec![],
 			<Test as Config>::config(),
 		)
 		.unwrap();
@@ -1416,3 +1528,889 @@ fn should_update_storage() {
 		assert_eq!(ContractStorageSizes::<Test>::get(&contract_address), used_storage);
 	});
 }
+
+#[test]
+fn trace_call_should_record_nested_calls() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Factory {
+	//     Contract[] newContracts;
+	//
+	//     function createContract () public payable {
+	//         Contract newContract = new Contract();
+	//         newContracts.push(newContract);
+	//     }
+	// }
+	//
+	// contract Contract {}
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5061016f806100206000396000f3fe608060405260043610610041576000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff168063412a5a6d14610046575b600080fd5b61004e610050565b005b600061005a6100e2565b604051809103906000f080158015610076573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055505050565b6040516052806100f28339019056fe6080604052348015600f57600080fd5b50603580601d6000396000f3fe6080604052600080fdfea165627a7a7230582092dc1966a8880ddf11e067f9dd56a632c11a78a4afd4a9f05924d427367958cc0029a165627a7a723058202b2cc7384e11c452cdbf39b68dada2d5e10a632cc0174a354b8b8c83237e28a40029"
+	).unwrap();
+	new_test_ext().execute_with(|| {
+		let result = Runner::<Test>::create(
+			alice(),
+			contract,
+			0,
+			1000000000,
+			1000000000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		let factory_contract_address = result.address;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(factory_contract_address);
+
+		// Factory.createContract
+		let create_contract = from_hex("0x412a5a6d").unwrap();
+		let outcome = Runner::<Test>::trace_call(
+			alice(),
+			alice(),
+			factory_contract_address,
+			create_contract.clone(),
+			0,
+			1000000000,
+			1000000000,
+			vec![],
+			<Test as Config>::config(),
+			TracerConfig::CallTracer,
+		)
+		.unwrap();
+
+		let contract_address = H160::from_str("7b8f8ca099f6e33cf1817cf67d0556429cfc54e4").unwrap();
+		let trace = match outcome {
+			TraceOutcome::CallTracer(trace) => trace,
+			_ => panic!("expected call trace"),
+		};
+		assert_eq!(trace.call_type, CallType::Call);
+		assert_eq!(trace.from, alice());
+		assert_eq!(trace.to, factory_contract_address);
+		assert_eq!(trace.input, create_contract);
+		assert_eq!(trace.gas, 1000000000);
+		assert_eq!(trace.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(trace.calls.len(), 1);
+
+		let create = &trace.calls[0];
+		assert_eq!(create.call_type, CallType::Create);
+		assert_eq!(create.from, factory_contract_address);
+		assert_eq!(create.to, contract_address);
+		assert_eq!(create.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(create.output, Pallet::<Test>::code_at_address(&contract_address).into_inner());
+		assert!(!create.output.is_empty());
+		assert!(create.gas_used > 0 && create.gas_used < trace.gas_used);
+		assert!(create.calls.is_empty());
+
+		// tracing does not commit any state
+		assert!(Pallet::<Test>::accounts(contract_address).is_none());
+		assert_eq!(Pallet::<Test>::account_basic(&alice()).nonce, 2.into());
+	});
+}
+
+#[test]
+fn trace_call_should_record_struct_logs() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	function foo() public pure {
+	// 		require(false, "error message");
+	// 	}
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060df8061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063c298557814602d575b600080fd5b60336035565b005b600060a8576040517f08c379a000000000000000000000000000000000000000000000000000000000815260040180806020018281038252600d8152602001807f6572726f72206d6573736167650000000000000000000000000000000000000081525060200191505060405180910390fd5b56fea265627a7a7231582066b3ee33bedba8a318d0d66610145030fdc0f982b11f5160d366e15e4d8ba2ef64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 1000000, 1000000, vec![], <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(contract_address);
+
+		// call method `foo`
+		let foo = from_hex("0xc2985578").unwrap();
+		let outcome = Runner::<Test>::trace_call(
+			alice(),
+			alice(),
+			contract_address,
+			foo,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Test as Config>::config(),
+			TracerConfig::StructLogger {
+				disable_stack: false,
+				disable_storage: false,
+				enable_memory: true,
+			},
+		)
+		.unwrap();
+
+		let (exit_reason, return_value, struct_logs) = match outcome {
+			TraceOutcome::StructLogger {
+				exit_reason,
+				return_value,
+				struct_logs,
+				..
+			} => (exit_reason, return_value, struct_logs),
+			_ => panic!("expected struct logs"),
+		};
+		assert_eq!(exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert!(String::from_utf8_lossy(&return_value).contains("error message"));
+
+		// PUSH1 0x80
+		let first = &struct_logs[0];
+		assert_eq!((first.pc, first.op, first.gas_cost, first.depth), (0, 0x60, 3, 1));
+		assert!(first.stack.is_empty());
+		// PUSH1 0x40
+		assert_eq!(struct_logs[1].stack, vec![H256::from_low_u64_be(0x80)]);
+		// MSTORE
+		assert_eq!(struct_logs[3].memory.len(), 0x60);
+		// REVERT
+		let last = struct_logs.last().unwrap();
+		assert_eq!(last.op, 0xfd);
+		assert!(struct_logs.iter().all(|log| log.depth == 1 && log.storage.is_empty()));
+	});
+}
+
+#[test]
+fn trace_call_should_limit_struct_logs() {
+	// init code deploying `JUMPDEST PUSH1 0x00 JUMP`, an endless loop
+	let contract = from_hex("0x635b6000566000526004601cf3").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 1000000, 1000000, vec![], <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(contract_address);
+
+		let trace = |tracer| {
+			Runner::<Test>::trace_call(
+				alice(),
+				alice(),
+				contract_address,
+				vec![],
+				0,
+				2000000,
+				1000000,
+				vec![],
+				<Test as Config>::config(),
+				tracer,
+			)
+		};
+
+		// the loop runs out of gas after more than `MAX_STRUCT_LOGS` steps
+		assert_eq!(
+			trace(TracerConfig::StructLogger {
+				disable_stack: false,
+				disable_storage: false,
+				enable_memory: false,
+			}),
+			Err(DispatchError::Other("TraceTooLarge"))
+		);
+		assert!(matches!(
+			trace(TracerConfig::CallTracer),
+			Ok(TraceOutcome::CallTracer(CallTrace {
+				exit_reason: ExitReason::Error(ExitError::OutOfGas),
+				..
+			}))
+		));
+	});
+}
+
+fn eth_address(secret: &secp256k1::SecretKey) -> H160 {
+	let public = secp256k1::PublicKey::from_secret_key(secret);
+	H160::from_slice(&sp_io::hashing::keccak_256(&public.serialize()[1..65])[12..])
+}
+
+fn eth_transaction(
+	secret: &secp256k1::SecretKey,
+	chain_id: u64,
+	nonce: u64,
+	action: Option<H160>,
+	input: Vec<u8>,
+	gas_limit: u64,
+) -> Vec<u8> {
+	let tx = transaction::EthereumTransaction {
+		nonce: nonce.into(),
+		gas_price: 1.into(),
+		gas_limit,
+		action,
+		value: 0.into(),
+		input,
+		chain_id: Some(chain_id),
+		signature: [0u8; 65],
+	};
+	let (sig, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&tx.signing_hash().0), secret);
+	let sig = sig.serialize();
+
+	let mut stream = rlp::RlpStream::new_list(9);
+	stream.append(&tx.nonce);
+	stream.append(&tx.gas_price);
+	stream.append(&tx.gas_limit);
+	match action {
+		Some(ref to) => stream.append(to),
+		None => stream.append_empty_data(),
+	};
+	stream.append(&tx.value);
+	stream.append(&tx.input);
+	stream.append(&(chain_id * 2 + 35 + recovery_id.serialize() as u64));
+	stream.append(&U256::from(&sig[0..32]));
+	stream.append(&U256::from(&sig[32..64]));
+	stream.out().to_vec()
+}
+
+#[test]
+fn eth_transact_should_create_and_call_contract() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let secret = secp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+		let sender = eth_address(&secret);
+		let _ = Balances::deposit_creating(
+			&<Test as Config>::AddressMapping::get_account_id(&sender),
+			INITIAL_BALANCE,
+		);
+
+		let contract_address = Handler::<Test>::create_address(evm::CreateScheme::Legacy { caller: sender }).unwrap();
+		let raw = eth_transaction(&secret, 1, 0, None, contract, 1000000);
+		assert_ok!(EVM::eth_transact(Origin::none(), raw));
+		System::assert_last_event(Event::setheum_evm(crate::Event::Created(contract_address)));
+		assert_eq!(Pallet::<Test>::account_basic(&sender).nonce, 1.into());
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(contract_address);
+
+		let raw = eth_transaction(&secret, 1, 1, Some(contract_address), multiply, 1000000);
+		assert_ok!(EVM::eth_transact(Origin::none(), raw));
+		System::assert_last_event(Event::setheum_evm(crate::Event::Executed(contract_address)));
+		assert_eq!(Pallet::<Test>::account_basic(&sender).nonce, 2.into());
+	});
+}
+
+#[test]
+fn eth_transact_should_validate_unsigned() {
+	use frame_support::unsigned::ValidateUnsigned;
+
+	new_test_ext().execute_with(|| {
+		let secret = secp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+		let sender = eth_address(&secret);
+		let _ = Balances::deposit_creating(
+			&<Test as Config>::AddressMapping::get_account_id(&sender),
+			INITIAL_BALANCE,
+		);
+
+		let call = |chain_id, nonce| {
+			crate::Call::<Test>::eth_transact(eth_transaction(&secret, chain_id, nonce, Some(alice()), vec![], 100000))
+		};
+
+		let valid = EVM::validate_unsigned(TransactionSource::External, &call(1, 0)).unwrap();
+		assert!(valid.requires.is_empty());
+		assert_ok!(EVM::pre_dispatch(&call(1, 0)));
+
+		// signed for another chain
+		assert_eq!(
+			EVM::validate_unsigned(TransactionSource::External, &call(2, 0)),
+			InvalidTransaction::BadProof.into()
+		);
+		assert_eq!(
+			EVM::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::<Test>::eth_transact(vec![1, 2, 3])
+			),
+			InvalidTransaction::Call.into()
+		);
+
+		// future transactions wait in the pool for the previous nonce
+		let next = EVM::validate_unsigned(TransactionSource::External, &call(1, 1)).unwrap();
+		assert_eq!(next.requires, valid.provides);
+		assert_eq!(EVM::pre_dispatch(&call(1, 1)), Err(InvalidTransaction::Future.into()));
+
+		assert_ok!(EVM::eth_transact(
+			Origin::none(),
+			eth_transaction(&secret, 1, 0, Some(alice()), vec![], 100000)
+		));
+		assert_eq!(
+			EVM::validate_unsigned(TransactionSource::External, &call(1, 0)),
+			InvalidTransaction::Stale.into()
+		);
+		assert_ok!(EVM::pre_dispatch(&call(1, 1)));
+	});
+}
+
+#[test]
+fn trace_eth_transact_should_work() {
+	// init code deploying `STOP`
+	let contract = from_hex("0x60016000f3").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let secret = secp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+		let sender = eth_address(&secret);
+		let who = <Test as Config>::AddressMapping::get_account_id(&sender);
+		let _ = Balances::deposit_creating(&who, INITIAL_BALANCE);
+
+		let contract_address = Handler::<Test>::create_address(evm::CreateScheme::Legacy { caller: sender }).unwrap();
+		let raw = eth_transaction(&secret, 1, 0, None, contract, 1000000);
+		let trace = match EVM::trace_eth_transact(&raw, TracerConfig::CallTracer) {
+			Ok(TraceOutcome::CallTracer(trace)) => trace,
+			outcome => panic!("expected call trace, got {:?}", outcome),
+		};
+		assert_eq!(trace.call_type, CallType::Create);
+		assert_eq!(trace.from, sender);
+		assert_eq!(trace.to, contract_address);
+		assert_eq!(trace.gas, 1000000);
+		assert_eq!(trace.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		// the fee is taken, the execution is not committed
+		assert!(Balances::free_balance(&who) < INITIAL_BALANCE);
+		assert!(Pallet::<Test>::accounts(contract_address).is_none());
+
+		// the nonce must be the next one
+		let raw = eth_transaction(&secret, 1, 1, Some(alice()), vec![], 100000);
+		assert_noop!(
+			EVM::trace_eth_transact(&raw, TracerConfig::CallTracer),
+			Error::<Test>::InvalidEthTransaction
+		);
+	});
+}
+
+/// Init code deploying `runtime` as is.
+fn deploy_runtime(runtime: &[u8]) -> H160 {
+	let result = Runner::<Test>::create(
+		alice(),
+		calibration::init_code(runtime),
+		0,
+		1000000,
+		1000000,
+		vec![],
+		<Test as Config>::config(),
+	)
+	.unwrap();
+	assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+	result.address
+}
+
+fn call_used_gas(contract: H160, access_list: Vec<AccessListItem>) -> u64 {
+	let result = Runner::<Test>::call(
+		alice(),
+		alice(),
+		contract,
+		vec![],
+		0,
+		1000000,
+		1000000,
+		access_list,
+		<Test as Config>::config(),
+	)
+	.unwrap();
+	assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+	result.used_gas.as_u64()
+}
+
+#[test]
+fn storage_reads_are_warm_after_first_access() {
+	// PUSH1 0 SLOAD POP PUSH1 0 SLOAD POP STOP
+	let runtime = from_hex("0x600054506000545000").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let contract = deploy_runtime(&runtime);
+		assert_eq!(call_used_gas(contract, vec![]), 22_718);
+	});
+
+	new_test_ext().execute_with(|| {
+		EVM_HARD_FORK.with(|v| *v.borrow_mut() = HardFork::Berlin);
+		let contract = deploy_runtime(&runtime);
+		// a cold read of 2100 and a warm read of 100
+		assert_eq!(call_used_gas(contract, vec![]), 23_318);
+
+		// the slot is warm, but the access list costs 2400 for the address and
+		// 1900 for the key
+		let access_list = vec![AccessListItem {
+			address: contract,
+			storage_keys: vec![H256::zero()],
+		}];
+		assert_eq!(call_used_gas(contract, access_list), 25_618);
+	});
+}
+
+#[test]
+fn account_accesses_are_warm_after_first_access() {
+	// PUSH20 bob BALANCE POP PUSH20 bob BALANCE POP STOP
+	let runtime = from_hex("0x7310000000000000000000000000000000000000023150731000000000000000000000000000000000000002315000")
+		.unwrap();
+
+	new_test_ext().execute_with(|| {
+		let contract = deploy_runtime(&runtime);
+		assert_eq!(call_used_gas(contract, vec![]), 22_718);
+	});
+
+	new_test_ext().execute_with(|| {
+		EVM_HARD_FORK.with(|v| *v.borrow_mut() = HardFork::Berlin);
+		let contract = deploy_runtime(&runtime);
+		// a cold access of 2600 and a warm access of 100
+		assert_eq!(call_used_gas(contract, vec![]), 24_018);
+	});
+}
+
+#[test]
+fn london_rejects_ef_code() {
+	new_test_ext().execute_with(|| {
+		EVM_HARD_FORK.with(|v| *v.borrow_mut() = HardFork::London);
+		let init = vec![0x60, 0x01, 0x80, 0x60, 0x0b, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3, 0xef];
+		let result = Runner::<Test>::create(
+			alice(),
+			init,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Error(ExitError::Other("InvalidCode".into()))
+		);
+		assert!(Pallet::<Test>::accounts(result.address).is_none());
+
+		// the code of earlier hard forks is still accepted
+		EVM_HARD_FORK.with(|v| *v.borrow_mut() = HardFork::Berlin);
+		let contract = deploy_runtime(&[0xef]);
+		assert_eq!(EVM::code_at_address(&contract).into_inner(), vec![0xef]);
+	});
+}
+
+#[test]
+fn opcode_mixes_burn_their_gas_limit() {
+	for hard_fork in &[HardFork::Istanbul, HardFork::London] {
+		new_test_ext().execute_with(|| {
+			EVM_HARD_FORK.with(|v| *v.borrow_mut() = *hard_fork);
+			for mix in &[&calibration::ARITHMETIC[..], &calibration::STORAGE[..], &calibration::HASHING[..]] {
+				let contract = deploy_runtime(mix);
+				// the loop stops with less than 30_000 gas left
+				assert!(call_used_gas(contract, vec![]) > 1000000 - 30000);
+			}
+		});
+	}
+}
+
+#[test]
+fn weight_per_gas_is_the_highest_of_the_opcode_mixes() {
+	// the hashing mix of `()`
+	assert_eq!(calibration::WeightPerGas::<Test>::get(), 20_680);
+}
+
+#[test]
+fn call_weight_is_refunded_to_the_used_gas() {
+	new_test_ext().execute_with(|| {
+		// STOP
+		let contract = deploy_runtime(&[0x00]);
+		let used_gas = call_used_gas(contract, vec![]);
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		let gas_limit = 10 * calibration::GAS_PER_STEP;
+		let call = crate::Call::<Test>::call(contract, vec![], 0, gas_limit, 1000000, vec![]);
+		assert_eq!(call.get_dispatch_info().weight, gas_limit);
+
+		let post_info = EVM::call(Origin::signed(alice_account_id), contract, vec![], 0, gas_limit, 1000000, vec![])
+			.unwrap();
+		assert_eq!(post_info.actual_weight, Some(used_gas));
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+	});
+}
+
+fn upgrade_code(contract: H160, code: Vec<u8>) {
+	let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+	assert_ok!(EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, code));
+	System::set_block_number(System::block_number() + CodeUpgradeDelay::get());
+	assert_ok!(EVM::activate_code_upgrade(Origin::signed(alice_account_id), contract));
+}
+
+#[test]
+fn should_propose_and_activate_code_upgrade() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		// STOP
+		let contract = deploy_runtime(&[0x00]);
+		let old_code_hash = EVM::code_hash_at_address(&contract);
+		// PUSH1 0 STOP
+		let new_code = vec![0x60, 0x00, 0x00];
+		let new_code_hash = code_hash(&new_code);
+
+		let alice_balance = balance(alice());
+		let storage_size = ContractStorageSizes::<Test>::get(&contract);
+
+		assert_noop!(
+			EVM::propose_code_upgrade(Origin::signed(bob_account_id), contract, new_code.clone()),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, vec![0x00]),
+			Error::<Test>::CodeUnchanged
+		);
+
+		assert_ok!(EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, new_code.clone()));
+		System::assert_last_event(Event::setheum_evm(crate::Event::CodeUpgradeProposed(
+			contract,
+			new_code_hash,
+			1 + CodeUpgradeDelay::get(),
+		)));
+
+		// the maintainer pays the storage of the proposed code
+		assert_eq!(balance(alice()), alice_balance - 3 * StorageDepositPerByte::get());
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract), storage_size + 3);
+		assert_eq!(
+			CodeInfos::<Test>::get(&new_code_hash),
+			Some(CodeInfo {
+				code_size: 3,
+				ref_count: 1,
+			})
+		);
+		assert_eq!(EVM::code_hash_at_address(&contract), old_code_hash);
+
+		assert_noop!(
+			EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, vec![0x01]),
+			Error::<Test>::CodeUpgradeAlreadyProposed
+		);
+		assert_noop!(
+			EVM::activate_code_upgrade(Origin::signed(alice_account_id.clone()), contract),
+			Error::<Test>::CodeUpgradeNotDue
+		);
+
+		System::set_block_number(1 + CodeUpgradeDelay::get());
+		assert_ok!(EVM::activate_code_upgrade(Origin::signed(alice_account_id), contract));
+		System::assert_last_event(Event::setheum_evm(crate::Event::CodeUpgradeActivated(contract, new_code_hash)));
+
+		assert_eq!(EVM::code_at_address(&contract).into_inner(), new_code);
+		assert_eq!(EVM::code_versions(&contract).into_inner(), vec![old_code_hash]);
+		assert_eq!(EVM::code_upgrades(&contract), None);
+		assert_eq!(
+			CodeInfos::<Test>::get(&old_code_hash),
+			Some(CodeInfo {
+				code_size: 1,
+				ref_count: 1,
+			})
+		);
+	});
+}
+
+#[test]
+fn should_cancel_code_upgrade() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		let contract = deploy_runtime(&[0x00]);
+		let new_code = vec![0x60, 0x00, 0x00];
+		let new_code_hash = code_hash(&new_code);
+
+		let alice_balance = balance(alice());
+		let storage_size = ContractStorageSizes::<Test>::get(&contract);
+
+		assert_noop!(
+			EVM::cancel_code_upgrade(Origin::signed(alice_account_id.clone()), contract),
+			Error::<Test>::CodeUpgradeNotFound
+		);
+
+		assert_ok!(EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, new_code.clone()));
+		assert_noop!(
+			EVM::cancel_code_upgrade(Origin::signed(bob_account_id), contract),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(EVM::cancel_code_upgrade(Origin::signed(CouncilAccount::get()), contract));
+		System::assert_last_event(Event::setheum_evm(crate::Event::CodeUpgradeCanceled(contract, new_code_hash)));
+
+		// the storage of the code is refunded
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract), storage_size);
+		assert_eq!(CodeInfos::<Test>::get(&new_code_hash), None);
+		assert!(!Codes::<Test>::contains_key(&new_code_hash));
+
+		assert_ok!(EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, new_code));
+		assert_ok!(EVM::cancel_code_upgrade(Origin::signed(alice_account_id), contract));
+		assert_eq!(EVM::code_upgrades(&contract), None);
+	});
+}
+
+#[test]
+fn should_roll_back_code() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		let contract = deploy_runtime(&[0x00]);
+		let old_code_hash = EVM::code_hash_at_address(&contract);
+		let new_code = vec![0x60, 0x00, 0x00];
+		let new_code_hash = code_hash(&new_code);
+		upgrade_code(contract, new_code);
+
+		let alice_balance = balance(alice());
+		let storage_size = ContractStorageSizes::<Test>::get(&contract);
+
+		assert_noop!(
+			EVM::propose_code_rollback(Origin::signed(alice_account_id.clone()), contract, new_code_hash),
+			Error::<Test>::CodeVersionNotFound
+		);
+		assert_ok!(EVM::propose_code_rollback(Origin::signed(alice_account_id.clone()), contract, old_code_hash));
+
+		// canceling a rollback keeps the code version
+		assert_ok!(EVM::cancel_code_upgrade(Origin::signed(alice_account_id.clone()), contract));
+		assert_eq!(EVM::code_versions(&contract).into_inner(), vec![old_code_hash]);
+
+		// proposing the code of a code version is a rollback
+		assert_ok!(EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, vec![0x00]));
+		System::set_block_number(System::block_number() + CodeUpgradeDelay::get());
+		assert_ok!(EVM::activate_code_upgrade(Origin::signed(alice_account_id), contract));
+
+		assert_eq!(EVM::code_hash_at_address(&contract), old_code_hash);
+		assert_eq!(EVM::code_versions(&contract).into_inner(), vec![new_code_hash]);
+		// the rollback is already paid for
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract), storage_size);
+		assert_eq!(CodeInfos::<Test>::get(&old_code_hash).map(|info| info.ref_count), Some(1));
+		assert_eq!(CodeInfos::<Test>::get(&new_code_hash).map(|info| info.ref_count), Some(1));
+	});
+}
+
+#[test]
+fn code_versions_are_dropped_beyond_max_code_versions() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		let contract = deploy_runtime(&[0x00]);
+		let old_code_hash = EVM::code_hash_at_address(&contract);
+		let codes: Vec<Vec<u8>> = (1..=4).map(|i| vec![0x60, i, 0x00]).collect();
+		let code_hashes: Vec<H256> = codes.iter().map(|code| code_hash(code.as_slice())).collect();
+
+		upgrade_code(contract, codes[0].clone());
+		upgrade_code(contract, codes[1].clone());
+
+		let alice_balance = balance(alice());
+		let storage_size = ContractStorageSizes::<Test>::get(&contract);
+
+		upgrade_code(contract, codes[2].clone());
+
+		// the oldest code version is dropped, and its storage refunded
+		assert_eq!(EVM::code_versions(&contract).into_inner(), vec![code_hashes[0], code_hashes[1]]);
+		assert_eq!(CodeInfos::<Test>::get(&old_code_hash), None);
+		assert_eq!(balance(alice()), alice_balance - 2 * StorageDepositPerByte::get());
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract), storage_size + 2);
+
+		// selfdestruct drops the proposed code and the code versions
+		assert_ok!(EVM::propose_code_upgrade(Origin::signed(alice_account_id.clone()), contract, codes[3].clone()));
+		assert_ok!(EVM::selfdestruct(Origin::signed(alice_account_id), contract));
+		for code_hash in code_hashes {
+			assert_eq!(CodeInfos::<Test>::get(&code_hash), None);
+			assert!(!Codes::<Test>::contains_key(&code_hash));
+		}
+		assert!(EVM::code_versions(&contract).is_empty());
+		assert_eq!(EVM::code_upgrades(&contract), None);
+	});
+}
+
+#[test]
+fn mirrored_nft_contract_is_deployed_once() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(primitives::MIRRORED_NFT_PREDEPLOY_ADDRESS);
+		assert_eq!(EVM::code_at_address(&address).into_inner(), mirrored_nft::CODE.to_vec());
+		let contract_info = EVM::accounts(address).unwrap().contract_info.unwrap();
+		assert_eq!(contract_info.maintainer, alice());
+		assert!(contract_info.deployed);
+
+		// the runtime upgrade keeps the deployed contract
+		assert_eq!(
+			<EVM as Hooks<u64>>::on_runtime_upgrade(),
+			<Test as frame_system::Config>::DbWeight::get().reads(1)
+		);
+		assert_eq!(CodeInfos::<Test>::get(&code_hash(&mirrored_nft::CODE)).unwrap().ref_count, 1);
+	});
+}
+
+/// Assemble the `mirrored_nft.asm` syntax: `name:` is a labelled
+/// `JUMPDEST`, `PUSH2 @name` pushes the offset of the label.
+fn assemble(source: &str) -> Vec<u8> {
+	fn opcode(mnemonic: &str) -> u8 {
+		let numbered = |prefix: &str, base: u8| {
+			mnemonic
+				.strip_prefix(prefix)
+				.and_then(|n| n.parse::<u8>().ok())
+				.map(|n| base + n - 1)
+		};
+		if let Some(op) = numbered("PUSH", 0x60)
+			.or_else(|| numbered("DUP", 0x80))
+			.or_else(|| numbered("SWAP", 0x90))
+		{
+			return op;
+		}
+		match mnemonic {
+			"STOP" => 0x00,
+			"ADD" => 0x01,
+			"SUB" => 0x03,
+			"LT" => 0x10,
+			"GT" => 0x11,
+			"EQ" => 0x14,
+			"ISZERO" => 0x15,
+			"AND" => 0x16,
+			"OR" => 0x17,
+			"SHL" => 0x1b,
+			"SHR" => 0x1c,
+			"ADDRESS" => 0x30,
+			"CALLER" => 0x33,
+			"CALLVALUE" => 0x34,
+			"CALLDATALOAD" => 0x35,
+			"CALLDATASIZE" => 0x36,
+			"CALLDATACOPY" => 0x37,
+			"EXTCODESIZE" => 0x3b,
+			"RETURNDATASIZE" => 0x3d,
+			"RETURNDATACOPY" => 0x3e,
+			"MLOAD" => 0x51,
+			"MSTORE" => 0x52,
+			"JUMP" => 0x56,
+			"JUMPI" => 0x57,
+			"GAS" => 0x5a,
+			"LOG3" => 0xa3,
+			"LOG4" => 0xa4,
+			"CALL" => 0xf1,
+			"RETURN" => 0xf3,
+			"REVERT" => 0xfd,
+			_ => panic!("unknown mnemonic {}", mnemonic),
+		}
+	}
+
+	let lines = source
+		.lines()
+		.map(|line| line.split(';').next().unwrap().trim())
+		.filter(|line| !line.is_empty())
+		.collect::<Vec<_>>();
+
+	let mut labels = std::collections::BTreeMap::new();
+	let mut offset = 0;
+	for line in &lines {
+		if let Some(label) = line.strip_suffix(':') {
+			assert!(labels.insert(label, offset).is_none(), "duplicate label {}", label);
+			offset += 1;
+		} else {
+			for token in line.split_whitespace() {
+				offset += if token.starts_with("0x") {
+					(token.len() - 2) / 2
+				} else if token.starts_with('@') {
+					2
+				} else {
+					1
+				};
+			}
+		}
+	}
+
+	let mut code = Vec::new();
+	for line in &lines {
+		if line.ends_with(':') {
+			code.push(0x5b);
+			continue;
+		}
+		for token in line.split_whitespace() {
+			if let Some(label) = token.strip_prefix('@') {
+				let offset: usize = *labels.get(label).unwrap_or_else(|| panic!("unknown label {}", label));
+				code.extend_from_slice(&(offset as u16).to_be_bytes());
+			} else if let Some(hex) = token.strip_prefix("0x") {
+				code.extend(from_hex(hex).unwrap());
+			} else {
+				code.push(opcode(token));
+			}
+		}
+	}
+	code
+}
+
+#[test]
+fn mirrored_nft_code_is_assembled_from_source() {
+	assert_eq!(assemble(include_str!("mirrored_nft.asm")), mirrored_nft::CODE.to_vec());
+}
+
+#[test]
+fn max_gas_per_extrinsic_fits_the_max_extrinsic_weight() {
+	let max_gas = calibration::MaxGasPerExtrinsic::<Test>::get();
+	let weight_per_gas = calibration::WeightPerGas::<Test>::get();
+	let max_extrinsic = <Test as frame_system::Config>::BlockWeights::get()
+		.get(DispatchClass::Normal)
+		.max_extrinsic
+		.unwrap();
+	assert!(max_gas > 0);
+	assert!(max_gas * weight_per_gas <= max_extrinsic);
+	assert!((max_gas + 1) * weight_per_gas > max_extrinsic);
+}
+
+#[test]
+fn gas_limit_above_max_gas_per_extrinsic_should_fail() {
+	use frame_support::unsigned::ValidateUnsigned;
+
+	new_test_ext().execute_with(|| {
+		let gas_limit = MaxGasPerExtrinsic::get() + 1;
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let contract = deploy_runtime(&[0x00]);
+
+		assert_noop!(
+			EVM::call(
+				Origin::signed(alice_account_id.clone()),
+				contract,
+				vec![],
+				0,
+				gas_limit,
+				1000000,
+				vec![]
+			),
+			Error::<Test>::GasLimitTooHigh
+		);
+		assert_noop!(
+			EVM::create(
+				Origin::signed(alice_account_id.clone()),
+				calibration::init_code(&[0x00]),
+				0,
+				gas_limit,
+				1000000,
+				vec![]
+			),
+			Error::<Test>::GasLimitTooHigh
+		);
+		assert_noop!(
+			EVM::create2(
+				Origin::signed(alice_account_id),
+				calibration::init_code(&[0x00]),
+				H256::zero(),
+				0,
+				gas_limit,
+				1000000,
+				vec![]
+			),
+			Error::<Test>::GasLimitTooHigh
+		);
+
+		let secret = secp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+		let raw = eth_transaction(&secret, 1, 0, Some(contract), vec![], gas_limit);
+		assert_eq!(
+			EVM::validate_unsigned(TransactionSource::External, &crate::Call::<Test>::eth_transact(raw.clone())),
+			InvalidTransaction::ExhaustsResources.into()
+		);
+		assert_noop!(
+			EVM::eth_transact(Origin::none(), raw),
+			Error::<Test>::InvalidEthTransaction
+		);
+	});
+}
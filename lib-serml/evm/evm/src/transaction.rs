@@ -0,0 +1,123 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Raw Ethereum transactions, as sent by wallets to `eth_sendRawTransaction`.
+
+use crate::EvmAddress;
+use primitive_types::{H256, U256};
+use rlp::{DecoderError, Rlp, RlpStream};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryInto, prelude::*};
+
+/// A legacy Ethereum transaction, RLP encoded as
+/// `[nonce, gas_price, gas_limit, to, value, input, v, r, s]`.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct EthereumTransaction {
+	pub nonce: U256,
+	pub gas_price: U256,
+	pub gas_limit: u64,
+	/// The contract called, or `None` to create a contract.
+	pub action: Option<EvmAddress>,
+	pub value: U256,
+	pub input: Vec<u8>,
+	/// The chain id of an EIP-155 signature, `None` for pre-EIP-155 ones.
+	pub chain_id: Option<u64>,
+	/// `r`, `s` and the recovery id.
+	pub signature: [u8; 65],
+}
+
+impl EthereumTransaction {
+	pub fn decode(raw: &[u8]) -> Result<Self, DecoderError> {
+		let rlp = Rlp::new(raw);
+		if !rlp.is_list() {
+			return Err(DecoderError::RlpExpectedToBeList);
+		}
+		if rlp.item_count()? != 9 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+
+		let gas_limit: U256 = rlp.val_at(2)?;
+		let action = {
+			let to = rlp.at(3)?;
+			if to.is_empty() {
+				None
+			} else {
+				Some(to.as_val()?)
+			}
+		};
+
+		let v: u64 = rlp.val_at(6)?;
+		let (chain_id, recovery_id) = match v {
+			27 | 28 => (None, v - 27),
+			v if v >= 35 => (Some((v - 35) / 2), (v - 35) % 2),
+			_ => return Err(DecoderError::Custom("invalid signature v")),
+		};
+
+		let r: U256 = rlp.val_at(7)?;
+		let s: U256 = rlp.val_at(8)?;
+		let mut signature = [0u8; 65];
+		r.to_big_endian(&mut signature[0..32]);
+		s.to_big_endian(&mut signature[32..64]);
+		signature[64] = recovery_id as u8;
+
+		Ok(Self {
+			nonce: rlp.val_at(0)?,
+			gas_price: rlp.val_at(1)?,
+			gas_limit: gas_limit
+				.try_into()
+				.map_err(|_| DecoderError::Custom("gas limit overflow"))?,
+			action,
+			value: rlp.val_at(4)?,
+			input: rlp.val_at(5)?,
+			chain_id,
+			signature,
+		})
+	}
+
+	/// The hash signed by the sender, as specified by EIP-155.
+	pub fn signing_hash(&self) -> H256 {
+		let mut stream = RlpStream::new_list(if self.chain_id.is_some() { 9 } else { 6 });
+		stream.append(&self.nonce);
+		stream.append(&self.gas_price);
+		stream.append(&self.gas_limit);
+		match self.action {
+			Some(ref to) => stream.append(to),
+			None => stream.append_empty_data(),
+		};
+		stream.append(&self.value);
+		stream.append(&self.input);
+		if let Some(chain_id) = self.chain_id {
+			stream.append(&chain_id);
+			stream.append(&0u8);
+			stream.append(&0u8);
+		}
+		H256(keccak_256(&stream.out()))
+	}
+
+	/// Recover the address of the sender from the signature.
+	pub fn sender(&self) -> Option<EvmAddress> {
+		let pubkey = secp256k1_ecdsa_recover(&self.signature, &self.signing_hash().0).ok()?;
+		Some(EvmAddress::from_slice(&keccak_256(&pubkey)[12..]))
+	}
+}
+
+/// The hash Ethereum tooling knows a raw transaction by.
+pub fn transaction_hash(raw: &[u8]) -> H256 {
+	H256(keccak_256(raw))
+}
@@ -54,6 +54,13 @@ pub trait WeightInfo {
 	fn disable_contract_development() -> Weight;
 	fn set_code() -> Weight;
 	fn selfdestruct() -> Weight;
+	fn propose_code_upgrade() -> Weight;
+	fn propose_code_rollback() -> Weight;
+	fn cancel_code_upgrade() -> Weight;
+	fn activate_code_upgrade() -> Weight;
+	fn evm_arithmetic(n: u32, ) -> Weight;
+	fn evm_storage(n: u32, ) -> Weight;
+	fn evm_hashing(n: u32, ) -> Weight;
 }
 
 /// Weights for setheum_evm using the Setheum node and recommended hardware.
@@ -94,6 +101,49 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	fn propose_code_upgrade() -> Weight {
+		(104_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn propose_code_rollback() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_code_upgrade() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn activate_code_upgrade() -> Weight {
+		(129_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn evm_arithmetic(n: u32, ) -> Weight {
+		(62_000_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((1_742_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn evm_storage(n: u32, ) -> Weight {
+		(64_000_000 as Weight)
+			// Standard Error: 21_000
+			.saturating_add((318_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn evm_hashing(n: u32, ) -> Weight {
+		(61_000_000 as Weight)
+			// Standard Error: 17_000
+			.saturating_add((2_068_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -133,4 +183,47 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
+	fn propose_code_upgrade() -> Weight {
+		(104_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn propose_code_rollback() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_code_upgrade() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn activate_code_upgrade() -> Weight {
+		(129_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn evm_arithmetic(n: u32, ) -> Weight {
+		(62_000_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((1_742_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn evm_storage(n: u32, ) -> Weight {
+		(64_000_000 as Weight)
+			// Standard Error: 21_000
+			.saturating_add((318_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn evm_hashing(n: u32, ) -> Weight {
+		(61_000_000 as Weight)
+			// Standard Error: 17_000
+			.saturating_add((2_068_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
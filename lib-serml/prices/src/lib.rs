@@ -406,10 +406,12 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 			let token_0 = match symbol_0 {
 				DexShare::Token(token) => CurrencyId::Token(token),
 				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				DexShare::Fraction(vault_id) => CurrencyId::Fraction(vault_id),
 			};
 			let token_1 = match symbol_1 {
 				DexShare::Token(token) => CurrencyId::Token(token),
 				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				DexShare::Fraction(vault_id) => CurrencyId::Fraction(vault_id),
 			};
 			let (pool_0, _) = T::DEX::get_liquidity_pool(token_0, token_1);
 			let total_shares = T::Currency::total_issuance(currency_id);
@@ -453,10 +455,12 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 			let token_0 = match symbol_0 {
 				DexShare::Token(token) => CurrencyId::Token(token),
 				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				DexShare::Fraction(vault_id) => CurrencyId::Fraction(vault_id),
 			};
 			let token_1 = match symbol_1 {
 				DexShare::Token(token) => CurrencyId::Token(token),
 				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				DexShare::Fraction(vault_id) => CurrencyId::Fraction(vault_id),
 			};
 			let (pool_0, _) = T::DEX::get_liquidity_pool(token_0, token_1);
 			let total_shares = T::Currency::total_issuance(currency_id);
@@ -33,10 +33,10 @@ use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::{Amount, Balance, BlockNumber, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
+	traits::{AccountIdConversion, One, Saturating, Zero},
 	DispatchError, DispatchResult, FixedPointNumber,
 };
-use support::{SerpTreasury, DEXManager, Ratio};
+use support::{CashDropHandler, SerpTreasury, DEXManager, Ratio};
 mod mock;
 mod tests;
 pub mod weights;
@@ -123,7 +123,15 @@ pub mod module {
 	pub enum Error<T> {
 		/// The Stablecoin Price is stable and indifferent from peg
 		/// therefore cannot serp
-		PriceIsStableCannotSerp
+		PriceIsStableCannotSerp,
+		/// Invalid currency type, only SettCurrencies are accepted
+		InvalidCurrencyType,
+		/// Cashdrop is not enabled for the currency
+		CashDropNotEnabled,
+		/// The transfer amount is below the minimum claimable transfer
+		BelowMinimumClaimableTransfer,
+		/// Transfers to self cannot claim cashdrop
+		CashDropSelfTransfer,
 	}
 
 	#[pallet::event]
@@ -138,6 +146,11 @@ pub mod module {
 		CurrencySerpedUp(Balance, CurrencyId),
 		/// Currency SerpDown has been triggered successfully.
 		CurrencySerpDownTriggered(Balance, CurrencyId),
+		/// The minimum claimable transfer for cashdrop of the currency
+		/// updated, `None` disables cashdrop. \[currency_id, minimum_amount\]
+		MinimumClaimableTransferUpdated(CurrencyId, Option<Balance>),
+		/// Cashdrop claimed on transfer. \[who, currency_id, amount\]
+		CashDropClaimed(T::AccountId, CurrencyId, Balance),
 	}
 
 	/// The maximum amount of reserve amount for sale per setter auction
@@ -145,6 +158,23 @@ pub mod module {
 	#[pallet::getter(fn expected_setter_auction_size)]
 	pub type ExpectedSetterAuctionSize<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
 
+	/// The minimum transfer amount to claim cashdrop per SettCurrency,
+	/// cashdrop is enabled for the currency only if set.
+	///
+	/// MinimumClaimableTransfer: CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_claimable_transfer)]
+	pub type MinimumClaimableTransfer<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// The transfer amount claimed for cashdrop by accounts, which the
+	/// cashdrop entitlement of the account is based on.
+	///
+	/// CashDropClaims: double_map AccountId, CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn cashdrop_claims)]
+	pub type CashDropClaims<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub expected_setter_auction_size: Vec<(CurrencyId, Balance)>,
@@ -238,6 +268,29 @@ pub mod module {
 			T::SerpAuctionManagerHandler::new_setter_auction(initial_price, currency_amount, accepted_currency)?;
 			Ok(().into())
 		}
+
+		/// Update the minimum transfer amount to claim cashdrop for
+		/// `currency_id`, `None` disables cashdrop of the currency.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_minimum_claimable_transfer())]
+		#[transactional]
+		pub fn set_minimum_claimable_transfer(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			minimum_amount: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::StableCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCurrencyType,
+			);
+			MinimumClaimableTransfer::<T>::mutate_exists(currency_id, |maybe_minimum| {
+				*maybe_minimum = minimum_amount;
+			});
+			Self::deposit_event(Event::MinimumClaimableTransferUpdated(currency_id, minimum_amount));
+			Ok(().into())
+		}
 	}
 }
 
@@ -505,3 +558,15 @@ impl GenesisConfig {
 		<Self as GenesisBuild<T>>::assimilate_storage(self, storage)
 	}
 }
+
+impl<T: Config> CashDropHandler<T::AccountId, CurrencyId, Balance> for Pallet<T> {
+	fn claim_cashdrop(currency_id: CurrencyId, from: &T::AccountId, to: &T::AccountId, amount: Balance) -> DispatchResult {
+		ensure!(from != to, Error::<T>::CashDropSelfTransfer);
+		let minimum_amount = Self::minimum_claimable_transfer(currency_id).ok_or(Error::<T>::CashDropNotEnabled)?;
+		ensure!(amount >= minimum_amount, Error::<T>::BelowMinimumClaimableTransfer);
+
+		CashDropClaims::<T>::mutate(from, currency_id, |claimed| *claimed = claimed.saturating_add(amount));
+		Self::deposit_event(Event::CashDropClaimed(from.clone(), currency_id, amount));
+		Ok(())
+	}
+}
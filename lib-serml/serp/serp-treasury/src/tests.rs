@@ -114,3 +114,69 @@ fn auction_serplus_works() {
 		assert_eq!(TOTAL_SERPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 1);
 	});
 }
+
+#[test]
+fn set_minimum_claimable_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			SerpTreasuryModule::set_minimum_claimable_transfer(Origin::signed(5), USDJ, Some(10)),
+			BadOrigin,
+		);
+		assert_noop!(
+			SerpTreasuryModule::set_minimum_claimable_transfer(Origin::signed(1), DNAR, Some(10)),
+			Error::<Runtime>::InvalidCurrencyType,
+		);
+
+		assert_eq!(SerpTreasuryModule::minimum_claimable_transfer(USDJ), None);
+		assert_ok!(SerpTreasuryModule::set_minimum_claimable_transfer(
+			Origin::signed(1),
+			USDJ,
+			Some(10)
+		));
+		System::assert_last_event(Event::serp_treasury(crate::Event::MinimumClaimableTransferUpdated(
+			USDJ,
+			Some(10),
+		)));
+		assert_eq!(SerpTreasuryModule::minimum_claimable_transfer(USDJ), Some(10));
+
+		assert_ok!(SerpTreasuryModule::set_minimum_claimable_transfer(
+			Origin::signed(1),
+			USDJ,
+			None
+		));
+		assert_eq!(SerpTreasuryModule::minimum_claimable_transfer(USDJ), None);
+	});
+}
+
+#[test]
+fn claim_cashdrop_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			SerpTreasuryModule::claim_cashdrop(USDJ, &ALICE, &BOB, 100),
+			Error::<Runtime>::CashDropNotEnabled,
+		);
+
+		assert_ok!(SerpTreasuryModule::set_minimum_claimable_transfer(
+			Origin::signed(1),
+			USDJ,
+			Some(10)
+		));
+		assert_noop!(
+			SerpTreasuryModule::claim_cashdrop(USDJ, &ALICE, &ALICE, 100),
+			Error::<Runtime>::CashDropSelfTransfer,
+		);
+		assert_noop!(
+			SerpTreasuryModule::claim_cashdrop(USDJ, &ALICE, &BOB, 9),
+			Error::<Runtime>::BelowMinimumClaimableTransfer,
+		);
+
+		assert_eq!(SerpTreasuryModule::cashdrop_claims(ALICE, USDJ), 0);
+		assert_ok!(SerpTreasuryModule::claim_cashdrop(USDJ, &ALICE, &BOB, 100));
+		System::assert_last_event(Event::serp_treasury(crate::Event::CashDropClaimed(ALICE, USDJ, 100)));
+		assert_ok!(SerpTreasuryModule::claim_cashdrop(USDJ, &ALICE, &BOB, 50));
+		assert_eq!(SerpTreasuryModule::cashdrop_claims(ALICE, USDJ), 150);
+		assert_eq!(SerpTreasuryModule::cashdrop_claims(BOB, USDJ), 0);
+	});
+}
@@ -50,6 +50,7 @@ pub trait WeightInfo {
 	fn auction_serplus() -> Weight;
 	fn auction_diamond() -> Weight;
 	fn auction_setter() -> Weight;
+	fn set_minimum_claimable_transfer() -> Weight;
 }
 
 /// Weights for serp_treasury using the Setheum node and recommended hardware.
@@ -70,6 +71,10 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(204 as Weight))
 	}
+	fn set_minimum_claimable_transfer() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -89,4 +94,8 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(204 as Weight))
 	}
+	fn set_minimum_claimable_transfer() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
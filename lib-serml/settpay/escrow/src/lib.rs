@@ -0,0 +1,524 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # SettPay Escrow Module
+//!
+//! ## Overview
+//!
+//! Invoices and escrow for SettPay merchant checkouts. A payee creates an
+//! invoice for an amount of any currency, the payer pays it, and the funds
+//! are kept in escrow by the module account.
+//!
+//! The payer releases the escrow to the payee on delivery, in full or in
+//! parts, and the payee can refund the payer. Once the escrow period has
+//! passed, the payee can claim what remains. Either party can raise a
+//! dispute, which the arbiter of the invoice resolves by splitting the
+//! escrow between them. A dispute not resolved within `DisputePeriod` can be
+//! settled by either party, refunding the escrow to the payer.
+//!
+//! Payments released to the payee are claimed as SettPay cashdrop eligible
+//! transfers of the payer.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use orml_utilities::with_transaction_result;
+use primitives::{Balance, CurrencyId, InvoiceId};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+use support::CashDropHandler;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The status of an invoice.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum InvoiceStatus {
+	/// The invoice is waiting for payment.
+	Unpaid,
+	/// The invoice is paid and the payment is in escrow.
+	Paid,
+	/// The escrow is disputed and waits for the arbiter.
+	Disputed,
+}
+
+/// An invoice of `payee`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Invoice<AccountId, BlockNumber> {
+	/// The account to be paid.
+	pub payee: AccountId,
+	/// The account to pay, anyone can pay the invoice if `None`.
+	pub payer: Option<AccountId>,
+	/// The account resolving disputes, the invoice cannot be disputed if
+	/// `None`.
+	pub arbiter: Option<AccountId>,
+	/// The currency of the invoice.
+	pub currency_id: CurrencyId,
+	/// The amount of the invoice.
+	pub amount: Balance,
+	/// The amount kept in escrow.
+	pub escrowed: Balance,
+	/// The blocks the payment is kept in escrow for after it is paid.
+	pub escrow_period: BlockNumber,
+	/// The block the payee can claim the escrow at, set on payment.
+	pub release_at: Option<BlockNumber>,
+	/// The block the dispute can be settled without the arbiter at, set on
+	/// dispute.
+	pub dispute_deadline: Option<BlockNumber>,
+	/// The status of the invoice.
+	pub status: InvoiceStatus,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to pay invoices and reserve invoice deposits with.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The native currency id, to reserve invoice deposits in.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The deposit reserved from the payee for every invoice.
+		#[pallet::constant]
+		type InvoiceDeposit: Get<Balance>;
+
+		/// The Escrow module id, keeps the escrowed payments.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The blocks the arbiter has to resolve a dispute, after which the
+		/// escrow can be refunded to the payer.
+		#[pallet::constant]
+		type DisputePeriod: Get<Self::BlockNumber>;
+
+		/// The SettPay cashdrop handler, claims the released payments.
+		type CashDropHandler: CashDropHandler<Self::AccountId, CurrencyId, Balance>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The invoice amount is zero.
+		InvalidAmount,
+		/// The payee, the payer and the arbiter of an invoice must be
+		/// different accounts.
+		InvalidParticipant,
+		/// No more invoice id is available.
+		NoAvailableInvoiceId,
+		/// The invoice does not exist.
+		InvoiceNotFound,
+		/// The caller is not allowed to do this on the invoice.
+		NoPermission,
+		/// The invoice is not in the required status.
+		InvalidStatus,
+		/// The invoice has no arbiter to resolve a dispute.
+		NoArbiter,
+		/// The escrow period of the invoice has not passed yet.
+		EscrowPeriodNotPassed,
+		/// The amount is zero or more than the escrow.
+		InvalidEscrowAmount,
+		/// The dispute period of the invoice has not passed yet.
+		DisputePeriodNotPassed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Invoice created. \[invoice_id, payee, currency_id, amount\]
+		InvoiceCreated(InvoiceId, T::AccountId, CurrencyId, Balance),
+		/// Invoice cancelled by the payee before payment. \[invoice_id\]
+		InvoiceCancelled(InvoiceId),
+		/// Invoice paid into escrow. \[invoice_id, payer, amount\]
+		InvoicePaid(InvoiceId, T::AccountId, Balance),
+		/// Escrowed payment released to the payee. \[invoice_id, payee,
+		/// amount\]
+		PaymentReleased(InvoiceId, T::AccountId, Balance),
+		/// Escrowed payment refunded to the payer. \[invoice_id, payer,
+		/// amount\]
+		PaymentRefunded(InvoiceId, T::AccountId, Balance),
+		/// Invoice disputed. \[invoice_id, who\]
+		InvoiceDisputed(InvoiceId, T::AccountId),
+		/// Dispute resolved by the arbiter. \[invoice_id, payee_amount,
+		/// payer_amount\]
+		DisputeResolved(InvoiceId, Balance, Balance),
+		/// Dispute not resolved within the dispute period, the escrow is
+		/// refunded to the payer. \[invoice_id, payer_amount\]
+		DisputeExpired(InvoiceId, Balance),
+		/// Invoice settled and its escrow emptied. \[invoice_id\]
+		InvoiceCompleted(InvoiceId),
+	}
+
+	/// The next invoice id.
+	#[pallet::storage]
+	#[pallet::getter(fn next_invoice_id)]
+	pub type NextInvoiceId<T: Config> = StorageValue<_, InvoiceId, ValueQuery>;
+
+	/// The invoices.
+	///
+	/// Invoices: map InvoiceId => Option<Invoice>
+	#[pallet::storage]
+	#[pallet::getter(fn invoices)]
+	pub type Invoices<T: Config> =
+		StorageMap<_, Twox64Concat, InvoiceId, Invoice<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an invoice of `amount` of `currency_id`, kept in escrow for
+		/// `escrow_period` blocks after payment.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payee.
+		///
+		/// - `payer`: the account to pay the invoice, anyone if `None`.
+		/// - `arbiter`: the account to resolve disputes, no disputes if
+		///   `None`.
+		#[pallet::weight(<T as Config>::WeightInfo::create_invoice())]
+		#[transactional]
+		pub fn create_invoice(
+			origin: OriginFor<T>,
+			payer: Option<<T::Lookup as StaticLookup>::Source>,
+			arbiter: Option<<T::Lookup as StaticLookup>::Source>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			escrow_period: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let payee = ensure_signed(origin)?;
+			let payer = payer.map(T::Lookup::lookup).transpose()?;
+			let arbiter = arbiter.map(T::Lookup::lookup).transpose()?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(
+				payer.as_ref() != Some(&payee) && arbiter.as_ref() != Some(&payee),
+				Error::<T>::InvalidParticipant
+			);
+			ensure!(arbiter.is_none() || arbiter != payer, Error::<T>::InvalidParticipant);
+
+			let invoice_id =
+				NextInvoiceId::<T>::try_mutate(|id| -> sp_std::result::Result<InvoiceId, DispatchError> {
+					let current_id = *id;
+					*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableInvoiceId)?;
+					Ok(current_id)
+				})?;
+			T::Currency::reserve(T::GetNativeCurrencyId::get(), &payee, T::InvoiceDeposit::get())?;
+
+			Invoices::<T>::insert(
+				invoice_id,
+				Invoice {
+					payee: payee.clone(),
+					payer,
+					arbiter,
+					currency_id,
+					amount,
+					escrowed: Zero::zero(),
+					escrow_period,
+					release_at: None,
+					dispute_deadline: None,
+					status: InvoiceStatus::Unpaid,
+				},
+			);
+			Self::deposit_event(Event::InvoiceCreated(invoice_id, payee, currency_id, amount));
+			Ok(().into())
+		}
+
+		/// Cancel an unpaid invoice.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payee.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_invoice())]
+		#[transactional]
+		pub fn cancel_invoice(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let invoice = Self::invoices(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(who == invoice.payee, Error::<T>::NoPermission);
+			ensure!(invoice.status == InvoiceStatus::Unpaid, Error::<T>::InvalidStatus);
+
+			Self::remove_invoice(invoice_id, &invoice);
+			Self::deposit_event(Event::InvoiceCancelled(invoice_id));
+			Ok(().into())
+		}
+
+		/// Pay an invoice, the payment is kept in escrow.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payer of
+		/// the invoice, or by anyone but the payee and the arbiter if the
+		/// invoice has no payer.
+		#[pallet::weight(<T as Config>::WeightInfo::pay_invoice())]
+		#[transactional]
+		pub fn pay_invoice(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Invoices::<T>::try_mutate_exists(invoice_id, |maybe_invoice| -> DispatchResult {
+				let invoice = maybe_invoice.as_mut().ok_or(Error::<T>::InvoiceNotFound)?;
+				ensure!(invoice.status == InvoiceStatus::Unpaid, Error::<T>::InvalidStatus);
+				ensure!(
+					invoice
+						.payer
+						.as_ref()
+						.map_or(who != invoice.payee, |payer| *payer == who),
+					Error::<T>::NoPermission
+				);
+				// the arbiter cannot resolve disputes on its own payment
+				ensure!(invoice.arbiter.as_ref() != Some(&who), Error::<T>::NoPermission);
+
+				T::Currency::transfer(invoice.currency_id, &who, &Self::account_id(), invoice.amount)?;
+				invoice.payer = Some(who.clone());
+				invoice.escrowed = invoice.amount;
+				invoice.release_at =
+					Some(<frame_system::Pallet<T>>::block_number().saturating_add(invoice.escrow_period));
+				invoice.status = InvoiceStatus::Paid;
+
+				Self::deposit_event(Event::InvoicePaid(invoice_id, who, invoice.amount));
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
+		/// Release `amount` of the escrow to the payee, on delivery of the
+		/// invoice in full or in part.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payer.
+		#[pallet::weight(<T as Config>::WeightInfo::release_payment())]
+		#[transactional]
+		pub fn release_payment(
+			origin: OriginFor<T>,
+			invoice_id: InvoiceId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut invoice = Self::invoices(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(invoice.payer.as_ref() == Some(&who), Error::<T>::NoPermission);
+			ensure!(invoice.status == InvoiceStatus::Paid, Error::<T>::InvalidStatus);
+
+			Self::do_release(invoice_id, &mut invoice, amount)?;
+			Self::complete_or_update(invoice_id, invoice);
+			Ok(().into())
+		}
+
+		/// Refund `amount` of the escrow to the payer.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payee.
+		#[pallet::weight(<T as Config>::WeightInfo::refund_payment())]
+		#[transactional]
+		pub fn refund_payment(
+			origin: OriginFor<T>,
+			invoice_id: InvoiceId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut invoice = Self::invoices(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(who == invoice.payee, Error::<T>::NoPermission);
+			ensure!(invoice.status == InvoiceStatus::Paid, Error::<T>::InvalidStatus);
+
+			Self::do_refund(invoice_id, &mut invoice, amount)?;
+			Self::complete_or_update(invoice_id, invoice);
+			Ok(().into())
+		}
+
+		/// Claim the remaining escrow after the escrow period has passed
+		/// without dispute.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payee.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_payment())]
+		#[transactional]
+		pub fn claim_payment(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut invoice = Self::invoices(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(who == invoice.payee, Error::<T>::NoPermission);
+			ensure!(invoice.status == InvoiceStatus::Paid, Error::<T>::InvalidStatus);
+			ensure!(
+				invoice.release_at.map_or(false, |release_at| release_at
+					<= <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::EscrowPeriodNotPassed
+			);
+
+			let amount = invoice.escrowed;
+			Self::do_release(invoice_id, &mut invoice, amount)?;
+			Self::complete_or_update(invoice_id, invoice);
+			Ok(().into())
+		}
+
+		/// Dispute a paid invoice, freezing its escrow until the arbiter
+		/// resolves it or the dispute period passes.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payee or
+		/// the payer.
+		#[pallet::weight(<T as Config>::WeightInfo::dispute_invoice())]
+		#[transactional]
+		pub fn dispute_invoice(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Invoices::<T>::try_mutate_exists(invoice_id, |maybe_invoice| -> DispatchResult {
+				let invoice = maybe_invoice.as_mut().ok_or(Error::<T>::InvoiceNotFound)?;
+				ensure!(
+					who == invoice.payee || invoice.payer.as_ref() == Some(&who),
+					Error::<T>::NoPermission
+				);
+				ensure!(invoice.status == InvoiceStatus::Paid, Error::<T>::InvalidStatus);
+				ensure!(invoice.arbiter.is_some(), Error::<T>::NoArbiter);
+
+				invoice.status = InvoiceStatus::Disputed;
+				invoice.dispute_deadline =
+					Some(<frame_system::Pallet<T>>::block_number().saturating_add(T::DisputePeriod::get()));
+				Self::deposit_event(Event::InvoiceDisputed(invoice_id, who));
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
+		/// Resolve a dispute, releasing `payee_amount` of the escrow to the
+		/// payee and refunding the rest to the payer.
+		///
+		/// The dispatch origin of this call must be `Signed` by the arbiter.
+		#[pallet::weight(<T as Config>::WeightInfo::resolve_dispute())]
+		#[transactional]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			invoice_id: InvoiceId,
+			#[pallet::compact] payee_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut invoice = Self::invoices(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(invoice.arbiter.as_ref() == Some(&who), Error::<T>::NoPermission);
+			ensure!(invoice.status == InvoiceStatus::Disputed, Error::<T>::InvalidStatus);
+			ensure!(payee_amount <= invoice.escrowed, Error::<T>::InvalidEscrowAmount);
+
+			let payer_amount = invoice.escrowed.saturating_sub(payee_amount);
+			if !payee_amount.is_zero() {
+				Self::do_release(invoice_id, &mut invoice, payee_amount)?;
+			}
+			if !payer_amount.is_zero() {
+				Self::do_refund(invoice_id, &mut invoice, payer_amount)?;
+			}
+
+			Self::deposit_event(Event::DisputeResolved(invoice_id, payee_amount, payer_amount));
+			Self::complete_or_update(invoice_id, invoice);
+			Ok(().into())
+		}
+
+		/// Settle a dispute the arbiter has not resolved within the dispute
+		/// period, refunding the escrow to the payer.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payee or
+		/// the payer.
+		#[pallet::weight(<T as Config>::WeightInfo::settle_expired_dispute())]
+		#[transactional]
+		pub fn settle_expired_dispute(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut invoice = Self::invoices(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(
+				who == invoice.payee || invoice.payer.as_ref() == Some(&who),
+				Error::<T>::NoPermission
+			);
+			ensure!(invoice.status == InvoiceStatus::Disputed, Error::<T>::InvalidStatus);
+			ensure!(
+				invoice
+					.dispute_deadline
+					.map_or(false, |deadline| deadline <= <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::DisputePeriodNotPassed
+			);
+
+			let payer_amount = invoice.escrowed;
+			Self::do_refund(invoice_id, &mut invoice, payer_amount)?;
+
+			Self::deposit_event(Event::DisputeExpired(invoice_id, payer_amount));
+			Self::complete_or_update(invoice_id, invoice);
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get account of Escrow module.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	fn do_release(
+		invoice_id: InvoiceId,
+		invoice: &mut Invoice<T::AccountId, T::BlockNumber>,
+		amount: Balance,
+	) -> DispatchResult {
+		ensure!(
+			!amount.is_zero() && amount <= invoice.escrowed,
+			Error::<T>::InvalidEscrowAmount
+		);
+		T::Currency::transfer(invoice.currency_id, &Self::account_id(), &invoice.payee, amount)?;
+		invoice.escrowed = invoice.escrowed.saturating_sub(amount);
+
+		// the payment counts as a SettPay transfer of the payer, an ineligible
+		// claim must not block the release, and is reverted.
+		if let Some(payer) = invoice.payer.as_ref() {
+			let _ = with_transaction_result(|| {
+				T::CashDropHandler::claim_cashdrop(invoice.currency_id, payer, &invoice.payee, amount)
+			});
+		}
+
+		Self::deposit_event(Event::PaymentReleased(invoice_id, invoice.payee.clone(), amount));
+		Ok(())
+	}
+
+	fn do_refund(
+		invoice_id: InvoiceId,
+		invoice: &mut Invoice<T::AccountId, T::BlockNumber>,
+		amount: Balance,
+	) -> DispatchResult {
+		ensure!(
+			!amount.is_zero() && amount <= invoice.escrowed,
+			Error::<T>::InvalidEscrowAmount
+		);
+		let payer = invoice.payer.clone().ok_or(Error::<T>::InvalidStatus)?;
+		T::Currency::transfer(invoice.currency_id, &Self::account_id(), &payer, amount)?;
+		invoice.escrowed = invoice.escrowed.saturating_sub(amount);
+
+		Self::deposit_event(Event::PaymentRefunded(invoice_id, payer, amount));
+		Ok(())
+	}
+
+	fn complete_or_update(invoice_id: InvoiceId, invoice: Invoice<T::AccountId, T::BlockNumber>) {
+		if invoice.escrowed.is_zero() {
+			Self::remove_invoice(invoice_id, &invoice);
+			Self::deposit_event(Event::InvoiceCompleted(invoice_id));
+		} else {
+			Invoices::<T>::insert(invoice_id, invoice);
+		}
+	}
+
+	fn remove_invoice(invoice_id: InvoiceId, invoice: &Invoice<T::AccountId, T::BlockNumber>) {
+		T::Currency::unreserve(T::GetNativeCurrencyId::get(), &invoice.payee, T::InvoiceDeposit::get());
+		Invoices::<T>::remove(invoice_id);
+	}
+}
@@ -0,0 +1,175 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the escrow module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, parameter_types};
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CAROL: AccountId = 3;
+pub const DAVE: AccountId = 4;
+
+pub const DNAR: CurrencyId = CurrencyId::Token(TokenSymbol::DNAR);
+pub const USDJ: CurrencyId = CurrencyId::Token(TokenSymbol::USDJ);
+
+mod escrow {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = DNAR;
+	pub const InvoiceDeposit: Balance = 10;
+	pub const EscrowPalletId: PalletId = PalletId(*b"set/escr");
+	pub const DisputePeriod: BlockNumber = 20;
+}
+
+thread_local! {
+	pub static CASHDROP_CLAIMS: RefCell<Vec<(CurrencyId, AccountId, AccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct MockCashDropHandler;
+impl CashDropHandler<AccountId, CurrencyId, Balance> for MockCashDropHandler {
+	fn claim_cashdrop(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		CASHDROP_CLAIMS.with(|v| v.borrow_mut().push((currency_id, *from, *to, amount)));
+		Ok(())
+	}
+}
+
+pub fn cashdrop_claims() -> Vec<(CurrencyId, AccountId, AccountId, Balance)> {
+	CASHDROP_CLAIMS.with(|v| v.borrow().clone())
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Tokens;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type InvoiceDeposit = InvoiceDeposit;
+	type PalletId = EscrowPalletId;
+	type DisputePeriod = DisputePeriod;
+	type CashDropHandler = MockCashDropHandler;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		EscrowModule: escrow::{Pallet, Storage, Call, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+	}
+);
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![
+				(ALICE, DNAR, 1000),
+				(ALICE, USDJ, 1000),
+				(BOB, DNAR, 1000),
+				(BOB, USDJ, 1000),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,319 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the escrow module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn create_and_pay_invoice() {
+	let invoice_id = EscrowModule::next_invoice_id();
+	assert_ok!(EscrowModule::create_invoice(
+		Origin::signed(ALICE),
+		Some(BOB),
+		Some(CAROL),
+		USDJ,
+		100,
+		10
+	));
+	assert_ok!(EscrowModule::pay_invoice(Origin::signed(BOB), invoice_id));
+}
+
+#[test]
+fn create_invoice_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EscrowModule::create_invoice(Origin::signed(ALICE), Some(BOB), None, USDJ, 0, 10),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			EscrowModule::create_invoice(Origin::signed(ALICE), Some(ALICE), None, USDJ, 100, 10),
+			Error::<Runtime>::InvalidParticipant
+		);
+		assert_noop!(
+			EscrowModule::create_invoice(Origin::signed(ALICE), None, Some(ALICE), USDJ, 100, 10),
+			Error::<Runtime>::InvalidParticipant
+		);
+		assert_noop!(
+			EscrowModule::create_invoice(Origin::signed(ALICE), Some(BOB), Some(BOB), USDJ, 100, 10),
+			Error::<Runtime>::InvalidParticipant
+		);
+
+		assert_ok!(EscrowModule::create_invoice(
+			Origin::signed(ALICE),
+			Some(BOB),
+			Some(CAROL),
+			USDJ,
+			100,
+			10
+		));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceCreated(0, ALICE, USDJ, 100)));
+		assert_eq!(EscrowModule::next_invoice_id(), 1);
+		assert_eq!(
+			EscrowModule::invoices(0),
+			Some(Invoice {
+				payee: ALICE,
+				payer: Some(BOB),
+				arbiter: Some(CAROL),
+				currency_id: USDJ,
+				amount: 100,
+				escrowed: 0,
+				escrow_period: 10,
+				release_at: None,
+				dispute_deadline: None,
+				status: InvoiceStatus::Unpaid,
+			})
+		);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 10);
+	});
+}
+
+#[test]
+fn cancel_invoice_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EscrowModule::create_invoice(
+			Origin::signed(ALICE),
+			None,
+			None,
+			USDJ,
+			100,
+			10
+		));
+		assert_noop!(
+			EscrowModule::cancel_invoice(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::cancel_invoice(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceCancelled(0)));
+		assert_eq!(EscrowModule::invoices(0), None);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 0);
+
+		create_and_pay_invoice();
+		assert_noop!(
+			EscrowModule::cancel_invoice(Origin::signed(ALICE), 1),
+			Error::<Runtime>::InvalidStatus
+		);
+	});
+}
+
+#[test]
+fn pay_invoice_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EscrowModule::create_invoice(
+			Origin::signed(ALICE),
+			Some(BOB),
+			None,
+			USDJ,
+			100,
+			10
+		));
+		assert_noop!(
+			EscrowModule::pay_invoice(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::pay_invoice(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::escrow(crate::Event::InvoicePaid(0, BOB, 100)));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 900);
+		assert_eq!(Tokens::free_balance(USDJ, &EscrowModule::account_id()), 100);
+		let invoice = EscrowModule::invoices(0).unwrap();
+		assert_eq!(invoice.status, InvoiceStatus::Paid);
+		assert_eq!(invoice.escrowed, 100);
+		assert_eq!(invoice.release_at, Some(11));
+		assert_noop!(
+			EscrowModule::pay_invoice(Origin::signed(BOB), 0),
+			Error::<Runtime>::InvalidStatus
+		);
+
+		// an invoice without payer can be paid by anyone except the payee
+		assert_ok!(EscrowModule::create_invoice(
+			Origin::signed(ALICE),
+			None,
+			None,
+			USDJ,
+			50,
+			10
+		));
+		assert_noop!(
+			EscrowModule::pay_invoice(Origin::signed(ALICE), 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::pay_invoice(Origin::signed(BOB), 1));
+		assert_eq!(EscrowModule::invoices(1).unwrap().payer, Some(BOB));
+
+		// nor by the arbiter
+		assert_ok!(EscrowModule::create_invoice(
+			Origin::signed(ALICE),
+			None,
+			Some(CAROL),
+			USDJ,
+			50,
+			10
+		));
+		assert_noop!(
+			EscrowModule::pay_invoice(Origin::signed(CAROL), 2),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::pay_invoice(Origin::signed(BOB), 2));
+	});
+}
+
+#[test]
+fn release_and_refund_payment_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_and_pay_invoice();
+		assert_noop!(
+			EscrowModule::release_payment(Origin::signed(ALICE), 0, 40),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EscrowModule::release_payment(Origin::signed(BOB), 0, 101),
+			Error::<Runtime>::InvalidEscrowAmount
+		);
+
+		assert_ok!(EscrowModule::release_payment(Origin::signed(BOB), 0, 40));
+		System::assert_last_event(Event::escrow(crate::Event::PaymentReleased(0, ALICE, 40)));
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1040);
+		assert_eq!(EscrowModule::invoices(0).unwrap().escrowed, 60);
+		assert_eq!(cashdrop_claims(), vec![(USDJ, BOB, ALICE, 40)]);
+
+		assert_noop!(
+			EscrowModule::refund_payment(Origin::signed(BOB), 0, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::refund_payment(Origin::signed(ALICE), 0, 10));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 910);
+		assert_eq!(EscrowModule::invoices(0).unwrap().escrowed, 50);
+
+		assert_ok!(EscrowModule::release_payment(Origin::signed(BOB), 0, 50));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceCompleted(0)));
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1090);
+		assert_eq!(Tokens::free_balance(USDJ, &EscrowModule::account_id()), 0);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 0);
+		assert_eq!(EscrowModule::invoices(0), None);
+	});
+}
+
+#[test]
+fn claim_payment_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_and_pay_invoice();
+		assert_noop!(
+			EscrowModule::claim_payment(Origin::signed(ALICE), 0),
+			Error::<Runtime>::EscrowPeriodNotPassed
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			EscrowModule::claim_payment(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::claim_payment(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceCompleted(0)));
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1100);
+		assert_eq!(EscrowModule::invoices(0), None);
+	});
+}
+
+#[test]
+fn dispute_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EscrowModule::create_invoice(
+			Origin::signed(ALICE),
+			Some(BOB),
+			None,
+			USDJ,
+			100,
+			10
+		));
+		assert_ok!(EscrowModule::pay_invoice(Origin::signed(BOB), 0));
+		assert_noop!(
+			EscrowModule::dispute_invoice(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoArbiter
+		);
+
+		create_and_pay_invoice();
+		assert_noop!(
+			EscrowModule::dispute_invoice(Origin::signed(DAVE), 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::dispute_invoice(Origin::signed(BOB), 1));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceDisputed(1, BOB)));
+		assert_eq!(EscrowModule::invoices(1).unwrap().status, InvoiceStatus::Disputed);
+		assert_eq!(EscrowModule::invoices(1).unwrap().dispute_deadline, Some(21));
+
+		// the escrow is frozen while disputed
+		System::set_block_number(20);
+		assert_noop!(
+			EscrowModule::claim_payment(Origin::signed(ALICE), 1),
+			Error::<Runtime>::InvalidStatus
+		);
+		assert_noop!(
+			EscrowModule::release_payment(Origin::signed(BOB), 1, 10),
+			Error::<Runtime>::InvalidStatus
+		);
+
+		assert_noop!(
+			EscrowModule::resolve_dispute(Origin::signed(ALICE), 1, 30),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EscrowModule::resolve_dispute(Origin::signed(CAROL), 1, 101),
+			Error::<Runtime>::InvalidEscrowAmount
+		);
+		assert_ok!(EscrowModule::resolve_dispute(Origin::signed(CAROL), 1, 30));
+		System::assert_has_event(Event::escrow(crate::Event::DisputeResolved(1, 30, 70)));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceCompleted(1)));
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1030);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 870);
+		assert_eq!(EscrowModule::invoices(1), None);
+	});
+}
+
+#[test]
+fn expired_dispute_is_refunded() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_and_pay_invoice();
+		assert_noop!(
+			EscrowModule::settle_expired_dispute(Origin::signed(BOB), 0),
+			Error::<Runtime>::InvalidStatus
+		);
+		assert_ok!(EscrowModule::dispute_invoice(Origin::signed(ALICE), 0));
+
+		System::set_block_number(20);
+		assert_noop!(
+			EscrowModule::settle_expired_dispute(Origin::signed(BOB), 0),
+			Error::<Runtime>::DisputePeriodNotPassed
+		);
+
+		System::set_block_number(21);
+		assert_noop!(
+			EscrowModule::settle_expired_dispute(Origin::signed(DAVE), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EscrowModule::settle_expired_dispute(Origin::signed(BOB), 0));
+		System::assert_has_event(Event::escrow(crate::Event::DisputeExpired(0, 100)));
+		System::assert_last_event(Event::escrow(crate::Event::InvoiceCompleted(0)));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1000);
+		assert_eq!(EscrowModule::invoices(0), None);
+		assert_eq!(cashdrop_claims(), vec![]);
+	});
+}
@@ -0,0 +1,464 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # SettPay Standing Orders Module
+//!
+//! ## Overview
+//!
+//! Standing orders are scheduled and recurring payments of SettPay. A payer
+//! authorizes a recipient to be paid `amount` of a currency every `period`
+//! blocks, up to `max_total` in total and until `expiry`.
+//!
+//! Orders in `Push` mode are executed by the module in `on_initialize`, at
+//! most `MaxPaymentsPerBlock` per block, the rest are deferred to the
+//! following blocks. A failed payment is retried after `RetryPeriod` blocks up to
+//! `MaxRetries` times, after which the payment of the period is skipped.
+//! Orders in `Pull` mode are collected by the recipient, who may collect all
+//! the payments due so far at once.
+//!
+//! Payments go through `MultiCurrency`, so standing orders work with any
+//! currency including ERC20 currency ids.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use orml_utilities::with_transaction_result;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{One, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of a standing order.
+pub type OrderId = u64;
+
+/// How the payments of a standing order are made.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum PaymentMode {
+	/// Paid to the recipient by the module when due.
+	Push,
+	/// Collected by the recipient when due.
+	Pull,
+}
+
+/// A standing order authorized by `payer`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct StandingOrder<AccountId, BlockNumber> {
+	/// The account paying the order.
+	pub payer: AccountId,
+	/// The account paid by the order.
+	pub recipient: AccountId,
+	/// The currency of the payments.
+	pub currency_id: CurrencyId,
+	/// The amount paid every period.
+	pub amount: Balance,
+	/// The payment period in blocks.
+	pub period: BlockNumber,
+	/// How the payments are made.
+	pub mode: PaymentMode,
+	/// The block at which the next payment is due.
+	pub next_payment_at: BlockNumber,
+	/// The block at which a `Push` order is scheduled to be executed, it is
+	/// after `next_payment_at` when the payment is retried.
+	pub due_at: BlockNumber,
+	/// No payment is due at or after this block.
+	pub expiry: BlockNumber,
+	/// The maximum total amount to pay.
+	pub max_total: Balance,
+	/// The total amount paid so far.
+	pub total_paid: Balance,
+	/// The failed attempts of the due payment.
+	pub failed_attempts: u32,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to make payments and reserve order deposits with.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The native currency id, to reserve order deposits in.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The deposit reserved from the payer for every standing order.
+		#[pallet::constant]
+		type OrderDeposit: Get<Balance>;
+
+		/// The maximum number of payments executed in a block.
+		#[pallet::constant]
+		type MaxPaymentsPerBlock: Get<u32>;
+
+		/// The maximum retries of a failed payment.
+		#[pallet::constant]
+		type MaxRetries: Get<u32>;
+
+		/// The blocks to wait before retrying a failed payment.
+		#[pallet::constant]
+		type RetryPeriod: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The payment amount is zero or above the maximum total.
+		InvalidAmount,
+		/// The payment period is zero.
+		InvalidPeriod,
+		/// The order expires before its first payment.
+		InvalidExpiry,
+		/// The payer cannot pay itself.
+		InvalidRecipient,
+		/// No more order id is available.
+		NoAvailableOrderId,
+		/// The standing order does not exist.
+		OrderNotFound,
+		/// The caller is neither the payer nor the recipient.
+		NoPermission,
+		/// The standing order is not collected by the recipient.
+		NotPullOrder,
+		/// No payment is due yet.
+		NothingDue,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Standing order created. \[order_id, payer, recipient, currency_id,
+		/// amount, period\]
+		StandingOrderCreated(OrderId, T::AccountId, T::AccountId, CurrencyId, Balance, T::BlockNumber),
+		/// Standing order cancelled. \[order_id, who\]
+		StandingOrderCancelled(OrderId, T::AccountId),
+		/// Standing order has paid its maximum total or expired. \[order_id\]
+		StandingOrderCompleted(OrderId),
+		/// Payment of standing order made. \[order_id, payer, recipient,
+		/// currency_id, amount\]
+		PaymentExecuted(OrderId, T::AccountId, T::AccountId, CurrencyId, Balance),
+		/// Payment of standing order failed. \[order_id, error,
+		/// failed_attempts\]
+		PaymentFailed(OrderId, DispatchError, u32),
+		/// Payment of standing order skipped after the maximum retries.
+		/// \[order_id\]
+		PaymentSkipped(OrderId),
+	}
+
+	/// The next standing order id.
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The standing orders.
+	///
+	/// StandingOrders: map OrderId => Option<StandingOrder>
+	#[pallet::storage]
+	#[pallet::getter(fn standing_orders)]
+	pub type StandingOrders<T: Config> =
+		StorageMap<_, Twox64Concat, OrderId, StandingOrder<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// The push orders to pay at the block.
+	///
+	/// DuePayments: double_map BlockNumber, OrderId => ()
+	#[pallet::storage]
+	#[pallet::getter(fn due_payments)]
+	pub type DuePayments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, OrderId, (), OptionQuery>;
+
+	/// The earliest block whose due push payments have not all been
+	/// executed, because of `MaxPaymentsPerBlock`.
+	///
+	/// DuePaymentsCursor: Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn due_payments_cursor)]
+	pub type DuePaymentsCursor<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Execute the push payments due until `now`, starting from the
+		/// earliest deferred ones. At most `MaxPaymentsPerBlock` payments
+		/// are executed and as many blocks scanned, the rest are deferred to
+		/// the following blocks.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let max_payments = T::MaxPaymentsPerBlock::get();
+			let mut block = Self::due_payments_cursor().map_or(now, |cursor| cursor.min(now));
+			let mut count: u32 = 0;
+			let mut scanned: u32 = 0;
+
+			let cursor = loop {
+				let due: Vec<OrderId> = DuePayments::<T>::iter_prefix(block)
+					.map(|(order_id, _)| order_id)
+					.take(max_payments.saturating_sub(count) as usize)
+					.collect();
+				for order_id in due {
+					DuePayments::<T>::remove(block, order_id);
+					Self::execute_push_payment(order_id, now);
+					count += 1;
+				}
+
+				if count >= max_payments {
+					break Some(block);
+				}
+				if block >= now {
+					break None;
+				}
+				scanned += 1;
+				block = block.saturating_add(One::one());
+				if scanned >= max_payments {
+					break Some(block);
+				}
+			};
+			match cursor {
+				Some(block) => DuePaymentsCursor::<T>::put(block),
+				None => DuePaymentsCursor::<T>::kill(),
+			}
+
+			T::WeightInfo::on_initialize(count).saturating_add(T::DbWeight::get().reads(scanned.into()))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a standing order paying `amount` of `currency_id` to
+		/// `recipient` every `period` blocks, starting one period from now.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payer.
+		///
+		/// - `max_total`: the maximum total amount to pay.
+		/// - `expiry`: no payment is due at or after this block.
+		/// - `mode`: whether the module pays or the recipient collects.
+		#[pallet::weight(<T as Config>::WeightInfo::create_standing_order())]
+		#[transactional]
+		pub fn create_standing_order(
+			origin: OriginFor<T>,
+			recipient: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			period: T::BlockNumber,
+			#[pallet::compact] max_total: Balance,
+			expiry: T::BlockNumber,
+			mode: PaymentMode,
+		) -> DispatchResultWithPostInfo {
+			let payer = ensure_signed(origin)?;
+			let recipient = T::Lookup::lookup(recipient)?;
+			ensure!(payer != recipient, Error::<T>::InvalidRecipient);
+			ensure!(!amount.is_zero() && amount <= max_total, Error::<T>::InvalidAmount);
+			ensure!(!period.is_zero(), Error::<T>::InvalidPeriod);
+
+			let next_payment_at = <frame_system::Pallet<T>>::block_number().saturating_add(period);
+			ensure!(expiry > next_payment_at, Error::<T>::InvalidExpiry);
+
+			let order_id = NextOrderId::<T>::try_mutate(|id| -> sp_std::result::Result<OrderId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableOrderId)?;
+				Ok(current_id)
+			})?;
+			T::Currency::reserve(T::GetNativeCurrencyId::get(), &payer, T::OrderDeposit::get())?;
+
+			StandingOrders::<T>::insert(
+				order_id,
+				StandingOrder {
+					payer: payer.clone(),
+					recipient: recipient.clone(),
+					currency_id,
+					amount,
+					period,
+					mode,
+					next_payment_at,
+					due_at: next_payment_at,
+					expiry,
+					max_total,
+					total_paid: Zero::zero(),
+					failed_attempts: 0,
+				},
+			);
+			if mode == PaymentMode::Push {
+				DuePayments::<T>::insert(next_payment_at, order_id, ());
+			}
+
+			Self::deposit_event(Event::StandingOrderCreated(
+				order_id,
+				payer,
+				recipient,
+				currency_id,
+				amount,
+				period,
+			));
+			Ok(().into())
+		}
+
+		/// Cancel a standing order and unreserve its deposit.
+		///
+		/// The dispatch origin of this call must be `Signed` by the payer or
+		/// the recipient of the order.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_standing_order())]
+		#[transactional]
+		pub fn cancel_standing_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order = Self::standing_orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(who == order.payer || who == order.recipient, Error::<T>::NoPermission);
+
+			Self::remove_order(order_id, &order);
+			Self::deposit_event(Event::StandingOrderCancelled(order_id, who));
+			Ok(().into())
+		}
+
+		/// Collect all payments due so far of a `Pull` standing order.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// recipient of the order.
+		#[pallet::weight(<T as Config>::WeightInfo::collect_payment())]
+		#[transactional]
+		pub fn collect_payment(origin: OriginFor<T>, order_id: OrderId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut order = Self::standing_orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(who == order.recipient, Error::<T>::NoPermission);
+			ensure!(order.mode == PaymentMode::Pull, Error::<T>::NotPullOrder);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				order.next_payment_at <= now && order.next_payment_at < order.expiry,
+				Error::<T>::NothingDue
+			);
+
+			// all periods due until now, excluding those at or after expiry
+			let last_due_at = now.min(order.expiry.saturating_sub(One::one()));
+			let periods = (last_due_at - order.next_payment_at) / order.period + One::one();
+			let amount = order
+				.amount
+				.saturating_mul(periods.unique_saturated_into())
+				.min(order.max_total.saturating_sub(order.total_paid));
+
+			T::Currency::transfer(order.currency_id, &order.payer, &order.recipient, amount)?;
+			order.total_paid = order.total_paid.saturating_add(amount);
+			order.next_payment_at = order
+				.next_payment_at
+				.saturating_add(order.period.saturating_mul(periods));
+			Self::deposit_event(Event::PaymentExecuted(
+				order_id,
+				order.payer.clone(),
+				order.recipient.clone(),
+				order.currency_id,
+				amount,
+			));
+
+			if Self::is_completed(&order) {
+				Self::remove_order(order_id, &order);
+				Self::deposit_event(Event::StandingOrderCompleted(order_id));
+			} else {
+				StandingOrders::<T>::insert(order_id, order);
+			}
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Pay the due payment of a `Push` standing order, and schedule the
+	/// next payment or retry.
+	fn execute_push_payment(order_id: OrderId, now: T::BlockNumber) {
+		// the order may have been cancelled since scheduled
+		let mut order = match Self::standing_orders(order_id) {
+			Some(order) => order,
+			None => return,
+		};
+
+		if now >= order.expiry {
+			Self::remove_order(order_id, &order);
+			Self::deposit_event(Event::StandingOrderCompleted(order_id));
+			return;
+		}
+
+		let amount = order.amount.min(order.max_total.saturating_sub(order.total_paid));
+		match with_transaction_result(|| -> DispatchResult {
+			T::Currency::transfer(order.currency_id, &order.payer, &order.recipient, amount)
+		}) {
+			Ok(()) => {
+				order.total_paid = order.total_paid.saturating_add(amount);
+				order.next_payment_at = order.next_payment_at.saturating_add(order.period);
+				order.failed_attempts = 0;
+				Self::deposit_event(Event::PaymentExecuted(
+					order_id,
+					order.payer.clone(),
+					order.recipient.clone(),
+					order.currency_id,
+					amount,
+				));
+			}
+			Err(e) => {
+				order.failed_attempts = order.failed_attempts.saturating_add(1);
+				Self::deposit_event(Event::PaymentFailed(order_id, e, order.failed_attempts));
+
+				if order.failed_attempts <= T::MaxRetries::get() {
+					order.due_at = now.saturating_add(T::RetryPeriod::get());
+					DuePayments::<T>::insert(order.due_at, order_id, ());
+					StandingOrders::<T>::insert(order_id, order);
+					return;
+				}
+
+				order.next_payment_at = order.next_payment_at.saturating_add(order.period);
+				order.failed_attempts = 0;
+				Self::deposit_event(Event::PaymentSkipped(order_id));
+			}
+		}
+
+		if Self::is_completed(&order) {
+			Self::remove_order(order_id, &order);
+			Self::deposit_event(Event::StandingOrderCompleted(order_id));
+		} else {
+			// payments missed by retries are due right away
+			order.due_at = order.next_payment_at.max(now.saturating_add(One::one()));
+			DuePayments::<T>::insert(order.due_at, order_id, ());
+			StandingOrders::<T>::insert(order_id, order);
+		}
+	}
+
+	fn is_completed(order: &StandingOrder<T::AccountId, T::BlockNumber>) -> bool {
+		order.total_paid >= order.max_total || order.next_payment_at >= order.expiry
+	}
+
+	/// Remove the standing order with its scheduled payment, and unreserve
+	/// its deposit.
+	fn remove_order(order_id: OrderId, order: &StandingOrder<T::AccountId, T::BlockNumber>) {
+		T::Currency::unreserve(T::GetNativeCurrencyId::get(), &order.payer, T::OrderDeposit::get());
+		if order.mode == PaymentMode::Push {
+			DuePayments::<T>::remove(order.due_at, order_id);
+		}
+		StandingOrders::<T>::remove(order_id);
+	}
+}
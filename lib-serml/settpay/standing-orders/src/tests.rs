@@ -0,0 +1,345 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the standing orders module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn due_order_ids(block: BlockNumber) -> Vec<OrderId> {
+	let mut order_ids: Vec<OrderId> = DuePayments::<Runtime>::iter_prefix(block)
+		.map(|(order_id, _)| order_id)
+		.collect();
+	order_ids.sort_unstable();
+	order_ids
+}
+
+#[test]
+fn create_standing_order_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StandingOrdersModule::create_standing_order(
+				Origin::signed(ALICE),
+				ALICE,
+				USDJ,
+				100,
+				10,
+				500,
+				100,
+				PaymentMode::Push
+			),
+			Error::<Runtime>::InvalidRecipient
+		);
+		assert_noop!(
+			StandingOrdersModule::create_standing_order(
+				Origin::signed(ALICE),
+				BOB,
+				USDJ,
+				0,
+				10,
+				500,
+				100,
+				PaymentMode::Push
+			),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			StandingOrdersModule::create_standing_order(
+				Origin::signed(ALICE),
+				BOB,
+				USDJ,
+				600,
+				10,
+				500,
+				100,
+				PaymentMode::Push
+			),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			StandingOrdersModule::create_standing_order(
+				Origin::signed(ALICE),
+				BOB,
+				USDJ,
+				100,
+				0,
+				500,
+				100,
+				PaymentMode::Push
+			),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			StandingOrdersModule::create_standing_order(
+				Origin::signed(ALICE),
+				BOB,
+				USDJ,
+				100,
+				10,
+				500,
+				11,
+				PaymentMode::Push
+			),
+			Error::<Runtime>::InvalidExpiry
+		);
+
+		assert_ok!(StandingOrdersModule::create_standing_order(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			500,
+			100,
+			PaymentMode::Push
+		));
+		System::assert_last_event(Event::standing_orders(crate::Event::StandingOrderCreated(
+			0, ALICE, BOB, USDJ, 100, 10,
+		)));
+		assert_eq!(
+			StandingOrdersModule::standing_orders(0),
+			Some(StandingOrder {
+				payer: ALICE,
+				recipient: BOB,
+				currency_id: USDJ,
+				amount: 100,
+				period: 10,
+				mode: PaymentMode::Push,
+				next_payment_at: 11,
+				due_at: 11,
+				expiry: 100,
+				max_total: 500,
+				total_paid: 0,
+				failed_attempts: 0,
+			})
+		);
+		assert_eq!(due_order_ids(11), vec![0]);
+		assert_eq!(StandingOrdersModule::next_order_id(), 1);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 10);
+	});
+}
+
+#[test]
+fn cancel_standing_order_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StandingOrdersModule::create_standing_order(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			500,
+			100,
+			PaymentMode::Push
+		));
+		assert_noop!(
+			StandingOrdersModule::cancel_standing_order(Origin::signed(BOB), 1),
+			Error::<Runtime>::OrderNotFound
+		);
+		assert_noop!(
+			StandingOrdersModule::cancel_standing_order(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(StandingOrdersModule::cancel_standing_order(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::standing_orders(crate::Event::StandingOrderCancelled(0, BOB)));
+		assert_eq!(StandingOrdersModule::standing_orders(0), None);
+		assert_eq!(due_order_ids(11), vec![]);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 0);
+
+		run_to_block(11);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1000);
+	});
+}
+
+#[test]
+fn push_payments_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StandingOrdersModule::create_standing_order(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			250,
+			100,
+			PaymentMode::Push
+		));
+
+		run_to_block(10);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1000);
+
+		run_to_block(11);
+		System::assert_last_event(Event::standing_orders(crate::Event::PaymentExecuted(
+			0, ALICE, BOB, USDJ, 100,
+		)));
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 900);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1100);
+		assert_eq!(due_order_ids(21), vec![0]);
+
+		run_to_block(21);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1200);
+
+		// the last payment is capped by the maximum total
+		run_to_block(31);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 750);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1250);
+		System::assert_last_event(Event::standing_orders(crate::Event::StandingOrderCompleted(0)));
+		assert_eq!(StandingOrdersModule::standing_orders(0), None);
+		assert_eq!(due_order_ids(41), vec![]);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 0);
+	});
+}
+
+#[test]
+fn push_payments_are_bounded_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(StandingOrdersModule::create_standing_order(
+				Origin::signed(ALICE),
+				BOB,
+				USDJ,
+				100,
+				10,
+				500,
+				100,
+				PaymentMode::Push
+			));
+		}
+		assert_eq!(due_order_ids(11), vec![0, 1, 2]);
+
+		run_to_block(11);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1200);
+		assert_eq!(due_order_ids(11).len(), 1);
+		assert_eq!(StandingOrdersModule::due_payments_cursor(), Some(11));
+
+		run_to_block(12);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1300);
+		assert_eq!(due_order_ids(11), vec![]);
+		assert_eq!(due_order_ids(21), vec![0, 1, 2]);
+		assert_eq!(StandingOrdersModule::due_payments_cursor(), None);
+	});
+}
+
+#[test]
+fn failed_push_payment_is_retried_then_skipped() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StandingOrdersModule::create_standing_order(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			2000,
+			10,
+			10000,
+			100,
+			PaymentMode::Push
+		));
+
+		run_to_block(11);
+		System::assert_last_event(Event::standing_orders(crate::Event::PaymentFailed(
+			0,
+			orml_tokens::Error::<Runtime>::BalanceTooLow.into(),
+			1,
+		)));
+		assert_eq!(due_order_ids(16), vec![0]);
+
+		run_to_block(16);
+		assert_eq!(StandingOrdersModule::standing_orders(0).unwrap().failed_attempts, 2);
+		assert_eq!(due_order_ids(21), vec![0]);
+
+		run_to_block(21);
+		System::assert_last_event(Event::standing_orders(crate::Event::PaymentSkipped(0)));
+		let order = StandingOrdersModule::standing_orders(0).unwrap();
+		assert_eq!(order.failed_attempts, 0);
+		assert_eq!(order.next_payment_at, 21);
+		assert_eq!(due_order_ids(22), vec![0]);
+
+		// retried payment succeeds once funded
+		assert_ok!(Tokens::deposit(USDJ, &ALICE, 2000));
+		run_to_block(22);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 3000);
+		assert_eq!(due_order_ids(31), vec![0]);
+	});
+}
+
+#[test]
+fn collect_payment_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StandingOrdersModule::create_standing_order(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			1000,
+			45,
+			PaymentMode::Pull
+		));
+		assert_eq!(due_order_ids(11), vec![]);
+		assert_noop!(
+			StandingOrdersModule::collect_payment(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			StandingOrdersModule::collect_payment(Origin::signed(BOB), 0),
+			Error::<Runtime>::NothingDue
+		);
+
+		run_to_block(25);
+		assert_ok!(StandingOrdersModule::collect_payment(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::standing_orders(crate::Event::PaymentExecuted(
+			0, ALICE, BOB, USDJ, 200,
+		)));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1200);
+		assert_eq!(StandingOrdersModule::standing_orders(0).unwrap().next_payment_at, 31);
+		assert_noop!(
+			StandingOrdersModule::collect_payment(Origin::signed(BOB), 0),
+			Error::<Runtime>::NothingDue
+		);
+
+		// no payment is due at or after expiry
+		run_to_block(60);
+		assert_ok!(StandingOrdersModule::collect_payment(Origin::signed(BOB), 0));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1400);
+		System::assert_last_event(Event::standing_orders(crate::Event::StandingOrderCompleted(0)));
+		assert_eq!(StandingOrdersModule::standing_orders(0), None);
+	});
+}
+
+#[test]
+fn collect_payment_fails_for_push_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StandingOrdersModule::create_standing_order(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			1000,
+			100,
+			PaymentMode::Push
+		));
+		assert_noop!(
+			StandingOrdersModule::collect_payment(Origin::signed(BOB), 0),
+			Error::<Runtime>::NotPullOrder
+		);
+	});
+}
@@ -0,0 +1,34 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use codec::Codec;
+use primitives::{Balance, StreamId};
+
+sp_api::decl_runtime_apis! {
+	pub trait StreamsApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The live balance of `who` in a payment stream, the accrued and
+		/// not withdrawn amount for the recipient, the unstreamed amount for
+		/// the sender, and zero for any other account.
+		fn balance_of(stream_id: StreamId, who: AccountId) -> Balance;
+	}
+}
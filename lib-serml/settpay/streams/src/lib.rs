@@ -0,0 +1,318 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # SettPay Streams Module
+//!
+//! ## Overview
+//!
+//! Payment streams of SettPay. A sender locks a deposit of any currency in
+//! the module account, and it streams to the recipient linearly per block
+//! from `start_block` to `stop_block`.
+//!
+//! The recipient can withdraw what has accrued at any time. Either party can
+//! cancel the stream, the accrued part goes to the recipient and the
+//! unstreamed part is returned to the sender.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use primitives::{Balance, CurrencyId, StreamId};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+	DispatchError, RuntimeDebug,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// A payment stream from `sender` to `recipient`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Stream<AccountId, BlockNumber> {
+	/// The account paying the stream.
+	pub sender: AccountId,
+	/// The account paid by the stream.
+	pub recipient: AccountId,
+	/// The currency of the stream.
+	pub currency_id: CurrencyId,
+	/// The total amount to stream.
+	pub deposit: Balance,
+	/// The block the stream starts at.
+	pub start_block: BlockNumber,
+	/// The block the stream is fully streamed at.
+	pub stop_block: BlockNumber,
+	/// The amount withdrawn by the recipient so far.
+	pub withdrawn: Balance,
+}
+
+impl<AccountId, BlockNumber: Copy + Saturating + UniqueSaturatedInto<u128> + PartialOrd>
+	Stream<AccountId, BlockNumber>
+{
+	/// The amount streamed to the recipient at block `now`.
+	pub fn streamed_at(&self, now: BlockNumber) -> Balance {
+		if now <= self.start_block {
+			Zero::zero()
+		} else if now >= self.stop_block {
+			self.deposit
+		} else {
+			let elapsed: u128 = now.saturating_sub(self.start_block).unique_saturated_into();
+			let duration: u128 = self.stop_block.saturating_sub(self.start_block).unique_saturated_into();
+			multiply_by_rational(self.deposit, elapsed, duration).unwrap_or_default()
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to stream and reserve stream deposits with.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The native currency id, to reserve stream deposits in.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The deposit reserved from the sender for every stream.
+		#[pallet::constant]
+		type StreamDeposit: Get<Balance>;
+
+		/// The Streams module id, keeps the streamed currencies.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The stream deposit is zero.
+		InvalidDeposit,
+		/// The stream starts in the past or does not stop after its start.
+		InvalidPeriod,
+		/// The sender cannot stream to itself.
+		InvalidRecipient,
+		/// No more stream id is available.
+		NoAvailableStreamId,
+		/// The stream does not exist.
+		StreamNotFound,
+		/// The caller is neither the sender nor the recipient.
+		NoPermission,
+		/// Nothing has accrued to withdraw.
+		NothingToWithdraw,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Stream created. \[stream_id, sender, recipient, currency_id,
+		/// deposit, start_block, stop_block\]
+		StreamCreated(
+			StreamId,
+			T::AccountId,
+			T::AccountId,
+			CurrencyId,
+			Balance,
+			T::BlockNumber,
+			T::BlockNumber,
+		),
+		/// Withdrawn from stream by the recipient. \[stream_id, recipient,
+		/// amount\]
+		WithdrawnFromStream(StreamId, T::AccountId, Balance),
+		/// Stream cancelled. \[stream_id, who, recipient_amount,
+		/// sender_amount\]
+		StreamCancelled(StreamId, T::AccountId, Balance, Balance),
+		/// Stream fully streamed and withdrawn. \[stream_id\]
+		StreamCompleted(StreamId),
+	}
+
+	/// The next stream id.
+	#[pallet::storage]
+	#[pallet::getter(fn next_stream_id)]
+	pub type NextStreamId<T: Config> = StorageValue<_, StreamId, ValueQuery>;
+
+	/// The payment streams.
+	///
+	/// Streams: map StreamId => Option<Stream>
+	#[pallet::storage]
+	#[pallet::getter(fn streams)]
+	pub type Streams<T: Config> =
+		StorageMap<_, Twox64Concat, StreamId, Stream<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a stream of `deposit` of `currency_id` to `recipient`,
+		/// streamed linearly from `start_block` to `stop_block`.
+		///
+		/// The dispatch origin of this call must be `Signed` by the sender.
+		#[pallet::weight(<T as Config>::WeightInfo::create_stream())]
+		#[transactional]
+		pub fn create_stream(
+			origin: OriginFor<T>,
+			recipient: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			#[pallet::compact] deposit: Balance,
+			start_block: T::BlockNumber,
+			stop_block: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let recipient = T::Lookup::lookup(recipient)?;
+			ensure!(sender != recipient, Error::<T>::InvalidRecipient);
+			ensure!(!deposit.is_zero(), Error::<T>::InvalidDeposit);
+			ensure!(
+				start_block >= <frame_system::Pallet<T>>::block_number() && stop_block > start_block,
+				Error::<T>::InvalidPeriod
+			);
+
+			let stream_id = NextStreamId::<T>::try_mutate(|id| -> sp_std::result::Result<StreamId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableStreamId)?;
+				Ok(current_id)
+			})?;
+			T::Currency::reserve(T::GetNativeCurrencyId::get(), &sender, T::StreamDeposit::get())?;
+			T::Currency::transfer(currency_id, &sender, &Self::account_id(), deposit)?;
+
+			Streams::<T>::insert(
+				stream_id,
+				Stream {
+					sender: sender.clone(),
+					recipient: recipient.clone(),
+					currency_id,
+					deposit,
+					start_block,
+					stop_block,
+					withdrawn: Zero::zero(),
+				},
+			);
+			Self::deposit_event(Event::StreamCreated(
+				stream_id,
+				sender,
+				recipient,
+				currency_id,
+				deposit,
+				start_block,
+				stop_block,
+			));
+			Ok(().into())
+		}
+
+		/// Withdraw all accrued amount of a stream.
+		///
+		/// The dispatch origin of this call must be `Signed` by the
+		/// recipient of the stream.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_from_stream())]
+		#[transactional]
+		pub fn withdraw_from_stream(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut stream = Self::streams(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(who == stream.recipient, Error::<T>::NoPermission);
+
+			let streamed = stream.streamed_at(<frame_system::Pallet<T>>::block_number());
+			let amount = streamed.saturating_sub(stream.withdrawn);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToWithdraw);
+
+			T::Currency::transfer(stream.currency_id, &Self::account_id(), &stream.recipient, amount)?;
+			stream.withdrawn = streamed;
+			Self::deposit_event(Event::WithdrawnFromStream(stream_id, who, amount));
+
+			if stream.withdrawn >= stream.deposit {
+				Self::remove_stream(stream_id, &stream);
+				Self::deposit_event(Event::StreamCompleted(stream_id));
+			} else {
+				Streams::<T>::insert(stream_id, stream);
+			}
+			Ok(().into())
+		}
+
+		/// Cancel a stream, paying the accrued amount to the recipient and
+		/// returning the unstreamed amount to the sender.
+		///
+		/// The dispatch origin of this call must be `Signed` by the sender
+		/// or the recipient of the stream.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_stream())]
+		#[transactional]
+		pub fn cancel_stream(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stream = Self::streams(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(
+				who == stream.sender || who == stream.recipient,
+				Error::<T>::NoPermission
+			);
+
+			let streamed = stream.streamed_at(<frame_system::Pallet<T>>::block_number());
+			let recipient_amount = streamed.saturating_sub(stream.withdrawn);
+			let sender_amount = stream.deposit.saturating_sub(streamed);
+			let module_account = Self::account_id();
+			T::Currency::transfer(stream.currency_id, &module_account, &stream.recipient, recipient_amount)?;
+			T::Currency::transfer(stream.currency_id, &module_account, &stream.sender, sender_amount)?;
+
+			Self::remove_stream(stream_id, &stream);
+			Self::deposit_event(Event::StreamCancelled(stream_id, who, recipient_amount, sender_amount));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get account of Streams module.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The live balance of `who` in the stream, the accrued and not
+	/// withdrawn amount for the recipient, the unstreamed amount for the
+	/// sender, and zero for any other account.
+	pub fn balance_of(stream_id: StreamId, who: &T::AccountId) -> Balance {
+		Self::streams(stream_id)
+			.map(|stream| {
+				let streamed = stream.streamed_at(<frame_system::Pallet<T>>::block_number());
+				if *who == stream.recipient {
+					streamed.saturating_sub(stream.withdrawn)
+				} else if *who == stream.sender {
+					stream.deposit.saturating_sub(streamed)
+				} else {
+					Zero::zero()
+				}
+			})
+			.unwrap_or_default()
+	}
+
+	fn remove_stream(stream_id: StreamId, stream: &Stream<T::AccountId, T::BlockNumber>) {
+		T::Currency::unreserve(T::GetNativeCurrencyId::get(), &stream.sender, T::StreamDeposit::get());
+		Streams::<T>::remove(stream_id);
+	}
+}
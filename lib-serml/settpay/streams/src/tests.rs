@@ -0,0 +1,175 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the streams module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+#[test]
+fn create_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StreamsModule::create_stream(Origin::signed(ALICE), ALICE, USDJ, 100, 10, 20),
+			Error::<Runtime>::InvalidRecipient
+		);
+		assert_noop!(
+			StreamsModule::create_stream(Origin::signed(ALICE), BOB, USDJ, 0, 10, 20),
+			Error::<Runtime>::InvalidDeposit
+		);
+		assert_noop!(
+			StreamsModule::create_stream(Origin::signed(ALICE), BOB, USDJ, 100, 0, 20),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			StreamsModule::create_stream(Origin::signed(ALICE), BOB, USDJ, 100, 10, 10),
+			Error::<Runtime>::InvalidPeriod
+		);
+
+		assert_ok!(StreamsModule::create_stream(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			20
+		));
+		System::assert_last_event(Event::streams(crate::Event::StreamCreated(
+			0, ALICE, BOB, USDJ, 100, 10, 20,
+		)));
+		assert_eq!(StreamsModule::next_stream_id(), 1);
+		assert_eq!(
+			StreamsModule::streams(0),
+			Some(Stream {
+				sender: ALICE,
+				recipient: BOB,
+				currency_id: USDJ,
+				deposit: 100,
+				start_block: 10,
+				stop_block: 20,
+				withdrawn: 0,
+			})
+		);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 900);
+		assert_eq!(Tokens::free_balance(USDJ, &StreamsModule::account_id()), 100);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 10);
+	});
+}
+
+#[test]
+fn balance_of_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(StreamsModule::balance_of(0, &BOB), 0);
+		assert_ok!(StreamsModule::create_stream(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			20
+		));
+		assert_eq!(StreamsModule::balance_of(0, &BOB), 0);
+		assert_eq!(StreamsModule::balance_of(0, &ALICE), 100);
+
+		System::set_block_number(13);
+		assert_eq!(StreamsModule::balance_of(0, &BOB), 30);
+		assert_eq!(StreamsModule::balance_of(0, &ALICE), 70);
+		assert_eq!(StreamsModule::balance_of(0, &CAROL), 0);
+
+		System::set_block_number(25);
+		assert_eq!(StreamsModule::balance_of(0, &BOB), 100);
+		assert_eq!(StreamsModule::balance_of(0, &ALICE), 0);
+	});
+}
+
+#[test]
+fn withdraw_from_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StreamsModule::withdraw_from_stream(Origin::signed(BOB), 0),
+			Error::<Runtime>::StreamNotFound
+		);
+		assert_ok!(StreamsModule::create_stream(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			20
+		));
+		assert_noop!(
+			StreamsModule::withdraw_from_stream(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			StreamsModule::withdraw_from_stream(Origin::signed(BOB), 0),
+			Error::<Runtime>::NothingToWithdraw
+		);
+
+		System::set_block_number(14);
+		assert_ok!(StreamsModule::withdraw_from_stream(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::streams(crate::Event::WithdrawnFromStream(0, BOB, 40)));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1040);
+		assert_eq!(StreamsModule::streams(0).unwrap().withdrawn, 40);
+		assert_eq!(StreamsModule::balance_of(0, &BOB), 0);
+
+		System::set_block_number(30);
+		assert_ok!(StreamsModule::withdraw_from_stream(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::streams(crate::Event::StreamCompleted(0)));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1100);
+		assert_eq!(Tokens::free_balance(USDJ, &StreamsModule::account_id()), 0);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 0);
+		assert_eq!(StreamsModule::streams(0), None);
+	});
+}
+
+#[test]
+fn cancel_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StreamsModule::create_stream(
+			Origin::signed(ALICE),
+			BOB,
+			USDJ,
+			100,
+			10,
+			20
+		));
+		assert_noop!(
+			StreamsModule::cancel_stream(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		System::set_block_number(12);
+		assert_ok!(StreamsModule::withdraw_from_stream(Origin::signed(BOB), 0));
+		System::set_block_number(16);
+		assert_ok!(StreamsModule::cancel_stream(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::streams(crate::Event::StreamCancelled(0, ALICE, 40, 40)));
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1060);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 940);
+		assert_eq!(Tokens::free_balance(USDJ, &StreamsModule::account_id()), 0);
+		assert_eq!(Tokens::reserved_balance(DNAR, &ALICE), 0);
+		assert_eq!(StreamsModule::streams(0), None);
+		assert_noop!(
+			StreamsModule::cancel_stream(Origin::signed(BOB), 0),
+			Error::<Runtime>::StreamNotFound
+		);
+	});
+}
@@ -97,6 +97,19 @@ pub trait SetheumDEXManager<AccountId, CurrencyId, Balance> {
 		max_supply_amount: Balance,
 		price_impact_limit: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError>;
+
+	/// Borrow `borrow_amount` of `borrow_currency_id` from the liquidity
+	/// pool, run `callback`, and repay in `repay_currency_id` with the
+	/// exchange fee. Return the repay amount.
+	fn flash_swap<F: FnOnce() -> DispatchResult>(
+		who: &AccountId,
+		borrow_currency_id: CurrencyId,
+		paired_currency_id: CurrencyId,
+		borrow_amount: Balance,
+		repay_currency_id: CurrencyId,
+		max_repay_amount: Balance,
+		callback: F,
+	) -> sp_std::result::Result<Balance, DispatchError>;
 }
 
 impl<AccountId, CurrencyId, Balance> SetheumDEXManager<AccountId, CurrencyId, Balance> for ()
@@ -142,6 +155,18 @@ where
 	) -> sp_std::result::Result<Balance, DispatchError> {
 		Ok(Default::default())
 	}
+
+	fn flash_swap<F: FnOnce() -> DispatchResult>(
+		_who: &AccountId,
+		_borrow_currency_id: CurrencyId,
+		_paired_currency_id: CurrencyId,
+		_borrow_amount: Balance,
+		_repay_currency_id: CurrencyId,
+		_max_repay_amount: Balance,
+		_callback: F,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Ok(Default::default())
+	}
 }
 
 /// An abstraction of serp treasury for the SERP (Setheum Elastic Reserve Protocol).
@@ -211,7 +236,7 @@ pub trait SerpTreasury<AccountId> {
 	fn burn_dexer(who: &AccountId, dexer: Self::Balance) -> DispatchResult;
 
 	/// deposit surplus(propperstable currency) to serp treasury by `from`
-	fn deposit_surplus(currency_id: Self::CurrencyId, from: &AccountId, surplus: Self::Balance) -> DispatchResult;
+	fn deposit_serplus(currency_id: Self::CurrencyId, from: &AccountId, serplus: Self::Balance) -> DispatchResult;
 
 	/// deposit reserve asset (Setter (SETT)) to serp treasury by `who`
 	fn deposit_setter(from: &AccountId, amount: Self::Balance) -> DispatchResult;
@@ -282,6 +307,20 @@ impl<AccountId, CurrencyId, Balance> DEXIncentives<AccountId, CurrencyId, Balanc
 	}
 }
 
+/// An abstraction of SettPay cashdrops claimed on currency transfers.
+pub trait CashDropHandler<AccountId, CurrencyId, Balance> {
+	/// Record a transfer of `amount` from `from` to `to` against the cashdrop
+	/// entitlement of `from`. Eligibility (minimum amount, enabled currencies,
+	/// self-transfers, etc.) is checked here and an ineligible claim errors.
+	fn claim_cashdrop(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+impl<AccountId, CurrencyId, Balance> CashDropHandler<AccountId, CurrencyId, Balance> for () {
+	fn claim_cashdrop(_: CurrencyId, _: &AccountId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
 /// Return true if the call of EVM precompile contract is allowed.
 pub trait PrecompileCallerFilter {
 	fn is_allowed(caller: H160) -> bool;
@@ -324,6 +363,9 @@ pub struct InvokeContext {
 	pub origin: EvmAddress,
 }
 
+/// The gas limit of `EVMBridge::transfer`.
+pub const ERC20_TRANSFER_GAS_LIMIT: u64 = 2_100_000;
+
 /// An abstraction of EVMBridge
 pub trait EVMBridge<AccountId, Balance> {
 	/// Execute ERC20.name() to read token name from ERC20 contract
@@ -402,7 +444,7 @@ pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 		tip: Balance,
 		pays_fee: Pays,
 		class: DispatchClass,
-	) -> Result<(), TransactionValidityError>;
+	) -> Result<Balance, TransactionValidityError>;
 }
 
 // TODO: Use this logic for SettPayCashDrop
@@ -458,8 +500,8 @@ impl<AccountId, Balance: Default + Copy, NegativeImbalance: Imbalance<Balance>>
 		_tip: Balance,
 		_pays_fee: Pays,
 		_class: DispatchClass,
-	) -> Result<(), TransactionValidityError> {
-		Ok(())
+	) -> Result<Balance, TransactionValidityError> {
+		Ok(Default::default())
 	}
 }
 
@@ -546,3 +588,53 @@ impl CurrencyIdMapping for () {
 		None
 	}
 }
+
+/// The ERC-721 extensions of an NFT module, backing the mirrored ERC-721
+/// contracts of its classes in the EVM.
+pub trait NFTMirror<AccountId> {
+	type ClassId;
+	type TokenId;
+	type Balance;
+
+	/// Returns the number of `class_id` tokens owned by `who`.
+	fn class_balance(class_id: Self::ClassId, who: &AccountId) -> Self::Balance;
+	/// Returns the metadata of `token`, used as its ERC-721 token URI.
+	fn token_uri(token: (Self::ClassId, Self::TokenId)) -> Option<Vec<u8>>;
+	/// Returns the account approved to transfer `token`.
+	fn approved(token: (Self::ClassId, Self::TokenId)) -> Option<AccountId>;
+	/// Returns true if `operator` may transfer all `class_id` tokens of
+	/// `owner`.
+	fn is_approved_for_all(owner: &AccountId, operator: &AccountId, class_id: Self::ClassId) -> bool;
+	/// Approve `approved` to transfer `token`, or clear the approval with
+	/// `None`. `who` must be the owner of `token` or an operator of the
+	/// owner.
+	fn approve(who: &AccountId, approved: Option<AccountId>, token: (Self::ClassId, Self::TokenId)) -> DispatchResult;
+	/// Approve or revoke `operator` to transfer all `class_id` tokens of
+	/// `owner`.
+	fn set_approval_for_all(
+		owner: &AccountId,
+		operator: &AccountId,
+		class_id: Self::ClassId,
+		approved: bool,
+	) -> DispatchResult;
+	/// Transfer `token` from `from` to `to` on behalf of `who`, which must be
+	/// `from`, the account approved for `token` or an operator of `from`.
+	fn transfer_from(
+		who: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult;
+}
+
+/// Accounts recovered by a rescuer, e.g. through `pallet_recovery`.
+pub trait AccountRecovery<AccountId> {
+	/// Returns the lost account `rescuer` has recovered.
+	fn recovered_account(rescuer: &AccountId) -> Option<AccountId>;
+}
+
+impl<AccountId> AccountRecovery<AccountId> for () {
+	fn recovered_account(_rescuer: &AccountId) -> Option<AccountId> {
+		None
+	}
+}
@@ -38,18 +38,20 @@ use orml_traits::{
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
 };
+use orml_utilities::with_transaction_result;
 use primitives::{evm::EvmAddress, CurrencyId};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
+	traits::{CheckedSub, Convert, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
 	DispatchError, DispatchResult,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
 };
-use support::{AddressMapping, EVMBridge, InvokeContext};
+use support::{AddressMapping, CashDropHandler, EVMBridge, InvokeContext, ERC20_TRANSFER_GAS_LIMIT};
 
 mod mock;
 mod tests;
@@ -90,6 +92,16 @@ pub mod module {
 		/// Mapping from address to account id.
 		type AddressMapping: AddressMapping<Self::AccountId>;
 		type EVMBridge: EVMBridge<Self::AccountId, BalanceOf<Self>>;
+
+		/// Convert the gas of Erc20 transfers through `EVMBridge` to weight.
+		type GasToWeight: Convert<u64, Weight>;
+
+		/// Handler to record transfers claimed for SettPay cashdrops.
+		type CashDropHandler: CashDropHandler<Self::AccountId, CurrencyId, BalanceOf<Self>>;
+
+		/// The maximum number of transfers in a `transfer_many` batch.
+		#[pallet::constant]
+		type MaxTransfers: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -102,6 +114,8 @@ pub mod module {
 		Erc20InvalidOperation,
 		/// EVM account not found
 		EvmAccountNotFound,
+		/// The batch has more than `MaxTransfers` transfers
+		TooManyTransfers,
 	}
 
 	#[pallet::event]
@@ -115,6 +129,9 @@ pub mod module {
 		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 		/// Withdraw success. [currency_id, who, amount]
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Batch transfer completed, transfers at `failed_indices` are
+		/// skipped. [from, succeeded_count, failed_indices]
+		TransferredMany(T::AccountId, u32, Vec<u32>),
 	}
 
 	#[pallet::pallet]
@@ -125,7 +142,6 @@ pub mod module {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		// TODO: Update to add "claim_cashdrop" using `claim: bool`
 		/// Transfer some balance to another account under `currency_id`.
 		///
 		/// The dispatch origin for this call must be `Signed` by the
@@ -144,7 +160,6 @@ pub mod module {
 		}
 
 		/// Transfer some native currency to another account.
-		// TODO: Update to add "claim_cashdrop" using `claim: bool`
 		///
 		/// The dispatch origin for this call must be `Signed` by the
 		/// transactor.
@@ -162,6 +177,104 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Transfer some balance to another account under `currency_id`,
+		/// and claim the cashdrop of the transfer if `claim_cashdrop`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_non_native_currency_with_cashdrop())]
+		#[transactional]
+		pub fn transfer_with_cashdrop(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			claim_cashdrop: bool,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+			if claim_cashdrop {
+				T::CashDropHandler::claim_cashdrop(currency_id, &from, &to, amount)?;
+			}
+			Ok(().into())
+		}
+
+		/// Transfer some native currency to another account, and claim the
+		/// cashdrop of the transfer if `claim_cashdrop`. The transfer fails
+		/// if the `CashDropHandler` rejects the claim.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_native_currency_with_cashdrop())]
+		#[transactional]
+		pub fn transfer_native_currency_with_cashdrop(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+			claim_cashdrop: bool,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let native_currency_id = T::GetNativeCurrencyId::get();
+			T::NativeCurrency::transfer(&from, &to, amount)?;
+			if claim_cashdrop {
+				T::CashDropHandler::claim_cashdrop(native_currency_id, &from, &to, amount)?;
+			}
+
+			Self::deposit_event(Event::Transferred(native_currency_id, from, to, amount));
+			Ok(().into())
+		}
+
+		/// Transfer many currencies to many accounts in one call.
+		///
+		/// If `all_or_nothing`, any failed transfer reverts the whole batch,
+		/// otherwise failed transfers are skipped and reported in the
+		/// `TransferredMany` event, and their weight is refunded. Erc20
+		/// transfers are weighted by the gas limit of `EVMBridge::transfer`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(Pallet::<T>::transfer_many_weight(
+			transfers.len() as u32,
+			transfers.iter().filter(|(_, currency_id, _)| currency_id.is_erc20_currency_id()).count() as u32,
+		))]
+		#[transactional]
+		pub fn transfer_many(
+			origin: OriginFor<T>,
+			transfers: Vec<(<T::Lookup as StaticLookup>::Source, CurrencyIdOf<T>, BalanceOf<T>)>,
+			all_or_nothing: bool,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			ensure!(
+				transfers.len() as u32 <= T::MaxTransfers::get(),
+				Error::<T>::TooManyTransfers
+			);
+
+			let mut succeeded_count: u32 = 0;
+			// failed Erc20 transfers may have used their gas, so they are not refunded
+			let mut erc20_count: u32 = 0;
+			let mut failed_indices: Vec<u32> = Vec::new();
+
+			for (index, (dest, currency_id, amount)) in transfers.into_iter().enumerate() {
+				if currency_id.is_erc20_currency_id() {
+					erc20_count += 1;
+				}
+				let result = T::Lookup::lookup(dest)
+					.map_err(DispatchError::from)
+					.and_then(|to| with_transaction_result(|| Self::do_transfer(currency_id, &from, &to, amount)));
+
+				match result {
+					Ok(()) => succeeded_count += 1,
+					Err(e) if all_or_nothing => return Err(e.into()),
+					Err(_) => failed_indices.push(index as u32),
+				}
+			}
+
+			Self::deposit_event(Event::TransferredMany(from, succeeded_count, failed_indices));
+			Ok(Some(Self::transfer_many_weight(succeeded_count, erc20_count)).into())
+		}
+
 		/// update amount of account `who` under `currency_id`.
 		///
 		/// The dispatch origin of this call must be _Root_.
@@ -180,6 +293,50 @@ pub mod module {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// The weight of `transfer_many` with `count` transfers, `erc20_count` of
+	/// which are Erc20 transfers.
+	fn transfer_many_weight(count: u32, erc20_count: u32) -> Weight {
+		T::WeightInfo::transfer_many(count)
+			.saturating_add(T::GasToWeight::convert(ERC20_TRANSFER_GAS_LIMIT).saturating_mul(erc20_count as Weight))
+	}
+
+	/// Transfer `amount` of `currency_id` from `from` to `to`, without
+	/// depositing the `Transferred` event.
+	fn do_transfer(
+		currency_id: CurrencyIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+
+		match currency_id {
+			CurrencyId::Erc20(contract) => {
+				let sender = T::AddressMapping::get_evm_address(&from).ok_or(Error::<T>::EvmAccountNotFound)?;
+				let origin = T::EVMBridge::get_origin().unwrap_or_default();
+				let origin_address = T::AddressMapping::get_or_create_evm_address(&origin);
+				let address = T::AddressMapping::get_or_create_evm_address(&to);
+				T::EVMBridge::transfer(
+					InvokeContext {
+						contract,
+						sender,
+						origin: origin_address,
+					},
+					address,
+					amount,
+				)?;
+			}
+			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::transfer(from, to, amount)?,
+			_ => T::MultiCurrency::transfer(currency_id, from, to, amount)?,
+		}
+
+		Ok(())
+	}
+}
+
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type CurrencyId = CurrencyIdOf<T>;
 	type Balance = BalanceOf<T>;
@@ -262,7 +419,6 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		}
 	}
 
-	// TODO: Update to add "claim_cashdrop" using `claim: bool`
 	fn transfer(
 		currency_id: Self::CurrencyId,
 		from: &T::AccountId,
@@ -273,27 +429,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 			return Ok(());
 		}
 
-		match currency_id {
-			CurrencyId::Erc20(contract) => {
-				let sender = T::AddressMapping::get_evm_address(&from).ok_or(Error::<T>::EvmAccountNotFound)?;
-				let origin = T::EVMBridge::get_origin().unwrap_or_default();
-				let origin_address = T::AddressMapping::get_or_create_evm_address(&origin);
-				let address = T::AddressMapping::get_or_create_evm_address(&to);
-				// TODO: Update to add "claim_cashdrop" using `claim: bool`
-				T::EVMBridge::transfer(
-					InvokeContext {
-						contract,
-						sender,
-						origin: origin_address,
-					},
-					address,
-					amount,
-				)?;
-			}
-			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::transfer(from, to, amount)?,
-			_ => T::MultiCurrency::transfer(currency_id, from, to, amount)?,
-		}
-
+		Self::do_transfer(currency_id, from, to, amount)?;
 		Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
 		Ok(())
 	}
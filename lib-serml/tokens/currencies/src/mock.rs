@@ -34,7 +34,7 @@ use support::{mocks::MockAddressMapping, AddressMapping};
 use super::*;
 use frame_system::EnsureSignedBy;
 use sp_core::{bytes::from_hex, H160};
-use sp_std::str::FromStr;
+use sp_std::{cell::RefCell, str::FromStr};
 
 pub use crate as currencies;
 
@@ -99,6 +99,23 @@ impl tokens::Config for Runtime {
 pub const NATIVE_CURRENCY_ID: CurrencyId = CurrencyId::Token(TokenSymbol::DNAR);
 pub const X_TOKEN_ID: CurrencyId = CurrencyId::Token(TokenSymbol::USDJ);
 
+pub const MINIMUM_CLAIMABLE_TRANSFER: Balance = 10;
+
+thread_local! {
+	pub static CASHDROP_CLAIMS: RefCell<Vec<(CurrencyId, AccountId, AccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct MockCashDropHandler;
+impl CashDropHandler<AccountId, CurrencyId, Balance> for MockCashDropHandler {
+	fn claim_cashdrop(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		if from == to || amount < MINIMUM_CLAIMABLE_TRANSFER {
+			return Err(DispatchError::Other("NotClaimable"));
+		}
+		CASHDROP_CLAIMS.with(|v| v.borrow_mut().push((currency_id, from.clone(), to.clone(), amount)));
+		Ok(())
+	}
+}
+
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
 }
@@ -156,7 +173,10 @@ impl setheum_evm::Config for Runtime {
 	type Precompiles = ();
 	type ChainId = ();
 	type GasToWeight = ();
+	type MaxGasPerExtrinsic = ();
 	type ChargeTransactionPayment = ();
+	type EthTransactionStorageLimit = ();
+	type HardFork = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
 
@@ -164,6 +184,9 @@ impl setheum_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CodeUpgradeDelay = ();
+	type MaxCodeVersions = ();
+	type CodeUpgradeCancelOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type WeightInfo = ();
 }
@@ -172,6 +195,17 @@ impl setheum_evm_bridge::Config for Runtime {
 	type EVM = EVM;
 }
 
+parameter_types! {
+	pub const MaxTransfers: u32 = 4;
+}
+
+pub struct GasToWeight;
+impl Convert<u64, Weight> for GasToWeight {
+	fn convert(gas: u64) -> Weight {
+		gas
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -180,6 +214,9 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = EVMBridge;
+	type GasToWeight = GasToWeight;
+	type CashDropHandler = MockCashDropHandler;
+	type MaxTransfers = MaxTransfers;
 }
 
 pub type NativeCurrency = Currency<Runtime, GetNativeCurrencyId>;
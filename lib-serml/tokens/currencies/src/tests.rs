@@ -24,7 +24,8 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
 	alice, bob, deploy_contracts, erc20_address, eva, AccountId, AdaptedBasicCurrency, Currencies, Event, ExtBuilder,
-	NativeCurrency, Origin, PalletBalances, Runtime, System, Tokens, EVM, ID_1, NATIVE_CURRENCY_ID, X_TOKEN_ID,
+	NativeCurrency, Origin, PalletBalances, Runtime, System, Tokens, CASHDROP_CLAIMS, EVM, ID_1, NATIVE_CURRENCY_ID,
+	X_TOKEN_ID,
 };
 use sp_core::H160;
 use sp_runtime::traits::BadOrigin;
@@ -269,6 +270,87 @@ fn update_balance_call_fails_if_not_root_origin() {
 	});
 }
 
+#[test]
+fn transfer_with_cashdrop_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer_with_cashdrop(
+				Some(alice()).into(),
+				bob(),
+				X_TOKEN_ID,
+				20,
+				false
+			));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 80);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 120);
+			assert_eq!(CASHDROP_CLAIMS.with(|v| v.borrow().len()), 0);
+
+			assert_ok!(Currencies::transfer_with_cashdrop(
+				Some(alice()).into(),
+				bob(),
+				X_TOKEN_ID,
+				30,
+				true
+			));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 50);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 150);
+			assert_eq!(
+				CASHDROP_CLAIMS.with(|v| v.borrow().clone()),
+				vec![(X_TOKEN_ID, alice(), bob(), 30)]
+			);
+			System::assert_last_event(Event::currencies(crate::Event::Transferred(
+				X_TOKEN_ID,
+				alice(),
+				bob(),
+				30,
+			)));
+
+			// ineligible claims revert the transfer
+			assert_noop!(
+				Currencies::transfer_with_cashdrop(Some(alice()).into(), bob(), X_TOKEN_ID, 5, true),
+				DispatchError::Other("NotClaimable")
+			);
+			assert_noop!(
+				Currencies::transfer_with_cashdrop(Some(alice()).into(), alice(), X_TOKEN_ID, 20, true),
+				DispatchError::Other("NotClaimable")
+			);
+		});
+}
+
+#[test]
+fn transfer_native_currency_with_cashdrop_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer_native_currency_with_cashdrop(
+				Some(alice()).into(),
+				bob(),
+				50,
+				true
+			));
+			assert_eq!(NativeCurrency::free_balance(&alice()), 50);
+			assert_eq!(NativeCurrency::free_balance(&bob()), 150);
+			assert_eq!(
+				CASHDROP_CLAIMS.with(|v| v.borrow().clone()),
+				vec![(NATIVE_CURRENCY_ID, alice(), bob(), 50)]
+			);
+			System::assert_last_event(Event::currencies(crate::Event::Transferred(
+				NATIVE_CURRENCY_ID,
+				alice(),
+				bob(),
+				50,
+			)));
+
+			assert_noop!(
+				Currencies::transfer_native_currency_with_cashdrop(Some(alice()).into(), bob(), 5, true),
+				DispatchError::Other("NotClaimable")
+			);
+		});
+}
+
 #[test]
 fn call_event_should_work() {
 	ExtBuilder::default()
@@ -470,6 +552,123 @@ fn erc20_transfer_should_work() {
 		});
 }
 
+#[test]
+fn transfer_many_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let post_info = Currencies::transfer_many(
+				Some(alice()).into(),
+				vec![
+					(bob(), NATIVE_CURRENCY_ID, 10),
+					(eva(), X_TOKEN_ID, 20),
+					(bob(), X_TOKEN_ID, 1000),
+					(eva(), NATIVE_CURRENCY_ID, 30),
+				],
+				false,
+			)
+			.unwrap();
+			// the skipped transfer is refunded
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_many(3))
+			);
+			assert_eq!(NativeCurrency::free_balance(&alice()), 60);
+			assert_eq!(NativeCurrency::free_balance(&bob()), 110);
+			assert_eq!(NativeCurrency::free_balance(&eva()), 30);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 80);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 100);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 20);
+			System::assert_last_event(Event::currencies(crate::Event::TransferredMany(alice(), 3, vec![2])));
+		});
+}
+
+#[test]
+fn transfer_many_all_or_nothing_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer_many(
+					Some(alice()).into(),
+					vec![(bob(), NATIVE_CURRENCY_ID, 10), (bob(), X_TOKEN_ID, 1000)],
+					true
+				),
+				tokens::Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_ok!(Currencies::transfer_many(
+				Some(alice()).into(),
+				vec![(bob(), NATIVE_CURRENCY_ID, 10), (bob(), X_TOKEN_ID, 50)],
+				true
+			));
+			assert_eq!(NativeCurrency::free_balance(&bob()), 110);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 150);
+			System::assert_last_event(Event::currencies(crate::Event::TransferredMany(alice(), 2, vec![])));
+		});
+}
+
+#[test]
+fn transfer_many_should_be_bounded() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer_many(Some(alice()).into(), vec![(bob(), NATIVE_CURRENCY_ID, 1); 5], false),
+				Error::<Runtime>::TooManyTransfers
+			);
+			assert_ok!(Currencies::transfer_many(
+				Some(alice()).into(),
+				vec![(bob(), NATIVE_CURRENCY_ID, 1); 4],
+				false
+			));
+			assert_eq!(NativeCurrency::free_balance(&bob()), 104);
+		});
+}
+
+#[test]
+fn erc20_transfer_many_should_work() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			deploy_contracts();
+			let alice_balance = 10000;
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+			let post_info = Currencies::transfer_many(
+				Origin::signed(alice()),
+				vec![
+					(bob(), CurrencyId::Erc20(erc20_address()), 100),
+					(bob(), NATIVE_CURRENCY_ID, 1000),
+					(bob(), CurrencyId::Erc20(erc20_address()), alice_balance),
+				],
+				false,
+			)
+			.unwrap();
+			// the failed Erc20 transfer is charged its gas
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_many(2) + 2 * ERC20_TRANSFER_GAS_LIMIT)
+			);
+			assert_eq!(
+				Currencies::free_balance(CurrencyId::Erc20(erc20_address()), &alice()),
+				alice_balance - 100
+			);
+			assert_eq!(
+				Currencies::free_balance(CurrencyId::Erc20(erc20_address()), &bob()),
+				100
+			);
+			assert_eq!(NativeCurrency::free_balance(&bob()), 101000);
+			System::assert_last_event(Event::currencies(crate::Event::TransferredMany(alice(), 2, vec![2])));
+		});
+}
+
 #[test]
 fn erc20_transfer_should_fail() {
 	ExtBuilder::default()
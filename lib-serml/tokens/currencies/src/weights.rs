@@ -58,6 +58,9 @@ pub trait WeightInfo {
 	fn update_balance_non_native_currency() -> Weight;
 	fn update_balance_native_currency_creating() -> Weight;
 	fn update_balance_native_currency_killing() -> Weight;
+	fn transfer_non_native_currency_with_cashdrop() -> Weight;
+	fn transfer_native_currency_with_cashdrop() -> Weight;
+	fn transfer_many(c: u32, ) -> Weight;
 }
 
 /// Weights for setheum_currencies using the Setheum node and recommended hardware.
@@ -82,6 +85,22 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 	fn update_balance_native_currency_killing() -> Weight {
 		(13_000_000 as Weight)
 	}
+	fn transfer_non_native_currency_with_cashdrop() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn transfer_native_currency_with_cashdrop() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_many(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((66_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -105,4 +124,20 @@ impl WeightInfo for () {
 	fn update_balance_native_currency_killing() -> Weight {
 		(13_000_000 as Weight)
 	}
+	fn transfer_non_native_currency_with_cashdrop() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn transfer_native_currency_with_cashdrop() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_many(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((66_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
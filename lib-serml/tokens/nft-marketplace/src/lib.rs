@@ -0,0 +1,528 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # NFT Marketplace Module
+//!
+//! ## Overview
+//!
+//! On-chain marketplace of `setheum_nft` tokens. Owners can list tokens at a
+//! fixed price in any currency, accept offers, or sell them by English
+//! auction through `orml_auction`.
+//!
+//! Listed and auctioned tokens are locked against transfer and burn. Every
+//! sale is settled atomically by `setheum_nft`, which pays the class royalty
+//! and the seller out of the price and moves the token to the buyer.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::{Auction, AuctionHandler, Change, MultiReservableCurrency, OnNewBidResult};
+use primitives::{AuctionId, Balance, CurrencyId};
+use setheum_nft::{ClassIdOf, TokenIdOf};
+use sp_runtime::{
+	traits::{Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// A fixed-price listing of a token.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Listing<AccountId, BlockNumber> {
+	/// The owner of the listed token.
+	pub seller: AccountId,
+	/// The currency of the price.
+	pub currency_id: CurrencyId,
+	/// The price of the token.
+	pub price: Balance,
+	/// The block the listing expires at, never if `None`.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// An offer to buy a token, the amount is reserved from the offerer.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Offer<BlockNumber> {
+	/// The currency of the offer.
+	pub currency_id: CurrencyId,
+	/// The offered amount.
+	pub amount: Balance,
+	/// The block the offer expires at, never if `None`.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// An English auction of a token.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct NFTAuction<AccountId, ClassId, TokenId> {
+	/// The owner of the auctioned token.
+	pub seller: AccountId,
+	/// The auctioned token.
+	pub token: (ClassId, TokenId),
+	/// The currency of the bids.
+	pub currency_id: CurrencyId,
+	/// The minimum bid.
+	pub min_price: Balance,
+}
+
+pub type TokenOf<T> = (ClassIdOf<T>, TokenIdOf<T>);
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + setheum_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to reserve offers and bids with.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The auction to run English auctions of tokens with.
+		type Auction: Auction<Self::AccountId, Self::BlockNumber, AuctionId = AuctionId, Balance = Balance>;
+
+		/// The handler of auctions which are not token auctions, as
+		/// `orml_auction` has a single handler.
+		type FallbackAuctionHandler: AuctionHandler<Self::AccountId, Balance, Self::BlockNumber, AuctionId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The token does not exist.
+		TokenNotFound,
+		/// The caller has no permission on the token, listing, offer or
+		/// auction.
+		NoPermission,
+		/// The price or amount is zero.
+		InvalidPrice,
+		/// The expiry is not in the future.
+		InvalidExpiry,
+		/// The auction duration is zero.
+		InvalidDuration,
+		/// The listing does not exist.
+		ListingNotFound,
+		/// The listing has expired.
+		ListingExpired,
+		/// The listing price is above the maximum price of the buyer.
+		PriceTooHigh,
+		/// The owner cannot buy or offer for its own token.
+		InvalidBuyer,
+		/// The offerer already has an offer for the token.
+		OfferAlreadyExists,
+		/// The offer does not exist.
+		OfferNotFound,
+		/// The offer has expired.
+		OfferExpired,
+		/// The auction does not exist.
+		AuctionNotFound,
+		/// The auction already has a bid and cannot be cancelled.
+		AuctionHasBid,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Token listed. \[seller, class_id, token_id, currency_id, price\]
+		TokenListed(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, CurrencyId, Balance),
+		/// Listing cancelled. \[class_id, token_id\]
+		ListingCancelled(ClassIdOf<T>, TokenIdOf<T>),
+		/// Listed token bought. \[buyer, class_id, token_id, currency_id,
+		/// price\]
+		TokenBought(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, CurrencyId, Balance),
+		/// Offer made. \[offerer, class_id, token_id, currency_id, amount\]
+		OfferMade(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, CurrencyId, Balance),
+		/// Offer cancelled. \[offerer, class_id, token_id\]
+		OfferCancelled(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Offer accepted. \[seller, offerer, class_id, token_id, currency_id,
+		/// amount\]
+		OfferAccepted(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			CurrencyId,
+			Balance,
+		),
+		/// Auction created. \[auction_id, seller, class_id, token_id,
+		/// currency_id, min_price\]
+		AuctionCreated(AuctionId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>, CurrencyId, Balance),
+		/// Auction cancelled by the seller. \[auction_id\]
+		AuctionCancelled(AuctionId),
+		/// Auction dealt. \[auction_id, winner, price\]
+		AuctionDealt(AuctionId, T::AccountId, Balance),
+		/// Auction ended without a bid or failed to settle. \[auction_id\]
+		AuctionAborted(AuctionId),
+	}
+
+	/// Fixed-price listings of tokens.
+	///
+	/// Listings: double_map ClassId, TokenId => Option<Listing>
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ClassIdOf<T>,
+		Twox64Concat,
+		TokenIdOf<T>,
+		Listing<T::AccountId, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Offers for tokens.
+	///
+	/// Offers: double_map (ClassId, TokenId), AccountId => Option<Offer>
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TokenOf<T>, Twox64Concat, T::AccountId, Offer<T::BlockNumber>, OptionQuery>;
+
+	/// English auctions of tokens.
+	///
+	/// Auctions: map AuctionId => Option<NFTAuction>
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, NFTAuction<T::AccountId, ClassIdOf<T>, TokenIdOf<T>>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// List a token at a fixed price, locking it until it is sold or the
+		/// listing is cancelled.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `expires_at`: the block the listing expires at, never if `None`
+		#[pallet::weight(<T as Config>::WeightInfo::list_token())]
+		#[transactional]
+		pub fn list_token(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] price: Balance,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::owner_of(token)? == who, Error::<T>::NoPermission);
+			ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+			Self::ensure_future(expires_at)?;
+
+			setheum_nft::Pallet::<T>::lock_token(token)?;
+			Listings::<T>::insert(
+				token.0,
+				token.1,
+				Listing {
+					seller: who.clone(),
+					currency_id,
+					price,
+					expires_at,
+				},
+			);
+
+			Self::deposit_event(Event::TokenListed(who, token.0, token.1, currency_id, price));
+			Ok(().into())
+		}
+
+		/// Cancel a listing and unlock the token. The seller can cancel its
+		/// listing at any time, anyone can cancel an expired listing.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_listing())]
+		#[transactional]
+		pub fn cancel_listing(origin: OriginFor<T>, token: TokenOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let listing = Self::listings(token.0, token.1).ok_or(Error::<T>::ListingNotFound)?;
+			ensure!(
+				who == listing.seller || Self::is_expired(listing.expires_at),
+				Error::<T>::NoPermission
+			);
+
+			Self::remove_listing(token);
+			Ok(().into())
+		}
+
+		/// Buy a listed token at its listing price.
+		///
+		/// - `max_price`: the maximum price the buyer accepts to pay
+		#[pallet::weight(<T as Config>::WeightInfo::buy_token())]
+		#[transactional]
+		pub fn buy_token(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			#[pallet::compact] max_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let listing = Self::listings(token.0, token.1).ok_or(Error::<T>::ListingNotFound)?;
+			ensure!(who != listing.seller, Error::<T>::InvalidBuyer);
+			ensure!(!Self::is_expired(listing.expires_at), Error::<T>::ListingExpired);
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+
+			Listings::<T>::remove(token.0, token.1);
+			setheum_nft::Pallet::<T>::unlock_token(token);
+			setheum_nft::Pallet::<T>::do_sell(&listing.seller, &who, token, listing.currency_id, listing.price)?;
+
+			Self::deposit_event(Event::TokenBought(
+				who,
+				token.0,
+				token.1,
+				listing.currency_id,
+				listing.price,
+			));
+			Ok(().into())
+		}
+
+		/// Offer to buy a token, reserving the offered amount.
+		///
+		/// - `expires_at`: the block the offer expires at, never if `None`
+		#[pallet::weight(<T as Config>::WeightInfo::make_offer())]
+		#[transactional]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::owner_of(token)? != who, Error::<T>::InvalidBuyer);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidPrice);
+			ensure!(!Offers::<T>::contains_key(token, &who), Error::<T>::OfferAlreadyExists);
+			Self::ensure_future(expires_at)?;
+
+			<T as Config>::Currency::reserve(currency_id, &who, amount)?;
+			Offers::<T>::insert(
+				token,
+				&who,
+				Offer {
+					currency_id,
+					amount,
+					expires_at,
+				},
+			);
+
+			Self::deposit_event(Event::OfferMade(who, token.0, token.1, currency_id, amount));
+			Ok(().into())
+		}
+
+		/// Cancel an offer and unreserve the offered amount. The offerer can
+		/// cancel its offer at any time, anyone can cancel an expired offer.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_offer())]
+		#[transactional]
+		pub fn cancel_offer(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			offerer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let offerer = T::Lookup::lookup(offerer)?;
+			let offer = Self::offers(token, &offerer).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(
+				who == offerer || Self::is_expired(offer.expires_at),
+				Error::<T>::NoPermission
+			);
+
+			Offers::<T>::remove(token, &offerer);
+			<T as Config>::Currency::unreserve(offer.currency_id, &offerer, offer.amount);
+
+			Self::deposit_event(Event::OfferCancelled(offerer, token.0, token.1));
+			Ok(().into())
+		}
+
+		/// Accept an offer, selling the token to the offerer for the offered
+		/// amount. A fixed-price listing of the token is cancelled.
+		#[pallet::weight(<T as Config>::WeightInfo::accept_offer())]
+		#[transactional]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			offerer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let offerer = T::Lookup::lookup(offerer)?;
+			ensure!(Self::owner_of(token)? == who, Error::<T>::NoPermission);
+			let offer = Self::offers(token, &offerer).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(!Self::is_expired(offer.expires_at), Error::<T>::OfferExpired);
+
+			Offers::<T>::remove(token, &offerer);
+			<T as Config>::Currency::unreserve(offer.currency_id, &offerer, offer.amount);
+			if Listings::<T>::contains_key(token.0, token.1) {
+				Self::remove_listing(token);
+			}
+			setheum_nft::Pallet::<T>::do_sell(&who, &offerer, token, offer.currency_id, offer.amount)?;
+
+			Self::deposit_event(Event::OfferAccepted(
+				who,
+				offerer,
+				token.0,
+				token.1,
+				offer.currency_id,
+				offer.amount,
+			));
+			Ok(().into())
+		}
+
+		/// Sell a token by English auction, locking it until the auction
+		/// ends.
+		///
+		/// - `min_price`: the minimum bid
+		/// - `duration`: the blocks the auction lasts
+		#[pallet::weight(<T as Config>::WeightInfo::create_auction())]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] min_price: Balance,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::owner_of(token)? == who, Error::<T>::NoPermission);
+			ensure!(!min_price.is_zero(), Error::<T>::InvalidPrice);
+			ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
+
+			setheum_nft::Pallet::<T>::lock_token(token)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let auction_id = T::Auction::new_auction(now, Some(now.saturating_add(duration)))?;
+			Auctions::<T>::insert(
+				auction_id,
+				NFTAuction {
+					seller: who.clone(),
+					token,
+					currency_id,
+					min_price,
+				},
+			);
+
+			Self::deposit_event(Event::AuctionCreated(
+				auction_id,
+				who,
+				token.0,
+				token.1,
+				currency_id,
+				min_price,
+			));
+			Ok(().into())
+		}
+
+		/// Cancel an auction without bids and unlock the token.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_auction())]
+		#[transactional]
+		pub fn cancel_auction(origin: OriginFor<T>, auction_id: AuctionId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let auction = Self::auctions(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(who == auction.seller, Error::<T>::NoPermission);
+			ensure!(
+				T::Auction::auction_info(auction_id).map_or(true, |info| info.bid.is_none()),
+				Error::<T>::AuctionHasBid
+			);
+
+			T::Auction::remove_auction(auction_id);
+			Auctions::<T>::remove(auction_id);
+			setheum_nft::Pallet::<T>::unlock_token(auction.token);
+
+			Self::deposit_event(Event::AuctionCancelled(auction_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn owner_of(token: TokenOf<T>) -> Result<T::AccountId, DispatchError> {
+		orml_nft::Pallet::<T>::tokens(token.0, token.1)
+			.map(|token_info| token_info.owner)
+			.ok_or_else(|| Error::<T>::TokenNotFound.into())
+	}
+
+	fn is_expired(expires_at: Option<T::BlockNumber>) -> bool {
+		expires_at.map_or(false, |expires_at| {
+			expires_at <= <frame_system::Pallet<T>>::block_number()
+		})
+	}
+
+	fn ensure_future(expires_at: Option<T::BlockNumber>) -> DispatchResult {
+		ensure!(!Self::is_expired(expires_at), Error::<T>::InvalidExpiry);
+		Ok(())
+	}
+
+	fn remove_listing(token: TokenOf<T>) {
+		Listings::<T>::remove(token.0, token.1);
+		setheum_nft::Pallet::<T>::unlock_token(token);
+		Self::deposit_event(Event::ListingCancelled(token.0, token.1));
+	}
+}
+
+impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId> for Pallet<T> {
+	fn on_new_bid(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> OnNewBidResult<T::BlockNumber> {
+		let auction = match Self::auctions(id) {
+			Some(auction) => auction,
+			None => return T::FallbackAuctionHandler::on_new_bid(now, id, new_bid, last_bid),
+		};
+
+		let (bidder, amount) = new_bid;
+		let accept_bid = bidder != auction.seller
+			&& amount >= auction.min_price
+			&& <T as Config>::Currency::reserve(auction.currency_id, &bidder, amount).is_ok();
+		if accept_bid {
+			if let Some((last_bidder, last_amount)) = last_bid {
+				<T as Config>::Currency::unreserve(auction.currency_id, &last_bidder, last_amount);
+			}
+		}
+
+		OnNewBidResult {
+			accept_bid,
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
+		let auction = match Auctions::<T>::take(id) {
+			Some(auction) => auction,
+			None => return T::FallbackAuctionHandler::on_auction_ended(id, winner),
+		};
+
+		setheum_nft::Pallet::<T>::unlock_token(auction.token);
+		if let Some((bidder, price)) = winner {
+			<T as Config>::Currency::unreserve(auction.currency_id, &bidder, price);
+			// the sale is atomic, if it fails the token stays with the seller and
+			// the bid is returned to the bidder.
+			if setheum_nft::Pallet::<T>::do_sell(&auction.seller, &bidder, auction.token, auction.currency_id, price)
+				.is_ok()
+			{
+				Self::deposit_event(Event::AuctionDealt(id, bidder, price));
+				return;
+			}
+		}
+		Self::deposit_event(Event::AuctionAborted(id));
+	}
+}
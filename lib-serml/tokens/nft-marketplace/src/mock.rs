@@ -0,0 +1,356 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the NFT marketplace module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as nft_marketplace;
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_ok, construct_runtime, parameter_types,
+	traits::{Currency as _, Filter, InstanceFilter, OnFinalize},
+	PalletId, RuntimeDebug,
+};
+use orml_traits::{parameter_type_with_key, AuctionHandler, Change, OnNewBidResult};
+use primitives::{Amount, AuctionId, Balance, BlockNumber, CurrencyId, TokenSymbol};
+use setheum_nft::{ClassData, ClassProperty, Properties, TokenData};
+use sp_core::{crypto::AccountId32, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+	Permill,
+};
+use support::mocks::MockAddressMapping;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = AccountId32;
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = BaseFilter;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const ProxyDepositBase: u64 = 1;
+	pub const ProxyDepositFactor: u64 = 1;
+	pub const MaxProxies: u16 = 4;
+	pub const MaxPending: u32 = 2;
+	pub const AnnouncementDepositBase: u64 = 1;
+	pub const AnnouncementDepositFactor: u64 = 1;
+}
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, MaxEncodedLen)]
+pub enum ProxyType {
+	Any,
+	JustTransfer,
+	JustUtility,
+}
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::JustTransfer => matches!(c, Call::Balances(pallet_balances::Call::transfer(..))),
+			ProxyType::JustUtility => matches!(c, Call::Utility(..)),
+		}
+	}
+	fn is_superset(&self, o: &Self) -> bool {
+		self == &ProxyType::Any || self == o
+	}
+}
+pub struct BaseFilter;
+impl Filter<Call> for BaseFilter {
+	fn filter(c: &Call) -> bool {
+		match *c {
+			// Remark is used as a no-op call in the benchmarking
+			Call::System(SystemCall::remark(_)) => true,
+			Call::System(_) => false,
+			_ => true,
+		}
+	}
+}
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = ();
+	type CallHasher = BlakeTwo256;
+	type MaxPending = MaxPending;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+pub type NativeCurrency = setheum_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+}
+
+pub const NATIVE_CURRENCY_ID: CurrencyId = CurrencyId::Token(TokenSymbol::DNAR);
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+}
+
+parameter_types! {
+	pub const MaxTransfers: u32 = 10;
+}
+
+impl setheum_currencies::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = NativeCurrency;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+	type AddressMapping = MockAddressMapping;
+	type EVMBridge = ();
+	type GasToWeight = ();
+	type CashDropHandler = ();
+	type MaxTransfers = MaxTransfers;
+}
+
+parameter_types! {
+	pub const CreateClassDeposit: Balance = 200;
+	pub const CreateTokenDeposit: Balance = 100;
+	pub const NftPalletId: PalletId = PalletId(*b"set/sNFT");
+	pub const MaxRoyaltyRate: Permill = Permill::from_percent(50);
+	pub const NftDataDepositPerByte: Balance = 1;
+	pub const MaxAttributeKeyLength: u32 = 32;
+	pub const MaxAttributeValueLength: u32 = 256;
+	pub const MaxAttributesPerToken: u32 = 10;
+}
+impl setheum_nft::Config for Runtime {
+	type Event = Event;
+	type CreateClassDeposit = CreateClassDeposit;
+	type CreateTokenDeposit = CreateTokenDeposit;
+	type PalletId = NftPalletId;
+	type MultiCurrency = Currency;
+	type MaxRoyaltyRate = MaxRoyaltyRate;
+	type DataDepositPerByte = NftDataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxAttributesPerToken = MaxAttributesPerToken;
+	type Recovery = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxClassMetadata: u32 = 1024;
+	pub const MaxTokenMetadata: u32 = 1024;
+}
+
+impl orml_nft::Config for Runtime {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ClassData<Balance>;
+	type TokenData = TokenData<Balance>;
+	type MaxClassMetadata = MaxClassMetadata;
+	type MaxTokenMetadata = MaxTokenMetadata;
+}
+
+pub struct MockFallbackAuctionHandler;
+impl AuctionHandler<AccountId, Balance, u64, AuctionId> for MockFallbackAuctionHandler {
+	fn on_new_bid(
+		_now: u64,
+		_id: AuctionId,
+		_new_bid: (AccountId, Balance),
+		_last_bid: Option<(AccountId, Balance)>,
+	) -> OnNewBidResult<u64> {
+		OnNewBidResult {
+			accept_bid: false,
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(_id: AuctionId, _winner: Option<(AccountId, Balance)>) {}
+}
+
+impl orml_auction::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AuctionId = AuctionId;
+	type Handler = NFTMarketplace;
+	type WeightInfo = ();
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Currency;
+	type Auction = AuctionModule;
+	type FallbackAuctionHandler = MockFallbackAuctionHandler;
+	type WeightInfo = ();
+}
+
+use frame_system::Call as SystemCall;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		SetheumNFT: setheum_nft::{Pallet, Call, Event<T>},
+		NFTMarketplace: nft_marketplace::{Pallet, Storage, Call, Event<T>},
+		AuctionModule: orml_auction::{Pallet, Storage, Call, Event<T>},
+		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		Currency: setheum_currencies::{Pallet, Call, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = AccountId::new([1u8; 32]);
+pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CAROL: AccountId = AccountId::new([3u8; 32]);
+pub const USDJ: CurrencyId = CurrencyId::Token(TokenSymbol::USDJ);
+pub const CLASS_ID: <Runtime as orml_nft::Config>::ClassId = 0;
+pub const TOKEN_ID: <Runtime as orml_nft::Config>::TokenId = 0;
+pub const TOKEN: (
+	<Runtime as orml_nft::Config>::ClassId,
+	<Runtime as orml_nft::Config>::TokenId,
+) = (CLASS_ID, TOKEN_ID);
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 100000), (BOB, 100000), (CAROL, 100000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: vec![(ALICE, USDJ, 1000), (BOB, USDJ, 1000), (CAROL, USDJ, 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+
+			// ALICE creates a royalty class and mints a token to BOB
+			assert_ok!(SetheumNFT::create_class(
+				Origin::signed(ALICE),
+				vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::RoyaltyRequired)
+			));
+			let class_account: AccountId = NftPalletId::get().into_sub_account(CLASS_ID);
+			assert_ok!(SetheumNFT::set_royalty(
+				Origin::signed(class_account.clone()),
+				CLASS_ID,
+				Some((Permill::from_percent(10), ALICE))
+			));
+			assert_ok!(Balances::deposit_into_existing(&class_account, CreateTokenDeposit::get()).map(|_| ()));
+			assert_ok!(SetheumNFT::mint(
+				Origin::signed(class_account),
+				BOB,
+				CLASS_ID,
+				vec![1],
+				1
+			));
+		});
+		ext
+	}
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		AuctionModule::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+	}
+}
@@ -0,0 +1,283 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the NFT marketplace module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+
+fn owner_of_token() -> AccountId {
+	OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().owner
+}
+
+#[test]
+fn list_and_buy_token_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::list_token(Origin::signed(CAROL), TOKEN, USDJ, 100, None),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTMarketplace::list_token(Origin::signed(BOB), TOKEN, USDJ, 0, None),
+			Error::<Runtime>::InvalidPrice
+		);
+		assert_noop!(
+			NFTMarketplace::list_token(Origin::signed(BOB), TOKEN, USDJ, 100, Some(1)),
+			Error::<Runtime>::InvalidExpiry
+		);
+
+		assert_ok!(NFTMarketplace::list_token(
+			Origin::signed(BOB),
+			TOKEN,
+			USDJ,
+			100,
+			Some(10)
+		));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::TokenListed(
+			BOB, CLASS_ID, TOKEN_ID, USDJ, 100,
+		)));
+		assert!(SetheumNFT::is_locked(TOKEN));
+		assert_noop!(
+			SetheumNFT::transfer(Origin::signed(BOB), CAROL, TOKEN),
+			setheum_nft::Error::<Runtime>::RoyaltyPaymentRequired
+		);
+		assert_noop!(
+			NFTMarketplace::list_token(Origin::signed(BOB), TOKEN, USDJ, 100, None),
+			setheum_nft::Error::<Runtime>::TokenLocked
+		);
+
+		assert_noop!(
+			NFTMarketplace::buy_token(Origin::signed(BOB), TOKEN, 100),
+			Error::<Runtime>::InvalidBuyer
+		);
+		assert_noop!(
+			NFTMarketplace::buy_token(Origin::signed(CAROL), TOKEN, 99),
+			Error::<Runtime>::PriceTooHigh
+		);
+		assert_ok!(NFTMarketplace::buy_token(Origin::signed(CAROL), TOKEN, 100));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::TokenBought(
+			CAROL, CLASS_ID, TOKEN_ID, USDJ, 100,
+		)));
+		assert_eq!(owner_of_token(), CAROL);
+		assert!(!SetheumNFT::is_locked(TOKEN));
+		assert_eq!(NFTMarketplace::listings(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(Tokens::free_balance(USDJ, &CAROL), 900);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1090);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1010);
+	});
+}
+
+#[test]
+fn cancel_listing_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTMarketplace::list_token(
+			Origin::signed(BOB),
+			TOKEN,
+			USDJ,
+			100,
+			Some(10)
+		));
+		assert_noop!(
+			NFTMarketplace::cancel_listing(Origin::signed(CAROL), TOKEN),
+			Error::<Runtime>::NoPermission
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			NFTMarketplace::buy_token(Origin::signed(CAROL), TOKEN, 100),
+			Error::<Runtime>::ListingExpired
+		);
+		// anyone can cancel an expired listing
+		assert_ok!(NFTMarketplace::cancel_listing(Origin::signed(CAROL), TOKEN));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::ListingCancelled(
+			CLASS_ID, TOKEN_ID,
+		)));
+		assert_eq!(NFTMarketplace::listings(CLASS_ID, TOKEN_ID), None);
+		assert!(!SetheumNFT::is_locked(TOKEN));
+		assert_noop!(
+			NFTMarketplace::cancel_listing(Origin::signed(BOB), TOKEN),
+			Error::<Runtime>::ListingNotFound
+		);
+	});
+}
+
+#[test]
+fn offers_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::make_offer(Origin::signed(BOB), TOKEN, USDJ, 200, None),
+			Error::<Runtime>::InvalidBuyer
+		);
+		assert_ok!(NFTMarketplace::make_offer(
+			Origin::signed(CAROL),
+			TOKEN,
+			USDJ,
+			200,
+			None
+		));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::OfferMade(
+			CAROL, CLASS_ID, TOKEN_ID, USDJ, 200,
+		)));
+		assert_eq!(Tokens::reserved_balance(USDJ, &CAROL), 200);
+		assert_noop!(
+			NFTMarketplace::make_offer(Origin::signed(CAROL), TOKEN, USDJ, 300, None),
+			Error::<Runtime>::OfferAlreadyExists
+		);
+		assert_ok!(NFTMarketplace::make_offer(
+			Origin::signed(ALICE),
+			TOKEN,
+			USDJ,
+			50,
+			Some(5)
+		));
+
+		// accepting an offer cancels the listing
+		assert_ok!(NFTMarketplace::list_token(Origin::signed(BOB), TOKEN, USDJ, 500, None));
+		assert_noop!(
+			NFTMarketplace::accept_offer(Origin::signed(CAROL), TOKEN, CAROL),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTMarketplace::accept_offer(Origin::signed(BOB), TOKEN, CAROL));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::OfferAccepted(
+			BOB, CAROL, CLASS_ID, TOKEN_ID, USDJ, 200,
+		)));
+		assert_eq!(owner_of_token(), CAROL);
+		assert_eq!(NFTMarketplace::listings(CLASS_ID, TOKEN_ID), None);
+		assert!(!SetheumNFT::is_locked(TOKEN));
+		assert_eq!(NFTMarketplace::offers(TOKEN, CAROL), None);
+		assert_eq!(Tokens::reserved_balance(USDJ, &CAROL), 0);
+		assert_eq!(Tokens::free_balance(USDJ, &CAROL), 800);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1180);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 970);
+
+		System::set_block_number(5);
+		assert_noop!(
+			NFTMarketplace::accept_offer(Origin::signed(CAROL), TOKEN, ALICE),
+			Error::<Runtime>::OfferExpired
+		);
+		// anyone can cancel an expired offer
+		assert_ok!(NFTMarketplace::cancel_offer(Origin::signed(BOB), TOKEN, ALICE));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::OfferCancelled(
+			ALICE, CLASS_ID, TOKEN_ID,
+		)));
+		assert_eq!(Tokens::reserved_balance(USDJ, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1020);
+	});
+}
+
+#[test]
+fn auction_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(CAROL), TOKEN, USDJ, 100, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(BOB), TOKEN, USDJ, 100, 0),
+			Error::<Runtime>::InvalidDuration
+		);
+		assert_ok!(NFTMarketplace::create_auction(
+			Origin::signed(BOB),
+			TOKEN,
+			USDJ,
+			100,
+			10
+		));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::AuctionCreated(
+			0, BOB, CLASS_ID, TOKEN_ID, USDJ, 100,
+		)));
+		assert!(SetheumNFT::is_locked(TOKEN));
+
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(CAROL), 0, 50),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(BOB), 0, 100),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 100));
+		assert_eq!(Tokens::reserved_balance(USDJ, &ALICE), 100);
+		assert_ok!(AuctionModule::bid(Origin::signed(CAROL), 0, 150));
+		assert_eq!(Tokens::reserved_balance(USDJ, &ALICE), 0);
+		assert_eq!(Tokens::reserved_balance(USDJ, &CAROL), 150);
+		assert_noop!(
+			NFTMarketplace::cancel_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::AuctionHasBid
+		);
+
+		run_to_block(12);
+		System::assert_last_event(Event::nft_marketplace(crate::Event::AuctionDealt(0, CAROL, 150)));
+		assert_eq!(owner_of_token(), CAROL);
+		assert!(!SetheumNFT::is_locked(TOKEN));
+		assert_eq!(NFTMarketplace::auctions(0), None);
+		assert_eq!(Tokens::reserved_balance(USDJ, &CAROL), 0);
+		assert_eq!(Tokens::free_balance(USDJ, &CAROL), 850);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 1135);
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 1015);
+	});
+}
+
+#[test]
+fn cancel_and_abort_auction_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTMarketplace::create_auction(
+			Origin::signed(BOB),
+			TOKEN,
+			USDJ,
+			100,
+			10
+		));
+		assert_noop!(
+			NFTMarketplace::cancel_auction(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTMarketplace::cancel_auction(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::nft_marketplace(crate::Event::AuctionCancelled(0)));
+		assert_eq!(NFTMarketplace::auctions(0), None);
+		assert_eq!(AuctionModule::auctions(0), None);
+		assert!(!SetheumNFT::is_locked(TOKEN));
+
+		assert_ok!(NFTMarketplace::create_auction(
+			Origin::signed(BOB),
+			TOKEN,
+			USDJ,
+			100,
+			10
+		));
+		run_to_block(12);
+		System::assert_last_event(Event::nft_marketplace(crate::Event::AuctionAborted(1)));
+		assert_eq!(owner_of_token(), BOB);
+		assert!(!SetheumNFT::is_locked(TOKEN));
+	});
+}
+
+#[test]
+fn other_auctions_go_to_fallback_handler() {
+	ExtBuilder::default().build().execute_with(|| {
+		let auction_id = <AuctionModule as Auction<_, _>>::new_auction(1, Some(10)).unwrap();
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(CAROL), auction_id, 100),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+	});
+}
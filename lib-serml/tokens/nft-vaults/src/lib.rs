@@ -0,0 +1,419 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # NFT Vaults Module
+//!
+//! ## Overview
+//!
+//! Fractionalization of `setheum_nft` tokens. A curator locks a token, or all
+//! tokens of a class, in a vault and receives the whole supply of the
+//! fungible `CurrencyId::Fraction` currency of the vault, which can be
+//! transferred and traded on the DEX like any other currency.
+//!
+//! The holder of the whole supply can redeem the tokens of the vault. Anyone
+//! can buy the tokens out at the reserve price of the vault, paying for the
+//! shares they do not hold. The proceeds are not paid to the holders at the
+//! time of the buyout, but to whoever burns shares afterwards: shares left in
+//! a DEX liquidity pool can still be swapped out, or withdrawn with the
+//! liquidity, and then claimed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, MultiReservableCurrency, NFT};
+use primitives::{Balance, CurrencyId, VaultId};
+use setheum_nft::{ClassIdOf, TokenIdOf};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, Saturating, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The tokens locked in a vault.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum VaultAsset<ClassId, TokenId> {
+	/// A single token.
+	Token(ClassId, TokenId),
+	/// All tokens of a class.
+	Class(ClassId, Vec<TokenId>),
+}
+
+impl<ClassId: Copy, TokenId: Copy> VaultAsset<ClassId, TokenId> {
+	/// The tokens locked in the vault.
+	pub fn tokens(&self) -> Vec<(ClassId, TokenId)> {
+		match self {
+			VaultAsset::Token(class_id, token_id) => sp_std::vec![(*class_id, *token_id)],
+			VaultAsset::Class(class_id, token_ids) => token_ids.iter().map(|token_id| (*class_id, *token_id)).collect(),
+		}
+	}
+}
+
+/// The status of a vault.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum VaultStatus {
+	/// The tokens are locked in the vault.
+	Active,
+	/// The tokens are bought out, the holders can claim the proceeds.
+	BoughtOut,
+}
+
+/// A vault of fractionalized tokens.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Vault<AccountId, ClassId, TokenId> {
+	/// The account which fractionalized the tokens.
+	pub curator: AccountId,
+	/// The locked tokens.
+	pub asset: VaultAsset<ClassId, TokenId>,
+	/// The total supply of shares.
+	pub supply: Balance,
+	/// The currency of the reserve price.
+	pub currency_id: CurrencyId,
+	/// The price to buy out the whole supply.
+	pub reserve_price: Balance,
+	/// The status of the vault.
+	pub status: VaultStatus,
+}
+
+pub type VaultOf<T> = Vault<<T as frame_system::Config>::AccountId, ClassIdOf<T>, TokenIdOf<T>>;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + setheum_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to issue shares, reserve vault deposits and pay
+		/// buyouts with.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The native currency id, to reserve vault deposits in.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The deposit reserved from the curator for every vault.
+		#[pallet::constant]
+		type VaultDeposit: Get<Balance>;
+
+		/// The maximum number of tokens of a class to lock in a vault.
+		#[pallet::constant]
+		type MaxClassTokens: Get<u32>;
+
+		/// The NFT Vaults module id, the vault accounts are derived from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The supply of shares is zero or below the existential deposit.
+		InvalidSupply,
+		/// The reserve price is zero.
+		InvalidReservePrice,
+		/// The token does not exist.
+		TokenNotFound,
+		/// The class does not exist or has no token.
+		ClassNotFound,
+		/// The caller does not own the token or every token of the class.
+		NoPermission,
+		/// The class has more tokens than `MaxClassTokens`.
+		TooManyTokens,
+		/// No more vault id is available.
+		NoAvailableVaultId,
+		/// The vault does not exist.
+		VaultNotFound,
+		/// The vault is bought out.
+		VaultNotActive,
+		/// The vault is not bought out.
+		VaultNotBoughtOut,
+		/// The caller does not hold the whole supply of shares.
+		InsufficientShares,
+		/// The caller holds no share.
+		NoShares,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Tokens fractionalized. \[vault_id, curator, share_currency_id,
+		/// supply\]
+		Fractionalized(VaultId, T::AccountId, CurrencyId, Balance),
+		/// Tokens redeemed by the holder of the whole supply. \[vault_id,
+		/// who\]
+		Redeemed(VaultId, T::AccountId),
+		/// Tokens bought out. \[vault_id, buyer, currency_id, amount\]
+		BoughtOut(VaultId, T::AccountId, CurrencyId, Balance),
+		/// Buyout proceeds claimed. \[vault_id, who, shares, amount\]
+		ProceedsClaimed(VaultId, T::AccountId, Balance, Balance),
+		/// Vault closed, every share is burned. \[vault_id\]
+		VaultClosed(VaultId),
+	}
+
+	/// The next vault id.
+	#[pallet::storage]
+	#[pallet::getter(fn next_vault_id)]
+	pub type NextVaultId<T: Config> = StorageValue<_, VaultId, ValueQuery>;
+
+	/// The vaults of fractionalized tokens.
+	///
+	/// Vaults: map VaultId => Option<Vault>
+	#[pallet::storage]
+	#[pallet::getter(fn vaults)]
+	pub type Vaults<T: Config> = StorageMap<_, Twox64Concat, VaultId, VaultOf<T>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lock a token in a new vault and issue `supply` shares of it to the
+		/// caller.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `supply`: the total supply of shares
+		/// - `currency_id`: the currency of the reserve price
+		/// - `reserve_price`: the price to buy out the whole supply
+		#[pallet::weight(<T as Config>::WeightInfo::fractionalize_token())]
+		#[transactional]
+		pub fn fractionalize_token(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			#[pallet::compact] supply: Balance,
+			currency_id: CurrencyId,
+			#[pallet::compact] reserve_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenNotFound)?;
+			ensure!(who == token_info.owner, Error::<T>::NoPermission);
+
+			Self::do_fractionalize(
+				who,
+				VaultAsset::Token(token.0, token.1),
+				supply,
+				currency_id,
+				reserve_price,
+			)?;
+			Ok(().into())
+		}
+
+		/// Lock all tokens of a class in a new vault and issue `supply`
+		/// shares of them to the caller, who must own every token of the
+		/// class.
+		///
+		/// - `class_id`: the class ID
+		/// - `supply`: the total supply of shares
+		/// - `currency_id`: the currency of the reserve price
+		/// - `reserve_price`: the price to buy out the whole supply
+		#[pallet::weight(<T as Config>::WeightInfo::fractionalize_class(T::MaxClassTokens::get()))]
+		#[transactional]
+		pub fn fractionalize_class(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			#[pallet::compact] supply: Balance,
+			currency_id: CurrencyId,
+			#[pallet::compact] reserve_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut token_ids: Vec<TokenIdOf<T>> = Vec::new();
+			for (token_id, token_info) in orml_nft::Tokens::<T>::iter_prefix(class_id) {
+				ensure!(who == token_info.owner, Error::<T>::NoPermission);
+				ensure!(
+					token_ids.len() < T::MaxClassTokens::get() as usize,
+					Error::<T>::TooManyTokens
+				);
+				token_ids.push(token_id);
+			}
+			ensure!(!token_ids.is_empty(), Error::<T>::ClassNotFound);
+
+			Self::do_fractionalize(
+				who,
+				VaultAsset::Class(class_id, token_ids),
+				supply,
+				currency_id,
+				reserve_price,
+			)?;
+			Ok(().into())
+		}
+
+		/// Redeem the tokens of a vault, burning the whole supply of shares
+		/// held by the caller.
+		#[pallet::weight(<T as Config>::WeightInfo::redeem(T::MaxClassTokens::get()))]
+		#[transactional]
+		pub fn redeem(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let vault = Self::vaults(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			ensure!(vault.status == VaultStatus::Active, Error::<T>::VaultNotActive);
+			let share_currency_id = CurrencyId::Fraction(vault_id);
+			ensure!(
+				<T as Config>::Currency::free_balance(share_currency_id, &who) >= vault.supply,
+				Error::<T>::InsufficientShares
+			);
+
+			<T as Config>::Currency::withdraw(share_currency_id, &who, vault.supply)?;
+			Self::release_tokens(vault_id, &vault, &who)?;
+
+			Self::deposit_event(Event::Redeemed(vault_id, who));
+			Self::close_vault(vault_id, &vault);
+			Ok(().into())
+		}
+
+		/// Buy out the tokens of a vault at its reserve price, paying for
+		/// the shares not held by the caller and burning those held.
+		#[pallet::weight(<T as Config>::WeightInfo::buyout(T::MaxClassTokens::get()))]
+		#[transactional]
+		pub fn buyout(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut vault = Self::vaults(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			ensure!(vault.status == VaultStatus::Active, Error::<T>::VaultNotActive);
+
+			let share_currency_id = CurrencyId::Fraction(vault_id);
+			let shares = <T as Config>::Currency::free_balance(share_currency_id, &who).min(vault.supply);
+			let outstanding = vault.supply.saturating_sub(shares);
+			let amount = Self::buyout_price(&vault, outstanding);
+
+			<T as Config>::Currency::transfer(vault.currency_id, &who, &Self::vault_account_id(vault_id), amount)?;
+			<T as Config>::Currency::withdraw(share_currency_id, &who, shares)?;
+			Self::release_tokens(vault_id, &vault, &who)?;
+
+			Self::deposit_event(Event::BoughtOut(vault_id, who, vault.currency_id, amount));
+			if outstanding.is_zero() {
+				Self::close_vault(vault_id, &vault);
+			} else {
+				vault.status = VaultStatus::BoughtOut;
+				Vaults::<T>::insert(vault_id, vault);
+			}
+			Ok(().into())
+		}
+
+		/// Claim the part of the buyout proceeds of the shares held by the
+		/// caller, burning them.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_proceeds())]
+		#[transactional]
+		pub fn claim_proceeds(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let vault = Self::vaults(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			ensure!(vault.status == VaultStatus::BoughtOut, Error::<T>::VaultNotBoughtOut);
+
+			let share_currency_id = CurrencyId::Fraction(vault_id);
+			let shares = <T as Config>::Currency::free_balance(share_currency_id, &who);
+			ensure!(!shares.is_zero(), Error::<T>::NoShares);
+
+			// pay pro rata of the remaining proceeds, so that the last holder gets
+			// the rounding remainder.
+			let vault_account = Self::vault_account_id(vault_id);
+			let proceeds = <T as Config>::Currency::free_balance(vault.currency_id, &vault_account);
+			let total_shares = <T as Config>::Currency::total_issuance(share_currency_id);
+			let amount = multiply_by_rational(proceeds, shares, total_shares).unwrap_or_default();
+
+			<T as Config>::Currency::withdraw(share_currency_id, &who, shares)?;
+			<T as Config>::Currency::transfer(vault.currency_id, &vault_account, &who, amount)?;
+
+			Self::deposit_event(Event::ProceedsClaimed(vault_id, who, shares, amount));
+			if <T as Config>::Currency::total_issuance(share_currency_id).is_zero() {
+				Self::close_vault(vault_id, &vault);
+			}
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get account of a vault, which keeps its tokens and buyout proceeds.
+	pub fn vault_account_id(vault_id: VaultId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(vault_id)
+	}
+
+	/// The price to pay to buy out the `outstanding` shares of a vault.
+	pub fn buyout_price(vault: &VaultOf<T>, outstanding: Balance) -> Balance {
+		multiply_by_rational(vault.reserve_price, outstanding, vault.supply).unwrap_or(vault.reserve_price)
+	}
+
+	fn do_fractionalize(
+		who: T::AccountId,
+		asset: VaultAsset<ClassIdOf<T>, TokenIdOf<T>>,
+		supply: Balance,
+		currency_id: CurrencyId,
+		reserve_price: Balance,
+	) -> DispatchResult {
+		ensure!(!supply.is_zero(), Error::<T>::InvalidSupply);
+		ensure!(!reserve_price.is_zero(), Error::<T>::InvalidReservePrice);
+
+		let vault_id = NextVaultId::<T>::try_mutate(|id| -> sp_std::result::Result<VaultId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableVaultId)?;
+			Ok(current_id)
+		})?;
+		let share_currency_id = CurrencyId::Fraction(vault_id);
+		ensure!(
+			supply >= <T as Config>::Currency::minimum_balance(share_currency_id),
+			Error::<T>::InvalidSupply
+		);
+		<T as Config>::Currency::reserve(T::GetNativeCurrencyId::get(), &who, T::VaultDeposit::get())?;
+
+		let vault_account = Self::vault_account_id(vault_id);
+		for token in asset.tokens() {
+			<setheum_nft::Pallet<T> as NFT<T::AccountId>>::transfer(&who, &vault_account, token)?;
+		}
+		<T as Config>::Currency::deposit(share_currency_id, &who, supply)?;
+
+		Vaults::<T>::insert(
+			vault_id,
+			Vault {
+				curator: who.clone(),
+				asset,
+				supply,
+				currency_id,
+				reserve_price,
+				status: VaultStatus::Active,
+			},
+		);
+		Self::deposit_event(Event::Fractionalized(vault_id, who, share_currency_id, supply));
+		Ok(())
+	}
+
+	fn release_tokens(vault_id: VaultId, vault: &VaultOf<T>, to: &T::AccountId) -> DispatchResult {
+		let vault_account = Self::vault_account_id(vault_id);
+		for token in vault.asset.tokens() {
+			<setheum_nft::Pallet<T> as NFT<T::AccountId>>::transfer(&vault_account, to, token)?;
+		}
+		Ok(())
+	}
+
+	fn close_vault(vault_id: VaultId, vault: &VaultOf<T>) {
+		<T as Config>::Currency::unreserve(T::GetNativeCurrencyId::get(), &vault.curator, T::VaultDeposit::get());
+		Vaults::<T>::remove(vault_id);
+		Self::deposit_event(Event::VaultClosed(vault_id));
+	}
+}
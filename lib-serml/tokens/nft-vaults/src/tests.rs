@@ -0,0 +1,240 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the NFT vaults module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+
+fn owner_of(token_id: <Runtime as orml_nft::Config>::TokenId) -> AccountId {
+	OrmlNFT::tokens(CLASS_ID, token_id).unwrap().owner
+}
+
+#[test]
+fn fractionalize_token_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTVaults::fractionalize_token(Origin::signed(CAROL), TOKEN, 1000, USDJ, 500),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTVaults::fractionalize_token(Origin::signed(BOB), (CLASS_ID, 5), 1000, USDJ, 500),
+			Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			NFTVaults::fractionalize_token(Origin::signed(BOB), TOKEN, 0, USDJ, 500),
+			Error::<Runtime>::InvalidSupply
+		);
+		assert_noop!(
+			NFTVaults::fractionalize_token(Origin::signed(BOB), TOKEN, 9, USDJ, 500),
+			Error::<Runtime>::InvalidSupply
+		);
+		assert_noop!(
+			NFTVaults::fractionalize_token(Origin::signed(BOB), TOKEN, 1000, USDJ, 0),
+			Error::<Runtime>::InvalidReservePrice
+		);
+
+		assert_ok!(NFTVaults::fractionalize_token(
+			Origin::signed(BOB),
+			TOKEN,
+			1000,
+			USDJ,
+			500
+		));
+		System::assert_last_event(Event::nft_vaults(crate::Event::Fractionalized(0, BOB, SHARES, 1000)));
+		assert_eq!(
+			NFTVaults::vaults(0),
+			Some(Vault {
+				curator: BOB,
+				asset: VaultAsset::Token(CLASS_ID, TOKEN_ID),
+				supply: 1000,
+				currency_id: USDJ,
+				reserve_price: 500,
+				status: VaultStatus::Active,
+			})
+		);
+		assert_eq!(NFTVaults::next_vault_id(), 1);
+		assert_eq!(owner_of(TOKEN_ID), NFTVaults::vault_account_id(0));
+		assert_eq!(Currency::free_balance(SHARES, &BOB), 1000);
+		assert_eq!(Currency::reserved_balance(NATIVE_CURRENCY_ID, &BOB), 10);
+	});
+}
+
+#[test]
+fn fractionalize_class_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTVaults::fractionalize_class(Origin::signed(CAROL), CLASS_ID, 1000, USDJ, 500),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTVaults::fractionalize_class(Origin::signed(BOB), 1, 1000, USDJ, 500),
+			Error::<Runtime>::ClassNotFound
+		);
+
+		assert_ok!(NFTVaults::fractionalize_class(
+			Origin::signed(BOB),
+			CLASS_ID,
+			1000,
+			USDJ,
+			500
+		));
+		assert_eq!(
+			NFTVaults::vaults(0).unwrap().asset,
+			VaultAsset::Class(CLASS_ID, vec![0, 1])
+		);
+		assert_eq!(owner_of(0), NFTVaults::vault_account_id(0));
+		assert_eq!(owner_of(1), NFTVaults::vault_account_id(0));
+		assert_eq!(Currency::free_balance(SHARES, &BOB), 1000);
+	});
+}
+
+#[test]
+fn fractionalize_class_fails_with_too_many_tokens() {
+	ExtBuilder::default().build().execute_with(|| {
+		let class_account: AccountId = NftPalletId::get().into_sub_account(CLASS_ID);
+		assert_ok!(Balances::deposit_into_existing(&class_account, 2 * CreateTokenDeposit::get()).map(|_| ()));
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_account),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+		assert_noop!(
+			NFTVaults::fractionalize_class(Origin::signed(BOB), CLASS_ID, 1000, USDJ, 500),
+			Error::<Runtime>::TooManyTokens
+		);
+	});
+}
+
+#[test]
+fn redeem_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTVaults::fractionalize_token(
+			Origin::signed(BOB),
+			TOKEN,
+			1000,
+			USDJ,
+			500
+		));
+		assert_ok!(Currency::transfer(SHARES, &BOB, &CAROL, 400));
+		assert_noop!(
+			NFTVaults::redeem(Origin::signed(BOB), 0),
+			Error::<Runtime>::InsufficientShares
+		);
+
+		// holding the whole supply allows to redeem
+		assert_ok!(Currency::transfer(SHARES, &BOB, &CAROL, 600));
+		assert_ok!(NFTVaults::redeem(Origin::signed(CAROL), 0));
+		System::assert_has_event(Event::nft_vaults(crate::Event::Redeemed(0, CAROL)));
+		System::assert_last_event(Event::nft_vaults(crate::Event::VaultClosed(0)));
+		assert_eq!(owner_of(TOKEN_ID), CAROL);
+		assert_eq!(Currency::total_issuance(SHARES), 0);
+		assert_eq!(NFTVaults::vaults(0), None);
+		assert_eq!(Currency::reserved_balance(NATIVE_CURRENCY_ID, &BOB), 0);
+		assert_noop!(
+			NFTVaults::redeem(Origin::signed(CAROL), 0),
+			Error::<Runtime>::VaultNotFound
+		);
+	});
+}
+
+#[test]
+fn buyout_and_claim_proceeds_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTVaults::fractionalize_token(
+			Origin::signed(BOB),
+			TOKEN,
+			1000,
+			USDJ,
+			500
+		));
+		assert_ok!(Currency::transfer(SHARES, &BOB, &CAROL, 300));
+		assert_ok!(Currency::transfer(SHARES, &BOB, &ALICE, 200));
+		assert_noop!(
+			NFTVaults::claim_proceeds(Origin::signed(BOB), 0),
+			Error::<Runtime>::VaultNotBoughtOut
+		);
+
+		// CAROL pays for the 700 shares she does not hold
+		assert_ok!(NFTVaults::buyout(Origin::signed(CAROL), 0));
+		System::assert_last_event(Event::nft_vaults(crate::Event::BoughtOut(0, CAROL, USDJ, 350)));
+		assert_eq!(owner_of(TOKEN_ID), CAROL);
+		assert_eq!(Currency::free_balance(USDJ, &CAROL), 650);
+		assert_eq!(Currency::free_balance(SHARES, &CAROL), 0);
+		assert_eq!(Currency::free_balance(USDJ, &NFTVaults::vault_account_id(0)), 350);
+		assert_eq!(NFTVaults::vaults(0).unwrap().status, VaultStatus::BoughtOut);
+		assert_noop!(
+			NFTVaults::buyout(Origin::signed(ALICE), 0),
+			Error::<Runtime>::VaultNotActive
+		);
+		assert_noop!(
+			NFTVaults::redeem(Origin::signed(ALICE), 0),
+			Error::<Runtime>::VaultNotActive
+		);
+		assert_noop!(
+			NFTVaults::claim_proceeds(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NoShares
+		);
+
+		assert_ok!(NFTVaults::claim_proceeds(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::nft_vaults(crate::Event::ProceedsClaimed(0, BOB, 500, 250)));
+		assert_eq!(Currency::free_balance(USDJ, &BOB), 1250);
+		assert!(NFTVaults::vaults(0).is_some());
+
+		assert_ok!(NFTVaults::claim_proceeds(Origin::signed(ALICE), 0));
+		System::assert_has_event(Event::nft_vaults(crate::Event::ProceedsClaimed(0, ALICE, 200, 100)));
+		System::assert_last_event(Event::nft_vaults(crate::Event::VaultClosed(0)));
+		assert_eq!(Currency::free_balance(USDJ, &ALICE), 1100);
+		assert_eq!(Currency::free_balance(USDJ, &NFTVaults::vault_account_id(0)), 0);
+		assert_eq!(NFTVaults::vaults(0), None);
+		assert_eq!(Currency::reserved_balance(NATIVE_CURRENCY_ID, &BOB), 0);
+	});
+}
+
+#[test]
+fn shares_moved_after_buyout_can_claim_proceeds() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTVaults::fractionalize_token(
+			Origin::signed(BOB),
+			TOKEN,
+			1000,
+			USDJ,
+			500
+		));
+		// ALICE stands for a DEX liquidity pool holding shares
+		assert_ok!(Currency::transfer(SHARES, &BOB, &ALICE, 400));
+		assert_ok!(NFTVaults::buyout(Origin::signed(CAROL), 0));
+		System::assert_last_event(Event::nft_vaults(crate::Event::BoughtOut(0, CAROL, USDJ, 500)));
+
+		// the shares swapped out of the pool after the buyout claim their part
+		assert_ok!(Currency::transfer(SHARES, &ALICE, &CAROL, 400));
+		assert_ok!(NFTVaults::claim_proceeds(Origin::signed(CAROL), 0));
+		System::assert_last_event(Event::nft_vaults(crate::Event::ProceedsClaimed(0, CAROL, 400, 200)));
+		assert_eq!(Currency::free_balance(USDJ, &CAROL), 700);
+
+		assert_ok!(NFTVaults::claim_proceeds(Origin::signed(BOB), 0));
+		System::assert_has_event(Event::nft_vaults(crate::Event::ProceedsClaimed(0, BOB, 600, 300)));
+		assert_eq!(NFTVaults::vaults(0), None);
+	});
+}
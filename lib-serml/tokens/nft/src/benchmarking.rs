@@ -83,6 +83,7 @@ benchmarks! {
 
 	// burn NFT token
 	burn {
+		let a in 0 .. T::MaxAttributesPerToken::get();
 		let caller: T::AccountId = account("caller", 0, SEED);
 		let to: T::AccountId = account("to", 0, SEED);
 		let to_lookup = T::Lookup::unlookup(to.clone());
@@ -93,12 +94,16 @@ benchmarks! {
 		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
 		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
 		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
-		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		for i in 0 .. a {
+			crate::Pallet::<T>::set_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), i.to_le_bytes().to_vec(), vec![1])?;
+		}
 	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()))
 
 	// burn NFT token with remark
 	burn_with_remark {
 		let b in 0 .. *T::BlockLength::get().max.get(DispatchClass::Normal) as u32;
+		let a in 0 .. T::MaxAttributesPerToken::get();
 		let remark_message = vec![1; b as usize];
 		let caller: T::AccountId = account("caller", 0, SEED);
 		let to: T::AccountId = account("to", 0, SEED);
@@ -110,7 +115,10 @@ benchmarks! {
 		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
 		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
 		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
-		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		for i in 0 .. a {
+			crate::Pallet::<T>::set_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), i.to_le_bytes().to_vec(), vec![1])?;
+		}
 	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()), remark_message)
 
 	// destroy NFT class
@@ -125,6 +133,169 @@ benchmarks! {
 		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
 		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
 	}: _(RawOrigin::Signed(module_account), 0u32.into(), caller_lookup)
+
+	// set royalty of NFT class
+	set_royalty {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient);
+
+		let base_currency_amount = dollar(1000);
+
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Some((T::MaxRoyaltyRate::get(), recipient_lookup)))
+
+	// update metadata of NFT class
+	update_class_metadata {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::MetadataMutable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), vec![2])
+
+	// update metadata of NFT token
+	update_token_metadata {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::MetadataMutable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), vec![2])
+
+	// overwrite attribute of NFT token
+	set_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+		let key = vec![1; T::MaxAttributeKeyLength::get() as usize];
+		let value = vec![1; T::MaxAttributeValueLength::get() as usize];
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::MetadataMutable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::set_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), key.clone(), vec![1])?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), key, value)
+
+	// remove attribute of NFT token
+	remove_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+		let key = vec![1; T::MaxAttributeKeyLength::get() as usize];
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::MetadataMutable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::set_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), key.clone(), vec![1])?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), key)
+
+	// approve an account to transfer NFT token
+	approve {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+	}: _(RawOrigin::Signed(to), Some(caller_lookup), (0u32.into(), 0u32.into()))
+
+	// approve an operator of all NFT tokens of a class
+	set_approval_for_all {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let operator: T::AccountId = account("operator", 0, SEED);
+		let operator_lookup = T::Lookup::unlookup(operator);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(caller), operator_lookup, 0u32.into(), true)
+
+	// transfer NFT token as an operator of the owner
+	transfer_from {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let operator: T::AccountId = account("operator", 0, SEED);
+		let operator_lookup = T::Lookup::unlookup(operator.clone());
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup.clone(), 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::set_approval_for_all(RawOrigin::Signed(to).into(), operator_lookup, 0u32.into(), true)?;
+	}: _(RawOrigin::Signed(operator), to_lookup, caller_lookup, (0u32.into(), 0u32.into()))
+
+	// set the terms of NFT class
+	set_class_terms {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Expiring))?;
+		let terms = ClassTerms {
+			transfer_locked_until: Some(100u32.into()),
+			token_lifetime: Some(100u32.into()),
+		};
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), terms)
+
+	// burn expired NFT token
+	burn_expired {
+		let a in 0 .. T::MaxAttributesPerToken::get();
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Expiring))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		let terms = ClassTerms {
+			transfer_locked_until: None,
+			token_lifetime: Some(1u32.into()),
+		};
+		crate::Pallet::<T>::set_class_terms(RawOrigin::Signed(module_account.clone()).into(), 0u32.into(), terms)?;
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		for i in 0 .. a {
+			crate::Pallet::<T>::set_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), i.to_le_bytes().to_vec(), vec![1])?;
+		}
+		frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 1u32.into());
+	}: _(RawOrigin::Signed(caller), (0u32.into(), 0u32.into()))
 }
 
 #[cfg(test)]
@@ -139,11 +310,13 @@ mod mock {
 		weights::Weight,
 		PalletId, RuntimeDebug,
 	};
+	use orml_traits::parameter_type_with_key;
+	use primitives::{Amount, CurrencyId};
 	use sp_core::{crypto::AccountId32, H256};
 	use sp_runtime::{
 		testing::Header,
 		traits::{BlakeTwo256, IdentityLookup},
-		Perbill,
+		Perbill, Permill,
 	};
 
 	parameter_types! {
@@ -254,16 +427,45 @@ mod mock {
 		type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	}
 
+	parameter_type_with_key! {
+		pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+			Default::default()
+		};
+	}
+
+	impl orml_tokens::Config for Runtime {
+		type Event = ();
+		type Balance = Balance;
+		type Amount = Amount;
+		type CurrencyId = CurrencyId;
+		type WeightInfo = ();
+		type ExistentialDeposits = ExistentialDeposits;
+		type OnDust = ();
+		type MaxLocks = ();
+	}
+
 	parameter_types! {
 		pub const CreateClassDeposit: Balance = 200;
 		pub const CreateTokenDeposit: Balance = 100;
 		pub const NftPalletId: PalletId = PalletId(*b"set/sNFT");
+		pub const MaxRoyaltyRate: Permill = Permill::from_percent(50);
+		pub const NftDataDepositPerByte: Balance = 1;
+		pub const MaxAttributeKeyLength: u32 = 32;
+		pub const MaxAttributeValueLength: u32 = 256;
+		pub const MaxAttributesPerToken: u32 = 10;
 	}
 	impl crate::Config for Runtime {
 		type Event = ();
 		type CreateClassDeposit = CreateClassDeposit;
 		type CreateTokenDeposit = CreateTokenDeposit;
 		type PalletId = NftPalletId;
+		type MultiCurrency = Tokens;
+		type MaxRoyaltyRate = MaxRoyaltyRate;
+		type DataDepositPerByte = NftDataDepositPerByte;
+		type MaxAttributeKeyLength = MaxAttributeKeyLength;
+		type MaxAttributeValueLength = MaxAttributeValueLength;
+		type MaxAttributesPerToken = MaxAttributesPerToken;
+		type Recovery = ();
 		type WeightInfo = ();
 	}
 
@@ -295,6 +497,7 @@ mod mock {
 			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 			Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 			OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
+			Tokens: orml_tokens::{Pallet, Storage, Config<T>},
 			NFT: nft::{Pallet, Call, Event<T>},
 		}
 	);
@@ -359,4 +562,74 @@ mod tests {
 			assert_ok!(test_benchmark_destroy_class::<Runtime>());
 		});
 	}
+
+	#[test]
+	fn test_set_royalty() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_royalty::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_update_class_metadata() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_class_metadata::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_update_token_metadata() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_token_metadata::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_remove_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_approve() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_approve::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_approval_for_all() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_approval_for_all::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_transfer_from() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_from::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_class_terms() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_class_terms::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_burn_expired() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_burn_expired::<Runtime>());
+		});
+	}
 }
@@ -32,15 +32,16 @@ use frame_support::{
 	transactional, PalletId,
 };
 use frame_system::pallet_prelude::*;
-use orml_traits::NFT;
-use primitives::NFTBalance;
+use orml_traits::{MultiCurrency, NFT};
+use primitives::{Balance, CurrencyId, NFTBalance};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	traits::{AccountIdConversion, Hash, Saturating, StaticLookup, Zero},
-	DispatchResult, RuntimeDebug,
+	DispatchError, DispatchResult, Permill, RuntimeDebug,
 };
 use sp_std::vec::Vec;
+use support::{AccountRecovery, NFTMirror};
 
 pub mod benchmarking;
 mod mock;
@@ -59,6 +60,17 @@ pub enum ClassProperty {
 	Transferable = 0b00000001,
 	/// Token can be burned
 	Burnable = 0b00000010,
+	/// Token can only be transferred by a royalty-paying sale
+	RoyaltyRequired = 0b00000100,
+	/// Class and token metadata can be updated, and token attributes
+	/// overwritten or removed
+	MetadataMutable = 0b00001000,
+	/// Token is bound to its owner's account and can only be moved by
+	/// recovering the account
+	Soulbound = 0b00010000,
+	/// Token expires `token_lifetime` blocks after it is minted, and can then
+	/// be burned by anyone
+	Expiring = 0b00100000,
 }
 
 #[derive(Clone, Copy, PartialEq, Default, RuntimeDebug)]
@@ -96,6 +108,28 @@ pub struct TokenData<Balance> {
 	pub deposit: Balance,
 }
 
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Royalty<AccountId> {
+	/// The rate of the sale price paid as royalty
+	pub rate: Permill,
+	/// The account receiving the royalty
+	pub recipient: AccountId,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClassTerms<BlockNumber> {
+	/// Tokens cannot be transferred before this block
+	pub transfer_locked_until: Option<BlockNumber>,
+	/// Tokens of an `Expiring` class expire this many blocks after they are
+	/// minted
+	pub token_lifetime: Option<BlockNumber>,
+}
+
+pub type AttributeKey = Vec<u8>;
+pub type AttributeValue = Vec<u8>;
+
 pub type TokenIdOf<T> = <T as orml_nft::Config>::TokenId;
 pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
 pub type BalanceOf<T> =
@@ -125,6 +159,33 @@ pub mod module {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The currency to pay sales and royalties with
+		type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The maximum royalty rate of a class
+		#[pallet::constant]
+		type MaxRoyaltyRate: Get<Permill>;
+
+		/// The deposit per byte of token attributes
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The maximum length of a token attribute key
+		#[pallet::constant]
+		type MaxAttributeKeyLength: Get<u32>;
+
+		/// The maximum length of a token attribute value
+		#[pallet::constant]
+		type MaxAttributeValueLength: Get<u32>;
+
+		/// The maximum number of attributes of a token
+		#[pallet::constant]
+		type MaxAttributesPerToken: Get<u32>;
+
+		/// The accounts recovered by a rescuer, who may then claim their
+		/// tokens. `()` disables token recovery.
+		type Recovery: AccountRecovery<Self::AccountId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -146,6 +207,38 @@ pub mod module {
 		/// Can not destroy class
 		/// Total issuance is not 0
 		CannotDestroyClass,
+		/// Property of class only allows royalty-paying transfers
+		RoyaltyPaymentRequired,
+		/// Royalty rate is above `MaxRoyaltyRate`
+		RoyaltyRateTooHigh,
+		/// Token is locked and cannot be transferred or burned
+		TokenLocked,
+		/// Property of class don't support metadata update
+		MetadataImmutable,
+		/// Attribute key is longer than `MaxAttributeKeyLength`
+		AttributeKeyTooLong,
+		/// Attribute value is longer than `MaxAttributeValueLength`
+		AttributeValueTooLong,
+		/// Attribute not found
+		AttributeNotFound,
+		/// Token has `MaxAttributesPerToken` attributes
+		TooManyAttributes,
+		/// The owner of the token cannot be approved for it
+		ApprovalToOwner,
+		/// Property of class binds tokens to their owner
+		Soulbound,
+		/// Tokens of class cannot be transferred yet
+		TransferLocked,
+		/// Property of class don't support expiry
+		NonExpiring,
+		/// Token has expired
+		TokenExpired,
+		/// Token has not expired
+		TokenNotExpired,
+		/// The lost account has not been recovered by the operator
+		NotRecovered,
+		/// Transfer lock-up of class with tokens can only be shortened
+		CannotExtendTransferLock,
 	}
 
 	#[pallet::event]
@@ -163,13 +256,142 @@ pub mod module {
 		BurnedTokenWithRemark(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, T::Hash),
 		/// Destroyed NFT class. \[owner, class_id\]
 		DestroyedClass(T::AccountId, ClassIdOf<T>),
+		/// Updated royalty of NFT class. \[class_id, royalty\]
+		UpdatedRoyalty(ClassIdOf<T>, Option<Royalty<T::AccountId>>),
+		/// Sold NFT token. \[seller, buyer, class_id, token_id, currency_id,
+		/// price, royalty\]
+		SoldToken(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			CurrencyId,
+			Balance,
+			Balance,
+		),
+		/// Updated NFT class metadata. \[class_id, metadata\]
+		UpdatedClassMetadata(ClassIdOf<T>, CID),
+		/// Updated NFT token metadata. \[class_id, token_id, metadata\]
+		UpdatedTokenMetadata(ClassIdOf<T>, TokenIdOf<T>, CID),
+		/// Set NFT token attribute. \[class_id, token_id, key, value\]
+		SetAttribute(ClassIdOf<T>, TokenIdOf<T>, AttributeKey, AttributeValue),
+		/// Removed NFT token attribute. \[class_id, token_id, key\]
+		RemovedAttribute(ClassIdOf<T>, TokenIdOf<T>, AttributeKey),
+		/// Approved an account to transfer NFT token, or cleared the approval.
+		/// \[owner, approved, class_id, token_id\]
+		Approval(T::AccountId, Option<T::AccountId>, ClassIdOf<T>, TokenIdOf<T>),
+		/// Approved or revoked an operator of all NFT tokens of a class.
+		/// \[owner, operator, class_id, approved\]
+		ApprovalForAll(T::AccountId, T::AccountId, ClassIdOf<T>, bool),
+		/// Updated terms of NFT class. \[class_id, terms\]
+		UpdatedClassTerms(ClassIdOf<T>, ClassTerms<T::BlockNumber>),
+		/// Burned expired NFT token. \[who, owner, class_id, token_id\]
+		BurnedExpiredToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Recovered NFT token of a lost account. \[lost, rescuer, class_id,
+		/// token_id\]
+		RecoveredToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
 	}
 
+	/// Royalty of NFT classes.
+	///
+	/// ClassRoyalties: map ClassId => Option<Royalty>
+	#[pallet::storage]
+	#[pallet::getter(fn class_royalties)]
+	pub type ClassRoyalties<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, Royalty<T::AccountId>, OptionQuery>;
+
+	/// Attributes of NFT tokens, with the deposit reserved for them from
+	/// the class owner.
+	///
+	/// TokenAttributes: double_map (ClassId, TokenId), AttributeKey =>
+	/// Option<(AttributeValue, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn token_attributes)]
+	pub type TokenAttributes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(ClassIdOf<T>, TokenIdOf<T>),
+		Blake2_128Concat,
+		AttributeKey,
+		(AttributeValue, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// The number of attributes of NFT tokens.
+	///
+	/// TokenAttributeCounts: map (ClassId, TokenId) => u32
+	#[pallet::storage]
+	#[pallet::getter(fn token_attribute_counts)]
+	pub type TokenAttributeCounts<T: Config> =
+		StorageMap<_, Twox64Concat, (ClassIdOf<T>, TokenIdOf<T>), u32, ValueQuery>;
+
+	/// Transfer lock-up and token lifetime of NFT classes.
+	///
+	/// ClassTermsOf: map ClassId => Option<ClassTerms>
+	#[pallet::storage]
+	#[pallet::getter(fn class_terms)]
+	pub type ClassTermsOf<T: Config> =
+		StorageMap<_, Twox64Concat, ClassIdOf<T>, ClassTerms<T::BlockNumber>, OptionQuery>;
+
+	/// The block NFT tokens of `Expiring` classes expire at.
+	///
+	/// TokenExpiries: double_map ClassId, TokenId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn token_expiries)]
+	pub type TokenExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, T::BlockNumber, OptionQuery>;
+
+	/// Accounts approved to transfer NFT tokens on behalf of their owner.
+	///
+	/// TokenApprovals: double_map ClassId, TokenId => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn token_approvals)]
+	pub type TokenApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, T::AccountId, OptionQuery>;
+
+	/// The number of NFT tokens of a class owned by an account.
+	///
+	/// ClassBalances: double_map ClassId, AccountId => NFTBalance
+	#[pallet::storage]
+	#[pallet::getter(fn class_balances)]
+	pub type ClassBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, T::AccountId, NFTBalance, ValueQuery>;
+
+	/// Operators approved to transfer all NFT tokens of a class on behalf
+	/// of their owner.
+	///
+	/// OperatorApprovals: double_map ClassId, (Owner, Operator) => Option<()>
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, (T::AccountId, T::AccountId), (), OptionQuery>;
+
+	/// Tokens locked against transfer and burn, e.g. listed on the
+	/// marketplace.
+	///
+	/// LockedTokens: double_map ClassId, TokenId => Option<()>
+	#[pallet::storage]
+	pub type LockedTokens<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, (), OptionQuery>;
+
+	/// Whether `ClassBalances` has been filled from the tokens minted before
+	/// it was introduced.
+	///
+	/// ClassBalancesMigrated: bool
+	#[pallet::storage]
+	pub type ClassBalancesMigrated<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if ClassBalancesMigrated::<T>::get() {
+				T::DbWeight::get().reads(1)
+			} else {
+				Self::migrate_class_balances()
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -234,10 +456,19 @@ pub mod module {
 			T::Currency::transfer(&who, &to, total_deposit, KeepAlive)?;
 			T::Currency::reserve(&to, total_deposit)?;
 
+			let expires_at = Self::class_terms(class_id)
+				.and_then(|terms| terms.token_lifetime)
+				.filter(|_| class_info.data.properties.0.contains(ClassProperty::Expiring))
+				.map(|lifetime| frame_system::Pallet::<T>::block_number().saturating_add(lifetime));
+
 			let data = TokenData { deposit };
 			for _ in 0..quantity {
-				orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+				let token_id = orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+				if let Some(expires_at) = expires_at {
+					TokenExpiries::<T>::insert(class_id, token_id, expires_at);
+				}
 			}
+			Self::inc_class_balance(class_id, &to, quantity.into());
 
 			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
 			Ok(().into())
@@ -263,20 +494,23 @@ pub mod module {
 		/// Burn NFT token
 		///
 		/// - `token`: (class_id, token_id)
-		#[pallet::weight(<T as Config>::WeightInfo::burn())]
+		#[pallet::weight(<T as Config>::WeightInfo::burn(T::MaxAttributesPerToken::get()))]
 		#[transactional]
 		pub fn burn(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::do_burn(&who, token)?;
+			let attributes = Self::do_burn(&who, token)?;
 			Self::deposit_event(Event::BurnedToken(who, token.0, token.1));
-			Ok(().into())
+			Ok(Some(<T as Config>::WeightInfo::burn(attributes)).into())
 		}
 
 		/// Burn NFT token
 		///
 		/// - `token`: (class_id, token_id)
 		/// - `remark`: Vec<u8>
-		#[pallet::weight(<T as Config>::WeightInfo::burn_with_remark(remark.len() as u32))]
+		#[pallet::weight(<T as Config>::WeightInfo::burn_with_remark(
+			remark.len() as u32,
+			T::MaxAttributesPerToken::get()
+		))]
 		#[transactional]
 		pub fn burn_with_remark(
 			origin: OriginFor<T>,
@@ -284,10 +518,10 @@ pub mod module {
 			remark: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::do_burn(&who, token)?;
+			let attributes = Self::do_burn(&who, token)?;
 			let hash = T::Hashing::hash(&remark[..]);
 			Self::deposit_event(Event::BurnedTokenWithRemark(who, token.0, token.1, hash));
-			Ok(().into())
+			Ok(Some(<T as Config>::WeightInfo::burn_with_remark(remark.len() as u32, attributes)).into())
 		}
 
 		/// Destroy NFT class, remove dest from proxy, and send all the free
@@ -316,6 +550,9 @@ pub mod module {
 			T::Currency::unreserve(&who, data.deposit);
 
 			orml_nft::Pallet::<T>::destroy_class(&who, class_id)?;
+			ClassRoyalties::<T>::remove(class_id);
+			ClassTermsOf::<T>::remove(class_id);
+			OperatorApprovals::<T>::remove_prefix(class_id);
 
 			// this should unresere proxy deposit
 			pallet_proxy::Pallet::<T>::remove_proxy_delegate(&who, dest.clone(), Default::default(), Zero::zero())?;
@@ -325,6 +562,317 @@ pub mod module {
 			Self::deposit_event(Event::DestroyedClass(who, class_id));
 			Ok(().into())
 		}
+
+		/// Set or remove the royalty paid on sales of the class tokens
+		///
+		/// - `class_id`: The class ID
+		/// - `royalty`: (rate, recipient), `None` removes the royalty
+		#[pallet::weight(<T as Config>::WeightInfo::set_royalty())]
+		#[transactional]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			royalty: Option<(Permill, <T::Lookup as StaticLookup>::Source)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			let royalty = match royalty {
+				Some((rate, recipient)) => {
+					ensure!(rate <= T::MaxRoyaltyRate::get(), Error::<T>::RoyaltyRateTooHigh);
+					Some(Royalty {
+						rate,
+						recipient: T::Lookup::lookup(recipient)?,
+					})
+				}
+				None => None,
+			};
+			ClassRoyalties::<T>::mutate_exists(class_id, |maybe_royalty| *maybe_royalty = royalty.clone());
+
+			Self::deposit_event(Event::UpdatedRoyalty(class_id, royalty));
+			Ok(().into())
+		}
+
+		/// Update NFT class metadata
+		///
+		/// - `class_id`: The class ID
+		/// - `metadata`: external metadata
+		#[pallet::weight(<T as Config>::WeightInfo::update_class_metadata())]
+		#[transactional]
+		pub fn update_class_metadata(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			metadata: CID,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_metadata_mutable(&who, class_id)?;
+			ensure!(
+				metadata.len() as u32 <= <T as orml_nft::Config>::MaxClassMetadata::get(),
+				orml_nft::Error::<T>::MaxMetadataExceeded
+			);
+
+			orml_nft::Classes::<T>::try_mutate(class_id, |maybe_class_info| -> DispatchResult {
+				let class_info = maybe_class_info.as_mut().ok_or(Error::<T>::ClassIdNotFound)?;
+				class_info.metadata = metadata.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::UpdatedClassMetadata(class_id, metadata));
+			Ok(().into())
+		}
+
+		/// Update NFT token metadata
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `metadata`: external metadata
+		#[pallet::weight(<T as Config>::WeightInfo::update_token_metadata())]
+		#[transactional]
+		pub fn update_token_metadata(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			metadata: CID,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_metadata_mutable(&who, token.0)?;
+			ensure!(
+				metadata.len() as u32 <= <T as orml_nft::Config>::MaxTokenMetadata::get(),
+				orml_nft::Error::<T>::MaxMetadataExceeded
+			);
+
+			orml_nft::Tokens::<T>::try_mutate(token.0, token.1, |maybe_token_info| -> DispatchResult {
+				let token_info = maybe_token_info.as_mut().ok_or(Error::<T>::TokenIdNotFound)?;
+				token_info.metadata = metadata.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::UpdatedTokenMetadata(token.0, token.1, metadata));
+			Ok(().into())
+		}
+
+		/// Set NFT token attribute, reserving a deposit per byte from the
+		/// class owner. Existing attributes of tokens of classes without
+		/// `MetadataMutable` cannot be overwritten.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `key`: attribute key
+		/// - `value`: attribute value
+		#[pallet::weight(<T as Config>::WeightInfo::set_attribute())]
+		#[transactional]
+		pub fn set_attribute(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			key: AttributeKey,
+			value: AttributeValue,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				orml_nft::Pallet::<T>::tokens(token.0, token.1).is_some(),
+				Error::<T>::TokenIdNotFound
+			);
+			ensure!(
+				key.len() as u32 <= T::MaxAttributeKeyLength::get(),
+				Error::<T>::AttributeKeyTooLong
+			);
+			ensure!(
+				value.len() as u32 <= T::MaxAttributeValueLength::get(),
+				Error::<T>::AttributeValueTooLong
+			);
+
+			let old_deposit = match Self::token_attributes(token, &key) {
+				Some((_, deposit)) => {
+					ensure!(
+						class_info.data.properties.0.contains(ClassProperty::MetadataMutable),
+						Error::<T>::MetadataImmutable
+					);
+					deposit
+				}
+				None => {
+					let count = Self::token_attribute_counts(token);
+					ensure!(count < T::MaxAttributesPerToken::get(), Error::<T>::TooManyAttributes);
+					TokenAttributeCounts::<T>::insert(token, count + 1);
+					Zero::zero()
+				}
+			};
+			let deposit = T::DataDepositPerByte::get().saturating_mul(((key.len() + value.len()) as u32).into());
+			if deposit > old_deposit {
+				T::Currency::reserve(&who, deposit.saturating_sub(old_deposit))?;
+			} else {
+				T::Currency::unreserve(&who, old_deposit.saturating_sub(deposit));
+			}
+			TokenAttributes::<T>::insert(token, &key, (value.clone(), deposit));
+
+			Self::deposit_event(Event::SetAttribute(token.0, token.1, key, value));
+			Ok(().into())
+		}
+
+		/// Remove NFT token attribute, unreserving its deposit
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `key`: attribute key
+		#[pallet::weight(<T as Config>::WeightInfo::remove_attribute())]
+		#[transactional]
+		pub fn remove_attribute(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			key: AttributeKey,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_metadata_mutable(&who, token.0)?;
+
+			let (_, deposit) = TokenAttributes::<T>::take(token, &key).ok_or(Error::<T>::AttributeNotFound)?;
+			TokenAttributeCounts::<T>::mutate_exists(token, |maybe_count| {
+				*maybe_count = maybe_count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(Event::RemovedAttribute(token.0, token.1, key));
+			Ok(().into())
+		}
+
+		/// Approve an account to transfer NFT token, or clear the approval
+		///
+		/// - `approved`: the approved account, `None` to clear the approval
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		#[transactional]
+		pub fn approve(
+			origin: OriginFor<T>,
+			approved: Option<<T::Lookup as StaticLookup>::Source>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let approved = approved.map(T::Lookup::lookup).transpose()?;
+			Self::do_approve(&who, approved, token)?;
+			Ok(().into())
+		}
+
+		/// Approve or revoke an operator of all NFT tokens of a class
+		///
+		/// - `operator`: the operator's account
+		/// - `class_id`: the class of the tokens
+		/// - `approved`: approve or revoke the operator
+		#[pallet::weight(<T as Config>::WeightInfo::set_approval_for_all())]
+		#[transactional]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+			class_id: ClassIdOf<T>,
+			approved: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			Self::do_set_approval_for_all(&who, &operator, class_id, approved)?;
+			Ok(().into())
+		}
+
+		/// Transfer NFT token on behalf of its owner, as the approved account
+		/// or an operator of the owner
+		///
+		/// - `from`: the token owner's account
+		/// - `to`: the recipient's account
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_from())]
+		#[transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::do_transfer_from(&who, &from, &to, token)?;
+			Ok(().into())
+		}
+
+		/// Set the transfer lock-up and token lifetime of NFT class
+		///
+		/// - `class_id`: the class to update
+		/// - `terms`: the transfer lock-up block and, for `Expiring` classes,
+		///   the lifetime of tokens minted from now on. Once the class has
+		///   tokens, the lock-up can only be shortened.
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_terms())]
+		#[transactional]
+		pub fn set_class_terms(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			terms: ClassTerms<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				terms.token_lifetime.is_none() || class_info.data.properties.0.contains(ClassProperty::Expiring),
+				Error::<T>::NonExpiring
+			);
+			if let Some(locked_until) = terms.transfer_locked_until {
+				let current_lock = Self::class_terms(class_id).and_then(|terms| terms.transfer_locked_until);
+				ensure!(
+					class_info.total_issuance == Zero::zero()
+						|| current_lock.map_or(false, |current| locked_until <= current),
+					Error::<T>::CannotExtendTransferLock
+				);
+			}
+
+			if terms == Default::default() {
+				ClassTermsOf::<T>::remove(class_id);
+			} else {
+				ClassTermsOf::<T>::insert(class_id, terms.clone());
+			}
+
+			Self::deposit_event(Event::UpdatedClassTerms(class_id, terms));
+			Ok(().into())
+		}
+
+		/// Burn expired NFT token, refunding its deposit to the owner
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::burn_expired(T::MaxAttributesPerToken::get()))]
+		#[transactional]
+		pub fn burn_expired(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_expired(token), Error::<T>::TokenNotExpired);
+			ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+
+			let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+			let attributes = Self::burn_token(&token_info.owner, &class_info.owner, token, token_info.data.deposit)?;
+
+			Self::deposit_event(Event::BurnedExpiredToken(who, token_info.owner, token.0, token.1));
+			Ok(Some(<T as Config>::WeightInfo::burn_expired(attributes)).into())
+		}
+
+		/// Claim NFT token of an account recovered by the caller, including
+		/// soulbound tokens
+		///
+		/// - `lost`: the recovered account
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads(1)))]
+		#[transactional]
+		pub fn recover_token(
+			origin: OriginFor<T>,
+			lost: <T::Lookup as StaticLookup>::Source,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let lost = T::Lookup::lookup(lost)?;
+			ensure!(
+				T::Recovery::recovered_account(&who).as_ref() == Some(&lost),
+				Error::<T>::NotRecovered
+			);
+			ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+
+			let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+			ensure!(lost == token_info.owner, Error::<T>::NoPermission);
+			Self::transfer_token(&lost, &who, token)?;
+
+			Self::deposit_event(Event::RecoveredToken(lost, who, token.0, token.1));
+			Ok(().into())
+		}
 	}
 }
 
@@ -334,39 +882,273 @@ impl<T: Config> Pallet<T> {
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
 		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
+		Self::ensure_transferable(token, data.properties)?;
+		ensure!(
+			!data.properties.0.contains(ClassProperty::RoyaltyRequired),
+			Error::<T>::RoyaltyPaymentRequired
+		);
+
+		Self::transfer_token(from, to, token)
+	}
+
+	/// Sell `token` of `seller` to `buyer` for `price` of `currency_id`,
+	/// paying the class royalty out of the price. Returns the royalty paid.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn do_sell(
+		seller: &T::AccountId,
+		buyer: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+		currency_id: CurrencyId,
+		price: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+		Self::ensure_transferable(token, class_info.data.properties)?;
+
+		let royalty = Self::royalty_of(token.0, price);
+		if let Some((recipient, amount)) = royalty.as_ref() {
+			T::MultiCurrency::transfer(currency_id, buyer, recipient, *amount)?;
+		}
+		let royalty_amount = royalty.map_or(Zero::zero(), |(_, amount)| amount);
+		T::MultiCurrency::transfer(currency_id, buyer, seller, price.saturating_sub(royalty_amount))?;
+
+		Self::transfer_token(seller, buyer, token)?;
+
+		Self::deposit_event(Event::SoldToken(
+			seller.clone(),
+			buyer.clone(),
+			token.0,
+			token.1,
+			currency_id,
+			price,
+			royalty_amount,
+		));
+		Ok(royalty_amount)
+	}
+
+	/// Lock `token` against transfer and burn.
+	pub fn lock_token(token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
+		ensure!(
+			orml_nft::Pallet::<T>::tokens(token.0, token.1).is_some(),
+			Error::<T>::TokenIdNotFound
+		);
+		ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+		LockedTokens::<T>::insert(token.0, token.1, ());
+		Ok(())
+	}
+
+	/// Unlock `token`.
+	pub fn unlock_token(token: (ClassIdOf<T>, TokenIdOf<T>)) {
+		LockedTokens::<T>::remove(token.0, token.1);
+	}
+
+	/// Whether `token` is locked against transfer and burn.
+	pub fn is_locked(token: (ClassIdOf<T>, TokenIdOf<T>)) -> bool {
+		LockedTokens::<T>::contains_key(token.0, token.1)
+	}
+
+	/// Whether `token` has expired.
+	pub fn is_expired(token: (ClassIdOf<T>, TokenIdOf<T>)) -> bool {
+		Self::token_expiries(token.0, token.1).map_or(false, |expires_at| {
+			expires_at <= frame_system::Pallet::<T>::block_number()
+		})
+	}
+
+	fn ensure_transferable(token: (ClassIdOf<T>, TokenIdOf<T>), properties: Properties) -> DispatchResult {
 		ensure!(
-			data.properties.0.contains(ClassProperty::Transferable),
+			properties.0.contains(ClassProperty::Transferable),
 			Error::<T>::NonTransferable
 		);
+		ensure!(!properties.0.contains(ClassProperty::Soulbound), Error::<T>::Soulbound);
+		if let Some(locked_until) = Self::class_terms(token.0).and_then(|terms| terms.transfer_locked_until) {
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= locked_until,
+				Error::<T>::TransferLocked
+			);
+		}
+		ensure!(!Self::is_expired(token), Error::<T>::TokenExpired);
+		ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+		Ok(())
+	}
+
+	/// The royalty recipient and amount of a sale of a `class_id` token
+	/// for `price`.
+	pub fn royalty_of(class_id: ClassIdOf<T>, price: Balance) -> Option<(T::AccountId, Balance)> {
+		Self::class_royalties(class_id)
+			.map(|royalty| (royalty.recipient, royalty.rate.mul_floor(price)))
+			.filter(|(_, amount)| !amount.is_zero())
+	}
 
+	fn transfer_token(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
+		Self::dec_class_balance(token.0, from);
+		Self::inc_class_balance(token.0, to, 1);
 
 		T::Currency::unreserve(&from, token_info.data.deposit);
 		T::Currency::transfer(&from, &to, token_info.data.deposit, AllowDeath)?;
 		T::Currency::reserve(&to, token_info.data.deposit)?;
 
+		TokenApprovals::<T>::remove(token.0, token.1);
+
 		Self::deposit_event(Event::TransferredToken(from.clone(), to.clone(), token.0, token.1));
 		Ok(())
 	}
 
+	/// Returns the number of attributes removed.
+	///
 	/// Ensured atomic.
 	#[transactional]
-	fn do_burn(who: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
+	fn do_burn(who: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> Result<u32, DispatchError> {
 		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
 		ensure!(
 			data.properties.0.contains(ClassProperty::Burnable),
 			Error::<T>::NonBurnable
 		);
+		ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
 
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 		ensure!(*who == token_info.owner, Error::<T>::NoPermission);
 
-		orml_nft::Pallet::<T>::burn(&who, token)?;
+		Self::burn_token(who, &class_info.owner, token, token_info.data.deposit)
+	}
+
+	/// Burn `token` with its attributes, bounded by `MaxAttributesPerToken`.
+	/// Returns the number of attributes removed.
+	fn burn_token(
+		owner: &T::AccountId,
+		class_owner: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+		deposit: BalanceOf<T>,
+	) -> Result<u32, DispatchError> {
+		orml_nft::Pallet::<T>::burn(owner, token)?;
+		Self::dec_class_balance(token.0, owner);
+
+		T::Currency::unreserve(owner, deposit);
+		TokenApprovals::<T>::remove(token.0, token.1);
+		TokenExpiries::<T>::remove(token.0, token.1);
+
+		// the attribute deposits are reserved from the class owner
+		let attributes_deposit = TokenAttributes::<T>::drain_prefix(token)
+			.fold(Zero::zero(), |total: BalanceOf<T>, (_, (_, deposit))| {
+				total.saturating_add(deposit)
+			});
+		T::Currency::unreserve(class_owner, attributes_deposit);
+		Ok(TokenAttributeCounts::<T>::take(token))
+	}
+
+	/// Rebuild `ClassBalances` from the owners of all tokens.
+	fn migrate_class_balances() -> Weight {
+		ClassBalances::<T>::remove_all();
+		let mut count: Weight = 0;
+		for (class_id, _, token_info) in orml_nft::Tokens::<T>::iter() {
+			Self::inc_class_balance(class_id, &token_info.owner, 1);
+			count += 1;
+		}
+		ClassBalancesMigrated::<T>::put(true);
+		T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+	}
 
-		T::Currency::unreserve(&who, token_info.data.deposit);
+	fn inc_class_balance(class_id: ClassIdOf<T>, who: &T::AccountId, amount: NFTBalance) {
+		ClassBalances::<T>::mutate(class_id, who, |balance| *balance = balance.saturating_add(amount));
+	}
+
+	fn dec_class_balance(class_id: ClassIdOf<T>, who: &T::AccountId) {
+		ClassBalances::<T>::mutate_exists(class_id, who, |maybe_balance| {
+			*maybe_balance = maybe_balance
+				.map(|balance| balance.saturating_sub(1))
+				.filter(|balance| !balance.is_zero());
+		});
+	}
+
+	/// Approve `approved` to transfer `token`, or clear the approval with
+	/// `None`. `who` must be the owner of `token` or an operator of the owner.
+	pub fn do_approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		let owner = token_info.owner;
+		ensure!(
+			*who == owner || Self::is_operator(&owner, who, token.0),
+			Error::<T>::NoPermission
+		);
+		ensure!(approved.as_ref() != Some(&owner), Error::<T>::ApprovalToOwner);
+
+		match approved.as_ref() {
+			Some(account) => TokenApprovals::<T>::insert(token.0, token.1, account),
+			None => TokenApprovals::<T>::remove(token.0, token.1),
+		}
+
+		Self::deposit_event(Event::Approval(owner, approved, token.0, token.1));
+		Ok(())
+	}
+
+	/// Approve or revoke `operator` to transfer all `class_id` tokens of
+	/// `owner`.
+	pub fn do_set_approval_for_all(
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		approved: bool,
+	) -> DispatchResult {
+		ensure!(
+			orml_nft::Pallet::<T>::classes(class_id).is_some(),
+			Error::<T>::ClassIdNotFound
+		);
+		ensure!(owner != operator, Error::<T>::ApprovalToOwner);
+
+		if approved {
+			OperatorApprovals::<T>::insert(class_id, (owner, operator), ());
+		} else {
+			OperatorApprovals::<T>::remove(class_id, (owner, operator));
+		}
+
+		Self::deposit_event(Event::ApprovalForAll(
+			owner.clone(),
+			operator.clone(),
+			class_id,
+			approved,
+		));
+		Ok(())
+	}
+
+	/// Transfer `token` from `from` to `to` on behalf of `who`, which must be
+	/// `from`, the account approved for `token` or an operator of `from`.
+	pub fn do_transfer_from(
+		who: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(*from == token_info.owner, Error::<T>::NoPermission);
+		ensure!(
+			who == from
+				|| Self::token_approvals(token.0, token.1).as_ref() == Some(who)
+				|| Self::is_operator(from, who, token.0),
+			Error::<T>::NoPermission
+		);
+
+		Self::do_transfer(from, to, token)
+	}
+
+	/// Whether `operator` may transfer all `class_id` tokens of `owner`.
+	pub fn is_operator(owner: &T::AccountId, operator: &T::AccountId, class_id: ClassIdOf<T>) -> bool {
+		OperatorApprovals::<T>::contains_key(class_id, (owner, operator))
+	}
+
+	fn ensure_metadata_mutable(who: &T::AccountId, class_id: ClassIdOf<T>) -> DispatchResult {
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(*who == class_info.owner, Error::<T>::NoPermission);
+		ensure!(
+			class_info.data.properties.0.contains(ClassProperty::MetadataMutable),
+			Error::<T>::MetadataImmutable
+		);
 		Ok(())
 	}
 }
@@ -388,3 +1170,51 @@ impl<T: Config> NFT<T::AccountId> for Pallet<T> {
 		Self::do_transfer(from, to, token)
 	}
 }
+
+impl<T: Config> NFTMirror<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+	type Balance = NFTBalance;
+
+	fn class_balance(class_id: Self::ClassId, who: &T::AccountId) -> Self::Balance {
+		Self::class_balances(class_id, who)
+	}
+
+	fn token_uri(token: (Self::ClassId, Self::TokenId)) -> Option<Vec<u8>> {
+		orml_nft::Pallet::<T>::tokens(token.0, token.1).map(|t| t.metadata)
+	}
+
+	fn approved(token: (Self::ClassId, Self::TokenId)) -> Option<T::AccountId> {
+		Self::token_approvals(token.0, token.1)
+	}
+
+	fn is_approved_for_all(owner: &T::AccountId, operator: &T::AccountId, class_id: Self::ClassId) -> bool {
+		Self::is_operator(owner, operator, class_id)
+	}
+
+	fn approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult {
+		Self::do_approve(who, approved, token)
+	}
+
+	fn set_approval_for_all(
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+		class_id: Self::ClassId,
+		approved: bool,
+	) -> DispatchResult {
+		Self::do_set_approval_for_all(owner, operator, class_id, approved)
+	}
+
+	fn transfer_from(
+		who: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult {
+		Self::do_transfer_from(who, from, to, token)
+	}
+}
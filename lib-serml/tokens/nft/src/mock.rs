@@ -33,6 +33,7 @@ use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
 };
 use support::mocks::MockAddressMapping;
 
@@ -166,6 +167,10 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
 }
 
+parameter_types! {
+	pub const MaxTransfers: u32 = 10;
+}
+
 impl setheum_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -174,18 +179,46 @@ impl setheum_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = ();
+	type GasToWeight = ();
+	type CashDropHandler = ();
+	type MaxTransfers = MaxTransfers;
 }
 
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
 	pub const NftPalletId: PalletId = PalletId(*b"set/sNFT");
+	pub const MaxRoyaltyRate: Permill = Permill::from_percent(50);
+	pub const NftDataDepositPerByte: Balance = 1;
+	pub const MaxAttributeKeyLength: u32 = 32;
+	pub const MaxAttributeValueLength: u32 = 256;
+	pub const MaxAttributesPerToken: u32 = 10;
+}
+
+/// CAROL has recovered the account of BOB.
+pub struct MockRecovery;
+impl AccountRecovery<AccountId> for MockRecovery {
+	fn recovered_account(rescuer: &AccountId) -> Option<AccountId> {
+		if *rescuer == CAROL {
+			Some(BOB)
+		} else {
+			None
+		}
+	}
 }
+
 impl Config for Runtime {
 	type Event = Event;
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type PalletId = NftPalletId;
+	type MultiCurrency = Currency;
+	type MaxRoyaltyRate = MaxRoyaltyRate;
+	type DataDepositPerByte = NftDataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxAttributesPerToken = MaxAttributesPerToken;
+	type Recovery = MockRecovery;
 	type WeightInfo = ();
 }
 
@@ -227,6 +260,8 @@ construct_runtime!(
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CAROL: AccountId = AccountId::new([3u8; 32]);
+pub const USDJ: CurrencyId = CurrencyId::Token(TokenSymbol::USDJ);
 pub const CLASS_ID: <Runtime as orml_nft::Config>::ClassId = 0;
 pub const CLASS_ID_NOT_EXIST: <Runtime as orml_nft::Config>::ClassId = 1;
 pub const TOKEN_ID: <Runtime as orml_nft::Config>::TokenId = 0;
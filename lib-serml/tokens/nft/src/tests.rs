@@ -22,11 +22,12 @@
 
 use super::*;
 use frame_support::traits::Currency;
-use frame_support::{assert_noop, assert_ok};
-use mock::{Event, *};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
+use mock::{Call, Event, *};
 use orml_nft::TokenInfo;
+use orml_traits::MultiCurrency;
 use primitives::Balance;
-use sp_runtime::{traits::BlakeTwo256, ArithmeticError};
+use sp_runtime::{traits::BlakeTwo256, ArithmeticError, Permill};
 use sp_std::convert::TryInto;
 
 fn free_balance(who: &AccountId) -> Balance {
@@ -306,6 +307,85 @@ fn burn_should_work() {
 	});
 }
 
+#[test]
+fn class_balances_should_follow_mint_transfer_and_burn() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 2);
+
+		assert_ok!(SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 1);
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, ALICE), 1);
+
+		assert_ok!(SetheumNFT::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID + 1)));
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 0);
+		assert!(!ClassBalances::<Runtime>::contains_key(CLASS_ID, BOB));
+		assert_eq!(SetheumNFT::class_balance(CLASS_ID, &ALICE), 1);
+	});
+}
+
+#[test]
+fn class_balances_should_be_migrated() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			ALICE,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		// tokens minted before `ClassBalances` was introduced
+		ClassBalances::<Runtime>::remove_all();
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 0);
+
+		<SetheumNFT as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert!(ClassBalancesMigrated::<Runtime>::get());
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 2);
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, ALICE), 1);
+
+		// the migration only runs once
+		<SetheumNFT as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 2);
+
+		assert_ok!(SetheumNFT::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::class_balances(CLASS_ID, BOB), 1);
+	});
+}
+
 #[test]
 fn burn_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -445,6 +525,35 @@ fn destroy_class_should_work() {
 	});
 }
 
+#[test]
+fn destroy_class_should_remove_terms_and_operator_approvals() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(SetheumNFT::set_class_terms(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ClassTerms {
+				transfer_locked_until: Some(10),
+				token_lifetime: None,
+			}
+		));
+		assert_ok!(SetheumNFT::set_approval_for_all(Origin::signed(BOB), ALICE, CLASS_ID, true));
+		assert_eq!(SetheumNFT::is_operator(&BOB, &ALICE, CLASS_ID), true);
+
+		assert_ok!(SetheumNFT::destroy_class(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ALICE
+		));
+		assert_eq!(SetheumNFT::class_terms(CLASS_ID), None);
+		assert_eq!(SetheumNFT::is_operator(&BOB, &ALICE, CLASS_ID), false);
+	});
+}
+
 #[test]
 fn destroy_class_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -494,3 +603,800 @@ fn destroy_class_should_fail() {
 		));
 	});
 }
+
+#[test]
+fn set_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_noop!(
+			SetheumNFT::set_royalty(Origin::signed(BOB), CLASS_ID, Some((Permill::from_percent(10), CAROL))),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			SetheumNFT::set_royalty(
+				Origin::signed(class_id_account()),
+				CLASS_ID_NOT_EXIST,
+				Some((Permill::from_percent(10), CAROL))
+			),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			SetheumNFT::set_royalty(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				Some((Permill::from_percent(60), CAROL))
+			),
+			Error::<Runtime>::RoyaltyRateTooHigh
+		);
+
+		assert_ok!(SetheumNFT::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some((Permill::from_percent(10), CAROL))
+		));
+		let royalty = Royalty {
+			rate: Permill::from_percent(10),
+			recipient: CAROL,
+		};
+		System::assert_last_event(Event::nft(crate::Event::UpdatedRoyalty(
+			CLASS_ID,
+			Some(royalty.clone()),
+		)));
+		assert_eq!(SetheumNFT::class_royalties(CLASS_ID), Some(royalty));
+		assert_eq!(SetheumNFT::royalty_of(CLASS_ID, 1000), Some((CAROL, 100)));
+
+		assert_ok!(SetheumNFT::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			None
+		));
+		System::assert_last_event(Event::nft(crate::Event::UpdatedRoyalty(CLASS_ID, None)));
+		assert_eq!(SetheumNFT::class_royalties(CLASS_ID), None);
+		assert_eq!(SetheumNFT::royalty_of(CLASS_ID, 1000), None);
+	});
+}
+
+#[test]
+fn sell_should_pay_royalty() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::RoyaltyRequired)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_ok!(SetheumNFT::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some((Permill::from_percent(10), CAROL))
+		));
+
+		// only royalty-paying transfers are allowed for the class
+		assert_noop!(
+			SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::RoyaltyPaymentRequired
+		);
+
+		assert_ok!(Tokens::deposit(USDJ, &ALICE, 1000));
+		assert_eq!(
+			SetheumNFT::do_sell(&BOB, &ALICE, (CLASS_ID, TOKEN_ID), USDJ, 1000),
+			Ok(100)
+		);
+		System::assert_last_event(Event::nft(crate::Event::SoldToken(
+			BOB, ALICE, CLASS_ID, TOKEN_ID, USDJ, 1000, 100,
+		)));
+		assert_eq!(Tokens::free_balance(USDJ, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(USDJ, &BOB), 900);
+		assert_eq!(Tokens::free_balance(USDJ, &CAROL), 100);
+		assert_eq!(OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().owner, ALICE);
+		assert_eq!(reserved_balance(&ALICE), <Runtime as Config>::CreateTokenDeposit::get());
+
+		// the seller must own the token
+		assert_ok!(Tokens::deposit(USDJ, &CAROL, 1000));
+		assert_noop!(
+			SetheumNFT::do_sell(&BOB, &CAROL, (CLASS_ID, TOKEN_ID), USDJ, 1000),
+			orml_nft::Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn locked_token_cannot_be_transferred_or_burned() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_noop!(
+			SetheumNFT::lock_token((CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		assert_ok!(SetheumNFT::lock_token((CLASS_ID, TOKEN_ID)));
+		assert!(SetheumNFT::is_locked((CLASS_ID, TOKEN_ID)));
+		assert_noop!(
+			SetheumNFT::lock_token((CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenLocked
+		);
+		assert_noop!(
+			SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenLocked
+		);
+		assert_noop!(
+			SetheumNFT::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenLocked
+		);
+
+		SetheumNFT::unlock_token((CLASS_ID, TOKEN_ID));
+		assert!(!SetheumNFT::is_locked((CLASS_ID, TOKEN_ID)));
+		assert_ok!(SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn update_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::MetadataMutable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_noop!(
+			SetheumNFT::update_class_metadata(Origin::signed(BOB), CLASS_ID, vec![2]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			SetheumNFT::update_token_metadata(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID_NOT_EXIST),
+				vec![2]
+			),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		// the class owner's proxy updates the metadata
+		assert_ok!(Proxy::proxy(
+			Origin::signed(ALICE),
+			class_id_account(),
+			None,
+			Box::new(Call::SetheumNFT(crate::Call::update_class_metadata(CLASS_ID, vec![2])))
+		));
+		System::assert_has_event(Event::nft(crate::Event::UpdatedClassMetadata(CLASS_ID, vec![2])));
+		assert_eq!(OrmlNFT::classes(CLASS_ID).unwrap().metadata, vec![2]);
+
+		assert_ok!(SetheumNFT::update_token_metadata(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![3]
+		));
+		System::assert_last_event(Event::nft(crate::Event::UpdatedTokenMetadata(
+			CLASS_ID,
+			TOKEN_ID,
+			vec![3],
+		)));
+		assert_eq!(OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().metadata, vec![3]);
+	});
+}
+
+#[test]
+fn update_metadata_should_fail_for_immutable_class() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_noop!(
+			SetheumNFT::update_class_metadata(Origin::signed(class_id_account()), CLASS_ID, vec![2]),
+			Error::<Runtime>::MetadataImmutable
+		);
+		assert_noop!(
+			SetheumNFT::update_token_metadata(Origin::signed(class_id_account()), (CLASS_ID, TOKEN_ID), vec![2]),
+			Error::<Runtime>::MetadataImmutable
+		);
+	});
+}
+
+#[test]
+fn attributes_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::MetadataMutable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(
+				&class_id_account(),
+				1 * <Runtime as Config>::CreateTokenDeposit::get() + 100
+			)
+			.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			SetheumNFT::set_attribute(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), vec![1], vec![2]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			SetheumNFT::set_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				vec![1; 33],
+				vec![2]
+			),
+			Error::<Runtime>::AttributeKeyTooLong
+		);
+		assert_noop!(
+			SetheumNFT::set_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				vec![1],
+				vec![2; 257]
+			),
+			Error::<Runtime>::AttributeValueTooLong
+		);
+
+		assert_ok!(SetheumNFT::set_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![1, 2],
+			vec![3, 4, 5]
+		));
+		System::assert_last_event(Event::nft(crate::Event::SetAttribute(
+			CLASS_ID,
+			TOKEN_ID,
+			vec![1, 2],
+			vec![3, 4, 5],
+		)));
+		assert_eq!(
+			SetheumNFT::token_attributes((CLASS_ID, TOKEN_ID), vec![1, 2]),
+			Some((vec![3, 4, 5], 5))
+		);
+		assert_eq!(SetheumNFT::token_attribute_counts((CLASS_ID, TOKEN_ID)), 1);
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 5);
+
+		// overwriting adjusts the deposit
+		assert_ok!(SetheumNFT::set_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![1, 2],
+			vec![3]
+		));
+		assert_eq!(SetheumNFT::token_attribute_counts((CLASS_ID, TOKEN_ID)), 1);
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 3);
+
+		assert_noop!(
+			SetheumNFT::remove_attribute(Origin::signed(class_id_account()), (CLASS_ID, TOKEN_ID), vec![9]),
+			Error::<Runtime>::AttributeNotFound
+		);
+		assert_ok!(SetheumNFT::remove_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![1, 2]
+		));
+		System::assert_last_event(Event::nft(crate::Event::RemovedAttribute(
+			CLASS_ID,
+			TOKEN_ID,
+			vec![1, 2],
+		)));
+		assert_eq!(SetheumNFT::token_attributes((CLASS_ID, TOKEN_ID), vec![1, 2]), None);
+		assert_eq!(SetheumNFT::token_attribute_counts((CLASS_ID, TOKEN_ID)), 0);
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+
+		// burning the token returns the attribute deposits
+		assert_ok!(SetheumNFT::set_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![1],
+			vec![2]
+		));
+		assert_ok!(SetheumNFT::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::token_attributes((CLASS_ID, TOKEN_ID), vec![1]), None);
+		assert_eq!(SetheumNFT::token_attribute_counts((CLASS_ID, TOKEN_ID)), 0);
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+	});
+}
+
+#[test]
+fn attributes_should_be_bounded_per_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::MetadataMutable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(
+				&class_id_account(),
+				1 * <Runtime as Config>::CreateTokenDeposit::get() + 100
+			)
+			.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		let max_attributes = <Runtime as Config>::MaxAttributesPerToken::get();
+		for key in 0..max_attributes as u8 {
+			assert_ok!(SetheumNFT::set_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				vec![key],
+				vec![1]
+			));
+		}
+		assert_eq!(SetheumNFT::token_attribute_counts((CLASS_ID, TOKEN_ID)), max_attributes);
+		assert_noop!(
+			SetheumNFT::set_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				vec![max_attributes as u8],
+				vec![1]
+			),
+			Error::<Runtime>::TooManyAttributes
+		);
+
+		// existing attributes can still be overwritten
+		assert_ok!(SetheumNFT::set_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![0],
+			vec![2]
+		));
+
+		// burning is weighted by the attributes removed
+		let post_info = SetheumNFT::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<Runtime as Config>::WeightInfo::burn(max_attributes))
+		);
+		assert_eq!(SetheumNFT::token_attribute_counts((CLASS_ID, TOKEN_ID)), 0);
+	});
+}
+
+#[test]
+fn attributes_of_immutable_class_cannot_be_changed() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(
+				&class_id_account(),
+				1 * <Runtime as Config>::CreateTokenDeposit::get() + 100
+			)
+			.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_ok!(SetheumNFT::set_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![1],
+			vec![2]
+		));
+		assert_noop!(
+			SetheumNFT::set_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				vec![1],
+				vec![3]
+			),
+			Error::<Runtime>::MetadataImmutable
+		);
+		assert_noop!(
+			SetheumNFT::remove_attribute(Origin::signed(class_id_account()), (CLASS_ID, TOKEN_ID), vec![1]),
+			Error::<Runtime>::MetadataImmutable
+		);
+	});
+}
+
+#[test]
+fn approve_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			SetheumNFT::approve(Origin::signed(ALICE), Some(CAROL), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			SetheumNFT::approve(Origin::signed(BOB), Some(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ApprovalToOwner
+		);
+		assert_noop!(
+			SetheumNFT::approve(Origin::signed(BOB), Some(CAROL), (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		assert_ok!(SetheumNFT::approve(
+			Origin::signed(BOB),
+			Some(CAROL),
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(Event::nft(crate::Event::Approval(BOB, Some(CAROL), CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::token_approvals(CLASS_ID, TOKEN_ID), Some(CAROL));
+
+		assert_noop!(
+			SetheumNFT::transfer_from(Origin::signed(ALICE), BOB, ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(SetheumNFT::transfer_from(
+			Origin::signed(CAROL),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(Event::nft(crate::Event::TransferredToken(
+			BOB, ALICE, CLASS_ID, TOKEN_ID,
+		)));
+		// the approval is cleared by the transfer
+		assert_eq!(SetheumNFT::token_approvals(CLASS_ID, TOKEN_ID), None);
+		assert_noop!(
+			SetheumNFT::transfer_from(Origin::signed(CAROL), ALICE, BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(SetheumNFT::approve(
+			Origin::signed(ALICE),
+			Some(CAROL),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(SetheumNFT::approve(Origin::signed(ALICE), None, (CLASS_ID, TOKEN_ID)));
+		System::assert_last_event(Event::nft(crate::Event::Approval(ALICE, None, CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::token_approvals(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn set_approval_for_all_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+
+		assert_noop!(
+			SetheumNFT::set_approval_for_all(Origin::signed(BOB), CAROL, CLASS_ID_NOT_EXIST, true),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			SetheumNFT::set_approval_for_all(Origin::signed(BOB), BOB, CLASS_ID, true),
+			Error::<Runtime>::ApprovalToOwner
+		);
+
+		assert_ok!(SetheumNFT::set_approval_for_all(
+			Origin::signed(BOB),
+			CAROL,
+			CLASS_ID,
+			true
+		));
+		System::assert_last_event(Event::nft(crate::Event::ApprovalForAll(BOB, CAROL, CLASS_ID, true)));
+		assert!(SetheumNFT::is_operator(&BOB, &CAROL, CLASS_ID));
+		assert_eq!(SetheumNFT::class_balance(CLASS_ID, &BOB), 2);
+
+		// an operator can approve other accounts and transfer
+		assert_ok!(SetheumNFT::approve(
+			Origin::signed(CAROL),
+			Some(ALICE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_eq!(SetheumNFT::token_approvals(CLASS_ID, TOKEN_ID), Some(ALICE));
+		assert_ok!(SetheumNFT::transfer_from(
+			Origin::signed(CAROL),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID + 1)
+		));
+		assert_eq!(SetheumNFT::class_balance(CLASS_ID, &BOB), 1);
+		assert_eq!(SetheumNFT::class_balance(CLASS_ID, &ALICE), 1);
+
+		assert_ok!(SetheumNFT::set_approval_for_all(
+			Origin::signed(BOB),
+			CAROL,
+			CLASS_ID,
+			false
+		));
+		System::assert_last_event(Event::nft(crate::Event::ApprovalForAll(BOB, CAROL, CLASS_ID, false)));
+		assert!(!SetheumNFT::is_operator(&BOB, &CAROL, CLASS_ID));
+		assert_noop!(
+			SetheumNFT::transfer_from(Origin::signed(CAROL), BOB, CAROL, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		// burning clears the approval
+		assert_ok!(SetheumNFT::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::token_approvals(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn soulbound_token_should_only_be_recovered() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Soulbound)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::Soulbound
+		);
+		assert_noop!(
+			SetheumNFT::do_sell(&BOB, &ALICE, (CLASS_ID, TOKEN_ID), USDJ, 100),
+			Error::<Runtime>::Soulbound
+		);
+
+		// only CAROL has recovered the account of BOB
+		assert_noop!(
+			SetheumNFT::recover_token(Origin::signed(ALICE), BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NotRecovered
+		);
+		assert_ok!(SetheumNFT::recover_token(
+			Origin::signed(CAROL),
+			BOB,
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(Event::nft(crate::Event::RecoveredToken(BOB, CAROL, CLASS_ID, TOKEN_ID)));
+		assert_eq!(SetheumNFT::owner((CLASS_ID, TOKEN_ID)), Some(CAROL));
+		assert_eq!(reserved_balance(&BOB), 0);
+		assert_eq!(reserved_balance(&CAROL), <Runtime as Config>::CreateTokenDeposit::get());
+		assert_noop!(
+			SetheumNFT::recover_token(Origin::signed(CAROL), BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn transfer_lock_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+
+		let terms = ClassTerms {
+			transfer_locked_until: Some(10),
+			token_lifetime: None,
+		};
+		assert_noop!(
+			SetheumNFT::set_class_terms(Origin::signed(ALICE), CLASS_ID, terms.clone()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(SetheumNFT::set_class_terms(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			terms.clone()
+		));
+		System::assert_last_event(Event::nft(crate::Event::UpdatedClassTerms(CLASS_ID, terms.clone())));
+		assert_eq!(SetheumNFT::class_terms(CLASS_ID), Some(terms));
+
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_noop!(
+			SetheumNFT::set_class_terms(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				ClassTerms {
+					transfer_locked_until: Some(11),
+					token_lifetime: None,
+				}
+			),
+			Error::<Runtime>::CannotExtendTransferLock
+		);
+
+		System::set_block_number(9);
+		assert_noop!(
+			SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TransferLocked
+		);
+
+		System::set_block_number(10);
+		assert_ok!(SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+
+		assert_ok!(SetheumNFT::set_class_terms(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Default::default()
+		));
+		assert_eq!(SetheumNFT::class_terms(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn expiring_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable)
+		));
+		assert_noop!(
+			SetheumNFT::set_class_terms(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				ClassTerms {
+					transfer_locked_until: None,
+					token_lifetime: Some(10),
+				}
+			),
+			Error::<Runtime>::NonExpiring
+		);
+
+		assert_ok!(SetheumNFT::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Expiring)
+		));
+		let class_id_account: AccountId = <Runtime as Config>::PalletId::get().into_sub_account(CLASS_ID + 1);
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account, 1 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(SetheumNFT::set_class_terms(
+			Origin::signed(class_id_account.clone()),
+			CLASS_ID + 1,
+			ClassTerms {
+				transfer_locked_until: None,
+				token_lifetime: Some(10),
+			}
+		));
+		assert_ok!(SetheumNFT::mint(
+			Origin::signed(class_id_account),
+			BOB,
+			CLASS_ID + 1,
+			vec![1],
+			1
+		));
+		assert_eq!(SetheumNFT::token_expiries(CLASS_ID + 1, TOKEN_ID), Some(11));
+		assert_eq!(reserved_balance(&BOB), <Runtime as Config>::CreateTokenDeposit::get());
+
+		assert_noop!(
+			SetheumNFT::burn_expired(Origin::signed(ALICE), (CLASS_ID + 1, TOKEN_ID)),
+			Error::<Runtime>::TokenNotExpired
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			SetheumNFT::transfer(Origin::signed(BOB), ALICE, (CLASS_ID + 1, TOKEN_ID)),
+			Error::<Runtime>::TokenExpired
+		);
+
+		// anyone can burn the expired token, refunding the deposit to its owner
+		let free_balance_bob = free_balance(&BOB);
+		assert_ok!(SetheumNFT::burn_expired(
+			Origin::signed(ALICE),
+			(CLASS_ID + 1, TOKEN_ID)
+		));
+		System::assert_last_event(Event::nft(crate::Event::BurnedExpiredToken(
+			ALICE,
+			BOB,
+			CLASS_ID + 1,
+			TOKEN_ID,
+		)));
+		assert_eq!(SetheumNFT::owner((CLASS_ID + 1, TOKEN_ID)), None);
+		assert_eq!(SetheumNFT::token_expiries(CLASS_ID + 1, TOKEN_ID), None);
+		assert_eq!(reserved_balance(&BOB), 0);
+		assert_eq!(
+			free_balance(&BOB),
+			free_balance_bob + <Runtime as Config>::CreateTokenDeposit::get()
+		);
+	});
+}
@@ -634,6 +634,11 @@ where
 		Self(fee)
 	}
 
+	/// The tip paid for priority.
+	pub fn tip(&self) -> PalletBalanceOf<T> {
+		self.0
+	}
+
 	fn withdraw_fee(
 		&self,
 		who: &T::AccountId,
@@ -839,7 +844,7 @@ where
 		tip: PalletBalanceOf<T>,
 		pays_fee: Pays,
 		class: DispatchClass,
-	) -> Result<(), TransactionValidityError> {
+	) -> Result<PalletBalanceOf<T>, TransactionValidityError> {
 		let fee = Pallet::<T>::compute_fee_raw(len, weight, tip, pays_fee, class).final_fee();
 
 		Pallet::<T>::ensure_can_charge_fee(who, fee, WithdrawReasons::TRANSACTION_PAYMENT);
@@ -855,6 +860,6 @@ where
 
 		// distribute fee
 		<T as Config>::OnTransactionPayment::on_unbalanced(actual_payment);
-		Ok(())
+		Ok(fee)
 	}
 }
@@ -129,6 +129,10 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = DNAR;
 }
 
+parameter_types! {
+	pub const MaxTransfers: u32 = 10;
+}
+
 impl setheum_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -137,6 +141,9 @@ impl setheum_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = ();
+	type GasToWeight = ();
+	type CashDropHandler = ();
+	type MaxTransfers = MaxTransfers;
 }
 
 ord_parameter_types! {
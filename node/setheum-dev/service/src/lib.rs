@@ -42,6 +42,7 @@ pub use sc_service::{
 	config::{DatabaseConfig, PrometheusConfig},
 	ChainSpec,
 };
+pub use setheum_rpc::EvmRpcLimits;
 pub use sp_api::ConstructRuntimeApi;
 
 pub mod chain_spec;
@@ -121,6 +122,7 @@ type LightClient<RuntimeApi, Executor> = sc_service::TLightClientWithBackend<Blo
 pub fn new_partial<RuntimeApi, Executor>(
 	config: &mut Configuration,
 	instant_sealing: bool,
+	evm_rpc_limits: EvmRpcLimits,
 	test: bool,
 ) -> Result<
 	PartialComponents<
@@ -245,6 +247,7 @@ where
 					subscription_executor,
 					finality_provider: finality_proof_provider.clone(),
 				},
+				evm_rpc_limits,
 			};
 
 			setheum_rpc::create_full(deps)
@@ -268,6 +271,7 @@ where
 pub fn new_full<RuntimeApi, Executor>(
 	mut config: Configuration,
 	instant_sealing: bool,
+	evm_rpc_limits: EvmRpcLimits,
 	test: bool,
 ) -> Result<
 	(
@@ -295,7 +299,7 @@ where
 		transaction_pool,
 		inherent_data_providers,
 		other: (rpc_extensions_builder, import_setup, rpc_setup),
-	} = new_partial::<RuntimeApi, Executor>(&mut config, instant_sealing, test)?;
+	} = new_partial::<RuntimeApi, Executor>(&mut config, instant_sealing, evm_rpc_limits, test)?;
 
 	let shared_voter_state = rpc_setup;
 
@@ -595,7 +599,7 @@ pub fn new_chain_ops(
 				import_queue,
 				task_manager,
 				..
-			} = new_partial::<newrome_runtime::RuntimeApi, NewromeExecutor>(config, false, false)?;
+			} = new_partial::<newrome_runtime::RuntimeApi, NewromeExecutor>(config, false, Default::default(), false)?;
 			Ok((Arc::new(Client::Newrome(client)), backend, import_queue, task_manager))
 		}
 		#[cfg(not(feature = "with-newrome-runtime"))]
@@ -609,7 +613,7 @@ pub fn new_chain_ops(
 				import_queue,
 				task_manager,
 				..
-			} = new_partial::<neom_runtime::RuntimeApi, NeomExecutor>(config, false, false)?;
+			} = new_partial::<neom_runtime::RuntimeApi, NeomExecutor>(config, false, Default::default(), false)?;
 			Ok((Arc::new(Client::Neom(client)), backend, import_queue, task_manager))
 		}
 		#[cfg(not(feature = "with-neom-runtime"))]
@@ -623,7 +627,7 @@ pub fn new_chain_ops(
 				import_queue,
 				task_manager,
 				..
-			} = new_partial::<setheum_runtime::RuntimeApi, SetheumExecutor>(config, false, false)?;
+			} = new_partial::<setheum_runtime::RuntimeApi, SetheumExecutor>(config, false, Default::default(), false)?;
 			Ok((Arc::new(Client::Setheum(client)), backend, import_queue, task_manager))
 		}
 		#[cfg(not(feature = "with-setheum-runtime"))]
@@ -654,13 +658,19 @@ pub fn build_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 pub fn build_full(
 	config: Configuration,
 	instant_sealing: bool,
+	evm_rpc_limits: EvmRpcLimits,
 	test: bool,
 ) -> Result<(Arc<Client>, sc_service::NetworkStatusSinks<Block>, TaskManager), ServiceError> {
 	if config.chain_spec.is_setheum() {
 		#[cfg(feature = "with-setheum-runtime")]
 		{
 			let (task_manager, _, client, _, _, network_status_sinks) =
-				new_full::<setheum_runtime::RuntimeApi, SetheumExecutor>(config, instant_sealing, test)?;
+				new_full::<setheum_runtime::RuntimeApi, SetheumExecutor>(
+					config,
+					instant_sealing,
+					evm_rpc_limits,
+					test,
+				)?;
 			Ok((Arc::new(Client::Setheum(client)), network_status_sinks, task_manager))
 		}
 		#[cfg(not(feature = "with-setheum-runtime"))]
@@ -669,7 +679,7 @@ pub fn build_full(
 		#[cfg(feature = "with-neom-runtime")]
 		{
 			let (task_manager, _, client, _, _, network_status_sinks) =
-				new_full::<neom_runtime::RuntimeApi, NeomExecutor>(config, instant_sealing, test)?;
+				new_full::<neom_runtime::RuntimeApi, NeomExecutor>(config, instant_sealing, evm_rpc_limits, test)?;
 			Ok((Arc::new(Client::Neom(client)), network_status_sinks, task_manager))
 		}
 		#[cfg(not(feature = "with-neom-runtime"))]
@@ -678,7 +688,12 @@ pub fn build_full(
 		#[cfg(feature = "with-newrome-runtime")]
 		{
 			let (task_manager, _, client, _, _, network_status_sinks) =
-				new_full::<newrome_runtime::RuntimeApi, NewromeExecutor>(config, instant_sealing, test)?;
+				new_full::<newrome_runtime::RuntimeApi, NewromeExecutor>(
+					config,
+					instant_sealing,
+					evm_rpc_limits,
+					test,
+				)?;
 			Ok((Arc::new(Client::Newrome(client)), network_status_sinks, task_manager))
 		}
 		#[cfg(not(feature = "with-newrome-runtime"))]
@@ -91,4 +91,17 @@ pub struct Cli {
 	/// Can only be used with `--dev`
 	#[structopt(long = "instant-sealing")]
 	pub instant_sealing: bool,
+
+	/// The most gas an EVM call dry run by the RPC may use
+	///
+	/// Applies to `evm_call`, `evm_estimateResources`, `evm_traceCall`,
+	/// `eth_call` and `eth_estimateGas`. Defaults to 50000000.
+	#[structopt(long = "rpc-evm-gas-limit")]
+	pub rpc_evm_gas_limit: Option<u64>,
+
+	/// The most storage in bytes an EVM call dry run by the RPC may use
+	///
+	/// Defaults to 4194304.
+	#[structopt(long = "rpc-evm-storage-limit")]
+	pub rpc_evm_storage_limit: Option<u32>,
 }
@@ -156,6 +156,14 @@ fn set_default_ss58_version(spec: &Box<dyn service::ChainSpec>) {
 	sp_core::crypto::set_default_ss58_version(ss58_version);
 }
 
+fn evm_rpc_limits(cli: &Cli) -> service::EvmRpcLimits {
+	let default = service::EvmRpcLimits::default();
+	service::EvmRpcLimits {
+		gas_limit: cli.rpc_evm_gas_limit.unwrap_or(default.gas_limit),
+		storage_limit: cli.rpc_evm_storage_limit.unwrap_or(default.storage_limit),
+	}
+}
+
 /// Parses setheum specific CLI arguments and run the service.
 pub fn run() -> sc_cli::Result<()> {
 	let cli = Cli::from_args();
@@ -171,12 +179,13 @@ pub fn run() -> sc_cli::Result<()> {
 				return Err("Instant sealing can be turned on only in `--dev` mode".into());
 			}
 
+			let evm_rpc_limits = evm_rpc_limits(&cli);
+
 			runner.run_node_until_exit(|config| async move {
 				match config.role {
 					Role::Light => service::build_light(config),
-					_ => {
-						service::build_full(config, cli.instant_sealing, false).map(|(_, _, task_manager)| task_manager)
-					}
+					_ => service::build_full(config, cli.instant_sealing, evm_rpc_limits, false)
+						.map(|(_, _, task_manager)| task_manager),
 				}
 				.map_err(sc_cli::Error::Service)
 			})
@@ -189,7 +198,7 @@ pub fn run() -> sc_cli::Result<()> {
 			set_default_ss58_version(chain_spec);
 
 			runner.sync_run(|config| {
-				let (client, _, _) = service::build_full(config, false, false)?;
+				let (client, _, _) = service::build_full(config, false, Default::default(), false)?;
 				cmd.run(client)
 			})
 		}
@@ -318,6 +318,7 @@ pub trait TokenInfo {
 pub enum DexShare {
 	Token(TokenSymbol),
 	Erc20(EvmAddress),
+	Fraction(VaultId),
 }
 
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
@@ -327,6 +328,8 @@ pub enum CurrencyId {
 	DexShare(DexShare, DexShare),
 	Erc20(EvmAddress),
 	ChainBridge(chainbridge::ResourceId),
+	/// Fungible shares of a fractionalized NFT vault
+	Fraction(VaultId),
 }
 
 impl CurrencyId {
@@ -342,6 +345,10 @@ impl CurrencyId {
 		matches!(self, CurrencyId::Erc20(_))
 	}
 
+	pub fn is_fraction_currency_id(&self) -> bool {
+		matches!(self, CurrencyId::Fraction(_))
+	}
+
 	pub fn split_dex_share_currency_id(&self) -> Option<(Self, Self)> {
 		match self {
 			CurrencyId::DexShare(token_symbol_0, token_symbol_1) => {
@@ -357,11 +364,13 @@ impl CurrencyId {
 		let token_symbol_0 = match currency_id_0 {
 			CurrencyId::Token(symbol) => DexShare::Token(symbol),
 			CurrencyId::Erc20(address) => DexShare::Erc20(address),
+			CurrencyId::Fraction(vault_id) => DexShare::Fraction(vault_id),
 			_ => return None,
 		};
 		let token_symbol_1 = match currency_id_1 {
 			CurrencyId::Token(symbol) => DexShare::Token(symbol),
 			CurrencyId::Erc20(address) => DexShare::Erc20(address),
+			CurrencyId::Fraction(vault_id) => DexShare::Fraction(vault_id),
 			_ => return None,
 		};
 		Some(CurrencyId::DexShare(token_symbol_0, token_symbol_1))
@@ -381,6 +390,9 @@ impl From<DexShare> for u32 {
 				let index = if leading_zeros > 16 { 16 } else { leading_zeros };
 				bytes[..].copy_from_slice(&address[index..index + 4][..]);
 			}
+			DexShare::Fraction(vault_id) => {
+				bytes = vault_id.to_be_bytes();
+			}
 		}
 		u32::from_be_bytes(bytes)
 	}
@@ -398,11 +410,11 @@ impl TryFrom<CurrencyId> for EvmAddress {
 			CurrencyId::DexShare(token_symbol_0, token_symbol_1) => {
 				let symbol_0 = match token_symbol_0 {
 					DexShare::Token(token) => CurrencyId::Token(token).currency_id().ok_or(()),
-					DexShare::Erc20(_) => Err(()),
+					DexShare::Erc20(_) | DexShare::Fraction(_) => Err(()),
 				}?;
 				let symbol_1 = match token_symbol_1 {
 					DexShare::Token(token) => CurrencyId::Token(token).currency_id().ok_or(()),
-					DexShare::Erc20(_) => Err(()),
+					DexShare::Erc20(_) | DexShare::Fraction(_) => Err(()),
 				}?;
 
 				let mut prefix = EvmAddress::default();
@@ -411,6 +423,7 @@ impl TryFrom<CurrencyId> for EvmAddress {
 			}
 			CurrencyId::Erc20(address) => Ok(address),
 			CurrencyId::ChainBridge(_) => Err(()),
+			CurrencyId::Fraction(_) => Err(()),
 		}
 	}
 }
@@ -420,6 +433,7 @@ impl Into<CurrencyId> for DexShare {
 		match self {
 			DexShare::Token(token) => CurrencyId::Token(token),
 			DexShare::Erc20(address) => CurrencyId::Erc20(address),
+			DexShare::Fraction(vault_id) => CurrencyId::Fraction(vault_id),
 		}
 	}
 }
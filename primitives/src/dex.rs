@@ -0,0 +1,44 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Balance;
+use codec::{Decode, Encode};
+use max_encoded_len::MaxEncodedLen;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+/// Index of a DEX statistics period.
+pub type StatsPeriodIndex = u32;
+
+/// Aggregated swap and liquidity statistics of a trading pair in a period.
+/// The tuples are ordered the same as the currencies of the `TradingPair`.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode, RuntimeDebug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PairStats {
+	/// The period these statistics are aggregated in.
+	pub period: StatsPeriodIndex,
+	/// The swapped amount of each currency, both supplied and received.
+	pub volume: (Balance, Balance),
+	/// The exchange fees collected in each currency.
+	pub fees: (Balance, Balance),
+	/// The number of swaps through the trading pair.
+	pub swap_count: u32,
+	/// The liquidity pool at the end of the period.
+	pub closing_reserves: (Balance, Balance),
+}
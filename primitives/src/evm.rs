@@ -16,12 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Balance;
+use crate::{Balance, H160_POSITION_NFT_CLASS, H160_PREFIX_NFT};
 use codec::{Decode, Encode};
 use evm::ExitReason;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
@@ -84,4 +84,119 @@ pub struct EstimateResourcesRequest {
 	pub value: Option<Balance>,
 	/// Data
 	pub data: Option<Vec<u8>>,
+	/// Access list
+	pub access_list: Option<Vec<AccessListItem>>,
+}
+
+/// An account, and the storage slots of it, a transaction declares it
+/// accesses. They are warm from the start of the transaction (EIP-2930).
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AccessListItem {
+	pub address: EvmAddress,
+	pub storage_keys: Vec<H256>,
+}
+
+/// The kind of a traced call frame.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+}
+
+/// A call frame recorded by the call tracer, with its nested calls.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallTrace {
+	pub call_type: CallType,
+	pub from: EvmAddress,
+	/// The callee, or the created contract for `Create` and `Create2`.
+	pub to: EvmAddress,
+	pub value: U256,
+	pub gas: u64,
+	pub gas_used: u64,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	pub exit_reason: ExitReason,
+	pub calls: Vec<CallTrace>,
+}
+
+/// A single opcode step recorded by the struct logger.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StructLog {
+	pub pc: u64,
+	pub op: u8,
+	/// Gas left before executing the opcode.
+	pub gas: u64,
+	pub gas_cost: u64,
+	/// Call depth, starting at 1.
+	pub depth: u32,
+	/// Stack items, bottom first.
+	pub stack: Vec<H256>,
+	pub memory: Vec<u8>,
+	/// The storage slot read or written by `SLOAD` and `SSTORE`, empty for
+	/// other opcodes.
+	pub storage: Vec<(H256, H256)>,
+}
+
+/// The tracer to run an EVM execution with.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TracerConfig {
+	CallTracer,
+	StructLogger {
+		disable_stack: bool,
+		disable_storage: bool,
+		enable_memory: bool,
+	},
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TraceOutcome {
+	CallTracer(CallTrace),
+	StructLogger {
+		exit_reason: ExitReason,
+		used_gas: u64,
+		return_value: Vec<u8>,
+		struct_logs: Vec<StructLog>,
+	},
+}
+
+/// A log emitted by an extrinsic of a block.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlockLog {
+	pub transaction_index: u32,
+	/// `keccak_256` of the raw transaction for Ethereum transactions, the
+	/// extrinsic hash otherwise.
+	pub transaction_hash: H256,
+	pub log: Log,
+}
+
+/// The mirrored ERC-721 address of the NFT class `class_id`.
+pub fn mirrored_nft_address(class_id: u32) -> EvmAddress {
+	let mut address = [0u8; 20];
+	address[0..H160_PREFIX_NFT.len()].copy_from_slice(&H160_PREFIX_NFT);
+	address[H160_POSITION_NFT_CLASS].copy_from_slice(&class_id.to_be_bytes());
+	EvmAddress::from(address)
+}
+
+/// The NFT class mirrored at `address`, if it is a mirrored ERC-721 address.
+pub fn mirrored_nft_class_id(address: EvmAddress) -> Option<u32> {
+	let address = address.as_bytes();
+	if !address.starts_with(&H160_PREFIX_NFT) {
+		return None;
+	}
+
+	let mut class_id = [0u8; 4];
+	class_id.copy_from_slice(&address[H160_POSITION_NFT_CLASS]);
+	Some(u32::from_be_bytes(class_id))
 }
@@ -21,7 +21,9 @@
 #![allow(clippy::upper_case_acronyms)]
 
 pub mod currency;
+pub mod dex;
 pub mod evm;
+pub mod signature;
 
 use codec::{Decode, Encode};
 use core::ops::Range;
@@ -34,6 +36,7 @@ use sp_runtime::{
 use sp_std::{convert::Into, prelude::*};
 
 pub use currency::{CurrencyId, DexShare, TokenSymbol};
+pub use signature::SetheumMultiSignature;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -85,6 +88,15 @@ pub type AuctionId = u32;
 /// Share type
 pub type Share = u128;
 
+/// Payment stream ID
+pub type StreamId = u64;
+
+/// Invoice ID
+pub type InvoiceId = u64;
+
+/// Fractionalized NFT vault ID
+pub type VaultId = u32;
+
 /// Header type.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 
@@ -127,9 +139,12 @@ impl TradingPair {
 	}
 
 	pub fn from_token_currency_ids(currency_id_0: CurrencyId, currency_id_1: CurrencyId) -> Option<Self> {
-		match (currency_id_0.is_token_currency_id() || currency_id_0.is_erc20_currency_id())
-			&& (currency_id_1.is_token_currency_id() || currency_id_1.is_erc20_currency_id())
-		{
+		let is_tradable = |currency_id: CurrencyId| {
+			currency_id.is_token_currency_id()
+				|| currency_id.is_erc20_currency_id()
+				|| currency_id.is_fraction_currency_id()
+		};
+		match is_tradable(currency_id_0) && is_tradable(currency_id_1) {
 			true => Some(TradingPair::new(currency_id_1, currency_id_0)),
 			_ => None,
 		}
@@ -167,6 +182,11 @@ pub const MIRRORED_NFT_ADDRESS_START: u64 = 0x2000000;
 /// Mirrored LP Tokens
 /// 0x10000000000000000
 pub const MIRRORED_LP_TOKENS_ADDRESS_START: u128 = 0x10000000000000000;
+/// Mirrored ERC-721 NFT classes
+/// 0x20000000000000000
+pub const MIRRORED_NFT_CLASSES_ADDRESS_START: u128 = 0x20000000000000000;
+/// Predeployed ERC-721 contract run at the mirrored NFT class addresses
+pub const MIRRORED_NFT_PREDEPLOY_ADDRESS: u64 = 0x900;
 /// System contract address prefix
 pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 11] = [0u8; 11];
 
@@ -183,11 +203,18 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 11] = [0u8; 11];
 ///
 /// Erc20
 /// - v[0..20] = evm address(20 bytes)
+///
+/// NFT class
+/// v[11] = 2 // MIRRORED_NFT_CLASSES_ADDRESS_START
+/// - v[16..20] = class id(4 bytes)
 pub const H160_TYPE_TOKEN: u8 = 1;
 pub const H160_TYPE_DEXSHARE: u8 = 1;
+pub const H160_TYPE_NFT: u8 = 2;
 pub const H160_POSITION_TOKEN: usize = 19;
 pub const H160_POSITION_DEXSHARE_LEFT: Range<usize> = 12..16;
 pub const H160_POSITION_DEXSHARE_RIGHT: Range<usize> = 16..20;
 pub const H160_POSITION_ERC20: Range<usize> = 0..20;
+pub const H160_POSITION_NFT_CLASS: Range<usize> = 16..20;
 pub const H160_PREFIX_TOKEN: [u8; 19] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0];
 pub const H160_PREFIX_DEXSHARE: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+pub const H160_PREFIX_NFT: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0];
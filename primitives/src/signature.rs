@@ -0,0 +1,90 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signatures of extrinsics, including those signed by Ethereum wallets.
+
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_runtime::{
+	traits::{Lazy, Verify},
+	AccountId32, MultiSignature, MultiSigner, RuntimeDebug,
+};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// A `MultiSignature`, or the signature of an Ethereum wallet over the
+/// EIP-712 typed data of an EVM call.
+///
+/// The first variants are encoded as the `MultiSignature` ones, so
+/// extrinsics signed with Substrate keys are unchanged.
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SetheumMultiSignature {
+	/// An Ed25519 signature.
+	Ed25519(ed25519::Signature),
+	/// An Sr25519 signature.
+	Sr25519(sr25519::Signature),
+	/// An ECDSA/SECP256k1 signature.
+	Ecdsa(ecdsa::Signature),
+	/// An ECDSA/SECP256k1 signature over EIP-712 typed data. The typed data
+	/// is built from the extrinsic, so the signature is checked by the
+	/// extrinsic rather than by `verify`.
+	Eip712(ecdsa::Signature),
+}
+
+impl Verify for SetheumMultiSignature {
+	type Signer = MultiSigner;
+
+	fn verify<L: Lazy<[u8]>>(&self, msg: L, signer: &AccountId32) -> bool {
+		match self {
+			Self::Ed25519(sig) => MultiSignature::Ed25519(sig.clone()).verify(msg, signer),
+			Self::Sr25519(sig) => MultiSignature::Sr25519(sig.clone()).verify(msg, signer),
+			Self::Ecdsa(sig) => MultiSignature::Ecdsa(sig.clone()).verify(msg, signer),
+			Self::Eip712(_) => false,
+		}
+	}
+}
+
+impl From<MultiSignature> for SetheumMultiSignature {
+	fn from(signature: MultiSignature) -> Self {
+		match signature {
+			MultiSignature::Ed25519(sig) => Self::Ed25519(sig),
+			MultiSignature::Sr25519(sig) => Self::Sr25519(sig),
+			MultiSignature::Ecdsa(sig) => Self::Ecdsa(sig),
+		}
+	}
+}
+
+impl From<ed25519::Signature> for SetheumMultiSignature {
+	fn from(sig: ed25519::Signature) -> Self {
+		Self::Ed25519(sig)
+	}
+}
+
+impl From<sr25519::Signature> for SetheumMultiSignature {
+	fn from(sig: sr25519::Signature) -> Self {
+		Self::Sr25519(sig)
+	}
+}
+
+impl From<ecdsa::Signature> for SetheumMultiSignature {
+	fn from(sig: ecdsa::Signature) -> Self {
+		Self::Ecdsa(sig)
+	}
+}
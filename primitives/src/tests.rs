@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::evm::EvmAddress;
+use crate::evm::{mirrored_nft_address, mirrored_nft_class_id, EvmAddress};
 use frame_support::assert_ok;
 use std::{
 	convert::{TryFrom, TryInto},
@@ -51,6 +51,9 @@ fn currency_id_into_u32_works() {
 
 	let currency_id = DexShare::Erc20(EvmAddress::from_str("0x0000000000000000000000000000000000000000").unwrap());
 	assert_eq!(Into::<u32>::into(currency_id), 0x00);
+
+	let currency_id = DexShare::Fraction(0x0102);
+	assert_eq!(Into::<u32>::into(currency_id), 0x0102);
 }
 
 #[test]
@@ -78,4 +81,60 @@ fn currency_id_try_into_evm_address_works() {
 
 	let erc20 = EvmAddress::from_str("0x1111111111111111111111111111111111111111").unwrap();
 	assert_eq!(EvmAddress::try_from(CurrencyId::Erc20(erc20)), Ok(erc20));
+
+	assert_eq!(EvmAddress::try_from(CurrencyId::Fraction(0)), Err(()));
+}
+
+#[test]
+fn fraction_currency_id_is_tradable() {
+	let fraction = CurrencyId::Fraction(1);
+	let dnar = CurrencyId::Token(TokenSymbol::DNAR);
+	let trading_pair = TradingPair::from_token_currency_ids(fraction, dnar).unwrap();
+	assert_eq!(trading_pair, TradingPair::new(dnar, fraction));
+	assert_eq!(
+		trading_pair.get_dex_share_currency_id(),
+		Some(CurrencyId::DexShare(DexShare::Token(TokenSymbol::DNAR), DexShare::Fraction(1)))
+	);
+	assert_eq!(
+		trading_pair.get_dex_share_currency_id().unwrap().split_dex_share_currency_id(),
+		Some((dnar, fraction))
+	);
+}
+
+#[test]
+fn mirrored_nft_address_works() {
+	assert_eq!(
+		mirrored_nft_address(0),
+		EvmAddress::from_str("0x0000000000000000000000020000000000000000").unwrap()
+	);
+	assert_eq!(
+		mirrored_nft_address(0x12345678),
+		EvmAddress::from_str("0x0000000000000000000000020000000012345678").unwrap()
+	);
+	assert_eq!(mirrored_nft_class_id(mirrored_nft_address(u32::MAX)), Some(u32::MAX));
+
+	// network contracts and mirrored tokens are not mirrored NFT classes
+	assert_eq!(
+		mirrored_nft_class_id(EvmAddress::from_low_u64_be(MIRRORED_NFT_ADDRESS_START)),
+		None
+	);
+	assert_eq!(
+		mirrored_nft_class_id(EvmAddress::try_from(CurrencyId::Token(TokenSymbol::DNAR)).unwrap()),
+		None
+	);
+}
+
+#[test]
+fn setheum_multi_signature_encodes_as_multi_signature() {
+	let signature = MultiSignature::Sr25519(sp_core::sr25519::Signature::from_raw([1u8; 64]));
+	let encoded = signature.encode();
+	assert_eq!(SetheumMultiSignature::from(signature).encode(), encoded);
+	assert_eq!(
+		SetheumMultiSignature::decode(&mut &encoded[..]),
+		Ok(SetheumMultiSignature::Sr25519(sp_core::sr25519::Signature::from_raw([1u8; 64])))
+	);
+
+	let signature = sp_core::ecdsa::Signature::from_raw([1u8; 65]);
+	assert_eq!(SetheumMultiSignature::Eip712(signature.clone()).encode()[0], 3);
+	assert!(!SetheumMultiSignature::Eip712(signature).verify(&b"message"[..], &AccountId::new([0u8; 32])));
 }
@@ -38,6 +38,8 @@ pub use sc_rpc::SubscriptionTaskExecutor;
 
 pub use sc_rpc::DenyUnsafe;
 
+pub use setheum_evm_rpc::RpcLimits as EvmRpcLimits;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 
@@ -91,6 +93,8 @@ pub struct FullDeps<C, P, SC, B> {
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
 	pub grandpa: GrandpaDeps<B>,
+	/// Caps on the calls dry run by the EVM RPCs.
+	pub evm_rpc_limits: EvmRpcLimits,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -98,13 +102,16 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: sc_client_api::BlockBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
+	C::Api: setheum_dex_rpc::DEXRuntimeRPCApi<Block>,
+	C::Api: setheum_evm_rpc::EVMRuntimeRPCApi<Block, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
+	P: TransactionPool<Block = Block> + Sync + Send + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
@@ -113,6 +120,8 @@ where
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use sc_consensus_babe_rpc::BabeRpcHandler;
 	use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
+	use setheum_dex_rpc::{DEXApi, DEX};
+	use setheum_evm_rpc::{EVMApi, EVMApiServer, EthApi, EthApiServer};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
@@ -123,6 +132,7 @@ where
 		deny_unsafe,
 		babe,
 		grandpa,
+		evm_rpc_limits,
 	} = deps;
 	let BabeDeps {
 		keystore,
@@ -139,7 +149,7 @@ where
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(
 		client.clone(),
-		pool,
+		pool.clone(),
 		deny_unsafe,
 	)));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
@@ -164,6 +174,18 @@ where
 		finality_provider,
 	)));
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
+	io.extend_with(DEXApi::to_delegate(DEX::new(client.clone())));
+	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(
+		client.clone(),
+		deny_unsafe,
+		evm_rpc_limits,
+	)));
+	io.extend_with(EthApiServer::to_delegate(EthApi::new(
+		client,
+		pool,
+		deny_unsafe,
+		evm_rpc_limits,
+	)));
 
 	io
 }
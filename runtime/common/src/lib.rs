@@ -22,6 +22,7 @@
 
 use frame_support::{
 	parameter_types,
+	traits::Get,
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, WEIGHT_PER_MILLIS},
 		DispatchClass, Weight,
@@ -34,6 +35,7 @@ use primitives::{
 };
 use sp_core::H160;
 use sp_runtime::{traits::Convert, transaction_validity::TransactionPriority, Perbill};
+use sp_std::marker::PhantomData;
 use static_assertions::const_assert;
 
 pub mod precompile;
@@ -41,6 +43,8 @@ pub use precompile::{
 	AllPrecompiles, DexPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
 	StateRentPrecompile,
 };
+pub mod unchecked_extrinsic;
+pub use unchecked_extrinsic::{EthereumTransactionMessage, SetheumUncheckedExtrinsic, TransactionAction};
 pub use primitives::currency::{
 	TokenInfo, 
 	DNAR, DRAM, SETT, USDJ, EURJ, JPYJ, GBPJ, AUDJ, CADJ, CHFJ, SGDJ, BRLJ, SARJ,
@@ -78,12 +82,14 @@ impl PrecompileCallerFilter for SystemContractsFilter {
 	}
 }
 
-/// Convert gas to weight
-pub struct GasToWeight;
-impl Convert<u64, Weight> for GasToWeight {
+/// Convert gas to weight, at `WeightPerGas` weight per unit of gas.
+///
+/// The runtimes use `setheum_evm::calibration::WeightPerGas`, derived from
+/// their benchmarks of the EVM opcode mixes.
+pub struct GasToWeight<WeightPerGas>(PhantomData<WeightPerGas>);
+impl<WeightPerGas: Get<Weight>> Convert<u64, Weight> for GasToWeight<WeightPerGas> {
 	fn convert(a: u64) -> u64 {
-		// TODO: estimate this
-		a as Weight
+		a.saturating_mul(WeightPerGas::get())
 	}
 }
 
@@ -172,6 +178,17 @@ mod tests {
 		assert!(!SystemContractsFilter::is_allowed(min_blocked_addr.into()));
 	}
 
+	#[test]
+	fn gas_to_weight_works() {
+		parameter_types! {
+			pub const WeightPerGas: Weight = 20_000;
+		}
+
+		assert_eq!(GasToWeight::<WeightPerGas>::convert(0), 0);
+		assert_eq!(GasToWeight::<WeightPerGas>::convert(21_000), 420_000_000);
+		assert_eq!(GasToWeight::<WeightPerGas>::convert(u64::max_value()), Weight::max_value());
+	}
+
 	#[test]
 	fn is_system_contract_works() {
 		assert!(is_system_contract(H160::from_low_u64_be(0)));
@@ -18,11 +18,15 @@
 
 use super::input::{Input, InputT};
 use frame_support::log;
-use setheum_evm::{Context, ExitError, ExitSucceed, Precompile};
-use setheum_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, DEXManager};
+use setheum_evm::{Context, ExitError, ExitReason, ExitSucceed, Precompile};
+use setheum_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, DEXManager, ExecutionMode, InvokeContext,
+	EVM as EVMT,
+};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use primitives::{Balance, CurrencyId};
 use sp_core::U256;
+use sp_runtime::DispatchError;
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 
 /// The `DEX` impl precompile.
@@ -34,10 +38,18 @@ use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 /// - Get liquidity. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
 /// - Swap with exact supply. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`,
 ///   `supply_amount`, `min_target_amount`.
-pub struct DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Dex)>,
+/// - Flash swap. Rest `input` bytes: `who`, `borrow_currency_id`, `paired_currency_id`,
+///   `borrow_amount`, `repay_currency_id`, `max_repay_amount`, `storage_limit`, `callback_data`. The
+///   `callback_data` is called on the `who` contract while the borrowed currency is lent to it,
+///   with the gas left after `FLASH_SWAP_BASE_GAS`. The gas used by the callback is charged on top of
+///   `FLASH_SWAP_BASE_GAS`.
+pub struct DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex, EVM>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Dex, EVM)>,
 );
 
+/// The gas of a flash swap, excluding its callback.
+pub const FLASH_SWAP_BASE_GAS: u64 = 50_000;
+
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u32)]
 pub enum Action {
@@ -49,20 +61,22 @@ pub enum Action {
 	SwapWithExactTarget = 0x9782ac81,
 	AddLiquidity = 0x67088D59,
 	RemoveLiquidity = 0x35315332,
+	FlashSwap = 0xf85f9e06,
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, Dex> Precompile
-	for DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex>
+impl<AccountId, AddressMapping, CurrencyIdMapping, Dex, EVM> Precompile
+	for DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex, EVM>
 where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
 	Dex: DEXManager<AccountId, CurrencyId, Balance>,
+	EVM: EVMT<AccountId>,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
-		_context: &Context,
+		target_gas: Option<u64>,
+		context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		//TODO: evaluate cost
 
@@ -271,6 +285,74 @@ where
 
 				Ok((ExitSucceed::Returned, vec![], 0))
 			}
+			Action::FlashSwap => {
+				let who = input.account_id_at(1)?;
+				let who_address = input.evm_address_at(1)?;
+				let borrow_currency_id = input.currency_id_at(2)?;
+				let paired_currency_id = input.currency_id_at(3)?;
+				let borrow_amount = input.balance_at(4)?;
+				let repay_currency_id = input.currency_id_at(5)?;
+				let max_repay_amount = input.balance_at(6)?;
+				let storage_limit = input.u32_at(7)?;
+				// solidity abi enocde bytes will add an offset at input[8]
+				let callback_data_len = input.u32_at(9)?;
+				let callback_data = input.bytes_at(10, callback_data_len as usize)?;
+				let gas_limit =
+					target_gas.ok_or_else(|| ExitError::Other("Dex flash_swap requires gas limit".into()))?;
+				let callback_gas_limit = gas_limit.checked_sub(FLASH_SWAP_BASE_GAS).ok_or(ExitError::OutOfGas)?;
+
+				log::debug!(
+					target: "evm",
+					"dex: flash_swap who: {:?}, borrow_currency_id: {:?}, paired_currency_id: {:?}, borrow_amount: {:?}, repay_currency_id: {:?}, max_repay_amount: {:?}",
+					who, borrow_currency_id, paired_currency_id, borrow_amount, repay_currency_id, max_repay_amount,
+				);
+
+				// call back the borrower contract while the borrowed currency is lent out
+				let invoke_context = InvokeContext {
+					contract: who_address,
+					sender: context.address,
+					origin: who_address,
+				};
+				let mut callback_used_gas: u64 = 0;
+				let value = Dex::flash_swap(
+					&who,
+					borrow_currency_id,
+					paired_currency_id,
+					borrow_amount,
+					repay_currency_id,
+					max_repay_amount,
+					|| {
+						let info = EVM::execute(
+							invoke_context,
+							callback_data,
+							Default::default(),
+							callback_gas_limit,
+							storage_limit,
+							ExecutionMode::Execute,
+						)?;
+						callback_used_gas = info.used_gas.low_u64();
+						match info.exit_reason {
+							ExitReason::Succeed(_) => Ok(()),
+							_ => Err(DispatchError::Other("flash swap callback failed")),
+						}
+					},
+				)
+				.map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				let cost = FLASH_SWAP_BASE_GAS.saturating_add(callback_used_gas);
+				if cost > gas_limit {
+					return Err(ExitError::OutOfGas);
+				}
+
+				// output
+				let mut be_bytes = [0u8; 32];
+				U256::from(value).to_big_endian(&mut be_bytes[..32]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), cost))
+			}
 		}
 	}
 }
@@ -329,5 +411,12 @@ mod tests {
 			)),
 			Into::<u32>::into(Action::RemoveLiquidity)
 		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector(
+				"flashSwap(address,address,address,uint256,address,uint256,uint32,bytes)"
+			)),
+			Into::<u32>::into(Action::FlashSwap)
+		);
 	}
 }
@@ -30,6 +30,7 @@ use frame_support::{
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use setheum_support::{
 	mocks::MockAddressMapping, AddressMapping as AddressMappingT, DEXIncentives, ExchangeRate, ExchangeRateProvider,
+	SerpTreasury,
 };
 use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
 pub use primitives::{
@@ -39,7 +40,7 @@ use sha3::{Digest, Keccak256};
 use sp_core::{crypto::AccountId32, H160, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, Convert, IdentityLookup, One as OneT},
-	DispatchResult, FixedPointNumber, FixedU128, Perbill,
+	DispatchResult, FixedPointNumber, FixedU128, Perbill, Permill,
 };
 use sp_std::{
 	collections::btree_map::BTreeMap,
@@ -158,6 +159,10 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = DNAR;
 }
 
+parameter_types! {
+	pub const MaxTransfers: u32 = 10;
+}
+
 impl setheum_currencies::Config for Test {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -166,6 +171,9 @@ impl setheum_currencies::Config for Test {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = EVMBridge;
+	type GasToWeight = GasToWeight;
+	type CashDropHandler = ();
+	type MaxTransfers = MaxTransfers;
 }
 
 impl setheum_evm_bridge::Config for Test {
@@ -181,12 +189,24 @@ parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
 	pub const NftPalletId: PalletId = PalletId(*b"set/sNFT");
+	pub const MaxRoyaltyRate: Permill = Permill::from_percent(50);
+	pub const NftDataDepositPerByte: Balance = 1;
+	pub const MaxAttributeKeyLength: u32 = 32;
+	pub const MaxAttributeValueLength: u32 = 256;
+	pub const MaxAttributesPerToken: u32 = 10;
 }
 impl setheum_nft::Config for Test {
 	type Event = Event;
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type PalletId = NftPalletId;
+	type MultiCurrency = Currencies;
+	type MaxRoyaltyRate = MaxRoyaltyRate;
+	type DataDepositPerByte = NftDataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxAttributesPerToken = MaxAttributesPerToken;
+	type Recovery = ();
 	type WeightInfo = ();
 }
 
@@ -297,6 +317,98 @@ impl pallet_scheduler::Config for Test {
 	type WeightInfo = ();
 }
 
+pub struct MockSerpTreasury;
+impl SerpTreasury<AccountId> for MockSerpTreasury {
+	type Amount = Amount;
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+	type BlockNumber = BlockNumber;
+
+	fn get_adjustment_frequency() -> BlockNumber {
+		Default::default()
+	}
+
+	fn get_total_setter() -> Balance {
+		Default::default()
+	}
+
+	fn get_standard_proportion(_: Balance) -> Ratio {
+		Default::default()
+	}
+
+	fn get_serplus_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_settpay_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_treasury_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_charity_fund_serpup(_: Balance, _: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_surpup(_: CurrencyId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_surpdown(_: CurrencyId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_serp_tes() -> DispatchResult {
+		Ok(())
+	}
+
+	fn serp_tes(_: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_standard(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_standard(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_propper(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_propper(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_setter(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_setter(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn issue_dexer(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn burn_dexer(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn deposit_serplus(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn deposit_setter(_: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
 pub struct MockDEXIncentives;
 impl DEXIncentives<AccountId, CurrencyId, Balance> for MockDEXIncentives {
 	fn do_deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
@@ -317,6 +429,11 @@ ord_parameter_types! {
 parameter_types! {
 	pub const TradingPathLimit: u32 = 3;
 	pub const DEXPalletId: PalletId = PalletId(*b"set/sdex");
+	pub const ExtendedProvisioningBlocks: BlockNumber = 0;
+	pub const StatsPeriod: BlockNumber = 10;
+	pub const MaxStatsPeriods: u32 = 3;
+	pub const ProtocolFeeForwardPeriod: BlockNumber = 0;
+	pub const MaxProtocolFeeForwardsPerBlock: u32 = 1;
 }
 
 impl setheum_dex::Config for Test {
@@ -329,6 +446,13 @@ impl setheum_dex::Config for Test {
 	type DEXIncentives = MockDEXIncentives;
 	type UpdateOriginOrigin = EnsureSignedBy<UpdateOriginOrigin, AccountId>;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type StatsPeriod = StatsPeriod;
+	type MaxStatsPeriods = MaxStatsPeriods;
+	type SerpTreasury = MockSerpTreasury;
+	type ProtocolFeeForwardPeriod = ProtocolFeeForwardPeriod;
+	type MaxProtocolFeeForwardsPerBlock = MaxProtocolFeeForwardsPerBlock;
+	type Call = Call;
 }
 
 pub type AdaptedBasicCurrency = setheum_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
@@ -352,7 +476,8 @@ pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	OriginCaller,
 	Test,
 >;
-pub type DexPrecompile = crate::DexPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, DexModule>;
+pub type DexPrecompile =
+	crate::DexPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, DexModule, ModuleEVM>;
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice_evm_addr();
@@ -368,6 +493,8 @@ ord_parameter_types! {
 	pub const DeploymentFee: u64 = 200;
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const ChainId: u64 = 1;
+	pub const MaxGasPerExtrinsic: u64 = 10_000_000;
+	pub const EthTransactionStorageLimit: u32 = 1000;
 }
 
 pub struct GasToWeight;
@@ -396,13 +523,19 @@ impl setheum_evm::Config for Test {
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type MaxGasPerExtrinsic = MaxGasPerExtrinsic;
 	type ChargeTransactionPayment = ChargeTransactionPayment;
+	type EthTransactionStorageLimit = EthTransactionStorageLimit;
+	type HardFork = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type CodeUpgradeDelay = ();
+	type MaxCodeVersions = ();
+	type CodeUpgradeCancelOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type WeightInfo = ();
 }
 
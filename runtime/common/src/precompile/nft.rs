@@ -18,7 +18,7 @@
 
 use frame_support::log;
 use setheum_evm::{Context, ExitError, ExitSucceed, Precompile};
-use setheum_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT};
+use setheum_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, NFTMirror};
 use sp_core::{H160, U256};
 use sp_std::{borrow::Cow, fmt::Debug, marker::PhantomData, prelude::*, result};
 
@@ -36,6 +36,21 @@ use primitives::NFTBalance;
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Query owner. Rest `input` bytes: `class_id`, `token_id`.
 /// - Transfer. Rest `input`bytes: `from`, `to`, `class_id`, `token_id`.
+///
+/// ERC-721 actions, called by the mirrored ERC-721 contract of each class
+/// which emits the `Transfer`/`Approval` logs and, for `safeTransferFrom`,
+/// checks `onERC721Received` of the recipient:
+/// - Query class balance. Rest `input` bytes: `account_id`, `class_id`.
+/// - Query token URI. Rest `input` bytes: `class_id`, `token_id`.
+/// - Query approved. Rest `input` bytes: `class_id`, `token_id`.
+/// - Query is approved for all. Rest `input` bytes: `owner`, `operator`,
+///   `class_id`.
+/// - Approve. Rest `input` bytes: `who`, `approved`, `class_id`,
+///   `token_id`. The zero address clears the approval.
+/// - Set approval for all. Rest `input` bytes: `owner`, `operator`,
+///   `class_id`, `approved`.
+/// - Transfer from. Rest `input` bytes: `who`, `from`, `to`, `class_id`,
+///   `token_id`.
 pub struct NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>(
 	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, NFT)>,
 );
@@ -46,6 +61,13 @@ pub enum Action {
 	QueryBalance = 0x70a08231,
 	QueryOwner = 0xd9dad80d,
 	Transfer = 0x411b252,
+	QueryClassBalance = 0x00fdd58e,
+	QueryTokenURI = 0x92cb829d,
+	QueryApproved = 0x5b8ef74e,
+	QueryIsApprovedForAll = 0xba24bb37,
+	Approve = 0x4dc5ecb3,
+	SetApprovalForAll = 0x96215862,
+	TransferFrom = 0x10f02113,
 }
 
 impl<AccountId, AddressMapping, CurrencyIdMapping, NFT> Precompile
@@ -54,7 +76,8 @@ where
 	AccountId: Clone + Debug,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
-	NFT: NFTT<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>,
+	NFT: NFTT<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>
+		+ NFTMirror<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>,
 {
 	fn execute(
 		input: &[u8],
@@ -89,10 +112,7 @@ where
 					Default::default()
 				};
 
-				let mut address = [0u8; 32];
-				address[12..].copy_from_slice(&owner.as_bytes().to_vec());
-
-				Ok((ExitSucceed::Returned, address.to_vec(), 0))
+				Ok((ExitSucceed::Returned, vec_u8_from_address(owner), 0))
 			}
 			Action::Transfer => {
 				let from = input.account_id_at(1)?;
@@ -108,6 +128,99 @@ where
 
 				Ok((ExitSucceed::Returned, vec![], 0))
 			}
+			Action::QueryClassBalance => {
+				let who = input.account_id_at(1)?;
+				let class_id = input.u32_at(2)?;
+
+				log::debug!(target: "evm", "nft: query_class_balance who: {:?}, class_id: {:?}", who, class_id);
+
+				let balance = vec_u8_from_balance(NFT::class_balance(class_id, &who));
+
+				Ok((ExitSucceed::Returned, balance, 0))
+			}
+			Action::QueryTokenURI => {
+				let class_id = input.u32_at(1)?;
+				let token_id = input.u64_at(2)?;
+
+				log::debug!(target: "evm", "nft: query_token_uri class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let uri =
+					NFT::token_uri((class_id, token_id)).ok_or_else(|| ExitError::Other("token not found".into()))?;
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bytes(&uri), 0))
+			}
+			Action::QueryApproved => {
+				let class_id = input.u32_at(1)?;
+				let token_id = input.u64_at(2)?;
+
+				log::debug!(target: "evm", "nft: query_approved class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let approved: H160 = if let Some(a) = NFT::approved((class_id, token_id)) {
+					AddressMapping::get_evm_address(&a).unwrap_or_else(|| AddressMapping::get_default_evm_address(&a))
+				} else {
+					Default::default()
+				};
+
+				Ok((ExitSucceed::Returned, vec_u8_from_address(approved), 0))
+			}
+			Action::QueryIsApprovedForAll => {
+				let owner = input.account_id_at(1)?;
+				let operator = input.account_id_at(2)?;
+				let class_id = input.u32_at(3)?;
+
+				log::debug!(target: "evm", "nft: query_is_approved_for_all owner: {:?}, operator: {:?}, class_id: {:?}", owner, operator, class_id);
+
+				let approved = NFT::is_approved_for_all(&owner, &operator, class_id);
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bool(approved), 0))
+			}
+			Action::Approve => {
+				let who = input.account_id_at(1)?;
+				let approved = input.evm_address_at(2)?;
+				let approved = if approved == H160::zero() {
+					None
+				} else {
+					Some(AddressMapping::get_account_id(&approved))
+				};
+
+				let class_id = input.u32_at(3)?;
+				let token_id = input.u64_at(4)?;
+
+				log::debug!(target: "evm", "nft: approve who: {:?}, approved: {:?}, class_id: {:?}, token_id: {:?}", who, approved, class_id, token_id);
+
+				NFT::approve(&who, approved, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], 0))
+			}
+			Action::SetApprovalForAll => {
+				let owner = input.account_id_at(1)?;
+				let operator = input.account_id_at(2)?;
+				let class_id = input.u32_at(3)?;
+				let approved = input.u32_at(4)? != 0;
+
+				log::debug!(target: "evm", "nft: set_approval_for_all owner: {:?}, operator: {:?}, class_id: {:?}, approved: {:?}", owner, operator, class_id, approved);
+
+				NFT::set_approval_for_all(&owner, &operator, class_id, approved)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], 0))
+			}
+			Action::TransferFrom => {
+				let who = input.account_id_at(1)?;
+				let from = input.account_id_at(2)?;
+				let to = input.account_id_at(3)?;
+
+				let class_id = input.u32_at(4)?;
+				let token_id = input.u64_at(5)?;
+
+				log::debug!(target: "evm", "nft: transfer_from who: {:?}, from: {:?}, to: {:?}, class_id: {:?}, token_id: {:?}", who, from, to, class_id, token_id);
+
+				NFT::transfer_from(&who, &from, &to, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], 0))
+			}
 		}
 	}
 }
@@ -118,6 +231,31 @@ fn vec_u8_from_balance(b: NFTBalance) -> Vec<u8> {
 	be_bytes.to_vec()
 }
 
+fn vec_u8_from_address(address: H160) -> Vec<u8> {
+	let mut be_bytes = [0u8; 32];
+	be_bytes[12..].copy_from_slice(address.as_bytes());
+	be_bytes.to_vec()
+}
+
+fn vec_u8_from_bool(b: bool) -> Vec<u8> {
+	let mut be_bytes = [0u8; 32];
+	be_bytes[31] = b as u8;
+	be_bytes.to_vec()
+}
+
+/// ABI-encode `b` as dynamic `bytes`/`string`: offset, length and the
+/// zero-padded data.
+fn vec_u8_from_bytes(b: &[u8]) -> Vec<u8> {
+	let mut encoded = [0u8; 64];
+	U256::from(32).to_big_endian(&mut encoded[..32]);
+	U256::from(b.len()).to_big_endian(&mut encoded[32..]);
+
+	let mut encoded = encoded.to_vec();
+	encoded.extend_from_slice(b);
+	encoded.resize(encoded.len() + (32 - b.len() % 32) % 32, 0);
+	encoded
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -139,5 +277,58 @@ mod tests {
 			u32::from_be_bytes(get_function_selector("transfer(address,address,uint256,uint256)")),
 			Into::<u32>::into(Action::Transfer)
 		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("balanceOf(address,uint256)")),
+			Into::<u32>::into(Action::QueryClassBalance)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("tokenURI(uint256,uint256)")),
+			Into::<u32>::into(Action::QueryTokenURI)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getApproved(uint256,uint256)")),
+			Into::<u32>::into(Action::QueryApproved)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("isApprovedForAll(address,address,uint256)")),
+			Into::<u32>::into(Action::QueryIsApprovedForAll)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("approve(address,address,uint256,uint256)")),
+			Into::<u32>::into(Action::Approve)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("setApprovalForAll(address,address,uint256,bool)")),
+			Into::<u32>::into(Action::SetApprovalForAll)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector(
+				"transferFrom(address,address,address,uint256,uint256)"
+			)),
+			Into::<u32>::into(Action::TransferFrom)
+		);
+	}
+
+	#[test]
+	fn vec_u8_from_bytes_works() {
+		assert_eq!(vec_u8_from_bytes(&[]), {
+			let mut expected = [0u8; 64];
+			expected[31] = 32;
+			expected.to_vec()
+		});
+
+		let encoded = vec_u8_from_bytes(&[1u8; 33]);
+		assert_eq!(encoded.len(), 128);
+		assert_eq!(encoded[31], 32);
+		assert_eq!(encoded[63], 33);
+		assert_eq!(encoded[64..97], [1u8; 33][..]);
+		assert_eq!(encoded[97..], [0u8; 31][..]);
 	}
 }
@@ -21,21 +21,23 @@ use super::*;
 use crate::precompile::{
 	mock::{
 		dnar_evm_address, alice, alice_evm_addr, usdj_evm_address, bob, bob_evm_addr, erc20_address_not_exists,
-		get_task_id, lp_dnar_usdj_evm_address, new_test_ext, renbtc_evm_address, run_to_block, Balances, DexModule,
-		DexPrecompile, Event as TestEvent, MultiCurrencyPrecompile, Oracle, OraclePrecompile, Origin, Price,
-		ScheduleCallPrecompile, System, Test, ALICE, USDJ, INITIAL_BALANCE, RENBTC,
+		get_function_selector, get_task_id, lp_dnar_usdj_evm_address, new_test_ext, renbtc_evm_address, run_to_block,
+		AccountId, Balances, DexModule, DexPrecompile, Event as TestEvent, ModuleEVM, MultiCurrencyPrecompile,
+		NFTModule, NFTPrecompile, NetworkContractAccount, Oracle, OraclePrecompile,
+		Origin, Price, ScheduleCallPrecompile, System, Test, Tokens, ALICE, USDJ, INITIAL_BALANCE, RENBTC,
 	},
 	schedule_call::TaskInfo,
 };
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Get};
 use hex_literal::hex;
-use setheum_evm::ExitError;
-use setheum_support::AddressMapping;
-use orml_traits::DataFeeder;
-use primitives::{Balance, PREDEPLOY_ADDRESS_START};
-use sp_core::{H160, U256};
-use sp_runtime::FixedPointNumber;
+use setheum_evm::{CallInfo, ExitError, ExitReason, ExitRevert, Log};
+use setheum_nft::{ClassProperty, Properties};
+use setheum_support::{AddressMapping, ExecutionMode, InvokeContext, EVM as EVMTrait};
+use orml_traits::{DataFeeder, MultiCurrency};
+use primitives::{evm::mirrored_nft_address, Balance, TradingPair, PREDEPLOY_ADDRESS_START};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber};
 use std::str::FromStr;
 
 pub struct DummyPrecompile;
@@ -282,6 +284,267 @@ fn multicurrency_precompile_should_work() {
 	});
 }
 
+#[test]
+fn nft_precompile_erc721_should_work() {
+	new_test_ext().execute_with(|| {
+		let class_account = <Test as setheum_nft::Config>::PalletId::get().into_sub_account(0u32);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice()),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice()),
+			class_account.clone(),
+			1_000
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_account),
+			alice(),
+			0,
+			b"ipfs://QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_vec(),
+			1
+		));
+
+		// the mirrored ERC-721 contract of the class calls the precompile
+		let context = Context {
+			address: Default::default(),
+			caller: mirrored_nft_address(0),
+			apparent_value: Default::default(),
+		};
+
+		// 1.QueryClassBalance
+		let mut input = [0u8; 4 + 3 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::QueryClassBalance).to_be_bytes());
+		// owner
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let mut expected_output = [0u8; 32];
+		expected_output[31] = 1;
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// 2.QueryTokenURI
+		let mut input = [0u8; 4 + 3 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::QueryTokenURI).to_be_bytes());
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// token_id
+		U256::from(0).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let uri = b"ipfs://QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u";
+		let mut expected_output = [0u8; 4 * 32];
+		expected_output[31] = 32;
+		expected_output[63] = uri.len() as u8;
+		expected_output[2 * 32..2 * 32 + uri.len()].copy_from_slice(&uri[..]);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// 3.Approve
+		let mut input = [0u8; 4 + 5 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::Approve).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// approved
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// token_id
+		U256::from(0).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, 0);
+
+		// 4.QueryApproved
+		let mut input = [0u8; 4 + 3 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::QueryApproved).to_be_bytes());
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// token_id
+		U256::from(0).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let mut expected_output = [0u8; 32];
+		expected_output[12..].copy_from_slice(bob_evm_addr().as_bytes());
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// 5.TransferFrom
+		let mut input = [0u8; 4 + 6 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::TransferFrom).to_be_bytes());
+		// who
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// to
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		// token_id
+		U256::from(0).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, 0);
+		assert_eq!(orml_nft::Pallet::<Test>::tokens(0, 0).unwrap().owner, bob());
+		assert_eq!(NFTModule::token_approvals(0, 0), None);
+
+		// the approval was used up
+		assert_noop!(
+			NFTPrecompile::execute(&input, None, &context),
+			ExitError::Other("NoPermission".into())
+		);
+
+		// 6.SetApprovalForAll
+		let mut input = [0u8; 4 + 5 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::SetApprovalForAll).to_be_bytes());
+		// owner
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// operator
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// approved
+		U256::from(1).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, 0);
+
+		// 7.QueryIsApprovedForAll
+		let mut input = [0u8; 4 + 4 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(nft::Action::QueryIsApprovedForAll).to_be_bytes());
+		// owner
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// operator
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// class_id
+		U256::from(0).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let mut expected_output = [0u8; 32];
+		expected_output[31] = 1;
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+	});
+}
+
+fn mirrored_nft_input(signature: &str, args: &[U256]) -> Vec<u8> {
+	let mut input = get_function_selector(signature).to_vec();
+	for arg in args {
+		let mut be_bytes = [0u8; 32];
+		arg.to_big_endian(&mut be_bytes);
+		input.extend_from_slice(&be_bytes);
+	}
+	input
+}
+
+fn mirrored_nft_view(input: Vec<u8>) -> CallInfo {
+	let context = InvokeContext {
+		contract: mirrored_nft_address(0),
+		sender: bob_evm_addr(),
+		origin: bob_evm_addr(),
+	};
+	<ModuleEVM as EVMTrait<AccountId>>::execute(context, input, Default::default(), 1_000_000, 0, ExecutionMode::View)
+		.unwrap()
+}
+
+#[test]
+fn mirrored_nft_contract_should_work() {
+	new_test_ext().execute_with(|| {
+		let class_account = <Test as setheum_nft::Config>::PalletId::get().into_sub_account(0u32);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice()),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice()),
+			class_account.clone(),
+			1_000
+		));
+		assert_ok!(NFTModule::mint(Origin::signed(class_account), alice(), 0, vec![1], 1));
+
+		let alice_arg = U256::from(alice_evm_addr().as_bytes());
+		let bob_arg = U256::from(bob_evm_addr().as_bytes());
+
+		// balanceOf
+		let info = mirrored_nft_view(mirrored_nft_input("balanceOf(address)", &[alice_arg]));
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(U256::from_big_endian(&info.output), U256::from(1));
+		let info = mirrored_nft_view(mirrored_nft_input("balanceOf(address)", &[bob_arg]));
+		assert_eq!(U256::from_big_endian(&info.output), U256::from(0));
+
+		// ownerOf
+		let info = mirrored_nft_view(mirrored_nft_input("ownerOf(uint256)", &[0.into()]));
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(H160::from_slice(&info.output[12..]), alice_evm_addr());
+		let info = mirrored_nft_view(mirrored_nft_input("ownerOf(uint256)", &[1.into()]));
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+
+		// transferFrom by someone else than the owner fails
+		assert_ok!(ModuleEVM::call(
+			Origin::signed(bob()),
+			mirrored_nft_address(0),
+			mirrored_nft_input("transferFrom(address,address,uint256)", &[alice_arg, bob_arg, 0.into()]),
+			0,
+			1_000_000,
+			0,
+			vec![]
+		));
+		assert_eq!(orml_nft::Pallet::<Test>::tokens(0, 0).unwrap().owner, alice());
+
+		// transferFrom by the owner
+		assert_ok!(ModuleEVM::call(
+			Origin::signed(alice()),
+			mirrored_nft_address(0),
+			mirrored_nft_input("transferFrom(address,address,uint256)", &[alice_arg, bob_arg, 0.into()]),
+			0,
+			1_000_000,
+			0,
+			vec![]
+		));
+		assert_eq!(orml_nft::Pallet::<Test>::tokens(0, 0).unwrap().owner, bob());
+		let event = TestEvent::setheum_evm(setheum_evm::Event::Log(Log {
+			address: mirrored_nft_address(0),
+			topics: vec![
+				H256::from(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")),
+				H256::from(alice_evm_addr()),
+				H256::from(bob_evm_addr()),
+				H256::zero(),
+			],
+			data: vec![],
+		}));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		let info = mirrored_nft_view(mirrored_nft_input("ownerOf(uint256)", &[0.into()]));
+		assert_eq!(H160::from_slice(&info.output[12..]), bob_evm_addr());
+		let info = mirrored_nft_view(mirrored_nft_input("balanceOf(address)", &[alice_arg]));
+		assert_eq!(U256::from_big_endian(&info.output), U256::from(0));
+		let info = mirrored_nft_view(mirrored_nft_input("balanceOf(address)", &[bob_arg]));
+		assert_eq!(U256::from_big_endian(&info.output), U256::from(1));
+	});
+}
+
 #[test]
 fn oracle_precompile_should_work() {
 	new_test_ext().execute_with(|| {
@@ -839,6 +1102,212 @@ fn dex_precompile_swap_with_exact_target_should_work() {
 	});
 }
 
+fn flash_swap_input(who: H160) -> [u8; 4 + 10 * 32] {
+	// array_size + action + who + borrow_currency_id + paired_currency_id + borrow_amount +
+	// repay_currency_id + max_repay_amount + storage_limit + offset + callback_data_len
+	let mut input = [0u8; 4 + 10 * 32];
+	// array size
+	U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+	// action
+	input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(dex::Action::FlashSwap).to_be_bytes());
+	// who
+	U256::from(who.as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+	// RENBTC
+	U256::from_big_endian(&renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+	// USDJ
+	U256::from_big_endian(&usdj_evm_address().as_bytes()).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+	// borrow_amount
+	U256::from(100).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+	// repay in RENBTC
+	U256::from_big_endian(&renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+	// max_repay_amount
+	U256::from(200).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+	// storage_limit
+	U256::from(1_000).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
+	// skip offset
+	// callback_data_len
+	U256::from(0).to_big_endian(&mut input[4 + 9 * 32..4 + 10 * 32]);
+	input
+}
+
+#[test]
+fn dex_precompile_flash_swap_should_work() {
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/USDJ
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, USDJ,));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			USDJ,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+		let trading_pair = TradingPair::from_token_currency_ids(RENBTC, USDJ).unwrap();
+		let balance = Tokens::free_balance(RENBTC, &alice());
+
+		let (reason, output, used_gas) =
+			DexPrecompile::execute(&flash_swap_input(alice_evm_addr()), Some(100_000), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		// the callback on an account without code only pays for the call transaction
+		assert_eq!(used_gas, dex::FLASH_SWAP_BASE_GAS + 21_000);
+
+		// the borrowed RENBTC is repaid with the flash loan fee
+		let repay_amount = U256::from_big_endian(&output).as_u128();
+		assert!(repay_amount > 100 && repay_amount <= 200);
+		assert_eq!(Tokens::free_balance(RENBTC, &alice()), balance - (repay_amount - 100));
+		assert_eq!(
+			DexModule::liquidity_pool(trading_pair),
+			(1_000 + (repay_amount - 100), 1_000_000)
+		);
+		assert!(!DexModule::flash_swap_locks(trading_pair));
+	});
+}
+
+#[test]
+fn dex_precompile_flash_swap_should_roll_back_failed_callback() {
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/USDJ
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, USDJ,));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			USDJ,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+		let trading_pair = TradingPair::from_token_currency_ids(RENBTC, USDJ).unwrap();
+		let balance = Tokens::free_balance(RENBTC, &alice());
+
+		// not enough gas for the flash swap itself
+		assert_eq!(
+			DexPrecompile::execute(
+				&flash_swap_input(alice_evm_addr()),
+				Some(dex::FLASH_SWAP_BASE_GAS - 1),
+				&context
+			),
+			Err(ExitError::OutOfGas)
+		);
+
+		// the callback runs out of gas
+		assert_eq!(
+			DexPrecompile::execute(
+				&flash_swap_input(alice_evm_addr()),
+				Some(dex::FLASH_SWAP_BASE_GAS + 1_000),
+				&context
+			),
+			Err(ExitError::Other("OutOfGas".into()))
+		);
+
+		// the borrowed RENBTC is returned to the pool
+		assert_eq!(Tokens::free_balance(RENBTC, &alice()), balance);
+		assert_eq!(DexModule::liquidity_pool(trading_pair), (1_000, 1_000_000));
+		assert!(!DexModule::flash_swap_locks(trading_pair));
+	});
+}
+
+#[test]
+fn dex_precompile_flash_swap_should_call_back_borrower() {
+	// init code of a contract that reverts every call
+	let reverting_contract = hex!("6460006000fd6000526005601bf3").to_vec();
+
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/USDJ
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, USDJ,));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			USDJ,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+
+		assert_ok!(ModuleEVM::create_network_contract(
+			Origin::signed(NetworkContractAccount::get()),
+			reverting_contract,
+			0,
+			1_000_000,
+			1_000,
+		));
+		let borrower = match System::events().last().unwrap().event {
+			TestEvent::setheum_evm(setheum_evm::Event::Created(address)) => address,
+			_ => panic!("borrower contract not created"),
+		};
+
+		let dex_precompile = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 5);
+		let trading_pair = TradingPair::from_token_currency_ids(RENBTC, USDJ).unwrap();
+		let balance = Tokens::free_balance(RENBTC, &alice());
+
+		// only system contracts can call the precompile
+		let user_context = Context {
+			address: dex_precompile,
+			caller: bob_evm_addr(),
+			apparent_value: Default::default(),
+		};
+		assert_eq!(
+			<Test as setheum_evm::Config>::Precompiles::execute(
+				dex_precompile,
+				&flash_swap_input(alice_evm_addr()),
+				Some(100_000),
+				&user_context
+			),
+			Some(Err(ExitError::Other("no permission".into())))
+		);
+
+		// the predeploy DEX contract calls the precompile on behalf of the borrower
+		let context = Context {
+			address: dex_precompile,
+			caller: H160::from_low_u64_be(PREDEPLOY_ADDRESS_START),
+			apparent_value: Default::default(),
+		};
+
+		// the callback goes to the borrower, not to the predeploy DEX contract
+		assert_eq!(
+			<Test as setheum_evm::Config>::Precompiles::execute(
+				dex_precompile,
+				&flash_swap_input(borrower),
+				Some(100_000),
+				&context
+			),
+			Some(Err(ExitError::Other("flash swap callback failed".into())))
+		);
+		assert_eq!(DexModule::liquidity_pool(trading_pair), (1_000, 1_000_000));
+
+		let (reason, output, _) = <Test as setheum_evm::Config>::Precompiles::execute(
+			dex_precompile,
+			&flash_swap_input(alice_evm_addr()),
+			Some(100_000),
+			&context,
+		)
+		.unwrap()
+		.unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let repay_amount = U256::from_big_endian(&output).as_u128();
+		assert_eq!(Tokens::free_balance(RENBTC, &alice()), balance - (repay_amount - 100));
+		assert!(!DexModule::flash_swap_locks(trading_pair));
+	});
+}
+
 #[test]
 fn task_id_max_and_min() {
 	let task_id = TaskInfo {
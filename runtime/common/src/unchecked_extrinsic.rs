@@ -0,0 +1,538 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unchecked extrinsics that may also be signed by Ethereum wallets, over
+//! the EIP-712 typed data of an EVM call.
+
+use codec::{Decode, Encode, EncodeLike, Input};
+use frame_support::weights::{DispatchInfo, GetDispatchInfo};
+use primitives::{evm::{AccessListItem, EvmAddress}, AccountId, Balance, Nonce, SetheumMultiSignature};
+use setheum_support::AddressMapping;
+use sp_core::{H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	generic::{self, CheckedExtrinsic},
+	traits::{self, Checkable, Convert, Extrinsic, ExtrinsicMetadata, Member, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	MultiAddress, OpaqueExtrinsic, RuntimeDebug,
+};
+use sp_std::{fmt, marker::PhantomData, prelude::*};
+
+/// The address format for describing accounts.
+pub type Address = MultiAddress<AccountId, primitives::AccountIndex>;
+
+/// The contract an EVM call is sent to.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum TransactionAction {
+	Call(EvmAddress),
+	Create,
+}
+
+/// The EVM call an Ethereum wallet signs as EIP-712 typed data:
+///
+/// ```text
+/// Transaction(string action,address to,uint256 nonce,uint256 tip,bytes data,uint256 value,uint256 gasLimit,uint256 storageLimit,AccessListItem[] accessList,uint256 validUntil)
+/// AccessListItem(address address,bytes32[] storageKeys)
+/// ```
+///
+/// in the `EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)`
+/// domain, salted with the genesis hash. `validUntil` is the block the era
+/// of the extrinsic ends at, `u64::MAX` for immortal extrinsics.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct EthereumTransactionMessage {
+	pub chain_id: u64,
+	pub genesis: H256,
+	pub nonce: Nonce,
+	pub tip: Balance,
+	pub gas_limit: u64,
+	pub storage_limit: u32,
+	pub action: TransactionAction,
+	pub value: Balance,
+	pub input: Vec<u8>,
+	pub access_list: Vec<AccessListItem>,
+	pub valid_until: u64,
+}
+
+impl EthereumTransactionMessage {
+	/// The hash the Ethereum wallet signs, as specified by EIP-712.
+	pub fn signing_hash(&self) -> [u8; 32] {
+		let domain_type_hash = keccak_256(b"EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)");
+		let mut domain = domain_type_hash.to_vec();
+		domain.extend_from_slice(&keccak_256(b"Setheum EVM"));
+		domain.extend_from_slice(&keccak_256(b"1"));
+		domain.extend_from_slice(&to_uint256(self.chain_id.into()));
+		domain.extend_from_slice(self.genesis.as_bytes());
+		let domain_separator = keccak_256(&domain);
+
+		let (action, to) = match self.action {
+			TransactionAction::Call(to) => (&b"Call"[..], to),
+			TransactionAction::Create => (&b"Create"[..], EvmAddress::default()),
+		};
+		let tx_type_hash = keccak_256(
+			b"Transaction(string action,address to,uint256 nonce,uint256 tip,bytes data,uint256 value,uint256 gasLimit,uint256 storageLimit,AccessListItem[] accessList,uint256 validUntil)AccessListItem(address address,bytes32[] storageKeys)",
+		);
+		let mut tx = tx_type_hash.to_vec();
+		tx.extend_from_slice(&keccak_256(action));
+		tx.extend_from_slice(H256::from(to).as_bytes());
+		tx.extend_from_slice(&to_uint256(self.nonce.into()));
+		tx.extend_from_slice(&to_uint256(self.tip.into()));
+		tx.extend_from_slice(&keccak_256(&self.input));
+		tx.extend_from_slice(&to_uint256(self.value.into()));
+		tx.extend_from_slice(&to_uint256(self.gas_limit.into()));
+		tx.extend_from_slice(&to_uint256(self.storage_limit.into()));
+		tx.extend_from_slice(&access_list_hash(&self.access_list));
+		tx.extend_from_slice(&to_uint256(self.valid_until.into()));
+		let tx_hash = keccak_256(&tx);
+
+		let mut msg = b"\x19\x01".to_vec();
+		msg.extend_from_slice(&domain_separator);
+		msg.extend_from_slice(&tx_hash);
+		keccak_256(&msg)
+	}
+}
+
+fn to_uint256(value: U256) -> [u8; 32] {
+	let mut bytes = [0u8; 32];
+	value.to_big_endian(&mut bytes);
+	bytes
+}
+
+/// `encodeData` of an `AccessListItem[]`: the hash of the concatenated
+/// `hashStruct` of the items.
+fn access_list_hash(access_list: &[AccessListItem]) -> [u8; 32] {
+	let item_type_hash = keccak_256(b"AccessListItem(address address,bytes32[] storageKeys)");
+	let items = access_list
+		.iter()
+		.flat_map(|item| {
+			let storage_keys = item
+				.storage_keys
+				.iter()
+				.flat_map(|key| key.as_bytes().to_vec())
+				.collect::<Vec<u8>>();
+			let mut encoded = item_type_hash.to_vec();
+			encoded.extend_from_slice(H256::from(item.address).as_bytes());
+			encoded.extend_from_slice(&keccak_256(&storage_keys));
+			keccak_256(&encoded).to_vec()
+		})
+		.collect::<Vec<u8>>();
+	keccak_256(&items)
+}
+
+/// An `UncheckedExtrinsic` that also accepts `SetheumMultiSignature::Eip712`
+/// signatures. `ConvertTx` builds the typed data of the call, and the sender
+/// must be the account `AddressMapping` maps the signer to.
+pub struct SetheumUncheckedExtrinsic<Call, Extra: SignedExtension, ConvertTx, Mapping>(
+	pub generic::UncheckedExtrinsic<Address, Call, SetheumMultiSignature, Extra>,
+	PhantomData<(ConvertTx, Mapping)>,
+);
+
+impl<Call, Extra: SignedExtension, ConvertTx, Mapping> SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping> {
+	/// New instance of an unsigned extrinsic aka "inherent".
+	pub fn new_unsigned(function: Call) -> Self {
+		Self(generic::UncheckedExtrinsic::new_unsigned(function), PhantomData)
+	}
+}
+
+impl<Call: Clone, Extra: SignedExtension, ConvertTx, Mapping> Clone
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn clone(&self) -> Self {
+		Self(self.0.clone(), PhantomData)
+	}
+}
+
+impl<Call: PartialEq, Extra: SignedExtension, ConvertTx, Mapping> PartialEq
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<Call: Eq, Extra: SignedExtension, ConvertTx, Mapping> Eq
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+}
+
+impl<Call: fmt::Debug, Extra: SignedExtension, ConvertTx, Mapping> fmt::Debug
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<Call: Encode, Extra: SignedExtension, ConvertTx, Mapping> Encode
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn encode(&self) -> Vec<u8> {
+		self.0.encode()
+	}
+}
+
+impl<Call: Encode, Extra: SignedExtension, ConvertTx, Mapping> EncodeLike
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+}
+
+impl<Call: Decode, Extra: SignedExtension, ConvertTx, Mapping> Decode
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		Ok(Self(Decode::decode(input)?, PhantomData))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Call: Encode, Extra: SignedExtension, ConvertTx, Mapping> serde::Serialize
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn serialize<S>(&self, seq: S) -> Result<S::Ok, S::Error>
+	where
+		S: ::serde::Serializer,
+	{
+		self.0.serialize(seq)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'a, Call: Decode, Extra: SignedExtension, ConvertTx, Mapping> serde::Deserialize<'a>
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn deserialize<D>(de: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'a>,
+	{
+		Ok(Self(generic::UncheckedExtrinsic::deserialize(de)?, PhantomData))
+	}
+}
+
+impl<Call, Extra: SignedExtension, ConvertTx, Mapping> Extrinsic
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	type Call = Call;
+
+	type SignaturePayload = (Address, SetheumMultiSignature, Extra);
+
+	fn is_signed(&self) -> Option<bool> {
+		self.0.is_signed()
+	}
+
+	fn new(function: Call, signed_data: Option<Self::SignaturePayload>) -> Option<Self> {
+		generic::UncheckedExtrinsic::new(function, signed_data).map(|xt| Self(xt, PhantomData))
+	}
+}
+
+impl<Call, Extra: SignedExtension, ConvertTx, Mapping> ExtrinsicMetadata
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	const VERSION: u8 =
+		<generic::UncheckedExtrinsic<Address, Call, SetheumMultiSignature, Extra> as ExtrinsicMetadata>::VERSION;
+	type SignedExtensions = Extra;
+}
+
+impl<Call: GetDispatchInfo, Extra: SignedExtension, ConvertTx, Mapping> GetDispatchInfo
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+{
+	fn get_dispatch_info(&self) -> DispatchInfo {
+		self.0.get_dispatch_info()
+	}
+}
+
+impl<Call: Encode, Extra: SignedExtension, ConvertTx, Mapping>
+	From<SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>> for OpaqueExtrinsic
+{
+	fn from(xt: SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>) -> Self {
+		xt.0.into()
+	}
+}
+
+impl<Call, Extra, ConvertTx, Mapping, Lookup> Checkable<Lookup>
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertTx, Mapping>
+where
+	Call: Encode + Member,
+	Extra: SignedExtension<AccountId = AccountId>,
+	ConvertTx: Convert<(Call, Extra), Result<EthereumTransactionMessage, InvalidTransaction>>,
+	Mapping: AddressMapping<AccountId>,
+	Lookup: traits::Lookup<Source = Address, Target = AccountId>,
+{
+	type Checked = CheckedExtrinsic<AccountId, Call, Extra>;
+
+	fn check(self, lookup: &Lookup) -> Result<Self::Checked, TransactionValidityError> {
+		let generic::UncheckedExtrinsic { signature, function } = self.0;
+
+		match signature {
+			Some((address, SetheumMultiSignature::Eip712(sig), extra)) => {
+				let msg = ConvertTx::convert((function.clone(), extra.clone()))?;
+				let signer = setheum_evm_accounts::eth_recover_hash(&sig, &msg.signing_hash())
+					.ok_or(InvalidTransaction::BadProof)?;
+
+				let account_id = lookup.lookup(address)?;
+				if Mapping::get_account_id(&signer) != account_id {
+					return Err(InvalidTransaction::BadProof.into());
+				}
+
+				Ok(CheckedExtrinsic {
+					signed: Some((account_id, extra)),
+					function,
+				})
+			}
+			signature => generic::UncheckedExtrinsic { signature, function }.check(lookup),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+	use sp_core::H160;
+	use sp_runtime::transaction_validity::UnknownTransaction;
+	use std::str::FromStr;
+
+	#[test]
+	fn eip712_signing_hash_works() {
+		let msg = EthereumTransactionMessage {
+			chain_id: 258,
+			genesis: H256::from_low_u64_be(1),
+			nonce: 1,
+			tip: 2,
+			gas_limit: 2_100_000,
+			storage_limit: 20_000,
+			action: TransactionAction::Call(H160::from_str("0x1111111111111111111111111111111111111111").unwrap()),
+			value: 3,
+			input: vec![0x12, 0x34],
+			access_list: vec![],
+			valid_until: 100,
+		};
+		assert_eq!(
+			msg.signing_hash(),
+			hex!["9c1928bee1ba6fbca12ca2aadc2c23ff3ca3e19a395469f6c32b05823c218934"]
+		);
+
+		// the typed data covers every field
+		assert_ne!(
+			msg.signing_hash(),
+			EthereumTransactionMessage {
+				action: TransactionAction::Create,
+				..msg.clone()
+			}
+			.signing_hash()
+		);
+		assert_ne!(
+			msg.signing_hash(),
+			EthereumTransactionMessage { tip: 0, ..msg.clone() }.signing_hash()
+		);
+		assert_ne!(
+			msg.signing_hash(),
+			EthereumTransactionMessage {
+				genesis: H256::zero(),
+				..msg.clone()
+			}
+			.signing_hash()
+		);
+		assert_ne!(
+			msg.signing_hash(),
+			EthereumTransactionMessage {
+				input: vec![],
+				..msg.clone()
+			}
+			.signing_hash()
+		);
+		assert_ne!(
+			msg.signing_hash(),
+			EthereumTransactionMessage {
+				valid_until: u64::max_value(),
+				..msg.clone()
+			}
+			.signing_hash()
+		);
+		assert_ne!(
+			msg.signing_hash(),
+			EthereumTransactionMessage {
+				access_list: vec![AccessListItem {
+					address: H160::from_str("0x1111111111111111111111111111111111111111").unwrap(),
+					storage_keys: vec![],
+				}],
+				..msg.clone()
+			}
+			.signing_hash()
+		);
+	}
+
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+	struct TestCall(Vec<u8>);
+
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+	struct TestExtra(Nonce);
+
+	impl SignedExtension for TestExtra {
+		const IDENTIFIER: &'static str = "TestExtra";
+		type AccountId = AccountId;
+		type Call = ();
+		type AdditionalSigned = ();
+		type Pre = ();
+
+		fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+			Ok(())
+		}
+	}
+
+	struct TestConvertTx;
+	impl Convert<(TestCall, TestExtra), Result<EthereumTransactionMessage, InvalidTransaction>> for TestConvertTx {
+		fn convert((call, extra): (TestCall, TestExtra)) -> Result<EthereumTransactionMessage, InvalidTransaction> {
+			Ok(EthereumTransactionMessage {
+				chain_id: 258,
+				genesis: H256::zero(),
+				nonce: extra.0,
+				tip: 0,
+				gas_limit: 2_100_000,
+				storage_limit: 20_000,
+				action: TransactionAction::Create,
+				value: 0,
+				input: call.0,
+				access_list: vec![],
+				valid_until: u64::max_value(),
+			})
+		}
+	}
+
+	struct TestMapping;
+	impl AddressMapping<AccountId> for TestMapping {
+		fn get_account_id(evm: &EvmAddress) -> AccountId {
+			let mut data = [0u8; 32];
+			data[0..4].copy_from_slice(b"evm:");
+			data[4..24].copy_from_slice(&evm[..]);
+			AccountId::from(data)
+		}
+
+		fn get_evm_address(account_id: &AccountId) -> Option<EvmAddress> {
+			let data: &[u8] = account_id.as_ref();
+			if data.starts_with(b"evm:") {
+				Some(EvmAddress::from_slice(&data[4..24]))
+			} else {
+				None
+			}
+		}
+
+		fn get_or_create_evm_address(account_id: &AccountId) -> EvmAddress {
+			Self::get_default_evm_address(account_id)
+		}
+
+		fn get_default_evm_address(account_id: &AccountId) -> EvmAddress {
+			Self::get_evm_address(account_id).unwrap_or_default()
+		}
+
+		fn is_linked(account_id: &AccountId, evm: &EvmAddress) -> bool {
+			Self::get_evm_address(account_id).as_ref() == Some(evm)
+		}
+	}
+
+	struct TestLookup;
+	impl traits::Lookup for TestLookup {
+		type Source = Address;
+		type Target = AccountId;
+
+		fn lookup(&self, address: Address) -> Result<AccountId, traits::LookupError> {
+			match address {
+				MultiAddress::Id(account_id) => Ok(account_id),
+				_ => Err(traits::LookupError),
+			}
+		}
+	}
+
+	type TestXt = SetheumUncheckedExtrinsic<TestCall, TestExtra, TestConvertTx, TestMapping>;
+
+	fn eth_account_id(secret: &secp256k1::SecretKey) -> AccountId {
+		let public = secp256k1::PublicKey::from_secret_key(secret);
+		let address = EvmAddress::from_slice(&keccak_256(&public.serialize()[1..65])[12..]);
+		TestMapping::get_account_id(&address)
+	}
+
+	fn eip712_sign(secret: &secp256k1::SecretKey, call: TestCall, extra: TestExtra) -> SetheumMultiSignature {
+		let msg = TestConvertTx::convert((call, extra)).unwrap();
+		let (sig, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&msg.signing_hash()), secret);
+		let mut raw = [0u8; 65];
+		raw[0..64].copy_from_slice(&sig.serialize()[..]);
+		raw[64] = recovery_id.serialize();
+		SetheumMultiSignature::Eip712(sp_core::ecdsa::Signature::from_raw(raw))
+	}
+
+	fn check(
+		address: AccountId,
+		signature: SetheumMultiSignature,
+		extra: TestExtra,
+	) -> Result<(), TransactionValidityError> {
+		let xt = TestXt::new(TestCall(vec![1]), Some((MultiAddress::Id(address), signature, extra))).unwrap();
+		xt.check(&TestLookup).map(|_| ())
+	}
+
+	#[test]
+	fn eip712_signed_extrinsic_should_be_checked() {
+		let alice = secp256k1::SecretKey::parse(&[1u8; 32]).unwrap();
+		let bob = secp256k1::SecretKey::parse(&[2u8; 32]).unwrap();
+		let signature = eip712_sign(&alice, TestCall(vec![1]), TestExtra(0));
+
+		let xt = TestXt::new(
+			TestCall(vec![1]),
+			Some((MultiAddress::Id(eth_account_id(&alice)), signature.clone(), TestExtra(0))),
+		)
+		.unwrap();
+		assert_eq!(
+			xt.check(&TestLookup),
+			Ok(CheckedExtrinsic {
+				signed: Some((eth_account_id(&alice), TestExtra(0))),
+				function: TestCall(vec![1]),
+			})
+		);
+
+		// the sender is not the signer
+		assert_eq!(
+			check(eth_account_id(&bob), signature.clone(), TestExtra(0)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		// signed by another key
+		assert_eq!(
+			check(
+				eth_account_id(&alice),
+				eip712_sign(&bob, TestCall(vec![1]), TestExtra(0)),
+				TestExtra(0)
+			),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		// the signed extensions are not the signed ones
+		assert_eq!(
+			check(eth_account_id(&alice), signature, TestExtra(1)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		// the sender can't be looked up
+		assert_eq!(
+			TestXt::new(
+				TestCall(vec![1]),
+				Some((
+					MultiAddress::Index(0),
+					eip712_sign(&alice, TestCall(vec![1]), TestExtra(0)),
+					TestExtra(0)
+				)),
+			)
+			.unwrap()
+			.check(&TestLookup),
+			Err(UnknownTransaction::CannotLookup.into())
+		);
+	}
+}
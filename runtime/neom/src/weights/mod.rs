@@ -23,12 +23,18 @@ pub mod serp_auction;
 pub mod settmint_engine;
 pub mod serp_treasury;
 pub mod setheum_currencies;
+pub mod setheum_evm;
 pub mod dex;
 pub mod settmint_gateway;
 pub mod setheum_incentives;
 pub mod setheum_nft;
+pub mod setheum_nft_marketplace;
+pub mod setheum_nft_vaults;
 pub mod setheum_prices;
 pub mod setheum_transaction_payment;
+pub mod settpay_escrow;
+pub mod settpay_streams;
+pub mod settpay_standing_orders;
 
 pub mod orml_auction;
 pub mod orml_authority;
@@ -77,4 +77,38 @@ impl<T: frame_system::Config> setheum_incentives::WeightInfo for WeightInfo<T> {
 			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	fn lock_dex_share() -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn unlock_dex_share() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn update_lock_period_multipliers(c: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_early_unlock_penalty() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn create_reward_stream() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_reward_stream() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn update_settmint_incentive_rewards(c: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((2_960_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 }
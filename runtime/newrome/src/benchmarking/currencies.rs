@@ -17,11 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::utils::{lookup_of_account, set_balance};
-use crate::{dollar, AccountId, Amount, Balance, Currencies, NativeTokenExistentialDeposit, Runtime, DNAR, SETT};
+use crate::{
+	dollar, AccountId, Amount, Balance, Currencies, MaxTransfers, NativeTokenExistentialDeposit, Runtime, DNAR, SETT,
+};
 
 use sp_std::prelude::*;
 
 use frame_benchmarking::account;
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
 use sp_runtime::traits::UniqueSaturatedInto;
 
@@ -82,6 +85,21 @@ runtime_benchmarks! {
 		assert_eq!(<Currencies as MultiCurrency<_>>::total_balance(native_currency_id, &to), amount);
 	}
 
+	// `transfer_many` of non-native currency to new accounts
+	transfer_many {
+		let c in 0 .. MaxTransfers::get();
+		let currency_id = SETT;
+		let amount: Balance = dollar(currency_id);
+		let from = account("from", 0, SEED);
+		set_balance(currency_id, &from, amount * c as Balance);
+
+		let mut transfers = vec![];
+		for i in 0 .. c {
+			let to: AccountId = account("to", i, SEED);
+			transfers.push((lookup_of_account(to), currency_id, amount));
+		}
+	}: _(RawOrigin::Signed(from), transfers, true)
+
 	// `update_balance` for non-native currency
 	update_balance_non_native_currency {
 		let currency_id = SETT;
@@ -150,6 +168,13 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn transfer_many() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_many());
+		});
+	}
+
 	#[test]
 	fn update_balance_non_native_currency() {
 		new_test_ext().execute_with(|| {
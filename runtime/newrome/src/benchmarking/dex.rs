@@ -17,15 +17,16 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, Balance, BlockNumber, Currencies, CurrencyId, Dex, EnabledTradingPairs, Runtime,
+	dollar, AccountId, Balance, BlockNumber, Call, Currencies, CurrencyId, Dex, EnabledTradingPairs, Ratio, Runtime,
 	TradingPathLimit,
 };
 
 use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrencyExtended;
-use sp_runtime::traits::UniqueSaturatedInto;
+use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -98,6 +99,33 @@ runtime_benchmarks! {
 		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
 	}: _(RawOrigin::Root, currency_id_a, currency_id_b, min_contribution_a, min_contribution_b, target_provision_a, target_provision_b, not_before)
 
+	// abort a Provisioning trading pair which has not reached its target
+	abort_provisioning {
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let currency_id_a = trading_pair.0;
+		let currency_id_b = trading_pair.1;
+		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+		Dex::list_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b, dollar(currency_id_a), dollar(currency_id_b), 200 * dollar(currency_id_a), 1_000 * dollar(currency_id_b), Default::default())?;
+	}: _(RawOrigin::Root, currency_id_a, currency_id_b)
+
+	// refund the provision of an aborted trading pair
+	refund_provision {
+		let founder: AccountId = account("founder", 0, SEED);
+		let caller: AccountId = account("caller", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let currency_id_a = trading_pair.0;
+		let currency_id_b = trading_pair.1;
+		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+		Dex::list_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b, dollar(currency_id_a), dollar(currency_id_b), 200 * dollar(currency_id_a), 1_000 * dollar(currency_id_b), Default::default())?;
+
+		// set balance
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(currency_id_a, &founder, (10 * dollar(currency_id_a)).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(currency_id_b, &founder, (10 * dollar(currency_id_b)).unique_saturated_into())?;
+
+		Dex::add_liquidity(RawOrigin::Signed(founder.clone()).into(), currency_id_a, currency_id_b, dollar(currency_id_a), dollar(currency_id_b), Default::default(), false)?;
+		Dex::abort_provisioning(RawOrigin::Root.into(), currency_id_a, currency_id_b)?;
+	}: _(RawOrigin::Signed(caller), founder, currency_id_a, currency_id_b)
+
 	// TODO:
 	// add tests for following situation:
 	// 1. disable a provisioning trading pair
@@ -198,6 +226,32 @@ runtime_benchmarks! {
 
 		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, (10_000 * dollar(path[0])).unique_saturated_into())?;
 	}: swap_with_exact_target(RawOrigin::Signed(taker), path.clone(), 10 * dollar(path[path.len() - 1]), 100 * dollar(path[0]))
+
+	flash_swap {
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &taker, (10_000 * dollar(trading_pair.1)).unique_saturated_into())?;
+		let call = Box::new(Call::System(frame_system::Call::remark(vec![])));
+	}: _(RawOrigin::Signed(taker), trading_pair.0, trading_pair.1, 10 * dollar(trading_pair.0), trading_pair.1, 100 * dollar(trading_pair.1), call)
+
+	// forward the protocol fee of a trading pair in `on_initialize`
+	forward_protocol_fee {
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+		Dex::set_protocol_fee_rate(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, Some(Ratio::saturating_from_rational(1, 6)))?;
+
+		// accrue the exchange fees
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, (10_000 * dollar(trading_pair.0)).unique_saturated_into())?;
+		Dex::swap_with_exact_supply(RawOrigin::Signed(taker).into(), vec![trading_pair.0, trading_pair.1], 1_000 * dollar(trading_pair.0), 0)?;
+		setheum_dex::ProtocolFeeForwardQueue::<Runtime>::put(vec![trading_pair]);
+	}: {
+		Dex::on_initialize(1);
+	}
 }
 
 #[cfg(test)]
@@ -212,6 +266,20 @@ mod tests {
 			.into()
 	}
 
+	#[test]
+	fn test_abort_provisioning() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_abort_provisioning());
+		});
+	}
+
+	#[test]
+	fn test_refund_provision() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_refund_provision());
+		});
+	}
+
 	#[test]
 	fn test_add_liquidity() {
 		new_test_ext().execute_with(|| {
@@ -254,6 +322,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_flash_swap() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_flash_swap());
+		});
+	}
+
+	#[test]
+	fn test_forward_protocol_fee() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_forward_protocol_fee());
+		});
+	}
+
 	#[test]
 	fn list_trading_pair() {
 		new_test_ext().execute_with(|| {
@@ -0,0 +1,197 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{dollar, AccountId, Escrow, EscrowDisputePeriod, Runtime, System, DNAR, USDJ};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::account;
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn create_invoice(payee: &AccountId, payer: &AccountId, arbiter: &AccountId) -> Result<(), &'static str> {
+	set_balance(DNAR, payee, 1_000 * dollar(DNAR));
+	Escrow::create_invoice(
+		RawOrigin::Signed(payee.clone()).into(),
+		Some(lookup_of_account(payer.clone())),
+		Some(lookup_of_account(arbiter.clone())),
+		USDJ,
+		100 * dollar(USDJ),
+		100,
+	)?;
+	Ok(())
+}
+
+fn create_and_pay_invoice(payee: &AccountId, payer: &AccountId, arbiter: &AccountId) -> Result<(), &'static str> {
+	create_invoice(payee, payer, arbiter)?;
+	set_balance(USDJ, payer, 1_000 * dollar(USDJ));
+	Escrow::pay_invoice(RawOrigin::Signed(payer.clone()).into(), 0)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, settpay_escrow }
+
+	_ {}
+
+	create_invoice {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		set_balance(DNAR, &payee, 1_000 * dollar(DNAR));
+	}: _(RawOrigin::Signed(payee), Some(lookup_of_account(payer)), Some(lookup_of_account(arbiter)), USDJ, 100 * dollar(USDJ), 100)
+
+	cancel_invoice {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_invoice(&payee, &payer, &arbiter)?;
+	}: _(RawOrigin::Signed(payee), 0)
+
+	pay_invoice {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_invoice(&payee, &payer, &arbiter)?;
+		set_balance(USDJ, &payer, 1_000 * dollar(USDJ));
+	}: _(RawOrigin::Signed(payer), 0)
+
+	release_payment {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_and_pay_invoice(&payee, &payer, &arbiter)?;
+	}: _(RawOrigin::Signed(payer), 0, 100 * dollar(USDJ))
+
+	refund_payment {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_and_pay_invoice(&payee, &payer, &arbiter)?;
+	}: _(RawOrigin::Signed(payee), 0, 100 * dollar(USDJ))
+
+	claim_payment {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_and_pay_invoice(&payee, &payer, &arbiter)?;
+		System::set_block_number(System::block_number() + 100);
+	}: _(RawOrigin::Signed(payee), 0)
+
+	dispute_invoice {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_and_pay_invoice(&payee, &payer, &arbiter)?;
+	}: _(RawOrigin::Signed(payer), 0)
+
+	resolve_dispute {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_and_pay_invoice(&payee, &payer, &arbiter)?;
+		Escrow::dispute_invoice(RawOrigin::Signed(payer).into(), 0)?;
+	}: _(RawOrigin::Signed(arbiter), 0, 50 * dollar(USDJ))
+
+	settle_expired_dispute {
+		let payee: AccountId = account("payee", 0, SEED);
+		let payer: AccountId = account("payer", 0, SEED);
+		let arbiter: AccountId = account("arbiter", 0, SEED);
+		create_and_pay_invoice(&payee, &payer, &arbiter)?;
+		Escrow::dispute_invoice(RawOrigin::Signed(payer.clone()).into(), 0)?;
+		System::set_block_number(System::block_number() + EscrowDisputePeriod::get());
+	}: _(RawOrigin::Signed(payer), 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_create_invoice() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_invoice());
+		});
+	}
+
+	#[test]
+	fn test_cancel_invoice() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_invoice());
+		});
+	}
+
+	#[test]
+	fn test_pay_invoice() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pay_invoice());
+		});
+	}
+
+	#[test]
+	fn test_release_payment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_release_payment());
+		});
+	}
+
+	#[test]
+	fn test_refund_payment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_refund_payment());
+		});
+	}
+
+	#[test]
+	fn test_claim_payment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_payment());
+		});
+	}
+
+	#[test]
+	fn test_dispute_invoice() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_dispute_invoice());
+		});
+	}
+
+	#[test]
+	fn test_resolve_dispute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_resolve_dispute());
+		});
+	}
+
+	#[test]
+	fn test_settle_expired_dispute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_settle_expired_dispute());
+		});
+	}
+}
@@ -16,12 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, AccountId, Event, EvmAccounts, Origin, Runtime, System, EVM, NEOM};
+use crate::{
+	dollar, AccountId, CodeUpgradeDelay, Event, EvmAccounts, MaxCodeSize, MaxCodeVersions, Origin, Runtime, System, EVM,
+	NEOM,
+};
 
 use super::utils::set_neom_balance;
 use frame_support::dispatch::DispatchError;
 use frame_system::RawOrigin;
 use orml_benchmarking::{runtime_benchmarks, whitelist_account};
+use setheum_evm::calibration;
 use sp_core::H160;
 use sp_io::hashing::keccak_256;
 use sp_std::str::FromStr;
@@ -54,13 +58,37 @@ fn deploy_contract(caller: AccountId) -> Result<H160, DispatchError> {
 	let contract = hex_literal::hex!("608060405234801561001057600080fd5b5061016f806100206000396000f3fe608060405260043610610041576000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff168063412a5a6d14610046575b600080fd5b61004e610050565b005b600061005a6100e2565b604051809103906000f080158015610076573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055505050565b6040516052806100f28339019056fe6080604052348015600f57600080fd5b50603580601d6000396000f3fe6080604052600080fdfea165627a7a7230582092dc1966a8880ddf11e067f9dd56a632c11a78a4afd4a9f05924d427367958cc0029a165627a7a723058202b2cc7384e11c452cdbf39b68dada2d5e10a632cc0174a354b8b8c83237e28a40029").to_vec();
 
 	System::set_block_number(1);
-	EVM::create(Origin::signed(caller), contract, 0, 1000000000, 1000000000)
+	EVM::create(Origin::signed(caller), contract, 0, 1000000000, 1000000000, vec![])
 		.map_or_else(|e| Err(e.error), |_| Ok(()))?;
 
 	System::assert_last_event(Event::setheum_evm(setheum_evm::Event::Created(contract_addr())));
 	Ok(contract_addr())
 }
 
+fn upgrade_code(contract: H160, code: Vec<u8>) -> Result<(), DispatchError> {
+	EVM::propose_code_upgrade(Origin::signed(alice_account_id()), contract, code)
+		.map_or_else(|e| Err(e.error), |_| Ok(()))?;
+	System::set_block_number(System::block_number() + CodeUpgradeDelay::get());
+	EVM::activate_code_upgrade(Origin::signed(alice_account_id()), contract)
+		.map_or_else(|e| Err(e.error), |_| Ok(()))
+}
+
+fn deploy_opcode_mix(runtime: &[u8]) -> Result<H160, DispatchError> {
+	let info = setheum_evm::Runner::<Runtime>::create(
+		EvmAccounts::eth_address(&alice()),
+		calibration::init_code(runtime),
+		0,
+		1000000,
+		1000000,
+		vec![],
+		<Runtime as setheum_evm::Config>::config(),
+	)?;
+	if !info.exit_reason.is_succeed() {
+		return Err("failed to deploy the opcode mix".into());
+	}
+	Ok(info.address)
+}
+
 pub fn alice_account_id() -> AccountId {
 	let address = EvmAccounts::eth_address(&alice());
 	let mut data = [0u8; 32];
@@ -145,6 +173,90 @@ runtime_benchmarks! {
 
 		whitelist_account!(alice_account);
 	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	propose_code_upgrade {
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let contract = deploy_contract(alice_account_id())?;
+		let new_code = vec![0x00; MaxCodeSize::get() as usize];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, new_code)
+
+	propose_code_rollback {
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let contract = deploy_contract(alice_account_id())?;
+		let code_hash = EVM::code_hash_at_address(&contract);
+		upgrade_code(contract, vec![0x00])?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, code_hash)
+
+	cancel_code_upgrade {
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let contract = deploy_contract(alice_account_id())?;
+		let new_code = vec![0x00; MaxCodeSize::get() as usize];
+		EVM::propose_code_upgrade(Origin::signed(alice_account_id()), contract, new_code)?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	activate_code_upgrade {
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let contract = deploy_contract(alice_account_id())?;
+		// fill the code versions, so the oldest is dropped
+		for i in 0 .. MaxCodeVersions::get() {
+			upgrade_code(contract, vec![0x00; i as usize + 1])?;
+		}
+		let new_code = vec![0x00; MaxCodeSize::get() as usize];
+		EVM::propose_code_upgrade(Origin::signed(alice_account_id()), contract, new_code)?;
+		System::set_block_number(System::block_number() + CodeUpgradeDelay::get());
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	evm_arithmetic {
+		let n in 1 .. calibration::MAX_STEPS;
+
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let gas_limit = n as u64 * calibration::GAS_PER_STEP;
+		let contract = deploy_opcode_mix(&calibration::ARITHMETIC)?;
+
+		whitelist_account!(alice_account);
+	}: call(RawOrigin::Signed(alice_account_id()), contract, vec![], 0, gas_limit, 1000000, vec![])
+
+	evm_storage {
+		let n in 1 .. calibration::MAX_STEPS;
+
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let gas_limit = n as u64 * calibration::GAS_PER_STEP;
+		let contract = deploy_opcode_mix(&calibration::STORAGE)?;
+
+		whitelist_account!(alice_account);
+	}: call(RawOrigin::Signed(alice_account_id()), contract, vec![], 0, gas_limit, 1000000, vec![])
+
+	evm_hashing {
+		let n in 1 .. calibration::MAX_STEPS;
+
+		let alice_account = alice_account_id();
+
+		set_neom_balance(&alice_account, 1_000_000 * dollar(NEOM));
+		let gas_limit = n as u64 * calibration::GAS_PER_STEP;
+		let contract = deploy_opcode_mix(&calibration::HASHING)?;
+
+		whitelist_account!(alice_account);
+	}: call(RawOrigin::Signed(alice_account_id()), contract, vec![], 0, gas_limit, 1000000, vec![])
 }
 
 #[cfg(test)]
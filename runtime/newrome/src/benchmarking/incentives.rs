@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, ReserveCurrencyIds, CurrencyId, GetStableCurrencyId, Incentives, Rate, Rewards, Runtime,
-	TokenSymbol, DNAR, USDJ, SETT,
+	dollar, AccountId, ReserveCurrencyIds, CurrencyId, GetStableCurrencyId, Incentives, MaxRewardStreams, Rate, Rewards,
+	Runtime, StableCurrencyIds, TokenSymbol, DNAR, USDJ, SETT,
 };
 
 use super::utils::set_balance;
@@ -26,6 +26,7 @@ use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use setheum_incentives::PoolId;
 use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -81,6 +82,59 @@ runtime_benchmarks! {
 		}
 	}: _(RawOrigin::Root, values)
 
+	lock_dex_share {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_balance(DNAR_USDJ_LP, &caller, 10_000 * dollar(USDJ));
+		Incentives::update_lock_period_multipliers(RawOrigin::Root.into(), vec![(100, Some(Rate::saturating_from_integer(2)))])?;
+	}: _(RawOrigin::Signed(caller), DNAR_USDJ_LP, 10_000 * dollar(USDJ), 100)
+
+	unlock_dex_share {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_balance(DNAR_USDJ_LP, &caller, 10_000 * dollar(USDJ));
+		Incentives::update_lock_period_multipliers(RawOrigin::Root.into(), vec![(100, Some(Rate::saturating_from_integer(2)))])?;
+		Incentives::update_early_unlock_penalty(RawOrigin::Root.into(), Some(Rate::saturating_from_rational(1, 10)))?;
+		Incentives::lock_dex_share(RawOrigin::Signed(caller.clone()).into(), DNAR_USDJ_LP, 10_000 * dollar(USDJ), 100)?;
+	}: _(RawOrigin::Signed(caller), DNAR_USDJ_LP, 0)
+
+	update_lock_period_multipliers {
+		let c in 0 .. 12;
+		let mut values = vec![];
+		for i in 0 .. c {
+			values.push((100 * (i + 1), Some(Rate::saturating_from_integer(2))));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_settmint_incentive_rewards {
+		let c in 0 .. StableCurrencyIds::get().len() as u32;
+		let currency_ids = StableCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			values.push((currency_ids[i as usize], 100 * dollar(DNAR)));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_early_unlock_penalty {
+	}: _(RawOrigin::Root, Some(Rate::saturating_from_rational(1, 10)))
+
+	create_reward_stream {
+		let sponsor: AccountId = account("sponsor", 0, SEED);
+		set_balance(DNAR, &sponsor, 20_000 * dollar(DNAR));
+		let stable_currency_ids = StableCurrencyIds::get();
+
+		// the other reward streams of the lp share are updated before counting
+		for i in 0 .. MaxRewardStreams::get() - 1 {
+			let currency_id = stable_currency_ids[i as usize];
+			set_balance(currency_id, &sponsor, 10_000 * dollar(currency_id));
+			Incentives::create_reward_stream(RawOrigin::Signed(sponsor.clone()).into(), DNAR_USDJ_LP, currency_id, 10_000 * dollar(currency_id), dollar(currency_id), 10_000)?;
+		}
+	}: _(RawOrigin::Signed(sponsor), DNAR_USDJ_LP, DNAR, 10_000 * dollar(DNAR), dollar(DNAR), 10_000)
+
+	cancel_reward_stream {
+		let sponsor: AccountId = account("sponsor", 0, SEED);
+		set_balance(DNAR, &sponsor, 20_000 * dollar(DNAR));
+		Incentives::create_reward_stream(RawOrigin::Signed(sponsor.clone()).into(), DNAR_USDJ_LP, DNAR, 10_000 * dollar(DNAR), dollar(DNAR), 10_000)?;
+	}: _(RawOrigin::Signed(sponsor), DNAR_USDJ_LP, DNAR)
 }
 
 #[cfg(test)]
@@ -136,4 +190,53 @@ mod tests {
 			assert_ok!(test_benchmark_update_dex_saving_rates());
 		});
 	}
+
+	#[test]
+	fn test_lock_dex_share() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_lock_dex_share());
+		});
+	}
+
+	#[test]
+	fn test_unlock_dex_share() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unlock_dex_share());
+		});
+	}
+
+	#[test]
+	fn test_update_lock_period_multipliers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_lock_period_multipliers());
+		});
+	}
+
+	#[test]
+	fn test_update_early_unlock_penalty() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_early_unlock_penalty());
+		});
+	}
+
+	#[test]
+	fn test_create_reward_stream() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_reward_stream());
+		});
+	}
+
+	#[test]
+	fn test_cancel_reward_stream() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_reward_stream());
+		});
+	}
+
+	#[test]
+	fn test_update_settmint_incentive_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_settmint_incentive_rewards());
+		});
+	}
 }
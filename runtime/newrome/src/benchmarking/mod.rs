@@ -25,6 +25,10 @@ pub mod dex;
 pub mod settmint_gateway;
 pub mod incentives;
 pub mod prices;
+pub mod standing_orders;
+pub mod streams;
+pub mod escrow;
+pub mod nft_vaults;
 pub mod transaction_payment;
 
 // orml benchmarking
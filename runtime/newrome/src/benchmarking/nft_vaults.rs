@@ -0,0 +1,156 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{dollar, AccountId, Balance, NFTVaults, NftPalletId, Runtime, DNAR, NFT, USDJ};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use orml_traits::MultiCurrency;
+use primitives::CurrencyId;
+use setheum_nft::{ClassProperty, Properties};
+use sp_runtime::traits::AccountIdConversion;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+const SUPPLY: Balance = 1_000_000;
+
+fn create_class_and_mint(owner: &AccountId, quantity: u32) -> Result<(), &'static str> {
+	let creator: AccountId = account("creator", 0, SEED);
+	set_balance(DNAR, &creator, 1_000 * dollar(DNAR));
+	let class_account: AccountId = NftPalletId::get().into_sub_account(orml_nft::Pallet::<Runtime>::next_class_id());
+	NFT::create_class(
+		RawOrigin::Signed(creator).into(),
+		vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+	)?;
+	set_balance(DNAR, &class_account, 1_000 * dollar(DNAR));
+	NFT::mint(
+		RawOrigin::Signed(class_account).into(),
+		lookup_of_account(owner.clone()),
+		0,
+		vec![1],
+		quantity,
+	)?;
+	Ok(())
+}
+
+fn create_vault(curator: &AccountId, quantity: u32) -> Result<(), &'static str> {
+	create_class_and_mint(curator, quantity)?;
+	set_balance(DNAR, curator, 1_000 * dollar(DNAR));
+	NFTVaults::fractionalize_class(
+		RawOrigin::Signed(curator.clone()).into(),
+		0,
+		SUPPLY,
+		USDJ,
+		100 * dollar(USDJ),
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, setheum_nft_vaults }
+
+	_ {}
+
+	fractionalize_token {
+		let curator: AccountId = account("curator", 0, SEED);
+		create_class_and_mint(&curator, 1)?;
+		set_balance(DNAR, &curator, 1_000 * dollar(DNAR));
+	}: _(RawOrigin::Signed(curator), (0, 0), SUPPLY, USDJ, 100 * dollar(USDJ))
+
+	fractionalize_class {
+		let n in 1 .. 100;
+		let curator: AccountId = account("curator", 0, SEED);
+		create_class_and_mint(&curator, n)?;
+		set_balance(DNAR, &curator, 1_000 * dollar(DNAR));
+	}: _(RawOrigin::Signed(curator), 0, SUPPLY, USDJ, 100 * dollar(USDJ))
+
+	redeem {
+		let n in 1 .. 100;
+		let curator: AccountId = account("curator", 0, SEED);
+		create_vault(&curator, n)?;
+	}: _(RawOrigin::Signed(curator), 0)
+
+	buyout {
+		let n in 1 .. 100;
+		let curator: AccountId = account("curator", 0, SEED);
+		let buyer: AccountId = account("buyer", 0, SEED);
+		create_vault(&curator, n)?;
+		set_balance(USDJ, &buyer, 1_000 * dollar(USDJ));
+	}: _(RawOrigin::Signed(buyer), 0)
+
+	claim_proceeds {
+		let curator: AccountId = account("curator", 0, SEED);
+		let buyer: AccountId = account("buyer", 0, SEED);
+		create_vault(&curator, 1)?;
+		set_balance(USDJ, &buyer, 1_000 * dollar(USDJ));
+		NFTVaults::buyout(RawOrigin::Signed(buyer).into(), 0)?;
+	}: _(RawOrigin::Signed(curator.clone()), 0)
+	verify {
+		assert_eq!(<crate::Currencies as MultiCurrency<_>>::total_issuance(CurrencyId::Fraction(0)), 0);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_fractionalize_token() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_fractionalize_token());
+		});
+	}
+
+	#[test]
+	fn test_fractionalize_class() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_fractionalize_class());
+		});
+	}
+
+	#[test]
+	fn test_redeem() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_redeem());
+		});
+	}
+
+	#[test]
+	fn test_buyout() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_buyout());
+		});
+	}
+
+	#[test]
+	fn test_claim_proceeds() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_proceeds());
+		});
+	}
+}
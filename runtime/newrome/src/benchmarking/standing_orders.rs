@@ -0,0 +1,139 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{dollar, AccountId, Runtime, StandingOrders, System, DNAR, USDJ};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use settpay_standing_orders::PaymentMode;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+runtime_benchmarks! {
+	{ Runtime, settpay_standing_orders }
+
+	_ {}
+
+	on_initialize {
+		let c in 1 .. 50;
+		let payer: AccountId = account("payer", 0, SEED);
+		set_balance(DNAR, &payer, 1_000 * dollar(DNAR));
+		set_balance(USDJ, &payer, 1_000 * dollar(USDJ));
+
+		for i in 0 .. c {
+			let recipient: AccountId = account("recipient", i, SEED);
+			StandingOrders::create_standing_order(
+				RawOrigin::Signed(payer.clone()).into(),
+				lookup_of_account(recipient),
+				USDJ,
+				dollar(USDJ),
+				10,
+				10 * dollar(USDJ),
+				1_000,
+				PaymentMode::Push,
+			)?;
+		}
+		let due_at = System::block_number() + 10;
+	}: {
+		StandingOrders::on_initialize(due_at);
+	}
+
+	create_standing_order {
+		let payer: AccountId = account("payer", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		set_balance(DNAR, &payer, 1_000 * dollar(DNAR));
+	}: _(RawOrigin::Signed(payer), lookup_of_account(recipient), USDJ, dollar(USDJ), 10, 10 * dollar(USDJ), 1_000, PaymentMode::Push)
+
+	cancel_standing_order {
+		let payer: AccountId = account("payer", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		set_balance(DNAR, &payer, 1_000 * dollar(DNAR));
+		StandingOrders::create_standing_order(
+			RawOrigin::Signed(payer.clone()).into(),
+			lookup_of_account(recipient),
+			USDJ,
+			dollar(USDJ),
+			10,
+			10 * dollar(USDJ),
+			1_000,
+			PaymentMode::Push,
+		)?;
+	}: _(RawOrigin::Signed(payer), 0)
+
+	collect_payment {
+		let payer: AccountId = account("payer", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		set_balance(DNAR, &payer, 1_000 * dollar(DNAR));
+		set_balance(USDJ, &payer, 1_000 * dollar(USDJ));
+		StandingOrders::create_standing_order(
+			RawOrigin::Signed(payer).into(),
+			lookup_of_account(recipient.clone()),
+			USDJ,
+			dollar(USDJ),
+			10,
+			10 * dollar(USDJ),
+			1_000,
+			PaymentMode::Pull,
+		)?;
+		System::set_block_number(System::block_number() + 100);
+	}: _(RawOrigin::Signed(recipient), 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
+
+	#[test]
+	fn test_create_standing_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_standing_order());
+		});
+	}
+
+	#[test]
+	fn test_cancel_standing_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_standing_order());
+		});
+	}
+
+	#[test]
+	fn test_collect_payment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_collect_payment());
+		});
+	}
+}
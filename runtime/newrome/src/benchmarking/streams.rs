@@ -0,0 +1,103 @@
+// This file is part of Setheum.
+
+// Copyright (C) 2019-2021 Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{dollar, AccountId, Runtime, Streams, System, DNAR, USDJ};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn create_stream(sender: &AccountId, recipient: &AccountId) -> Result<(), &'static str> {
+	set_balance(DNAR, sender, 1_000 * dollar(DNAR));
+	set_balance(USDJ, sender, 1_000 * dollar(USDJ));
+	let start_block = System::block_number() + 10;
+	Streams::create_stream(
+		RawOrigin::Signed(sender.clone()).into(),
+		lookup_of_account(recipient.clone()),
+		USDJ,
+		100 * dollar(USDJ),
+		start_block,
+		start_block + 100,
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, settpay_streams }
+
+	_ {}
+
+	create_stream {
+		let sender: AccountId = account("sender", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		set_balance(DNAR, &sender, 1_000 * dollar(DNAR));
+		set_balance(USDJ, &sender, 1_000 * dollar(USDJ));
+		let start_block = System::block_number() + 10;
+	}: _(RawOrigin::Signed(sender), lookup_of_account(recipient), USDJ, 100 * dollar(USDJ), start_block, start_block + 100)
+
+	withdraw_from_stream {
+		let sender: AccountId = account("sender", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		create_stream(&sender, &recipient)?;
+		System::set_block_number(System::block_number() + 60);
+	}: _(RawOrigin::Signed(recipient), 0)
+
+	cancel_stream {
+		let sender: AccountId = account("sender", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		create_stream(&sender, &recipient)?;
+		System::set_block_number(System::block_number() + 60);
+	}: _(RawOrigin::Signed(sender), 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_create_stream() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_stream());
+		});
+	}
+
+	#[test]
+	fn test_withdraw_from_stream() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_withdraw_from_stream());
+		});
+	}
+
+	#[test]
+	fn test_cancel_stream() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_stream());
+		});
+	}
+}
@@ -56,7 +56,7 @@ use sp_api::impl_runtime_apis;
 use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
-	OpaqueMetadata, H160,
+	OpaqueMetadata, H160, H256,
 };
 use sp_runtime::traits::{
 	BadOrigin, BlakeTwo256, Block as BlockT, NumberFor, OpaqueKeys, SaturatedConversion, StaticLookup, Zero,
@@ -66,8 +66,8 @@ use sp_runtime::{
 	curve::PiecewiseLinear,
 	generic, impl_opaque_keys,
 	traits::{AccountIdConversion, Zero, Convert, Identity},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, DispatchResult, FixedPointNumber,
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -77,9 +77,10 @@ use sp_version::RuntimeVersion;
 use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
 use setheum_currencies::{BasicCurrencyAdapter, Currency};
 use setheum_evm::{CallInfo, CreateInfo};
+use runtime_common::{EthereumTransactionMessage, TransactionAction};
 use setheum_evm_accounts::EvmAddressMapping;
 use setheum_evm_manager::EvmCurrencyIdMapping;
-use setheum_support::CurrencyIdMapping;
+use setheum_support::{AccountRecovery, AddressMapping, CurrencyIdMapping};
 use setheum_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 use orml_tokens::CurrencyAdapter;
 use orml_traits::{create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended};
@@ -99,9 +100,10 @@ pub use sp_runtime::{Perbill, Percent, Permill, Perquintill};
 pub use authority::AuthorityConfigImpl;
 pub use constants::{fee::*, time::*};
 pub use primitives::{
-	evm::EstimateResourcesRequest, AccountId, AccountIndex, Amount, AuctionId,
+	dex::{PairStats, StatsPeriodIndex}, evm::{AccessListItem, Account, BlockLog, EstimateResourcesRequest, TraceOutcome, TracerConfig},
+	AccountId, AccountIndex, Amount, AuctionId,
 	AuthoritysOriginId, Balance, BlockNumber, CurrencyId, DataProviderId,
-	EraIndex, Hash, Moment, Nonce, Share, Signature, TokenSymbol, TradingPair,
+	EraIndex, Hash, Moment, Nonce, Share, Signature, StreamId, TokenSymbol, TradingPair,
 };
 pub use runtime_common::{
 	cent, deposit, dollar, microcent, millicent, BlockLength, BlockWeights,
@@ -152,6 +154,9 @@ parameter_types! {
 	pub const WellfareTreasuryPalletId: PalletId = PalletId(*b"set/welf");
 	pub const IncentivesPalletId: PalletId = PalletId(*b"set/inct");
 	pub const NftPalletId: PalletId = PalletId(*b"set/sNFT");
+	pub const StreamsPalletId: PalletId = PalletId(*b"set/strm");
+	pub const EscrowPalletId: PalletId = PalletId(*b"set/escr");
+	pub const NftVaultsPalletId: PalletId = PalletId(*b"set/nftv");
 }
 
 pub fn get_all_module_accounts() -> Vec<AccountId> {
@@ -163,6 +168,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		SettPayTreasuryPalletId::get().into_account(),
 		WellfareTreasuryPalletId::get().into_account(),
 		IncentivesPalletId::get().into_account(),
+		StreamsPalletId::get().into_account(),
+		EscrowPalletId::get().into_account(),
 		ZeroAccountId::get(),
 	]
 }
@@ -1012,7 +1019,7 @@ impl orml_auction::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
 	type AuctionId = AuctionId;
-	type Handler = SerpAuctionManager;
+	type Handler = NFTMarketplace;
 	type WeightInfo = weights::orml_auction::WeightInfo<Runtime>;
 }
 
@@ -1093,6 +1100,7 @@ parameter_type_with_key! {
 			},
 			CurrencyId::Erc20(_) => Balance::max_value(), // not handled by orml-tokens
 			CurrencyId::ChainBridge(_) => Balance::max_value(), // TODO: update this before we enable ChainBridge
+			CurrencyId::Fraction(_) => 1_000, // vault shares have no decimals
 		}
 	};
 }
@@ -1193,6 +1201,10 @@ parameter_types! {
 	pub const GetDexCurrencyId: CurrencyId = DRAM; // TODO: Update and remove
 }
 
+parameter_types! {
+	pub const MaxTransfers: u32 = 100;
+}
+
 impl setheum_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -1201,6 +1213,9 @@ impl setheum_currencies::Config for Runtime {
 	type WeightInfo = weights::setheum_currencies::WeightInfo<Runtime>;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type EVMBridge = EVMBridge;
+	type GasToWeight = GasToWeight<setheum_evm::calibration::WeightPerGas<Runtime>>;
+	type CashDropHandler = SerpTreasury;
+	type MaxTransfers = MaxTransfers;
 }
 
 parameter_types! {
@@ -1352,7 +1367,7 @@ where
 		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
 		let address = Indices::unlookup(account);
 		let (call, extra, _) = raw_payload.deconstruct();
-		Some((call, (address, signature, extra)))
+		Some((call, (address, signature.into(), extra)))
 	}
 }
 
@@ -1402,6 +1417,11 @@ impl settmint_gateway::Config for Runtime {
 
 parameter_types! {
 	pub const TradingPathLimit: u32 = 3;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2 * DAYS;
+	pub const DexStatsPeriod: BlockNumber = HOURS;
+	pub const MaxDexStatsPeriods: u32 = 24 * 30;
+	pub const ProtocolFeeForwardPeriod: BlockNumber = DAYS;
+	pub const MaxProtocolFeeForwardsPerBlock: u32 = 10;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::new(SETT, DNAR),
 		TradingPair::new(SETT, DRAM),
@@ -1431,6 +1451,13 @@ impl dex::Config for Runtime {
 	type WeightInfo = weights::dex::WeightInfo<Runtime>;
 	type UpdateOrigin = EnsureRootOrHalfExchangeCouncil; // TODO: When root is removed, change to `EnsureHalfSetheumJuryOrHalfExchangeCouncil`.
 	type ListingOrigin = EnsureRootOrHalfExchangeCouncil; // TODO: When root is removed, change to `EnsureHalfSetheumJuryOrHalfExchangeCouncil`.
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type StatsPeriod = DexStatsPeriod;
+	type MaxStatsPeriods = MaxDexStatsPeriods;
+	type SerpTreasury = SerpTreasury;
+	type ProtocolFeeForwardPeriod = ProtocolFeeForwardPeriod;
+	type MaxProtocolFeeForwardsPerBlock = MaxProtocolFeeForwardsPerBlock;
+	type Call = Call;
 }
 
 parameter_types! {
@@ -1529,6 +1556,13 @@ impl orml_rewards::Config for Runtime {
 	type Handler = Incentives;
 }
 
+parameter_types! {
+	pub const MaxDexShareLocks: u32 = 10;
+	pub const MaxRewardStreams: u32 = 5;
+	pub RewardStreamDeposit: Balance = dollar(DNAR);
+	pub const RewardStreamClaimPeriod: BlockNumber = 30 * DAYS;
+}
+
 // TODO: Remove Other incentives except for DexIncentive 
 // TODO - and update ExchangeFeeWaiver (DexFeeWaiverIncentive) to the new Incentive model
 // TODO - based on trading volume, this new method will be 
@@ -1537,6 +1571,7 @@ impl setheum_incentives::Config for Runtime {
 	type Event = Event;
 	type DexIncentivePool = ZeroAccountId;
 	type DexPremiumPool = ZeroAccountId;
+	type SettMintIncentivePool = ZeroAccountId;
 	type DexPlusPool = ZeroAccountId;
 	type DexBonusPool = ZeroAccountId;
 	type DexExtraPool = ZeroAccountId;
@@ -1554,12 +1589,76 @@ impl setheum_incentives::Config for Runtime {
 	type Currency = Currencies;
 	type Dex = Dex;
 	type PalletId = IncentivesPalletId;
+	type MaxDexShareLocks = MaxDexShareLocks;
+	type MaxRewardStreams = MaxRewardStreams;
+	type RewardStreamDeposit = RewardStreamDeposit;
+	type RewardStreamClaimPeriod = RewardStreamClaimPeriod;
 	type WeightInfo = weights::setheum_incentives::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub StandingOrderDeposit: Balance = 10 * cent(DNAR);
+	pub const MaxStandingOrderPaymentsPerBlock: u32 = 50;
+	pub const MaxStandingOrderRetries: u32 = 3;
+	pub const StandingOrderRetryPeriod: BlockNumber = HOURS;
+}
+
+impl settpay_standing_orders::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type OrderDeposit = StandingOrderDeposit;
+	type MaxPaymentsPerBlock = MaxStandingOrderPaymentsPerBlock;
+	type MaxRetries = MaxStandingOrderRetries;
+	type RetryPeriod = StandingOrderRetryPeriod;
+	type WeightInfo = weights::settpay_standing_orders::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub StreamDeposit: Balance = 10 * cent(DNAR);
+}
+
+impl settpay_streams::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type StreamDeposit = StreamDeposit;
+	type PalletId = StreamsPalletId;
+	type WeightInfo = weights::settpay_streams::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub InvoiceDeposit: Balance = 10 * cent(DNAR);
+	pub const EscrowDisputePeriod: BlockNumber = 14 * DAYS;
+}
+
+impl settpay_escrow::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type InvoiceDeposit = InvoiceDeposit;
+	type PalletId = EscrowPalletId;
+	type DisputePeriod = EscrowDisputePeriod;
+	type CashDropHandler = SerpTreasury;
+	type WeightInfo = weights::settpay_escrow::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub CreateClassDeposit: Balance = 20 * millicent(DNAR);
 	pub CreateTokenDeposit: Balance = 2 * millicent(DNAR);
+	pub const MaxRoyaltyRate: Permill = Permill::from_percent(25);
+	pub const MaxAttributeKeyLength: u32 = 64;
+	pub const MaxAttributeValueLength: u32 = 1024;
+	pub const MaxAttributesPerToken: u32 = 16;
+}
+
+/// NFTs of accounts recovered through `pallet_recovery`, soulbound ones
+/// included, can be claimed by their rescuer.
+pub struct RecoveredAccounts;
+impl AccountRecovery<AccountId> for RecoveredAccounts {
+	fn recovered_account(rescuer: &AccountId) -> Option<AccountId> {
+		Recovery::proxy(rescuer)
+	}
 }
 
 impl setheum_nft::Config for Runtime {
@@ -1567,6 +1666,13 @@ impl setheum_nft::Config for Runtime {
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type PalletId = NftPalletId;
+	type MultiCurrency = Currencies;
+	type MaxRoyaltyRate = MaxRoyaltyRate;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxAttributesPerToken = MaxAttributesPerToken;
+	type Recovery = RecoveredAccounts;
 	type WeightInfo = weights::setheum_nft::WeightInfo<Runtime>;
 }
 
@@ -1584,6 +1690,29 @@ impl orml_nft::Config for Runtime {
 	type MaxTokenMetadata = MaxTokenMetadata;
 }
 
+impl setheum_nft_marketplace::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type Auction = Auction;
+	type FallbackAuctionHandler = SerpAuctionManager;
+	type WeightInfo = weights::setheum_nft_marketplace::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub VaultDeposit: Balance = 10 * cent(DNAR);
+	pub const MaxClassTokens: u32 = 100;
+}
+
+impl setheum_nft_vaults::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type VaultDeposit = VaultDeposit;
+	type MaxClassTokens = MaxClassTokens;
+	type PalletId = NftVaultsPalletId;
+	type WeightInfo = weights::setheum_nft_vaults::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	// One storage item; key size 32, value size 8; .
 	pub ProxyDepositBase: Balance = deposit(1, 8);
@@ -1628,6 +1757,8 @@ parameter_types! {
 	// TODO: update
 	pub const ChainId: u64 = 852;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	pub const CodeUpgradeDelay: BlockNumber = HOURS;
+	pub const MaxCodeVersions: u32 = 5;
 }
 
 #[cfg(feature = "with-sevm")]
@@ -1638,16 +1769,20 @@ parameter_types! {
 	pub const MaxCodeSize: u32 = 0x6000;
 	pub const DeveloperDeposit: Balance = 0;
 	pub const DeploymentFee: Balance = 0;
+	// `ISTANBUL_CONFIG` has no warm and cold access costs
+	pub const EvmHardFork: setheum_evm::HardFork = setheum_evm::HardFork::Istanbul;
 }
 
 #[cfg(not(feature = "with-sevm"))]
 parameter_types! {
 	pub NativeTokenExistentialDeposit: Balance = microcent(DNAR);
 	pub const NewContractExtraBytes: u32 = 10_000;
+	pub const EthTransactionStorageLimit: u32 = 64 * 1024;
 	pub StorageDepositPerByte: Balance = microcent(DNAR);
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub DeveloperDeposit: Balance = dollar(DNAR);
 	pub DeploymentFee: Balance = dollar(DNAR);
+	pub const EvmHardFork: setheum_evm::HardFork = setheum_evm::HardFork::London;
 }
 pub type MultiCurrencyPrecompile = runtime_common::MultiCurrencyPrecompile<
 	AccountId,
@@ -1675,7 +1810,7 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 >;
 
 pub type DexPrecompile =
-	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex, EVM>;
 
 	#[cfg(feature = "with-ethereum-compatibility")]
 static ISTANBUL_CONFIG: evm::Config = evm::Config::istanbul();
@@ -1698,14 +1833,20 @@ impl setheum_evm::Config for Runtime {
 		DexPrecompile,
 	>;
 	type ChainId = ChainId;
-	type GasToWeight = GasToWeight;
+	type GasToWeight = GasToWeight<setheum_evm::calibration::WeightPerGas<Runtime>>;
+	type MaxGasPerExtrinsic = setheum_evm::calibration::MaxGasPerExtrinsic<Runtime>;
 	type ChargeTransactionPayment = setheum_transaction_payment::ChargeTransactionPayment<Runtime>;
+	type EthTransactionStorageLimit = EthTransactionStorageLimit;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
+	type HardFork = EvmHardFork;
 	type DeveloperDeposit = DeveloperDeposit;
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureRootOrHalfGeneralCouncil; // TODO: When root is removed, change to `EnsureHalfSetheumJuryOrHalfGeneralCouncil`.
+	type CodeUpgradeDelay = CodeUpgradeDelay;
+	type MaxCodeVersions = MaxCodeVersions;
+	type CodeUpgradeCancelOrigin = EnsureRootOrHalfGeneralCouncil;
 	type WeightInfo = weights::setheum_evm::WeightInfo<Runtime>;
 
 	#[cfg(feature = "with-sevm")]
@@ -1761,7 +1902,8 @@ pub type SignedExtra = (
 	setheum_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
-pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+pub type UncheckedExtrinsic =
+	runtime_common::SetheumUncheckedExtrinsic<Call, SignedExtra, ConvertEthereumTx, EvmAddressMapping<Runtime>>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
@@ -1770,6 +1912,46 @@ pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExt
 pub type Executive =
 	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllModules, ()>;
 
+/// Builds the EIP-712 typed data Ethereum wallets sign for EVM calls, with
+/// the era, nonce and tip of the signed extensions.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ConvertEthereumTx;
+
+impl Convert<(Call, SignedExtra), Result<EthereumTransactionMessage, InvalidTransaction>> for ConvertEthereumTx {
+	fn convert((call, extra): (Call, SignedExtra)) -> Result<EthereumTransactionMessage, InvalidTransaction> {
+		use codec::Decode;
+
+		let (action, input, value, gas_limit, storage_limit, access_list) = match call {
+			Call::EVM(setheum_evm::Call::call(target, input, value, gas_limit, storage_limit, access_list)) => {
+				(TransactionAction::Call(target), input, value, gas_limit, storage_limit, access_list)
+			}
+			Call::EVM(setheum_evm::Call::create(input, value, gas_limit, storage_limit, access_list)) => {
+				(TransactionAction::Create, input, value, gas_limit, storage_limit, access_list)
+			}
+			_ => return Err(InvalidTransaction::Call),
+		};
+
+		let (_, _, _, check_era, check_nonce, _, charge_transaction_payment) = extra;
+		// `CheckEra` does not expose its era, which it encodes as is.
+		let era = generic::Era::decode(&mut &check_era.encode()[..]).map_err(|_| InvalidTransaction::Call)?;
+		let valid_until = era.death(System::block_number().into());
+
+		Ok(EthereumTransactionMessage {
+			chain_id: <Runtime as setheum_evm::Config>::ChainId::get(),
+			genesis: System::block_hash(0),
+			nonce: check_nonce.0,
+			tip: charge_transaction_payment.tip(),
+			gas_limit,
+			storage_limit,
+			action,
+			value,
+			input,
+			access_list,
+			valid_until,
+		})
+	}
+}
+
 #[allow(clippy::large_enum_variant)]
 construct_runtime!(
 	pub enum Runtime where
@@ -1788,6 +1970,8 @@ construct_runtime!(
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>} = 5,
 		Currencies: setheum_currencies::{Module, Call, Event<T>} = 6,
 		NFT: setheum_nft::{Module, Call, Event<T>} = 7,
+		NFTMarketplace: setheum_nft_marketplace::{Module, Storage, Call, Event<T>} = 58,
+		NFTVaults: setheum_nft_vaults::{Module, Storage, Call, Event<T>} = 59,
 		Vesting: orml_vesting::{Module, Storage, Call, Event<T>, Config<T>} = 8,
 		TransactionPayment: setheum_transaction_payment::{Module, Call, Storage} = 9,
 
@@ -1852,7 +2036,7 @@ construct_runtime!(
 
 		// Smart contracts
 		// Setheum EVM (SEVM)
-		EVM: setheum_evm::{Pallet, Config<T>, Call, Storage, Event<T>} = 48,
+		EVM: setheum_evm::{Pallet, Config<T>, Call, Storage, Event<T>, ValidateUnsigned} = 48,
 		EVMBridge: setheum_evm_bridge::{Pallet} = 49,
 		EvmAccounts: setheum_evm_accounts::{Pallet, Call, Storage, Event<T>} = 50,
 		EvmManager: setheum_evm_manager::{Pallet, Storage} = 51,
@@ -1862,6 +2046,11 @@ construct_runtime!(
 		ChainBridge: chainbridge::{Pallet, Call, Storage, Event<T>} = 53,
 		SetheumChainBridge: setheum_chainbridge::{Pallet, Call, Storage, Event<T>} = 54,
 
+		// SettPay
+		StandingOrders: settpay_standing_orders::{Module, Storage, Call, Event<T>} = 55,
+		Streams: settpay_streams::{Module, Storage, Call, Event<T>} = 56,
+		Escrow: settpay_escrow::{Module, Storage, Call, Event<T>} = 57,
+
 		// Dev
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>} = 255,
 	}
@@ -2061,6 +2250,27 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl setheum_dex_rpc_runtime_api::DEXRuntimeRPCApi<Block> for Runtime {
+		fn current_stats_period() -> StatsPeriodIndex {
+			Dex::current_stats_period()
+		}
+
+		fn get_pair_stats(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			from_period: StatsPeriodIndex,
+			to_period: StatsPeriodIndex,
+		) -> Vec<PairStats> {
+			Dex::get_pair_stats(currency_id_a, currency_id_b, from_period, to_period)
+		}
+	}
+
+	impl settpay_streams_runtime_api::StreamsApi<Block, AccountId> for Runtime {
+		fn balance_of(stream_id: StreamId, who: AccountId) -> Balance {
+			Streams::balance_of(stream_id, &who)
+		}
+	}
+
 	impl setheum_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -2069,6 +2279,7 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError> {
 			let config = if estimate {
@@ -2087,6 +2298,7 @@ impl_runtime_apis! {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list.unwrap_or_default(),
 				config.as_ref().unwrap_or(<Runtime as setheum_evm::Config>::config()),
 			)
 		}
@@ -2097,6 +2309,7 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError> {
 			let config = if estimate {
@@ -2113,6 +2326,7 @@ impl_runtime_apis! {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list.unwrap_or_default(),
 				config.as_ref().unwrap_or(<Runtime as setheum_evm::Config>::config()),
 			)
 		}
@@ -2121,8 +2335,8 @@ impl_runtime_apis! {
 			let utx = UncheckedExtrinsic::decode(&mut &*extrinsic)
 				.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter extrinsic, decode failed"))?;
 
-			let request = match utx.function {
-				Call::EVM(setheum_evm::Call::call(to, data, value, gas_limit, storage_limit)) => {
+			let request = match utx.0.function {
+				Call::EVM(setheum_evm::Call::call(to, data, value, gas_limit, storage_limit, access_list)) => {
 					Some(EstimateResourcesRequest {
 						from: None,
 						to: Some(to),
@@ -2130,9 +2344,10 @@ impl_runtime_apis! {
 						storage_limit: Some(storage_limit),
 						value: Some(value),
 						data: Some(data),
+						access_list: Some(access_list),
 					})
 				}
-				Call::EVM(setheum_evm::Call::create(data, value, gas_limit, storage_limit)) => {
+				Call::EVM(setheum_evm::Call::create(data, value, gas_limit, storage_limit, access_list)) => {
 					Some(EstimateResourcesRequest {
 						from: None,
 						to: None,
@@ -2140,6 +2355,7 @@ impl_runtime_apis! {
 						storage_limit: Some(storage_limit),
 						value: Some(value),
 						data: Some(data),
+						access_list: Some(access_list),
 					})
 				}
 				_ => None,
@@ -2147,6 +2363,158 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			setheum_evm::Runner::<Runtime>::trace_call(
+				from,
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default(),
+				<Runtime as setheum_evm::Config>::config(),
+				tracer,
+			)
+		}
+
+		fn trace_create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			setheum_evm::Runner::<Runtime>::trace_create(
+				from,
+				data,
+				None,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default(),
+				<Runtime as setheum_evm::Config>::config(),
+				tracer,
+			)
+		}
+
+		fn trace_extrinsic(
+			header: <Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			use frame_support::weights::GetDispatchInfo;
+			use sp_runtime::traits::{Checkable, SignedExtension};
+
+			Executive::initialize_block(&header);
+
+			for (i, extrinsic) in extrinsics.into_iter().enumerate() {
+				if (i as u32) < index {
+					// the outcome of the extrinsics before is part of the traced state
+					let _ = Executive::apply_extrinsic(extrinsic);
+					continue;
+				}
+
+				let len = extrinsic.encoded_size();
+				// checks the signature, of EIP-712 signed extrinsics too
+				let checked = extrinsic
+					.check(&frame_system::ChainContext::<Runtime>::default())
+					.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter extrinsic, check failed"))?;
+				let who = match checked.signed {
+					Some((who, extra)) => {
+						// the nonce and fee taken by the signed extensions are part of the traced state
+						let info = checked.function.get_dispatch_info();
+						extra
+							.pre_dispatch(&who, &checked.function, &info, len)
+							.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter extrinsic, signed extensions failed"))?;
+						who
+					}
+					None => {
+						return match checked.function {
+							Call::EVM(setheum_evm::Call::eth_transact(raw)) => EVM::trace_eth_transact(&raw, tracer),
+							_ => Err(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call")),
+						};
+					}
+				};
+				let from = EvmAddressMapping::<Runtime>::get_or_create_evm_address(&who);
+				let config = <Runtime as setheum_evm::Config>::config();
+
+				return match checked.function {
+					Call::EVM(setheum_evm::Call::call(to, data, value, gas_limit, storage_limit, access_list)) => {
+						setheum_evm::Runner::<Runtime>::trace_call(from, from, to, data, value, gas_limit, storage_limit, access_list, config, tracer)
+					}
+					Call::EVM(setheum_evm::Call::create(data, value, gas_limit, storage_limit, access_list)) => {
+						setheum_evm::Runner::<Runtime>::trace_create(from, data, None, value, gas_limit, storage_limit, access_list, config, tracer)
+					}
+					Call::EVM(setheum_evm::Call::create2(data, salt, value, gas_limit, storage_limit, access_list)) => {
+						setheum_evm::Runner::<Runtime>::trace_create(from, data, Some(salt), value, gas_limit, storage_limit, access_list, config, tracer)
+					}
+					_ => Err(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call")),
+				};
+			}
+
+			Err(sp_runtime::DispatchError::Other("Invalid parameter index, out of range"))
+		}
+
+		fn chain_id() -> u64 {
+			<Runtime as setheum_evm::Config>::ChainId::get()
+		}
+
+		fn max_gas_per_extrinsic() -> u64 {
+			<Runtime as setheum_evm::Config>::MaxGasPerExtrinsic::get()
+		}
+
+		fn account_basic(address: H160) -> Account {
+			EVM::account_basic(&address)
+		}
+
+		fn code_at(address: H160) -> Vec<u8> {
+			EVM::code_at_address(&address).into_inner()
+		}
+
+		fn storage_at(address: H160, index: H256) -> H256 {
+			EVM::account_storages(address, index)
+		}
+
+		fn block_logs(extrinsics: Vec<<Block as BlockT>::Extrinsic>) -> Vec<BlockLog> {
+			use sp_runtime::traits::Hash;
+
+			System::events()
+				.into_iter()
+				.filter_map(|record| match (record.phase, record.event) {
+					(frame_system::Phase::ApplyExtrinsic(index), Event::setheum_evm(setheum_evm::Event::Log(log))) => {
+						let extrinsic = extrinsics.get(index as usize)?;
+						let transaction_hash = match extrinsic.0.function {
+							Call::EVM(setheum_evm::Call::eth_transact(ref raw)) => setheum_evm::transaction::transaction_hash(raw),
+							_ => <Runtime as frame_system::Config>::Hashing::hash_of(extrinsic),
+						};
+						Some(BlockLog {
+							transaction_index: index,
+							transaction_hash,
+							log,
+						})
+					}
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn eth_transaction(raw: Vec<u8>) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(Call::EVM(setheum_evm::Call::eth_transact(raw)))
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
@@ -2205,6 +2573,10 @@ impl_runtime_apis! {
 			orml_add_benchmark!(params, batches, serp_treasury, benchmarking::serp_treasury);
 			// orml_add_benchmark!(params, batches, settmint_engine, benchmarking::settmint_engine);
 			orml_add_benchmark!(params, batches, settmint_gateway, benchmarking::settmint_gateway);
+			orml_add_benchmark!(params, batches, settpay_standing_orders, benchmarking::standing_orders);
+			orml_add_benchmark!(params, batches, settpay_streams, benchmarking::streams);
+			orml_add_benchmark!(params, batches, settpay_escrow, benchmarking::escrow);
+			orml_add_benchmark!(params, batches, setheum_nft_vaults, benchmarking::nft_vaults);
 			orml_add_benchmark!(params, batches, orml_tokens, benchmarking::tokens);
 			orml_add_benchmark!(params, batches, transaction_payment, benchmarking::transaction_payment);
 			orml_add_benchmark!(params, batches, orml_vesting, benchmarking::vesting);
@@ -63,4 +63,20 @@ impl<T: frame_system::Config> setheum_currencies::WeightInfo for WeightInfo<T> {
 	fn update_balance_native_currency_killing() -> Weight {
 		(24_402_000 as Weight)
 	}
+	fn transfer_non_native_currency_with_cashdrop() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn transfer_native_currency_with_cashdrop() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_many(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((66_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }